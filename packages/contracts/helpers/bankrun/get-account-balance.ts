@@ -1,5 +1,9 @@
 import { PublicKey } from "@metaplex-foundation/js";
-import { getAssociatedTokenAddress, AccountLayout } from "@solana/spl-token";
+import {
+  getAssociatedTokenAddress,
+  AccountLayout,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
 import { BanksClient } from "solana-bankrun";
 
 import { BN } from "../../constants";
@@ -11,9 +15,15 @@ import { BN } from "../../constants";
 export const getAccountBalance = async (
   client: BanksClient,
   userAddress: PublicKey,
-  tokenAddress: PublicKey
+  tokenAddress: PublicKey,
+  tokenProgramId: PublicKey = TOKEN_PROGRAM_ID
 ) => {
-  const ata = await getAssociatedTokenAddress(tokenAddress, userAddress, true);
+  const ata = await getAssociatedTokenAddress(
+    tokenAddress,
+    userAddress,
+    true,
+    tokenProgramId
+  );
 
   try {
     const rawAccountData = (await client.getAccount(ata))?.data;
@@ -1,40 +1,161 @@
-import {
-  getMerkleProof,
-  getMerkleRoot,
-  getMerkleTree,
-} from "@metaplex-foundation/js";
+import { BN } from "@coral-xyz/anchor";
 import { keccak_256 as keccak256 } from "@noble/hashes/sha3";
+import { PublicKey } from "@solana/web3.js";
 
 /**
- * Generate a merkle root from a list of addresses and converts it into an array to be consumable by the program.
+ * A whitelisted address along with its tier and maximum allocation in assets (0 for no limit).
+ * Plain addresses are whitelisted in tier 0 without allocation.
  */
-export const generateMerkleRoot = (addresses: string[]) =>
-  Array.from(getMerkleRoot(addresses));
+export type WhitelistEntry = {
+  address: string;
+  tier?: number;
+  maxAllocation?: BN;
+};
+
+/**
+ * Hashing scheme of the whitelist tree, stored per pool as `whitelistLeafVersion`.
+ * - legacy: leaves hash the base58 address of the user, whitelisted in tier 0 without allocation
+ * - tiered: leaves hash the user along with its tier and maximum allocation
+ * - standard: leaves of OpenZeppelin's StandardMerkleTree of [user, tier, maxAllocation] values typed
+ *   ["bytes32", "uint8", "uint64"], i.e. their ABI encoding hashed twice
+ * Internal nodes are hashed as sorted pairs in every version.
+ */
+export type WhitelistLeafVersion = "legacy" | "tiered" | "standard";
+
+const toWhitelistEntry = (
+  entry: string | WhitelistEntry
+): Required<WhitelistEntry> => ({
+  tier: 0,
+  maxAllocation: new BN(0),
+  ...(typeof entry === "string" ? { address: entry } : entry),
+});
+
+/**
+ * Serialize the data of a whitelist leaf as hashed by the program:
+ * user (32 bytes) || tier (u8) || max allocation (u64 little endian).
+ */
+export const whitelistLeafData = (entry: string | WhitelistEntry) => {
+  const { address, tier, maxAllocation } = toWhitelistEntry(entry);
+  return Buffer.concat([
+    new PublicKey(address).toBuffer(),
+    Buffer.from([tier]),
+    maxAllocation.toArrayLike(Buffer, "le", 8),
+  ]);
+};
 
+/**
+ * ABI encode the data of a standard whitelist leaf, as OpenZeppelin's StandardMerkleTree does:
+ * user || tier || max allocation, each as a 32 bytes word with integers in big endian.
+ */
+export const standardWhitelistLeafData = (entry: string | WhitelistEntry) => {
+  const { address, tier, maxAllocation } = toWhitelistEntry(entry);
+  return Buffer.concat([
+    new PublicKey(address).toBuffer(),
+    new BN(tier).toArrayLike(Buffer, "be", 32),
+    maxAllocation.toArrayLike(Buffer, "be", 32),
+  ]);
+};
+
+const hashLeaf = (
+  entry: string | WhitelistEntry,
+  version: WhitelistLeafVersion
+) => {
+  if (version === "legacy") {
+    return Buffer.from(keccak256(toWhitelistEntry(entry).address));
+  }
+  if (version === "tiered") {
+    return Buffer.from(keccak256(whitelistLeafData(entry)));
+  }
+  return Buffer.from(keccak256(keccak256(standardWhitelistLeafData(entry))));
+};
+
+const hashNode = (a: Buffer, b: Buffer) => {
+  const pair = Buffer.compare(a, b) <= 0 ? [a, b] : [b, a];
+  return Buffer.from(keccak256(Buffer.concat(pair)));
+};
+
+/**
+ * Levels of the whitelist tree, leaves first and root last.
+ * The last node of odd levels is promoted as is, as in merkletreejs.
+ */
+const merkleLevels = (
+  entries: (string | WhitelistEntry)[],
+  version: WhitelistLeafVersion
+) => {
+  const levels = [entries.map((entry) => hashLeaf(entry, version))];
+  while (levels[levels.length - 1].length > 1) {
+    const level = levels[levels.length - 1];
+    const parents: Buffer[] = [];
+    for (let i = 0; i < level.length; i += 2) {
+      parents.push(
+        i + 1 < level.length
+          ? hashNode(level[i], level[i + 1])
+          : level[i]
+      );
+    }
+    levels.push(parents);
+  }
+  return levels;
+};
+
+/**
+ * Generate a merkle root from a list of whitelist entries and converts it into an array to be consumable by the program.
+ */
+export const generateMerkleRoot = (
+  entries: (string | WhitelistEntry)[],
+  version: WhitelistLeafVersion = "legacy"
+) => {
+  const levels = merkleLevels(entries, version);
+  return Array.from(levels[levels.length - 1][0] ?? Buffer.alloc(32));
+};
+
+/**
+ * Generate the whitelist proof of a user, passed as is to the swaps.
+ * Users missing from the whitelist get an empty proof.
+ */
 export const generateMerkleProof = (
-  allowAddresses: string[],
-  userAddress: string
+  entries: (string | WhitelistEntry)[],
+  userAddress: string,
+  version: WhitelistLeafVersion = "legacy"
 ) => {
-  // Original call returns Uint8Array[]
-  const proof = getMerkleProof(allowAddresses, userAddress);
-  // Convert each Uint8Array to number[]
-  return proof.map((entry) => Array.from(entry));
+  let index = entries.findIndex(
+    (entry) => toWhitelistEntry(entry).address === userAddress
+  );
+  // Legacy leaves only hold the address
+  const { tier, maxAllocation } = toWhitelistEntry(
+    index === -1 || version === "legacy" ? userAddress : entries[index]
+  );
+  const proof: Buffer[] = [];
+  if (index !== -1) {
+    const levels = merkleLevels(entries, version);
+    for (const level of levels.slice(0, -1)) {
+      const sibling = level[index ^ 1];
+      if (sibling) {
+        proof.push(sibling);
+      }
+      index = Math.floor(index / 2);
+    }
+  }
+  // Convert each Buffer to number[]
+  return {
+    tier,
+    maxAllocation,
+    proof: proof.map((entry) => Array.from(entry)),
+  };
 };
 
 export const isValidMerkleProof = (
-  allowAddresses: string[],
+  entries: (string | WhitelistEntry)[],
   userAddress: string,
-  validMerkleProof: number[][] = generateMerkleProof(
-    allowAddresses,
-    userAddress
-  ),
-  merkleRoot: number[] = generateMerkleRoot(allowAddresses)
+  version: WhitelistLeafVersion = "legacy",
+  validMerkleProof = generateMerkleProof(entries, userAddress, version),
+  merkleRoot: number[] = generateMerkleRoot(entries, version)
 ) => {
-  const isTreeValid = getMerkleTree(allowAddresses).verify(
-    validMerkleProof.map((e) => Buffer.from(e)),
-    Buffer.from(keccak256(userAddress)),
-    Buffer.from(merkleRoot)
+  const { tier, maxAllocation, proof } = validMerkleProof;
+  const computedRoot = proof.reduce(
+    (node, sibling) => hashNode(node, Buffer.from(sibling)),
+    hashLeaf({ address: userAddress, tier, maxAllocation }, version)
   );
 
-  return isTreeValid;
+  return computedRoot.equals(Buffer.from(merkleRoot));
 };
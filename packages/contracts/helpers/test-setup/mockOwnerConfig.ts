@@ -16,4 +16,7 @@ export const createMockOwnerConfig = (
   platformFee: requestField?.platformFee || 100, // 1%
   referralFee: requestField?.referralFee || 100, // 1%
   swapFee: requestField?.swapFee || 100, // 1%
+  maxPlatformFee: requestField?.maxPlatformFee || 10000, // 100%
+  maxReferralFee: requestField?.maxReferralFee || 10000, // 100%
+  maxSwapFee: requestField?.maxSwapFee || 10000, // 100%
 });
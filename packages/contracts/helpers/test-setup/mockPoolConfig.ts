@@ -5,6 +5,7 @@ import {
   DecodeType,
   IdlTypes,
 } from "@coral-xyz/anchor/dist/cjs/program/namespace/types";
+import { PublicKey } from "@solana/web3.js";
 
 import {
   DEFAULT_SALE_END_TIME_BN,
@@ -33,15 +34,44 @@ export const createMockpoolConfig = (
   maxSharePrice: requestField?.maxSharePrice || new BN(10000000),
   maxSharesOut: requestField?.maxSharesOut || new BN(10000000),
   maxAssetsIn: requestField?.maxAssetsIn || new BN(10000000),
+  minAssetsRaised: requestField?.minAssetsRaised || new BN(0), // Default: no soft cap
+  maxAssetsRaised: requestField?.maxAssetsRaised || new BN(0), // Default: no hard cap
+  maxAssetsPerUser: requestField?.maxAssetsPerUser || new BN(0), // Default: no per-wallet limit
+  maxSharesPerUser: requestField?.maxSharesPerUser || new BN(0), // Default: no per-wallet limit
+  minAssetsPerBuy: requestField?.minAssetsPerBuy || new BN(0), // Default: no minimum buy size
   startWeightBasisPoints:
     requestField?.startWeightBasisPoints || 50 * PERCENTAGE_BASIS_POINTS, // Default: 50%
   endWeightBasisPoints:
     requestField?.endWeightBasisPoints || 50 * PERCENTAGE_BASIS_POINTS, // Default: 50%
+  weightCurve: requestField?.weightCurve || { linear: {} }, // Default: linear weight schedule
+  weightKnots:
+    requestField?.weightKnots ||
+    Array.from({ length: 8 }, () => ({
+      timestamp: new BN(0),
+      weightBasisPoints: 0,
+    })), // Default: no piecewise knots
   saleStartTime: requestField?.saleStartTime || DEFAULT_SALE_START_TIME_BN,
   saleEndTime: requestField?.saleEndTime || DEFAULT_SALE_END_TIME_BN,
   vestCliff: requestField?.vestCliff || DEFAULT_VESTING_CLIFF_BN,
   vestEnd: requestField?.vestEnd || DEFAULT_VESTING_END_BN,
   whitelistMerkleRoot: requestField?.whitelistMerkleRoot || [],
+  whitelistLeafVersion: requestField?.whitelistLeafVersion || { legacy: {} }, // Default: leaves hash the base58 address
+  whitelistSigner: requestField?.whitelistSigner || PublicKey.default, // Default: no whitelist attestations
+  whitelistTierStartTimes: requestField?.whitelistTierStartTimes || [
+    new BN(0),
+    new BN(0),
+    new BN(0),
+    new BN(0),
+  ], // Default: every tier opens with the sale
+  publicSaleTime: requestField?.publicSaleTime || new BN(0), // Default: the whitelist applies to the whole sale
+  referralSchedule: requestField?.referralSchedule || {
+    feeBasisPoints: null,
+    tiers: Array.from({ length: 4 }, () => ({
+      minReferredVolume: new BN(0),
+      feeBasisPoints: 0,
+    })),
+    secondLevelBasisPoints: 0,
+  }, // Default: the referral fee of the pool, without tiers or second level
   sellingAllowed: requestField?.sellingAllowed || false,
 });
 
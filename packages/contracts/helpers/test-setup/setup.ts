@@ -1,12 +1,13 @@
 import {
   TOKEN_PROGRAM_ID,
+  ExtensionType,
   createInitializeMintInstruction,
+  createInitializeTransferFeeConfigInstruction,
   getAccount,
   getAssociatedTokenAddress,
   createAssociatedTokenAccountInstruction,
   createMintToInstruction,
-  getMinimumBalanceForRentExemptMint,
-  MINT_SIZE,
+  getMintLen,
 } from "@solana/spl-token";
 import {
   Keypair,
@@ -31,6 +32,10 @@ export const setup = async ({
   decimalsTokenB = 9,
   supplyTokenA = 1000000,
   supplyTokenB = 1000000,
+  programIdTokenA = TOKEN_PROGRAM_ID,
+  programIdTokenB = TOKEN_PROGRAM_ID,
+  transferFeeBasisPointsTokenA,
+  transferFeeBasisPointsTokenB,
 }: {
   payer: Keypair;
   testUser?: Keypair;
@@ -40,6 +45,11 @@ export const setup = async ({
   decimalsTokenB?: number;
   supplyTokenA?: number;
   supplyTokenB?: number;
+  programIdTokenA?: PublicKey;
+  programIdTokenB?: PublicKey;
+  // Only supported by Token-2022 mints
+  transferFeeBasisPointsTokenA?: number;
+  transferFeeBasisPointsTokenB?: number;
 }) => {
   // Deploys two tokens for testing. Token A and Token B.
   const {
@@ -53,6 +63,8 @@ export const setup = async ({
     bankRunClient,
     decimals: decimalsTokenA,
     amount: supplyTokenA,
+    programId: programIdTokenA,
+    transferFeeBasisPoints: transferFeeBasisPointsTokenA,
   });
 
   const {
@@ -66,6 +78,8 @@ export const setup = async ({
     bankRunClient,
     decimals: decimalsTokenB,
     amount: supplyTokenB,
+    programId: programIdTokenB,
+    transferFeeBasisPoints: transferFeeBasisPointsTokenB,
   });
 
   return {
@@ -85,6 +99,8 @@ const createToken = async ({
   amount = 1000000,
   connection,
   bankRunClient,
+  programId = TOKEN_PROGRAM_ID,
+  transferFeeBasisPoints,
 }: {
   payer: Keypair;
   testUser?: Keypair;
@@ -92,12 +108,19 @@ const createToken = async ({
   amount?: number;
   connection: Connection;
   bankRunClient?: BanksClient;
+  programId?: PublicKey;
+  transferFeeBasisPoints?: number;
 }) => {
   const tokenMint = Keypair.generate();
 
   const transaction = new Transaction();
 
-  const lamports = await getMinimumBalanceForRentExemptMint(connection);
+  const extensions =
+    transferFeeBasisPoints !== undefined
+      ? [ExtensionType.TransferFeeConfig]
+      : [];
+  const space = getMintLen(extensions);
+  const lamports = await connection.getMinimumBalanceForRentExemption(space);
 
   transaction.add(
     // Create an account which will store our tokens.
@@ -105,23 +128,42 @@ const createToken = async ({
       fromPubkey: payer.publicKey,
       newAccountPubkey: tokenMint.publicKey,
       lamports,
-      space: MINT_SIZE,
-      programId: TOKEN_PROGRAM_ID,
-    }),
+      space,
+      programId,
+    })
+  );
+
+  if (transferFeeBasisPoints !== undefined) {
+    // The transfer fee extension must be initialized before the mint
+    transaction.add(
+      createInitializeTransferFeeConfigInstruction(
+        tokenMint.publicKey,
+        payer.publicKey,
+        payer.publicKey,
+        transferFeeBasisPoints,
+        BigInt("18446744073709551615"), // No maximum fee
+        programId
+      )
+    );
+  }
+
+  transaction.add(
     // create new token mint where we use the account we just created
     createInitializeMintInstruction(
       tokenMint.publicKey,
       decimals,
       payer.publicKey,
       null,
-      TOKEN_PROGRAM_ID
+      programId
     )
   );
 
   // Pre-compute the address of our token account which will hold the tokens.
   const tokenAccountAddress = await getAssociatedTokenAddress(
     tokenMint.publicKey,
-    payer.publicKey
+    payer.publicKey,
+    false,
+    programId
   );
 
   // getAccount throws an error if the account doesn't exist,
@@ -134,7 +176,8 @@ const createToken = async ({
         payer.publicKey, // fee payer
         tokenAccountAddress, // token account
         payer.publicKey, // token owner
-        tokenMint.publicKey // token mint
+        tokenMint.publicKey, // token mint
+        programId
       )
     );
   }
@@ -145,7 +188,9 @@ const createToken = async ({
       tokenMint.publicKey,
       tokenAccountAddress,
       payer.publicKey,
-      amount * 10 ** decimals
+      amount * 10 ** decimals,
+      [],
+      programId
     )
   );
 
@@ -155,6 +200,7 @@ const createToken = async ({
     tokenMint: tokenMint.publicKey,
     transaction,
     decimals,
+    programId,
   });
 
   if (bankRunClient) {
@@ -188,6 +234,7 @@ const _mintSomeTokensToTestUser = async ({
   transaction,
   decimals,
   amount = 1000,
+  programId = TOKEN_PROGRAM_ID,
 }: {
   user?: Keypair;
   payer: Keypair;
@@ -195,13 +242,16 @@ const _mintSomeTokensToTestUser = async ({
   transaction: Transaction;
   decimals: number;
   amount?: number;
+  programId?: PublicKey;
 }) => {
   if (!user) {
     return undefined;
   }
   const userTokenAccountAddress = await getAssociatedTokenAddress(
     tokenMint,
-    user.publicKey
+    user.publicKey,
+    false,
+    programId
   );
 
   transaction.add(
@@ -209,14 +259,17 @@ const _mintSomeTokensToTestUser = async ({
       payer.publicKey, // fee payer
       userTokenAccountAddress, // token account
       user.publicKey, // token owner
-      tokenMint // token mint
+      tokenMint, // token mint
+      programId
     ),
     // Mint some tokens to testUser
     createMintToInstruction(
       tokenMint,
       userTokenAccountAddress,
       payer.publicKey,
-      amount * 10 ** decimals
+      amount * 10 ** decimals,
+      [],
+      programId
     )
   );
 
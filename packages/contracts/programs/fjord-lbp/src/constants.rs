@@ -1,5 +1,32 @@
 pub const ONE_DAY_SECONDS: i64 = 60 * 60 * 24;
 
+/**
+ * Bounds of the delay between the proposal of a global fee change and its execution, starting at the minimum
+ */
+pub const MIN_FEE_CHANGE_DELAY: i64 = ONE_DAY_SECONDS;
+pub const MAX_FEE_CHANGE_DELAY: i64 = 30 * ONE_DAY_SECONDS;
+
+/**
+ * Time after the end of the sale and of the vesting after which the pool accounts can be closed even though some
+ * shares or referral assets were never claimed, which are then swept to the creator along with the rest of the pool
+ */
+pub const CLOSE_POOL_ACCOUNTS_GRACE_PERIOD: i64 = 180 * ONE_DAY_SECONDS;
+
+/**
+ * Number of tiers of the whitelist, each with its own opening time
+ */
+pub const MAX_WHITELIST_TIERS: usize = 4;
+
+/**
+ * Number of knots of a piecewise weight schedule, between the start and end weights of the sale
+ */
+pub const MAX_WEIGHT_KNOTS: usize = 8;
+
+/**
+ * Number of volume tiers of a referral schedule, each with its own referral fee
+ */
+pub const MAX_REFERRAL_TIERS: usize = 4;
+
 /**
  * Maximum percentage of reserve_in allowed to be swapped in when using get_amount_out (30%)
  */
@@ -65,6 +65,76 @@ pub enum PoolError {
     DuplicateFeeRecipient,
     #[msg("Zero Slippage")]
     ZeroSlippage,
+    #[msg("The asset token account is required unless native SOL is used")]
+    MissingAssetTokenAccount,
+    #[msg("Native SOL can only be used in pools whose asset is wrapped SOL")]
+    NativeSolDisallowed,
+    #[msg("The native unwrap account is required to receive native SOL")]
+    MissingNativeUnwrapAccount,
+    #[msg("The native unwrap account is only used to receive native SOL")]
+    UnexpectedNativeUnwrapAccount,
+    #[msg("The minimum amount of assets raised cannot exceed the hard cap")]
+    InvalidSaleCaps,
+    #[msg("The purchase would raise more assets than the hard cap")]
+    HardCapExceeded,
+    #[msg("The account already has the current layout")]
+    AccountAlreadyMigrated,
+    #[msg("Refunds are only available once a pool is closed below its soft cap")]
+    RefundingDisallowed,
+    #[msg("Assets in exceeds the maximum amount of assets per user")]
+    UserAssetsInExceeded,
+    #[msg("Shares out exceeds the maximum number of shares per user")]
+    UserSharesOutExceeded,
+    #[msg("Assets in is below the minimum amount of assets per buy")]
+    AssetsInTooLow,
+    #[msg("The minimum amount of assets per buy cannot exceed the per-wallet cap, the hard cap or the maximum assets in")]
+    InvalidMinAssetsPerBuy,
+    #[msg("Invalid whitelist tier")]
+    InvalidWhitelistTier,
+    #[msg("The whitelist tier of the user is not open yet")]
+    WhitelistTierNotOpen,
+    #[msg("Assets in exceeds the whitelist allocation of the user")]
+    WhitelistAllocationExceeded,
+    #[msg("The whitelist can only be updated before the sale ends")]
+    WhitelistUpdateDisallowed,
+    #[msg("The public sale must start before the sale ends")]
+    InvalidPublicSaleTime,
+    #[msg("The whitelist attestation is missing or invalid")]
+    InvalidWhitelistAttestation,
+    #[msg("The whitelist attestation has expired")]
+    WhitelistAttestationExpired,
+    #[msg("Cancelling is only allowed as long as no shares were ever bought in the pool")]
+    CancellingDisallowed,
+    #[msg("The pool configuration can only be updated before the sale starts")]
+    UpdatingDisallowed,
+    #[msg("The pool accounts can only be closed once the pool is closed and everything owed is claimed, or after the grace period")]
+    PoolAccountsClosingDisallowed,
+    #[msg("The weight schedule can only be adjusted during the sale")]
+    WeightAdjustmentDisallowed,
+    #[msg("Only the creator or the weight manager can adjust the weight schedule")]
+    UnauthorizedWeightAdjustment,
+    #[msg("Users cannot refer themselves")]
+    SelfReferral,
+    #[msg("The referrer must come with its state in the pool and match the referrer of the user's earlier purchases")]
+    InvalidReferrer,
+    #[msg("The referrer is not registered")]
+    UnregisteredReferrer,
+    #[msg("Referral fees must not exceed 100% and the referral tiers must come first in increasing volume order")]
+    InvalidReferralSchedule,
+    #[msg("Referral rewards can only be claimed once the pool is closed, unless it is refunding")]
+    ReferralClaimDisallowed,
+    #[msg("The fees of a pool can only be overridden until it is closed")]
+    PoolFeesUpdateDisallowed,
+    #[msg("No fee change is pending")]
+    FeeChangeNotProposed,
+    #[msg("The fee change can only be executed once its delay has elapsed")]
+    FeeChangeNotReady,
+    #[msg("The fees must not exceed their ceilings")]
+    FeeCeilingExceeded,
+    #[msg("Fee ceilings can only be lowered")]
+    FeeCeilingRaised,
+    #[msg("The fee change delay must be between one and thirty days")]
+    InvalidFeeChangeDelay,
 }
 
 // Access Control Errors
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::FeeMapping;
+
 // Emitted when a pool is created
 #[event]
 pub struct PoolCreatedEvent {
@@ -31,6 +33,16 @@ pub struct Sell {
     pub swap_fee: u64,
 }
 
+// Emitted when the hard cap is reached, ending the sale early
+#[event]
+pub struct SaleFinalized {
+    pub pool: Pubkey,
+    // The net amount of assets raised by the sale
+    pub total_assets_raised: u64,
+    // The new end of the sale
+    pub sale_end_time: i64,
+}
+
 #[event]
 pub struct Close {
     // The amount of assets transferred out during the pool closure
@@ -38,6 +50,8 @@ pub struct Close {
     pub platform_fees: u64,
     pub swap_fees_asset: u64,
     pub swap_fees_share: u64,
+    // Whether the sale missed its soft cap and the pool was closed in refund mode
+    pub refunding: bool,
 }
 
 #[event]
@@ -46,6 +60,141 @@ pub struct Redeem {
     pub shares: u64,
 }
 
+// Emitted when a referred user swaps, crediting its referrer on purchases and debiting it on sales
+#[event]
+pub struct Referral {
+    pub pool: Pubkey,
+    pub referrer: Pubkey,
+    pub user: Pubkey,
+    // The amount of assets bought or sold by the user
+    pub assets: u64,
+    // The referral fee credited to, or debited from, the referrer
+    pub referred_assets: u64,
+    // The referrer of the referrer earning the second-level split, default when none
+    pub second_level_referrer: Pubkey,
+    // The second-level split of the referral fee credited to, or debited from, the referrer of the referrer
+    pub second_level_referred_assets: u64,
+    pub is_sell: bool,
+}
+
+// Emitted when a referrer claims the assets it earned from referrals
+#[event]
+pub struct ReferralRewardsClaimed {
+    pub pool: Pubkey,
+    pub referrer: Pubkey,
+    pub assets: u64,
+}
+
+// Emitted when a buyer of a sale closed in refund mode gets its assets back
+#[event]
+pub struct Refund {
+    pub caller: Pubkey,
+    pub assets: u64,
+}
+
+// Emitted when the creator cancels a pool and withdraws its deposits
+#[event]
+pub struct Cancel {
+    pub pool: Pubkey,
+    // The amount of assets and shares returned to the creator
+    pub assets: u64,
+    pub shares: u64,
+}
+
+// Emitted when the creator closes the accounts of a closed pool to reclaim their rent
+#[event]
+pub struct PoolAccountsClosed {
+    pub pool: Pubkey,
+    // The assets and shares left in the pool, swept to the creator
+    pub assets: u64,
+    pub shares: u64,
+}
+
+// Emitted when the creator replaces the whitelist Merkle root of a pool
+#[event]
+pub struct WhitelistUpdated {
+    pub pool: Pubkey,
+    pub whitelist_merkle_root: [u8; 32],
+}
+
+// Emitted when the creator sets the time at which the public sale of a pool opens
+#[event]
+pub struct PublicSaleTimeUpdated {
+    pub pool: Pubkey,
+    pub public_sale_time: i64,
+}
+
+// Emitted when the creator updates the configuration of a pool before its sale starts
+#[event]
+pub struct PoolConfigUpdated {
+    pub pool: Pubkey,
+    // The amount of assets and shares added to the reserves
+    pub assets: u64,
+    pub shares: u64,
+}
+
+// Emitted when the creator or weight manager adjusts the weight schedule of a pool during its sale
+#[event]
+pub struct WeightsAdjusted {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+    // The asset weight the schedule resumes from, and its new end weight
+    pub weight_basis_points: u16,
+    pub end_weight_basis_points: u16,
+    pub paused: bool,
+}
+
+// Emitted when the owner and the creator of a pool override the fees snapshotted at its creation
+#[event]
+pub struct PoolFeesUpdated {
+    pub pool: Pubkey,
+    pub platform_fee: u16,
+    pub referral_fee: u16,
+    pub swap_fee: u16,
+}
+
+// Emitted when the owner proposes a change of the global fees
+#[event]
+pub struct FeesProposed {
+    pub platform_fee: u16,
+    pub referral_fee: u16,
+    pub swap_fee: u16,
+    pub fee_change_delay: i64,
+    pub executable_at: i64,
+}
+
+// Emitted when a proposed change of the global fees takes effect
+#[event]
+pub struct FeesExecuted {
+    pub platform_fee: u16,
+    pub referral_fee: u16,
+    pub swap_fee: u16,
+    pub fee_change_delay: i64,
+}
+
+// Emitted when the owner proposes new treasury fee recipients
+#[event]
+pub struct FeeRecipientsProposed {
+    pub swap_fee_recipient: Pubkey,
+    pub fee_recipients: Vec<FeeMapping>,
+    pub executable_at: i64,
+}
+
+// Emitted when proposed treasury fee recipients take effect
+#[event]
+pub struct FeeRecipientsExecuted {
+    pub swap_fee_recipient: Pubkey,
+    pub fee_recipients: Vec<FeeMapping>,
+}
+
+// Emitted when the owner lowers the fee ceilings, the global fees above them being lowered along
+#[event]
+pub struct FeeCeilingsLowered {
+    pub max_platform_fee: u16,
+    pub max_referral_fee: u16,
+    pub max_swap_fee: u16,
+}
+
 // For Read-only contexts
 #[event]
 pub struct PreviewAssetsIn {
@@ -69,8 +218,8 @@ pub struct PreviewSharesOut {
 
 #[event]
 pub struct ReservesAndWeights {
-    pub asset_reserve: u64,
-    pub share_reserve: u64,
-    pub asset_weight: u64,
-    pub share_weight: u64,
+    pub asset_reserve: u128,
+    pub share_reserve: u128,
+    pub asset_weight: u128,
+    pub share_weight: u128,
 }
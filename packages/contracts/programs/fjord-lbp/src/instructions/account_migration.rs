@@ -0,0 +1,289 @@
+use crate::{
+    AccessControlError, FeeMapping, LiquidityBootstrappingPool, OwnerConfig, PoolError, Treasury,
+    UserStateInPool, MIN_FEE_CHANGE_DELAY,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::{system_program, Discriminator};
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::MAX_FEE_BASIS_POINTS;
+use std::mem::size_of;
+
+// Layouts of the accounts created before the fields added since the first release, which left them too short to
+// deserialize. Their fields are carried over as is and the new fields take their defaults.
+
+#[derive(AnchorDeserialize)]
+struct LegacyOwnerConfig {
+    owner: Pubkey,
+    pending_owner: Option<Pubkey>,
+    treasury: Pubkey,
+    platform_fee: u16,
+    referral_fee: u16,
+    swap_fee: u16,
+    bump: u8,
+}
+
+#[derive(AnchorDeserialize)]
+struct LegacyTreasury {
+    swap_fee_recipient: Pubkey,
+    fee_recipients: Vec<FeeMapping>,
+}
+
+#[derive(AnchorDeserialize)]
+struct LegacyLiquidityBootstrappingPool {
+    asset_token: Pubkey,
+    share_token: Pubkey,
+    creator: Pubkey,
+    virtual_assets: u64,
+    virtual_shares: u64,
+    max_share_price: u64,
+    max_shares_out: u64,
+    max_assets_in: u64,
+    start_weight_basis_points: u16,
+    end_weight_basis_points: u16,
+    sale_start_time: i64,
+    sale_end_time: i64,
+    vest_cliff: i64,
+    vest_end: i64,
+    selling_allowed: bool,
+    total_purchased: u64,
+    total_referred: u64,
+    total_swap_fees_asset: u64,
+    total_swap_fees_share: u64,
+    closed: bool,
+    paused: bool,
+    whitelist_merkle_root: [u8; 32],
+    bump: u8,
+    salt: String,
+}
+
+#[derive(AnchorDeserialize)]
+struct LegacyUserStateInPool {
+    purchased_shares: u64,
+    referred_assets: u64,
+    redeemed_shares: u64,
+}
+
+#[derive(Accounts)]
+pub struct MigrateOwnerConfig<'info> {
+    /// CHECK: Owner config in its first layout, checked by its discriminator
+    #[account(mut, seeds = ["owner_config".as_bytes()], bump)]
+    pub config: UncheckedAccount<'info>,
+    /// CHECK: Treasury in its first layout, checked by its discriminator
+    #[account(mut, seeds = ["treasury".as_bytes()], bump)]
+    pub treasury: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigratePool<'info> {
+    /// CHECK: Pool in its first layout, checked by its owner and discriminator
+    #[account(mut, owner = crate::ID)]
+    pub pool: UncheckedAccount<'info>,
+    #[account(seeds = ["owner_config".as_bytes()], bump = config.bump)]
+    pub config: Account<'info, OwnerConfig>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateUserState<'info> {
+    /// CHECK: User state in its first layout, checked by its owner and discriminator
+    #[account(mut, owner = crate::ID)]
+    pub user_state_in_pool: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Decode `account` in its first layout, checking that it holds an account of type `T` that has not been migrated
+/// yet, i.e. that is shorter than the `len` bytes of the current layout.
+fn _load_legacy<T: Discriminator, L: AnchorDeserialize>(
+    account: &AccountInfo,
+    len: usize,
+) -> Result<L> {
+    let data = account.try_borrow_data()?;
+    if data.len() < 8 || data[..8] != T::DISCRIMINATOR {
+        return Err(ErrorCode::AccountDiscriminatorMismatch.into());
+    }
+    if data.len() >= len {
+        return Err(PoolError::AccountAlreadyMigrated.into());
+    }
+    L::deserialize(&mut &data[8..]).map_err(|_| ErrorCode::AccountDidNotDeserialize.into())
+}
+
+/// Grow `account` to `len` bytes and store `value` in it, topping up its rent exemption from `payer`.
+fn _store<'info, T: AccountSerialize>(
+    account: &AccountInfo<'info>,
+    value: &T,
+    len: usize,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let rent = Rent::get()?
+        .minimum_balance(len)
+        .saturating_sub(account.lamports());
+    if rent > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            rent,
+        )?;
+    }
+    account.realloc(len, true)?;
+    value.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])
+}
+
+// Migration of the accounts created before the upgrade
+pub mod migration {
+    use super::*;
+
+    /// Bring the owner config and treasury to their current layout, with the given fee ceilings and the shortest
+    /// fee change delay. Must run before the pools are migrated.
+    pub fn migrate_owner_config(
+        ctx: Context<MigrateOwnerConfig>,
+        max_platform_fee: u16,
+        max_referral_fee: u16,
+        max_swap_fee: u16,
+    ) -> Result<()> {
+        let accounts = ctx.accounts;
+        let legacy_config: LegacyOwnerConfig =
+            _load_legacy::<OwnerConfig, _>(&accounts.config, 8 + OwnerConfig::LEN)?;
+        if legacy_config.owner != accounts.owner.key() {
+            return Err(AccessControlError::Unauthorized.into());
+        }
+        if max_platform_fee > MAX_FEE_BASIS_POINTS
+            || max_referral_fee > MAX_FEE_BASIS_POINTS
+            || max_swap_fee > MAX_FEE_BASIS_POINTS
+        {
+            return Err(PoolError::MaxFeeExceeded.into());
+        }
+        if legacy_config.platform_fee > max_platform_fee
+            || legacy_config.referral_fee > max_referral_fee
+            || legacy_config.swap_fee > max_swap_fee
+        {
+            return Err(PoolError::FeeCeilingExceeded.into());
+        }
+
+        let config = OwnerConfig {
+            owner: legacy_config.owner,
+            pending_owner: legacy_config.pending_owner,
+            treasury: legacy_config.treasury,
+            platform_fee: legacy_config.platform_fee,
+            referral_fee: legacy_config.referral_fee,
+            swap_fee: legacy_config.swap_fee,
+            max_platform_fee,
+            max_referral_fee,
+            max_swap_fee,
+            fee_change_delay: MIN_FEE_CHANGE_DELAY,
+            pending_fees: None,
+            referrer_registry_required: false,
+            bump: legacy_config.bump,
+        };
+        _store(
+            &accounts.config,
+            &config,
+            8 + OwnerConfig::LEN,
+            &accounts.owner,
+            &accounts.system_program,
+        )?;
+
+        // The treasury only gained the pending fee recipients, it is migrated along with the config
+        let treasury: LegacyTreasury = _load_legacy::<Treasury, _>(&accounts.treasury, usize::MAX)?;
+        let len = 8 + Treasury::space(treasury.fee_recipients.len(), None);
+        _store(
+            &accounts.treasury,
+            &Treasury {
+                swap_fee_recipient: treasury.swap_fee_recipient,
+                fee_recipients: treasury.fee_recipients,
+                pending_fee_recipients: None,
+            },
+            len,
+            &accounts.owner,
+            &accounts.system_program,
+        )
+    }
+
+    /// Bring a pool to the current layout. Anyone can pay for the migration.
+    /// The first release charged every pool the fees of the owner config at the time of each swap, so the pool
+    /// snapshots the fees of the config at its migration, as done at creation, and later fee changes no longer
+    /// apply to it. Nor did it record past trades, so the pool counts as traded, and can no longer be cancelled,
+    /// once its sale has started. Every other feature added since takes its disabled default.
+    pub fn migrate_pool(ctx: Context<MigratePool>) -> Result<()> {
+        let accounts = ctx.accounts;
+        let len = 8 + size_of::<LiquidityBootstrappingPool>();
+        let legacy: LegacyLiquidityBootstrappingPool =
+            _load_legacy::<LiquidityBootstrappingPool, _>(&accounts.pool, len)?;
+
+        let pool = LiquidityBootstrappingPool {
+            asset_token: legacy.asset_token,
+            share_token: legacy.share_token,
+            creator: legacy.creator,
+            virtual_assets: legacy.virtual_assets,
+            virtual_shares: legacy.virtual_shares,
+            max_share_price: legacy.max_share_price,
+            max_shares_out: legacy.max_shares_out,
+            max_assets_in: legacy.max_assets_in,
+            start_weight_basis_points: legacy.start_weight_basis_points,
+            end_weight_basis_points: legacy.end_weight_basis_points,
+            sale_start_time: legacy.sale_start_time,
+            sale_end_time: legacy.sale_end_time,
+            vest_cliff: legacy.vest_cliff,
+            vest_end: legacy.vest_end,
+            selling_allowed: legacy.selling_allowed,
+            platform_fee: accounts.config.platform_fee,
+            referral_fee: accounts.config.referral_fee,
+            swap_fee: accounts.config.swap_fee,
+            total_purchased: legacy.total_purchased,
+            total_referred: legacy.total_referred,
+            total_swap_fees_asset: legacy.total_swap_fees_asset,
+            total_swap_fees_share: legacy.total_swap_fees_share,
+            closed: legacy.closed,
+            paused: legacy.paused,
+            // Shares may have been bought, and sold back, since the sale started
+            traded: Clock::get()?.unix_timestamp >= legacy.sale_start_time,
+            whitelist_merkle_root: legacy.whitelist_merkle_root,
+            bump: legacy.bump,
+            salt: legacy.salt,
+            ..Default::default()
+        };
+        _store(
+            &accounts.pool,
+            &pool,
+            len,
+            &accounts.payer,
+            &accounts.system_program,
+        )
+    }
+
+    /// Bring the state of a user in a pool to the current layout. Anyone can pay for the migration.
+    pub fn migrate_user_state(ctx: Context<MigrateUserState>) -> Result<()> {
+        let accounts = ctx.accounts;
+        let len = 8 + UserStateInPool::LEN;
+        let legacy: LegacyUserStateInPool =
+            _load_legacy::<UserStateInPool, _>(&accounts.user_state_in_pool, len)?;
+
+        let user_state = UserStateInPool {
+            purchased_shares: legacy.purchased_shares,
+            referred_assets: legacy.referred_assets,
+            redeemed_shares: legacy.redeemed_shares,
+            assets_paid: 0,
+            referrer: Pubkey::default(),
+            referred_volume: 0,
+            assets_spent: 0,
+        };
+        _store(
+            &accounts.user_state_in_pool,
+            &user_state,
+            len,
+            &accounts.payer,
+            &accounts.system_program,
+        )
+    }
+}
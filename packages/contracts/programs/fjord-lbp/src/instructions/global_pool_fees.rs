@@ -1,4 +1,9 @@
-use crate::{AccessControlError, FeeMapping, OwnerConfig, PoolError, Treasury};
+use crate::{
+    valid_referral_schedule, AccessControlError, FeeCeilingsLowered, FeeMapping,
+    FeeRecipientsExecuted, FeeRecipientsProposed, FeesExecuted, FeesProposed,
+    LiquidityBootstrappingPool, OwnerConfig, PendingFeeRecipients, PendingFees, PoolError,
+    PoolFeesUpdated, Treasury, MAX_FEE_CHANGE_DELAY, MIN_FEE_CHANGE_DELAY,
+};
 use anchor_lang::prelude::*;
 use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::MAX_FEE_BASIS_POINTS;
 
@@ -22,7 +27,7 @@ pub struct TreasuryFeeRecipientConfig<'info> {
         mut,
         seeds = ["treasury".as_bytes()],
         constraint = fee_percentages.len() == fee_recipients.len() @PoolError::InvalidFeeRecipients,
-        realloc = 8 + 32 + (4 + (32 + 2) * fee_recipients.len()),
+        realloc = 8 + Treasury::space(treasury.fee_recipients.len(), Some(fee_recipients.len())),
         realloc::payer = owner,
         realloc::zero = false,
         bump
@@ -40,33 +45,179 @@ pub struct TreasuryFeeRecipientConfig<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct ExecuteTreasuryFeeRecipients<'info> {
+    #[account(
+        mut,
+        seeds = ["treasury".as_bytes()],
+        realloc = 8 + Treasury::space(
+            treasury
+                .pending_fee_recipients
+                .as_ref()
+                .map_or(treasury.fee_recipients.len(), |pending| pending.fee_recipients.len()),
+            None
+        ),
+        realloc::payer = owner,
+        realloc::zero = false,
+        bump
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+    #[account(
+        seeds = ["owner_config".as_bytes()],
+        constraint = config.owner == owner.key() @AccessControlError::Unauthorized,
+        bump = config.bump
+    )]
+    pub config: Account<'info, OwnerConfig>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PoolFeeConfig<'info> {
+    #[account(mut, has_one = creator @AccessControlError::Unauthorized)]
+    pub pool: Box<Account<'info, LiquidityBootstrappingPool>>,
+    #[account(
+        seeds = ["owner_config".as_bytes()],
+        constraint = config.owner == owner.key() @AccessControlError::Unauthorized,
+        bump = config.bump
+    )]
+    pub config: Account<'info, OwnerConfig>,
+    pub owner: Signer<'info>,
+    pub creator: Signer<'info>, // Creator of the pool
+}
+
+/// Check that the fees do not exceed 100% in total, nor the ceilings of the owner config.
+fn _valid_fees(
+    config: &OwnerConfig,
+    platform_fee: u16,
+    referral_fee: u16,
+    swap_fee: u16,
+) -> Result<()> {
+    if platform_fee > MAX_FEE_BASIS_POINTS
+        || referral_fee > MAX_FEE_BASIS_POINTS
+        || swap_fee > MAX_FEE_BASIS_POINTS
+        || platform_fee + referral_fee + swap_fee > MAX_FEE_BASIS_POINTS
+    {
+        return Err(PoolError::MaxFeeExceeded.into());
+    }
+    if platform_fee > config.max_platform_fee
+        || referral_fee > config.max_referral_fee
+        || swap_fee > config.max_swap_fee
+    {
+        return Err(PoolError::FeeCeilingExceeded.into());
+    }
+    Ok(())
+}
+
 // Pool fee setter
 pub mod setter {
     use super::*;
-    pub fn set_fees(
+
+    /// Propose new global fees, and a new delay for the changes proposed after them, executable once the current
+    /// delay has elapsed. A new proposal replaces the pending one. Omitted parameters are left unchanged.
+    pub fn propose_fees(
         ctx: Context<FeeConfig>,
         platform_fee: Option<u16>,
         referral_fee: Option<u16>,
         swap_fee: Option<u16>,
+        fee_change_delay: Option<i64>,
     ) -> Result<()> {
-        if platform_fee.is_some() && platform_fee.unwrap() > MAX_FEE_BASIS_POINTS
-            || referral_fee.is_some() && referral_fee.unwrap() > MAX_FEE_BASIS_POINTS
-            || swap_fee.is_some() && swap_fee.unwrap() > MAX_FEE_BASIS_POINTS
-            || platform_fee.unwrap_or(0) + referral_fee.unwrap_or(0) + swap_fee.unwrap_or(0)
-                > MAX_FEE_BASIS_POINTS
-        {
-            return Err(PoolError::MaxFeeExceeded.into());
+        let config = &mut ctx.accounts.config;
+        let platform_fee = platform_fee.unwrap_or(config.platform_fee);
+        let referral_fee = referral_fee.unwrap_or(config.referral_fee);
+        let swap_fee = swap_fee.unwrap_or(config.swap_fee);
+        let fee_change_delay = fee_change_delay.unwrap_or(config.fee_change_delay);
+        _valid_fees(config, platform_fee, referral_fee, swap_fee)?;
+        if !(MIN_FEE_CHANGE_DELAY..=MAX_FEE_CHANGE_DELAY).contains(&fee_change_delay) {
+            return Err(PoolError::InvalidFeeChangeDelay.into());
         }
 
+        let executable_at = Clock::get()?.unix_timestamp + config.fee_change_delay;
+        config.pending_fees = Some(PendingFees {
+            platform_fee,
+            referral_fee,
+            swap_fee,
+            fee_change_delay,
+            executable_at,
+        });
+
+        emit!(FeesProposed {
+            platform_fee,
+            referral_fee,
+            swap_fee,
+            fee_change_delay,
+            executable_at,
+        });
+        Ok(())
+    }
+
+    /// Apply the pending fee change once its delay has elapsed, as long as it is within the current ceilings.
+    pub fn execute_fees(ctx: Context<FeeConfig>) -> Result<()> {
         let config = &mut ctx.accounts.config;
-        config.platform_fee = platform_fee.unwrap_or(config.platform_fee);
-        config.referral_fee = referral_fee.unwrap_or(config.referral_fee);
-        config.swap_fee = swap_fee.unwrap_or(config.swap_fee);
+        let pending = config.pending_fees.ok_or(PoolError::FeeChangeNotProposed)?;
+        if Clock::get()?.unix_timestamp < pending.executable_at {
+            return Err(PoolError::FeeChangeNotReady.into());
+        }
+        // The ceilings may have been lowered since the proposal
+        _valid_fees(
+            config,
+            pending.platform_fee,
+            pending.referral_fee,
+            pending.swap_fee,
+        )?;
 
+        config.platform_fee = pending.platform_fee;
+        config.referral_fee = pending.referral_fee;
+        config.swap_fee = pending.swap_fee;
+        config.fee_change_delay = pending.fee_change_delay;
+        config.pending_fees = None;
+
+        emit!(FeesExecuted {
+            platform_fee: pending.platform_fee,
+            referral_fee: pending.referral_fee,
+            swap_fee: pending.swap_fee,
+            fee_change_delay: pending.fee_change_delay,
+        });
         Ok(())
     }
 
-    pub fn set_fee_recipients(
+    /// Lower the fee ceilings right away, and the global fees above them along. Omitted ceilings are left unchanged.
+    pub fn lower_fee_ceilings(
+        ctx: Context<FeeConfig>,
+        max_platform_fee: Option<u16>,
+        max_referral_fee: Option<u16>,
+        max_swap_fee: Option<u16>,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let max_platform_fee = max_platform_fee.unwrap_or(config.max_platform_fee);
+        let max_referral_fee = max_referral_fee.unwrap_or(config.max_referral_fee);
+        let max_swap_fee = max_swap_fee.unwrap_or(config.max_swap_fee);
+        if max_platform_fee > config.max_platform_fee
+            || max_referral_fee > config.max_referral_fee
+            || max_swap_fee > config.max_swap_fee
+        {
+            return Err(PoolError::FeeCeilingRaised.into());
+        }
+
+        config.max_platform_fee = max_platform_fee;
+        config.max_referral_fee = max_referral_fee;
+        config.max_swap_fee = max_swap_fee;
+        config.platform_fee = config.platform_fee.min(max_platform_fee);
+        config.referral_fee = config.referral_fee.min(max_referral_fee);
+        config.swap_fee = config.swap_fee.min(max_swap_fee);
+
+        emit!(FeeCeilingsLowered {
+            max_platform_fee,
+            max_referral_fee,
+            max_swap_fee,
+        });
+        Ok(())
+    }
+
+    /// Propose new treasury fee recipients, executable once the fee change delay has elapsed.
+    /// A new proposal replaces the pending one.
+    pub fn propose_fee_recipients(
         ctx: Context<TreasuryFeeRecipientConfig>,
         swap_fee_recipient: Option<Pubkey>,
         fee_recipients: Vec<Pubkey>,
@@ -89,15 +240,93 @@ pub mod setter {
         }
 
         let treasury = &mut ctx.accounts.treasury;
-        treasury.swap_fee_recipient = swap_fee_recipient.unwrap_or(treasury.swap_fee_recipient);
-        treasury.fee_recipients.clear();
-        for (i, recipient) in fee_recipients.iter().enumerate() {
-            treasury.fee_recipients.push(FeeMapping {
+        let swap_fee_recipient = swap_fee_recipient.unwrap_or(treasury.swap_fee_recipient);
+        let fee_recipients: Vec<FeeMapping> = fee_recipients
+            .iter()
+            .zip(fee_percentages)
+            .map(|(recipient, percentage)| FeeMapping {
                 user: *recipient,
-                percentage: fee_percentages[i],
+                percentage,
             })
+            .collect();
+        let executable_at = Clock::get()?.unix_timestamp + ctx.accounts.config.fee_change_delay;
+        treasury.pending_fee_recipients = Some(PendingFeeRecipients {
+            swap_fee_recipient,
+            fee_recipients: fee_recipients.clone(),
+            executable_at,
+        });
+
+        emit!(FeeRecipientsProposed {
+            swap_fee_recipient,
+            fee_recipients,
+            executable_at,
+        });
+        Ok(())
+    }
+
+    /// Apply the pending treasury fee recipients once the fee change delay has elapsed.
+    pub fn execute_fee_recipients(ctx: Context<ExecuteTreasuryFeeRecipients>) -> Result<()> {
+        let treasury = &mut ctx.accounts.treasury;
+        let pending = treasury
+            .pending_fee_recipients
+            .take()
+            .ok_or(PoolError::FeeChangeNotProposed)?;
+        if Clock::get()?.unix_timestamp < pending.executable_at {
+            return Err(PoolError::FeeChangeNotReady.into());
+        }
+
+        treasury.swap_fee_recipient = pending.swap_fee_recipient;
+        treasury.fee_recipients = pending.fee_recipients.clone();
+
+        emit!(FeeRecipientsExecuted {
+            swap_fee_recipient: pending.swap_fee_recipient,
+            fee_recipients: pending.fee_recipients,
+        });
+        Ok(())
+    }
+
+    /// Override the fees a pool snapshotted from the owner config at its creation, as agreed between the owner
+    /// and the creator of the pool, who both sign, within the fee ceilings. Omitted fees are left unchanged, and
+    /// an overridden referral fee replaces the base fee of the referral schedule.
+    pub fn set_pool_fees(
+        ctx: Context<PoolFeeConfig>,
+        platform_fee: Option<u16>,
+        referral_fee: Option<u16>,
+        swap_fee: Option<u16>,
+    ) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        if pool.closed {
+            return Err(PoolError::PoolFeesUpdateDisallowed.into());
         }
 
+        // An overridden referral fee replaces the base fee of the referral schedule, which would otherwise prevail
+        let mut referral_schedule = pool.referral_schedule;
+        if referral_fee.is_some() {
+            referral_schedule.fee_basis_points = None;
+        }
+        let platform_fee = platform_fee.unwrap_or(pool.platform_fee);
+        let referral_fee = referral_fee.unwrap_or(pool.referral_fee);
+        let swap_fee = swap_fee.unwrap_or(pool.swap_fee);
+        _valid_fees(&ctx.accounts.config, platform_fee, referral_fee, swap_fee)?;
+        // The referral schedule of the pool must still fit along with the new fees
+        if !valid_referral_schedule(&referral_schedule, referral_fee, platform_fee, swap_fee) {
+            return Err(PoolError::InvalidReferralSchedule.into());
+        }
+        if referral_schedule.max_fee(referral_fee) > ctx.accounts.config.max_referral_fee {
+            return Err(PoolError::FeeCeilingExceeded.into());
+        }
+
+        pool.platform_fee = platform_fee;
+        pool.referral_fee = referral_fee;
+        pool.swap_fee = swap_fee;
+        pool.referral_schedule = referral_schedule;
+
+        emit!(PoolFeesUpdated {
+            pool: pool.key(),
+            platform_fee,
+            referral_fee,
+            swap_fee,
+        });
         Ok(())
     }
 }
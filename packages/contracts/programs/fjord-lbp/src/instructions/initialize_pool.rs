@@ -1,10 +1,10 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use std::mem::size_of;
 use std::fmt::Debug;
 
-use crate::{LiquidityBootstrappingPool, PoolCreatedEvent, PoolError, ONE_DAY_SECONDS};
+use crate::{transfer_tokens, valid_referral_schedule, valid_weight_knots, LiquidityBootstrappingPool, OwnerConfig, PoolCreatedEvent, PoolError, ReferralSchedule, WeightCurve, WeightKnot, WhitelistLeafVersion, MAX_WEIGHT_KNOTS, MAX_WHITELIST_TIERS, ONE_DAY_SECONDS};
 
 #[derive(Accounts)]
 #[instruction(salt: String)]
@@ -18,32 +18,54 @@ pub struct InitializePool<'info> {
     )]
     pub pool: Account<'info, LiquidityBootstrappingPool>,
     // Token mint
-    pub asset_token_mint: Account<'info, Mint>,
-    pub share_token_mint: Account<'info, Mint>,
+    #[account(mint::token_program = asset_token_program)]
+    pub asset_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = share_token_program)]
+    pub share_token_mint: Box<InterfaceAccount<'info, Mint>>,
     // Token accounts that the pool will use to hold the tokens
     #[account(
         init,
         payer = creator, 
         associated_token::mint = share_token_mint,
-        associated_token::authority = pool
+        associated_token::authority = pool,
+        associated_token::token_program = share_token_program
     )]
-    pub pool_share_token_account: Account<'info, TokenAccount>,
+    pub pool_share_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         init,
         payer = creator, 
         associated_token::mint = asset_token_mint,
-        associated_token::authority = pool
+        associated_token::authority = pool,
+        associated_token::token_program = asset_token_program
     )]
-    pub pool_asset_token_account: Account<'info, TokenAccount>,
+    pub pool_asset_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
     // User token account
-    #[account(mut, associated_token::mint = asset_token_mint, associated_token::authority = creator)]
-    pub creator_asset_token_account: Account<'info, TokenAccount>,
-    #[account(mut, associated_token::mint = share_token_mint, associated_token::authority = creator)]
-    pub creator_share_token_account: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = asset_token_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = asset_token_program
+    )]
+    pub creator_asset_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = share_token_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = share_token_program
+    )]
+    pub creator_share_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    // Global pool config, whose fees are snapshotted into the pool
+    #[account(
+        seeds = ["owner_config".as_bytes()],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, OwnerConfig>>,
     #[account(mut)]
     pub creator: Signer<'info>,  // Creator of the pool
     pub associated_token_program: Program<'info, AssociatedToken>,
-    pub token_program: Program<'info, Token>,
+    // Token programs owning the asset and share mints (SPL Token or Token-2022)
+    pub asset_token_program: Interface<'info, TokenInterface>,
+    pub share_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
@@ -58,126 +80,203 @@ pub fn create_pool(
   max_share_price: u64,
   max_shares_out: u64,
   max_assets_in: u64,
+  min_assets_raised: u64,
+  max_assets_raised: u64,
+  max_assets_per_user: u64,
+  max_shares_per_user: u64,
+  min_assets_per_buy: u64,
   start_weight_basis_points: u16,
   end_weight_basis_points: u16,
+  weight_curve: WeightCurve,
+  weight_knots: [WeightKnot; MAX_WEIGHT_KNOTS],
   sale_start_time: i64,
   sale_end_time: i64,
   vest_cliff: i64,
   vest_end: i64,
   whitelist_merkle_root: [u8; 32],
+  whitelist_leaf_version: WhitelistLeafVersion,
+  whitelist_signer: Pubkey,
+  whitelist_tier_start_times: [i64; MAX_WHITELIST_TIERS],
+  public_sale_time: i64,
+  referral_schedule: ReferralSchedule,
   selling_allowed: bool,
 ) -> Result<()> {
   let pool = &mut ctx.accounts.pool;
 
   require_keys_neq!(ctx.accounts.asset_token_mint.key(), ctx.accounts.share_token_mint.key(), PoolError::InvalidAssetOrShare);
 
+  pool.asset_token = ctx.accounts.asset_token_mint.key();
+  pool.share_token = ctx.accounts.share_token_mint.key();
+  pool.creator = ctx.accounts.creator.key();
+  
+  pool.virtual_assets = virtual_assets;
+  pool.virtual_shares = virtual_shares;
+  pool.max_share_price = max_share_price;
+  pool.max_shares_out = max_shares_out;
+  pool.max_assets_in = max_assets_in;
+  pool.min_assets_raised = min_assets_raised;
+  pool.max_assets_raised = max_assets_raised;
+  pool.max_assets_per_user = max_assets_per_user;
+  pool.max_shares_per_user = max_shares_per_user;
+  pool.min_assets_per_buy = min_assets_per_buy;
+
+  pool.start_weight_basis_points = start_weight_basis_points;
+  pool.end_weight_basis_points = end_weight_basis_points;
+  pool.weight_curve = weight_curve;
+  pool.weight_knots = weight_knots;
+
+  pool.sale_start_time = sale_start_time;
+  pool.sale_end_time = sale_end_time;
+  pool.vest_cliff = vest_cliff;
+  pool.vest_end = vest_end;
+
+  pool.selling_allowed = selling_allowed;
+  pool.whitelist_merkle_root = whitelist_merkle_root;
+  pool.whitelist_leaf_version = whitelist_leaf_version;
+  pool.whitelist_signer = whitelist_signer;
+  pool.whitelist_tier_start_times = whitelist_tier_start_times;
+  pool.public_sale_time = public_sale_time;
+  pool.referral_schedule = referral_schedule;
+  // Later changes of the global fees do not apply to the pool
+  pool.platform_fee = ctx.accounts.config.platform_fee;
+  pool.referral_fee = ctx.accounts.config.referral_fee;
+  pool.swap_fee = ctx.accounts.config.swap_fee;
+  pool.bump = ctx.bumps.pool;
+  pool.salt = salt;
+
+  validate_pool_config(pool, assets, shares)?;
+
+  // Referral tiers are held to the ceiling of the owner config, like the global referral fee
+  if pool.referral_schedule.max_fee(pool.referral_fee) > ctx.accounts.config.max_referral_fee {
+    return err!(PoolError::FeeCeilingExceeded);
+  }
+
+      // Check for sufficient share token balance
+  if ctx.accounts.creator_share_token_account.amount < shares {
+      return err!(PoolError::InsufficientShares);
+  }
+
+          // Check for sufficient asset token balance
+  if ctx.accounts.creator_asset_token_account.amount < assets {
+      return err!(PoolError::InsufficientAssets);
+  }
+
+  // Transfer the tokens to the pool
+  transfer_tokens(
+      ctx.accounts.asset_token_program.to_account_info(),
+      ctx.accounts.creator_asset_token_account.to_account_info(),
+      ctx.accounts.pool_asset_token_account.to_account_info(),
+      ctx.accounts.asset_token_mint.to_account_info(),
+      ctx.accounts.creator.to_account_info(),
+      assets,
+      ctx.accounts.asset_token_mint.decimals,
+  )?;
+
+  // Transfer the share tokens to the pool
+  transfer_tokens(
+      ctx.accounts.share_token_program.to_account_info(),
+      ctx.accounts.creator_share_token_account.to_account_info(),
+      ctx.accounts.pool_share_token_account.to_account_info(),
+      ctx.accounts.share_token_mint.to_account_info(),
+      ctx.accounts.creator.to_account_info(),
+      shares,
+      ctx.accounts.share_token_mint.decimals,
+  )?;
+
+  // Emit creation event
+  emit!(PoolCreatedEvent {
+      pool: ctx.accounts.pool.key()
+  });
+
+  Ok(())
+}
+
+/// Whether a purchase of the minimum size fits within the per-wallet cap, the hard cap and the maximum assets in
+/// of the pool, as otherwise every purchase fails.
+pub fn valid_min_assets_per_buy(pool: &LiquidityBootstrappingPool) -> bool {
+  (pool.max_assets_per_user == 0 || pool.min_assets_per_buy <= pool.max_assets_per_user)
+    && (pool.max_assets_raised == 0 || pool.min_assets_per_buy <= pool.max_assets_raised)
+    && pool.min_assets_per_buy < pool.max_assets_in
+}
+
+/// Validate the configuration of `pool`, holding `assets` and `shares`, when it is created or updated.
+pub fn validate_pool_config(pool: &LiquidityBootstrappingPool, assets: u64, shares: u64) -> Result<()> {
   // check if the sale start time is in the future
   let current_time = Clock::get()?.unix_timestamp;
 
-  if current_time + ONE_DAY_SECONDS > sale_end_time || sale_end_time - sale_start_time < ONE_DAY_SECONDS {
+  if current_time + ONE_DAY_SECONDS > pool.sale_end_time || pool.sale_end_time - pool.sale_start_time < ONE_DAY_SECONDS {
       return err!(PoolError::SalePeriodLow);
   }
 
-  if vest_end != 0 && sale_end_time <= vest_end {
-      if sale_end_time > vest_cliff {
+  if pool.vest_end != 0 && pool.sale_end_time <= pool.vest_end {
+      if pool.sale_end_time > pool.vest_cliff {
           return err!(PoolError::InvalidVestCliff);
       }
-      if vest_cliff >= vest_end {
+      if pool.vest_cliff >= pool.vest_end {
           return err!(PoolError::InvalidVestEnd);
       }
   }
 
-  if vest_end != 0 && sale_end_time > vest_end {
+  if pool.vest_end != 0 && pool.sale_end_time > pool.vest_end {
       return err!(PoolError::InvalidVestEnd);
   }
 
 
-  if !(100..=9900).contains(&start_weight_basis_points) || !(100..=9900).contains(&end_weight_basis_points) {
+  if !(100..=9900).contains(&pool.start_weight_basis_points) || !(100..=9900).contains(&pool.end_weight_basis_points) {
     return err!(PoolError::InvalidWeightConfig);
   }
 
-  if assets == 0 && virtual_assets == 0 {
+  // Exponential schedules decay over a positive half-life, piecewise schedules go through their knots in order during the sale
+  let valid_weight_curve = match pool.weight_curve {
+    WeightCurve::Linear => true,
+    WeightCurve::Exponential { half_life } => half_life > 0,
+    WeightCurve::Piecewise => valid_weight_knots(&pool.weight_knots, pool.sale_start_time, pool.sale_end_time),
+  };
+  if !valid_weight_curve {
+    return err!(PoolError::InvalidWeightConfig);
+  }
+
+  if assets == 0 && pool.virtual_assets == 0 {
       return err!(PoolError::InvalidAssetValue);
   }
 
-  if shares == 0 && virtual_shares == 0 {
+  if shares == 0 && pool.virtual_shares == 0 {
       return err!(PoolError::InvalidShareValue);
   }
 
-  if max_share_price == 0 {
+  if pool.max_share_price == 0 {
       return err!(PoolError::InvalidSharePrice);
   }
 
-  if max_shares_out == 0 {
+  if pool.max_shares_out == 0 {
       return err!(PoolError::InvalidMaxSharesOut);
   }
 
-  if max_assets_in == 0 {
+  if pool.max_assets_in == 0 {
       return err!(PoolError::InvalidMaxAssetsIn);
   }
 
-      // Check for sufficient share token balance
-  if ctx.accounts.creator_share_token_account.amount < shares {
-      return err!(PoolError::InsufficientShares);
+  // Every whitelist tier must open before the sale ends
+  if pool.whitelist_tier_start_times.iter().any(|&start_time| start_time >= pool.sale_end_time) {
+      return err!(PoolError::InvalidWhitelistTier);
   }
 
-          // Check for sufficient asset token balance
-  if ctx.accounts.creator_asset_token_account.amount < assets {
-      return err!(PoolError::InsufficientAssets);
+  if pool.public_sale_time != 0 && pool.public_sale_time >= pool.sale_end_time {
+      return err!(PoolError::InvalidPublicSaleTime);
   }
-  
 
-  pool.asset_token = ctx.accounts.asset_token_mint.key();
-  pool.share_token = ctx.accounts.share_token_mint.key();
-  pool.creator = ctx.accounts.creator.key();
-  
-  pool.virtual_assets = virtual_assets;
-  pool.virtual_shares = virtual_shares;
-  pool.max_share_price = max_share_price;
-  pool.max_shares_out = max_shares_out;
-  pool.max_assets_in = max_assets_in;
-
-  pool.start_weight_basis_points = start_weight_basis_points;
-  pool.end_weight_basis_points = end_weight_basis_points;
-
-  pool.sale_start_time = sale_start_time;
-  pool.sale_end_time = sale_end_time;
-  pool.vest_cliff = vest_cliff;
-  pool.vest_end = vest_end;
-
-  pool.selling_allowed = selling_allowed;
-  pool.whitelist_merkle_root = whitelist_merkle_root;
-  pool.bump = ctx.bumps.pool;
-  pool.salt = salt;
-
-  // Transfer the tokens to the pool
-  let asset_transfer_instruction = Transfer {
-      from: ctx.accounts.creator_asset_token_account.to_account_info(),
-      to: ctx.accounts.pool_asset_token_account.to_account_info(),
-      authority: ctx.accounts.creator.to_account_info(),
-  };
-  let asset_cpi_ctx = CpiContext::new(
-      ctx.accounts.token_program.to_account_info(),
-      asset_transfer_instruction
-  );
-  token::transfer(asset_cpi_ctx, assets)?;
+  // The soft cap must be reachable before the hard cap ends the sale
+  if pool.max_assets_raised != 0 && pool.min_assets_raised > pool.max_assets_raised {
+      return err!(PoolError::InvalidSaleCaps);
+  }
 
-  // Transfer the share tokens to the pool
-  let share_transfer_instruction = Transfer {
-      from: ctx.accounts.creator_share_token_account.to_account_info(),
-      to: ctx.accounts.pool_share_token_account.to_account_info(),
-      authority: ctx.accounts.creator.to_account_info(),
-  };
-  let share_cpi_ctx = CpiContext::new(
-      ctx.accounts.token_program.to_account_info(),
-      share_transfer_instruction
-  );
-  token::transfer(share_cpi_ctx, shares)?;
+  if !valid_min_assets_per_buy(pool) {
+      return err!(PoolError::InvalidMinAssetsPerBuy);
+  }
 
-  // Emit creation event
-  emit!(PoolCreatedEvent {
-      pool: ctx.accounts.pool.key()
-  });
+  if !valid_referral_schedule(&pool.referral_schedule, pool.referral_fee, pool.platform_fee, pool.swap_fee) {
+      return err!(PoolError::InvalidReferralSchedule);
+  }
 
   Ok(())
 }
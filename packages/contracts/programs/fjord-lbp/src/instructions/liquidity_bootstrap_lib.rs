@@ -1,28 +1,30 @@
 use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::MAX_FEE_BASIS_POINTS;
 
-const DECIMAL_SCALING: u64 = 8;
-const SCALED_DECIMALS: u64 = 10_u64.pow(DECIMAL_SCALING as u32);
+use crate::{WeightAdjustment, WeightCurve, WeightKnot, MAX_WEIGHT_KNOTS};
+
+const DECIMAL_SCALING: u32 = 8;
+const SCALED_DECIMALS: u128 = 10_u128.pow(DECIMAL_SCALING);
 pub mod structs {
     use anchor_lang::prelude::*;
     #[derive(AnchorDeserialize, AnchorSerialize, Clone)]
     pub struct ComputedReservesAndWeights {
-        pub asset_reserve: u64,
-        pub share_reserve: u64,
-        pub asset_weight: u64,
-        pub share_weight: u64,
+        pub asset_reserve: u128,
+        pub share_reserve: u128,
+        pub asset_weight: u128,
+        pub share_weight: u128,
     }
 }
 
 pub struct ScaledReserves {
-    pub asset_reserve_scaled: u64,
-    pub share_reserve_scaled: u64,
+    pub asset_reserve_scaled: u128,
+    pub share_reserve_scaled: u128,
 }
 
 pub struct FormattedReserves {
-    pub asset_weight: u64,
-    pub share_weight: u64,
-    pub asset_reserve_scaled: u64,
-    pub share_reserve_scaled: u64,
+    pub asset_weight: u128,
+    pub share_weight: u128,
+    pub asset_reserve_scaled: u128,
+    pub share_reserve_scaled: u128,
 }
 
 pub struct PreviewAmountArgs {
@@ -39,6 +41,9 @@ pub struct PreviewAmountArgs {
     pub sale_end_time: i64,
     pub start_weight_basis_points: u16,
     pub end_weight_basis_points: u16,
+    pub weight_curve: WeightCurve,
+    pub weight_knots: [WeightKnot; MAX_WEIGHT_KNOTS],
+    pub weight_adjustment: WeightAdjustment,
     pub total_swap_fees_asset: u64,
     pub total_swap_fees_share: u64,
 }
@@ -47,9 +52,9 @@ pub mod math {
     use self::structs::ComputedReservesAndWeights;
     use super::*;
     use crate::{
-        get_amount_in, get_amount_out, mul_div,
-        safe_math::{div, mul, safe_add, safe_sub},
-        safe_pow, weighted_math_lib, PreviewAmountArgs, SafeMathError,
+        adjusted_weight, get_amount_in, get_amount_out, mul_div,
+        safe_math::{div, mul, safe_add, safe_sub, to_u64},
+        safe_pow, PreviewAmountArgs, SafeMathError,
     };
 
     pub fn preview_shares_out(
@@ -62,7 +67,8 @@ pub mod math {
             asset_reserve_scaled,
             share_reserve_scaled,
         } = _get_scaled_reserves_and_weights(&args)?;
-        let assets_in_scaled = _scale_token(args.asset_token_decimal, assets_in, true)?;
+        let assets_in_scaled = _scale_token(args.asset_token_decimal, assets_in.into(), true)?;
+        let max_share_price = u128::from(args.max_share_price);
         let mut shares_out = get_amount_out(
             assets_in_scaled,
             asset_reserve_scaled,
@@ -71,11 +77,10 @@ pub mod math {
             share_weight,
         )?;
 
-        if mul_div(assets_in_scaled, SCALED_DECIMALS, shares_out)? > args.max_share_price {
-            shares_out = mul_div(assets_in_scaled, args.max_share_price, SCALED_DECIMALS)?;
+        if mul_div(assets_in_scaled, SCALED_DECIMALS, shares_out)? > max_share_price {
+            shares_out = mul_div(assets_in_scaled, SCALED_DECIMALS, max_share_price)?;
         }
-        shares_out = _scale_token(args.share_token_decimal, shares_out, false)?;
-        Ok(shares_out)
+        to_u64(_scale_token(args.share_token_decimal, shares_out, false)?)
     }
 
     pub fn preview_assets_in(
@@ -88,7 +93,8 @@ pub mod math {
             asset_reserve_scaled,
             share_reserve_scaled,
         } = _get_scaled_reserves_and_weights(&args)?;
-        let shares_out_scaled = _scale_token(args.share_token_decimal, shares_out, true)?;
+        let shares_out_scaled = _scale_token(args.share_token_decimal, shares_out.into(), true)?;
+        let max_share_price = u128::from(args.max_share_price);
         let mut assets_in = get_amount_in(
             shares_out_scaled,
             asset_reserve_scaled,
@@ -96,14 +102,13 @@ pub mod math {
             asset_weight,
             share_weight,
         )?;
-        if mul_div(assets_in, SCALED_DECIMALS, shares_out_scaled)? > args.max_share_price {
-            assets_in = mul_div(shares_out_scaled, SCALED_DECIMALS, args.max_share_price)?;
+        if mul_div(assets_in, SCALED_DECIMALS, shares_out_scaled)? > max_share_price {
+            assets_in = mul_div(shares_out_scaled, max_share_price, SCALED_DECIMALS)?;
         }
         if assets_in == 0 {
             return Err(SafeMathError::InvalidAssetsIn);
         }
-        assets_in = _scale_token(args.asset_token_decimal, assets_in, false)?;
-        Ok(assets_in)
+        to_u64(_scale_token(args.asset_token_decimal, assets_in, false)?)
     }
 
     pub fn preview_shares_in(
@@ -117,7 +122,8 @@ pub mod math {
             share_reserve_scaled,
         } = _get_scaled_reserves_and_weights(&args)?;
 
-        let assets_out_scaled = _scale_token(args.asset_token_decimal, assets_out, true)?;
+        let assets_out_scaled = _scale_token(args.asset_token_decimal, assets_out.into(), true)?;
+        let max_share_price = u128::from(args.max_share_price);
 
         let mut shares_in = get_amount_in(
             assets_out_scaled,
@@ -127,16 +133,15 @@ pub mod math {
             asset_weight,
         )?;
 
-        if mul_div(assets_out_scaled, SCALED_DECIMALS, shares_in)? > args.max_share_price {
-            shares_in = mul_div(assets_out_scaled, SCALED_DECIMALS, args.max_share_price)?;
+        if mul_div(assets_out_scaled, SCALED_DECIMALS, shares_in)? > max_share_price {
+            shares_in = mul_div(assets_out_scaled, SCALED_DECIMALS, max_share_price)?;
         }
 
         if shares_in == 0 {
             return Err(SafeMathError::InvalidSharesIn);
         }
 
-        shares_in = _scale_token(args.share_token_decimal, shares_in, false)?;
-        Ok(shares_in)
+        to_u64(_scale_token(args.share_token_decimal, shares_in, false)?)
     }
 
     pub fn preview_assets_out(
@@ -150,7 +155,8 @@ pub mod math {
             share_reserve_scaled,
         } = _get_scaled_reserves_and_weights(&args)?;
 
-        let shares_in_scaled = _scale_token(args.share_token_decimal, shares_in, true)?;
+        let shares_in_scaled = _scale_token(args.share_token_decimal, shares_in.into(), true)?;
+        let max_share_price = u128::from(args.max_share_price);
         let mut assets_out = get_amount_out(
             shares_in_scaled,
             share_reserve_scaled,
@@ -159,11 +165,10 @@ pub mod math {
             asset_weight,
         )?;
 
-        if mul_div(assets_out, SCALED_DECIMALS, shares_in_scaled)? > args.max_share_price {
-            assets_out = mul_div(shares_in_scaled, args.max_share_price, SCALED_DECIMALS)?;
+        if mul_div(assets_out, SCALED_DECIMALS, shares_in_scaled)? > max_share_price {
+            assets_out = mul_div(shares_in_scaled, max_share_price, SCALED_DECIMALS)?;
         }
-        assets_out = _scale_token(args.asset_token_decimal, assets_out, false)?;
-        Ok(assets_out)
+        to_u64(_scale_token(args.asset_token_decimal, assets_out, false)?)
     }
 
     fn _get_scaled_reserves_and_weights(
@@ -208,32 +213,38 @@ pub mod math {
             sale_end_time,
             start_weight_basis_points,
             end_weight_basis_points,
+            weight_curve,
+            weight_knots,
+            weight_adjustment,
             asset_token_decimal: _,
             share_token_decimal: _,
             max_share_price: _,
             total_swap_fees_asset,
             total_swap_fees_share,
         } = *args;
-        let asset_reserve: u64 =
-            safe_sub(safe_add(assets, virtual_assets)?, total_swap_fees_asset)?;
-        let share_reserve: u64 = safe_sub(
-            safe_sub(safe_add(shares, virtual_shares)?, total_purchased)?,
-            total_swap_fees_share,
+        let asset_reserve: u128 = safe_sub(
+            safe_add(u128::from(assets), u128::from(virtual_assets))?,
+            u128::from(total_swap_fees_asset),
         )?;
-        let total_seconds = sale_end_time - sale_start_time;
-
-        let mut seconds_elapsed = 0;
-        if current_time > sale_start_time {
-            seconds_elapsed = current_time - sale_start_time;
-        }
-        let asset_weight = weighted_math_lib::linear_interpolation(
-            start_weight_basis_points.into(),
-            end_weight_basis_points.into(),
-            seconds_elapsed.try_into().unwrap(),
-            total_seconds.try_into().unwrap(),
+        let share_reserve: u128 = safe_sub(
+            safe_sub(
+                safe_add(u128::from(shares), u128::from(virtual_shares))?,
+                u128::from(total_purchased),
+            )?,
+            u128::from(total_swap_fees_share),
+        )?;
+        let asset_weight = adjusted_weight(
+            weight_curve,
+            &weight_knots,
+            weight_adjustment,
+            start_weight_basis_points,
+            end_weight_basis_points,
+            sale_start_time,
+            sale_end_time,
+            current_time,
         )?;
 
-        let share_weight = MAX_FEE_BASIS_POINTS as u64 - asset_weight;
+        let share_weight = MAX_FEE_BASIS_POINTS as u128 - asset_weight;
 
         Ok(ComputedReservesAndWeights {
             asset_reserve,
@@ -246,8 +257,8 @@ pub mod math {
     fn scaled_reserves(
         asset_token_decimals: u8,
         share_token_decimals: u8,
-        asset_reserve: u64,
-        share_reserve: u64,
+        asset_reserve: u128,
+        share_reserve: u128,
     ) -> Result<ScaledReserves, SafeMathError> {
         Ok(ScaledReserves {
             asset_reserve_scaled: _scale_token(asset_token_decimals, asset_reserve, true)?,
@@ -255,34 +266,65 @@ pub mod math {
         })
     }
 
-    fn _scale_token(
+    pub(crate) fn _scale_token(
         token_decimals: u8,
-        amount: u64,
+        amount: u128,
         scale_before: bool,
-    ) -> Result<u64, SafeMathError> {
+    ) -> Result<u128, SafeMathError> {
         let mut scaled_amount = amount;
-        let token_decimals_u64 = u64::from(token_decimals);
+        let token_decimals_u32 = u32::from(token_decimals);
 
-        let dec_diff = if token_decimals_u64 < DECIMAL_SCALING {
-            DECIMAL_SCALING - token_decimals_u64
+        let dec_diff = if token_decimals_u32 < DECIMAL_SCALING {
+            DECIMAL_SCALING - token_decimals_u32
         } else {
-            token_decimals_u64 - DECIMAL_SCALING
+            token_decimals_u32 - DECIMAL_SCALING
         };
 
         // Determine whether to multiply or divide based on `scale_before` flag
-        if (token_decimals_u64 < DECIMAL_SCALING && scale_before)
-            || (token_decimals_u64 > DECIMAL_SCALING && !scale_before)
+        if (token_decimals_u32 < DECIMAL_SCALING && scale_before)
+            || (token_decimals_u32 > DECIMAL_SCALING && !scale_before)
         {
-            scaled_amount = mul(scaled_amount, safe_pow(10u64, dec_diff as u32)?)?;
-        } else if (token_decimals_u64 < DECIMAL_SCALING && !scale_before)
-            || (token_decimals_u64 > DECIMAL_SCALING && scale_before)
+            scaled_amount = mul(scaled_amount, safe_pow(10u128, dec_diff)?)?;
+        } else if (token_decimals_u32 < DECIMAL_SCALING && !scale_before)
+            || (token_decimals_u32 > DECIMAL_SCALING && scale_before)
         {
-            scaled_amount = div(scaled_amount, safe_pow(10u64, dec_diff as u32)?)?;
+            scaled_amount = div(scaled_amount, safe_pow(10u128, dec_diff)?)?;
         }
 
         Ok(scaled_amount)
     }
 
+    /// Compute the number of shares unlocked at `current_time` out of a user's total allocation.
+    /// Nothing is unlocked before `vest_cliff`, shares unlock linearly between `vest_cliff` and `vest_end`,
+    /// and the full allocation is unlocked from `vest_end` onwards. A `vest_end` of 0 disables vesting.
+    /// * `total_shares` - The total number of shares allocated to the user.
+    /// * `current_time` - The current timestamp.
+    /// * `vest_cliff` - The timestamp before which no shares are unlocked.
+    /// * `vest_end` - The timestamp at which all shares are unlocked.
+    pub fn compute_vested_shares(
+        total_shares: u64,
+        current_time: i64,
+        vest_cliff: i64,
+        vest_end: i64,
+    ) -> Result<u64, SafeMathError> {
+        if vest_end == 0 || current_time >= vest_end {
+            return Ok(total_shares);
+        }
+        if current_time < vest_cliff {
+            return Ok(0);
+        }
+        let seconds_vested = u128::try_from(current_time - vest_cliff)
+            .map_err(|_| SafeMathError::ConversionOverflow)?;
+        let vesting_seconds =
+            u128::try_from(vest_end - vest_cliff).map_err(|_| SafeMathError::ConversionOverflow)?;
+
+        to_u64(mul_div(
+            u128::from(total_shares),
+            seconds_vested,
+            vesting_seconds,
+        )?)
+    }
+
     pub fn calculate_fee(amount: u64, fee: u16) -> u64 {
         (u128::from(amount) * u128::from(fee) / u128::from(MAX_FEE_BASIS_POINTS)) as u64
     }
@@ -1,15 +1,19 @@
+pub mod account_migration;
 pub mod global_pool_fees;
 pub mod initialize_pool;
 pub mod liquidity_bootstrap_lib;
 pub mod pool_management;
 pub mod read_context;
 pub mod redemption;
+pub mod referrer_registry;
 pub mod swap;
 
+pub use account_migration::*;
 pub use global_pool_fees::*;
 pub use initialize_pool::*;
 pub use liquidity_bootstrap_lib::*;
 pub use pool_management::*;
 pub use read_context::*;
 pub use redemption::*;
+pub use referrer_registry::*;
 pub use swap::*;
@@ -1,8 +1,10 @@
 
 // Pool fee setter
-use crate::LiquidityBootstrappingPool;
+use crate::{adjusted_weight, close_token_account_from, is_native_mint, safe_sub, transfer_lamports_from, transfer_tokens, transfer_tokens_from, validate_pool_config, Cancel, ClosedPool, LiquidityBootstrappingPool, PoolAccountsClosed, PoolConfigUpdated, PoolError, PublicSaleTimeUpdated, WeightAdjustment, WeightCurve, WeightKnot, WeightsAdjusted, WhitelistUpdated, CLOSE_POOL_ACCOUNTS_GRACE_PERIOD, MAX_WEIGHT_KNOTS};
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, Token};
+use anchor_lang::Discriminator;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 #[derive(Accounts)]
 pub struct OnlyPoolCreator<'info> {
@@ -13,16 +15,524 @@ pub struct OnlyPoolCreator<'info> {
     )]
     pub pool: Account<'info, LiquidityBootstrappingPool>,
     // Token mint
-    pub asset_token_mint: Account<'info, Mint>,
-    pub share_token_mint: Account<'info, Mint>,
+    pub asset_token_mint: InterfaceAccount<'info, Mint>,
+    pub share_token_mint: InterfaceAccount<'info, Mint>,
     pub creator: Signer<'info>,  // Creator of the pool
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct CancelPool<'info> {
+    // Token mints, written to when harvesting the withheld Token-2022 transfer fees
+    #[account(mut, mint::token_program = asset_token_program)]
+    pub asset_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, mint::token_program = share_token_program)]
+    pub share_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+      mut,
+      seeds = [share_token_mint.key().as_ref(), asset_token_mint.key().as_ref(), creator.key().as_ref(), pool.salt.as_bytes()], 
+      bump = pool.bump,
+      close = creator
+    )]
+    pub pool: Box<Account<'info, LiquidityBootstrappingPool>>,
+    // Pool token accounts, closed by the cancellation
+    #[account(
+      mut,
+      associated_token::mint = asset_token_mint,
+      associated_token::authority = pool,
+      associated_token::token_program = asset_token_program
+    )]
+    pub pool_asset_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+      mut,
+      associated_token::mint = share_token_mint,
+      associated_token::authority = pool,
+      associated_token::token_program = share_token_program
+    )]
+    pub pool_share_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    // Creator token accounts receiving the deposits
+    #[account(
+      init_if_needed,
+      payer = creator,
+      associated_token::mint = asset_token_mint,
+      associated_token::authority = creator,
+      associated_token::token_program = asset_token_program
+    )]
+    pub creator_asset_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+      init_if_needed,
+      payer = creator,
+      associated_token::mint = share_token_mint,
+      associated_token::authority = creator,
+      associated_token::token_program = share_token_program
+    )]
+    pub creator_share_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub creator: Signer<'info>,  // Creator of the pool
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub asset_token_program: Interface<'info, TokenInterface>,
+    pub share_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePoolConfig<'info> {
+    // Token mints
+    #[account(mint::token_program = asset_token_program)]
+    pub asset_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = share_token_program)]
+    pub share_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+      mut,
+      seeds = [share_token_mint.key().as_ref(), asset_token_mint.key().as_ref(), creator.key().as_ref(), pool.salt.as_bytes()], 
+      bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, LiquidityBootstrappingPool>>,
+    // Pool token accounts receiving the added liquidity
+    #[account(
+      mut,
+      associated_token::mint = asset_token_mint,
+      associated_token::authority = pool,
+      associated_token::token_program = asset_token_program
+    )]
+    pub pool_asset_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+      mut,
+      associated_token::mint = share_token_mint,
+      associated_token::authority = pool,
+      associated_token::token_program = share_token_program
+    )]
+    pub pool_share_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    // Creator token accounts providing the added liquidity
+    #[account(
+      mut,
+      associated_token::mint = asset_token_mint,
+      associated_token::authority = creator,
+      associated_token::token_program = asset_token_program
+    )]
+    pub creator_asset_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+      mut,
+      associated_token::mint = share_token_mint,
+      associated_token::authority = creator,
+      associated_token::token_program = share_token_program
+    )]
+    pub creator_share_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub creator: Signer<'info>,  // Creator of the pool
+    pub asset_token_program: Interface<'info, TokenInterface>,
+    pub share_token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ClosePoolAccounts<'info> {
+    // Token mints, written to when harvesting the withheld Token-2022 transfer fees
+    #[account(mut, mint::token_program = asset_token_program)]
+    pub asset_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, mint::token_program = share_token_program)]
+    pub share_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// CHECK: Pool shrunk to a closed pool marker by the instruction, checked by its owner, discriminator and seeds
+    #[account(mut)]
+    pub pool: UncheckedAccount<'info>,
+    // Pool token accounts, closed along with the pool
+    #[account(
+      mut,
+      associated_token::mint = asset_token_mint,
+      associated_token::authority = pool,
+      associated_token::token_program = asset_token_program
+    )]
+    pub pool_asset_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+      mut,
+      associated_token::mint = share_token_mint,
+      associated_token::authority = pool,
+      associated_token::token_program = share_token_program
+    )]
+    pub pool_share_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    // Creator token accounts receiving what is left in the pool. The asset one can be omitted in native SOL pools,
+    // whose remaining assets are unwrapped to the creator along with the rent of the pool token account
+    #[account(
+      init_if_needed,
+      payer = creator,
+      associated_token::mint = asset_token_mint,
+      associated_token::authority = creator,
+      associated_token::token_program = asset_token_program
+    )]
+    pub creator_asset_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+      init_if_needed,
+      payer = creator,
+      associated_token::mint = share_token_mint,
+      associated_token::authority = creator,
+      associated_token::token_program = share_token_program
+    )]
+    pub creator_share_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub creator: Signer<'info>,  // Creator of the pool
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub asset_token_program: Interface<'info, TokenInterface>,
+    pub share_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AdjustWeights<'info> {
+    #[account(
+      mut,
+      constraint = authority.key() == pool.creator || authority.key() == pool.weight_manager @ PoolError::UnauthorizedWeightAdjustment
+    )]
+    pub pool: Box<Account<'info, LiquidityBootstrappingPool>>,
+    pub authority: Signer<'info>,  // Creator or weight manager of the pool
+}
+
 pub fn toggle_pause(ctx: Context<OnlyPoolCreator>) -> Result<()> {
   ctx.accounts.pool.paused = !ctx.accounts.pool.paused;
   Ok(())
 }
 
+// Pool configuration, weight and whitelist setters
+pub mod config {
+  use super::*;
+
+  pub fn set_whitelist_merkle_root(ctx: Context<OnlyPoolCreator>, whitelist_merkle_root: [u8; 32]) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+
+    // The whitelist can be replaced until the end of the sale, a zero root opens the pool to everyone
+    if pool.closed || Clock::get()?.unix_timestamp >= pool.sale_end_time {
+      return err!(PoolError::WhitelistUpdateDisallowed);
+    }
+    pool.whitelist_merkle_root = whitelist_merkle_root;
+
+    emit!(WhitelistUpdated {
+      pool: pool.key(),
+      whitelist_merkle_root,
+    });
+    Ok(())
+  }
+
+  /// Set the time after which anyone can trade without a whitelist proof, or never when 0.
+  pub fn set_public_sale_time(ctx: Context<OnlyPoolCreator>, public_sale_time: i64) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+
+    let current_time = Clock::get()?.unix_timestamp;
+    if pool.closed || current_time >= pool.sale_end_time {
+      return err!(PoolError::WhitelistUpdateDisallowed);
+    }
+    // The public sale cannot open in the past nor after the end of the sale
+    if public_sale_time != 0 && (public_sale_time < current_time || public_sale_time >= pool.sale_end_time) {
+      return err!(PoolError::InvalidPublicSaleTime);
+    }
+    pool.public_sale_time = public_sale_time;
+
+    emit!(PublicSaleTimeUpdated {
+      pool: pool.key(),
+      public_sale_time,
+    });
+    Ok(())
+  }
+
+  /// Let `weight_manager` adjust the weight schedule of the pool along with the creator, or nobody else when default.
+  pub fn set_weight_manager(ctx: Context<OnlyPoolCreator>, weight_manager: Pubkey) -> Result<()> {
+    ctx.accounts.pool.weight_manager = weight_manager;
+    Ok(())
+  }
+
+  /// Adjust the weight schedule of the pool during its sale, re-targeting its end weight and pausing or resuming it.
+  /// The schedule restarts from the current asset weight, so the weight never jumps: it is held there while paused,
+  /// and otherwise moves to the end weight over the rest of the sale, through the piecewise knots still ahead.
+  /// Omitted parameters are left unchanged.
+  pub fn adjust_weights(ctx: Context<AdjustWeights>, end_weight_basis_points: Option<u16>, paused: Option<bool>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let now = Clock::get()?.unix_timestamp;
+
+    if pool.closed || now < pool.sale_start_time || now >= pool.sale_end_time {
+      return err!(PoolError::WeightAdjustmentDisallowed);
+    }
+
+    let end_weight_basis_points = end_weight_basis_points.unwrap_or(pool.end_weight_basis_points);
+    if !(100..=9900).contains(&end_weight_basis_points) {
+      return err!(PoolError::InvalidWeightConfig);
+    }
+
+    // Weights are basis points, the current one always fits in a u16
+    let weight_basis_points = adjusted_weight(
+      pool.weight_curve,
+      &pool.weight_knots,
+      pool.weight_adjustment,
+      pool.start_weight_basis_points,
+      pool.end_weight_basis_points,
+      pool.sale_start_time,
+      pool.sale_end_time,
+      now,
+    )? as u16;
+    let paused = paused.unwrap_or(pool.weight_adjustment.paused);
+
+    pool.end_weight_basis_points = end_weight_basis_points;
+    pool.weight_adjustment = WeightAdjustment {
+      timestamp: now,
+      weight_basis_points,
+      paused,
+    };
+
+    emit!(WeightsAdjusted {
+      pool: pool.key(),
+      authority: ctx.accounts.authority.key(),
+      weight_basis_points,
+      end_weight_basis_points,
+      paused,
+    });
+    Ok(())
+  }
+
+  /// Update the configuration of the pool and add `assets` and `shares` to its reserves before its sale starts.
+  /// Omitted parameters are left unchanged, and the resulting configuration is validated as in `create_pool`.
+  #[allow(clippy::too_many_arguments)]
+  pub fn update_pool_config(
+    ctx: Context<UpdatePoolConfig>,
+    assets: u64,
+    shares: u64,
+    virtual_assets: Option<u64>,
+    virtual_shares: Option<u64>,
+    max_share_price: Option<u64>,
+    start_weight_basis_points: Option<u16>,
+    end_weight_basis_points: Option<u16>,
+    sale_start_time: Option<i64>,
+    sale_end_time: Option<i64>,
+    vest_cliff: Option<i64>,
+    vest_end: Option<i64>,
+    weight_curve: Option<WeightCurve>,
+    weight_knots: Option<[WeightKnot; MAX_WEIGHT_KNOTS]>,
+  ) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let current_time = Clock::get()?.unix_timestamp;
+
+    if pool.closed || current_time >= pool.sale_start_time {
+      return err!(PoolError::UpdatingDisallowed);
+    }
+
+    pool.virtual_assets = virtual_assets.unwrap_or(pool.virtual_assets);
+    pool.virtual_shares = virtual_shares.unwrap_or(pool.virtual_shares);
+    pool.max_share_price = max_share_price.unwrap_or(pool.max_share_price);
+    pool.start_weight_basis_points = start_weight_basis_points.unwrap_or(pool.start_weight_basis_points);
+    pool.end_weight_basis_points = end_weight_basis_points.unwrap_or(pool.end_weight_basis_points);
+    pool.sale_start_time = sale_start_time.unwrap_or(pool.sale_start_time);
+    pool.sale_end_time = sale_end_time.unwrap_or(pool.sale_end_time);
+    pool.vest_cliff = vest_cliff.unwrap_or(pool.vest_cliff);
+    pool.vest_end = vest_end.unwrap_or(pool.vest_end);
+    pool.weight_curve = weight_curve.unwrap_or(pool.weight_curve);
+    pool.weight_knots = weight_knots.unwrap_or(pool.weight_knots);
+
+    // The sale cannot be moved to start before the update lands
+    if current_time >= pool.sale_start_time {
+      return err!(PoolError::SalePeriodLow);
+    }
+
+    if ctx.accounts.creator_asset_token_account.amount < assets {
+      return err!(PoolError::InsufficientAssets);
+    }
+
+    if ctx.accounts.creator_share_token_account.amount < shares {
+      return err!(PoolError::InsufficientShares);
+    }
+
+    if assets != 0 {
+      transfer_tokens(
+        ctx.accounts.asset_token_program.to_account_info(),
+        ctx.accounts.creator_asset_token_account.to_account_info(),
+        ctx.accounts.pool_asset_token_account.to_account_info(),
+        ctx.accounts.asset_token_mint.to_account_info(),
+        ctx.accounts.creator.to_account_info(),
+        assets,
+        ctx.accounts.asset_token_mint.decimals,
+      )?;
+    }
+
+    if shares != 0 {
+      transfer_tokens(
+        ctx.accounts.share_token_program.to_account_info(),
+        ctx.accounts.creator_share_token_account.to_account_info(),
+        ctx.accounts.pool_share_token_account.to_account_info(),
+        ctx.accounts.share_token_mint.to_account_info(),
+        ctx.accounts.creator.to_account_info(),
+        shares,
+        ctx.accounts.share_token_mint.decimals,
+      )?;
+    }
+
+    // Validate against the reserves held after the top up, net of any transfer fees
+    ctx.accounts.pool_asset_token_account.reload()?;
+    ctx.accounts.pool_share_token_account.reload()?;
+    validate_pool_config(
+      pool,
+      ctx.accounts.pool_asset_token_account.amount,
+      ctx.accounts.pool_share_token_account.amount,
+    )?;
+
+    emit!(PoolConfigUpdated {
+      pool: pool.key(),
+      assets,
+      shares,
+    });
+    Ok(())
+  }
+}
+
+// Cancellation and closing of the pool accounts
+pub mod lifecycle {
+  use super::*;
+
+  /// Cancel the pool as long as no shares were ever bought in it, even if sold back since.
+  /// All the tokens held by the pool go back to the creator, and the pool account and its token accounts are
+  /// closed with their rent returned to the creator.
+  pub fn cancel_pool(ctx: Context<CancelPool>) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+
+    if pool.closed || pool.traded {
+      return err!(PoolError::CancellingDisallowed);
+    }
+
+    let pool_seeds: &[&[u8]] = &[
+      pool.share_token.as_ref(),
+      pool.asset_token.as_ref(),
+      pool.creator.as_ref(),
+      pool.salt.as_bytes(),
+      &[pool.bump],
+    ];
+    let assets = ctx.accounts.pool_asset_token_account.amount;
+    let shares = ctx.accounts.pool_share_token_account.amount;
+
+    for (token_program, mint, pool_token_account, creator_token_account, amount) in [
+      (&ctx.accounts.asset_token_program, &ctx.accounts.asset_token_mint, &ctx.accounts.pool_asset_token_account, &ctx.accounts.creator_asset_token_account, assets),
+      (&ctx.accounts.share_token_program, &ctx.accounts.share_token_mint, &ctx.accounts.pool_share_token_account, &ctx.accounts.creator_share_token_account, shares),
+    ] {
+      if amount != 0 {
+        transfer_tokens_from(
+          token_program.to_account_info(),
+          pool_token_account.to_account_info(),
+          creator_token_account.to_account_info(),
+          mint.to_account_info(),
+          pool.to_account_info(),
+          pool_seeds,
+          amount,
+          mint.decimals,
+        )?;
+      }
+      close_token_account_from(
+        token_program.to_account_info(),
+        mint.to_account_info(),
+        pool_token_account.to_account_info(),
+        ctx.accounts.creator.to_account_info(),
+        pool.to_account_info(),
+        pool_seeds,
+      )?;
+    }
+
+    // The pool account itself is closed to the creator on exit
+    emit!(Cancel {
+      pool: pool.key(),
+      assets,
+      shares,
+    });
+    Ok(())
+  }
 
+  /// Close the token accounts of the pool once the pool is closed and everything owed is claimed, returning their
+  /// rent to the creator. Everything owed is claimed once every buyer has redeemed all its shares or been refunded
+  /// and every referrer has claimed its assets, or regardless once `CLOSE_POOL_ACCOUNTS_GRACE_PERIOD` has passed
+  /// since the end of the sale and of the vesting: what is left in the pool, such as the rounding of the fee payouts
+  /// or the unclaimed shares and assets, is then swept to the creator.
+  /// The pool account is shrunk to a `ClosedPool` marker rather than closed, returning the rest of its rent, so
+  /// that no pool can be initialized at its address again and inherit the user states left in it.
+  /// Cancelled pools need no such step: `cancel_pool` already closes all three accounts.
+  pub fn close_pool_accounts(ctx: Context<ClosePoolAccounts>) -> Result<()> {
+    let pool_info = ctx.accounts.pool.to_account_info();
+    if pool_info.owner != ctx.program_id {
+      return err!(anchor_lang::error::ErrorCode::AccountOwnedByWrongProgram);
+    }
+    let pool = LiquidityBootstrappingPool::try_deserialize(&mut &pool_info.try_borrow_data()?[..])?;
+    let pool_seeds: &[&[u8]] = &[
+      pool.share_token.as_ref(),
+      pool.asset_token.as_ref(),
+      pool.creator.as_ref(),
+      pool.salt.as_bytes(),
+      &[pool.bump],
+    ];
+    if pool.share_token != ctx.accounts.share_token_mint.key()
+      || pool.asset_token != ctx.accounts.asset_token_mint.key()
+      || pool.creator != ctx.accounts.creator.key()
+      || Pubkey::create_program_address(pool_seeds, ctx.program_id) != Ok(pool_info.key())
+    {
+      return err!(anchor_lang::error::ErrorCode::ConstraintSeeds);
+    }
+
+    let owed = if pool.refunding {
+      pool.total_assets_paid != 0
+    } else {
+      pool.total_purchased != 0 || pool.total_referred != 0
+    };
+    let grace_period_end = pool.sale_end_time.max(pool.vest_end).saturating_add(CLOSE_POOL_ACCOUNTS_GRACE_PERIOD);
+    if !pool.closed || (owed && Clock::get()?.unix_timestamp < grace_period_end) {
+      return err!(PoolError::PoolAccountsClosingDisallowed);
+    }
+
+    let assets = ctx.accounts.pool_asset_token_account.amount;
+    match &ctx.accounts.creator_asset_token_account {
+      Some(creator_asset_token_account) if assets != 0 => transfer_tokens_from(
+        ctx.accounts.asset_token_program.to_account_info(),
+        ctx.accounts.pool_asset_token_account.to_account_info(),
+        creator_asset_token_account.to_account_info(),
+        ctx.accounts.asset_token_mint.to_account_info(),
+        pool_info.clone(),
+        pool_seeds,
+        assets,
+        ctx.accounts.asset_token_mint.decimals,
+      )?,
+      // Closing a wSOL account pays its remaining assets out in lamports
+      None if !is_native_mint(&ctx.accounts.asset_token_mint.key()) => return err!(PoolError::MissingAssetTokenAccount),
+      _ => {}
+    }
+    let shares = ctx.accounts.pool_share_token_account.amount;
+    if shares != 0 {
+      transfer_tokens_from(
+        ctx.accounts.share_token_program.to_account_info(),
+        ctx.accounts.pool_share_token_account.to_account_info(),
+        ctx.accounts.creator_share_token_account.to_account_info(),
+        ctx.accounts.share_token_mint.to_account_info(),
+        pool_info.clone(),
+        pool_seeds,
+        shares,
+        ctx.accounts.share_token_mint.decimals,
+      )?;
+    }
+
+    // The transfer fees withheld in the pool token accounts are harvested to the mints before closing them
+    for (token_program, mint, pool_token_account) in [
+      (&ctx.accounts.asset_token_program, &ctx.accounts.asset_token_mint, &ctx.accounts.pool_asset_token_account),
+      (&ctx.accounts.share_token_program, &ctx.accounts.share_token_mint, &ctx.accounts.pool_share_token_account),
+    ] {
+      close_token_account_from(
+        token_program.to_account_info(),
+        mint.to_account_info(),
+        pool_token_account.to_account_info(),
+        ctx.accounts.creator.to_account_info(),
+        pool_info.clone(),
+        pool_seeds,
+      )?;
+    }
+
+    // The rent of the pool account beyond that of the marker is returned once the token accounts are closed
+    let marker_rent = Rent::get()?.minimum_balance(ClosedPool::LEN);
+    let released_rent = safe_sub(pool_info.lamports(), marker_rent)?;
+    pool_info.realloc(ClosedPool::LEN, false)?;
+    pool_info.try_borrow_mut_data()?.copy_from_slice(&ClosedPool::DISCRIMINATOR);
+    transfer_lamports_from(&pool_info, &ctx.accounts.creator.to_account_info(), released_rent)?;
+
+    emit!(PoolAccountsClosed {
+      pool: pool_info.key(),
+      assets,
+      shares,
+    });
+    Ok(())
+  }
+}
@@ -1,13 +1,15 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{LiquidityBootstrappingPool, OwnerConfig};
 
 #[derive(Accounts)]
 pub struct ReturnPreviewContext<'info> {
   // Token mints
-  pub asset_token_mint: Account<'info, Mint>,
-  pub share_token_mint: Account<'info, Mint>,
+  #[account(mint::token_program = asset_token_program)]
+  pub asset_token_mint: Box<InterfaceAccount<'info, Mint>>,
+  #[account(mint::token_program = share_token_program)]
+  pub share_token_mint: Box<InterfaceAccount<'info, Mint>>,
   #[account(
     seeds = [share_token_mint.key().as_ref(), asset_token_mint.key().as_ref(), pool.creator.key().as_ref(), pool.salt.as_bytes()], 
     bump = pool.bump
@@ -15,18 +17,23 @@ pub struct ReturnPreviewContext<'info> {
   pub pool: Account<'info, LiquidityBootstrappingPool>,
    #[account(
     associated_token::mint = asset_token_mint,
-    associated_token::authority = pool
+    associated_token::authority = pool,
+    associated_token::token_program = asset_token_program
   )]
-  pub pool_asset_token_account: Account<'info, TokenAccount>,
+  pub pool_asset_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
   #[account(
     associated_token::mint = share_token_mint,
-    associated_token::authority = pool
+    associated_token::authority = pool,
+    associated_token::token_program = share_token_program
   )]
-  pub pool_share_token_account: Account<'info, TokenAccount>,
+  pub pool_share_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
   // Global pool config
   #[account(
     seeds = ["owner_config".as_bytes()],
     bump = config.bump
   )]
   pub config: Account<'info, OwnerConfig>,
+  // Token programs owning the asset and share mints
+  pub asset_token_program: Interface<'info, TokenInterface>,
+  pub share_token_program: Interface<'info, TokenInterface>,
 }
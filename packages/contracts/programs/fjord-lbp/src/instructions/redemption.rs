@@ -1,12 +1,12 @@
 use std::collections::{HashMap, HashSet};
 
 use anchor_lang::prelude::*;
-use anchor_spl::associated_token::{AssociatedToken, get_associated_token_address};
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::associated_token::{AssociatedToken, get_associated_token_address_with_program_id};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::math::calculate_fee;
+use crate::math::{calculate_fee, compute_vested_shares};
 use crate::{
-  safe_math, safe_sub, transfer_tokens_from, Close, FeeMapping, LiquidityBootstrappingPool, OwnerConfig, PoolError, Redeem, Treasury, UserStateInPool
+  is_native_mint, safe_math, safe_sub, transfer_lamports_from, transfer_tokens_from, unwrap_native, Close, FeeMapping, LiquidityBootstrappingPool, OwnerConfig, PoolError, Redeem, ReferralRewardsClaimed, Refund, Treasury, UserStateInPool
 };
 
 pub struct FeeRecipient<'a> {
@@ -17,8 +17,10 @@ pub struct FeeRecipient<'a> {
 #[derive(Accounts)]
 pub struct ClosePool<'info> {
     // Token mints -----------------------------------------------------
-    pub asset_token_mint: Box<Account<'info, Mint>>,
-    pub share_token_mint: Box<Account<'info, Mint>>,
+    #[account(mint::token_program = asset_token_program)]
+    pub asset_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = share_token_program)]
+    pub share_token_mint: Box<InterfaceAccount<'info, Mint>>,
     // The pool --------------------------------------------------------
     #[account(
       mut,
@@ -30,30 +32,34 @@ pub struct ClosePool<'info> {
     #[account(
       mut,
       associated_token::mint = asset_token_mint,
-      associated_token::authority = pool
+      associated_token::authority = pool,
+      associated_token::token_program = asset_token_program
     )]
-    pub pool_asset_token_account: Box<Account<'info, TokenAccount>>,
+    pub pool_asset_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
       mut,
       associated_token::mint = share_token_mint,
-      associated_token::authority = pool
+      associated_token::authority = pool,
+      associated_token::token_program = share_token_program
     )]
-    pub pool_share_token_account: Box<Account<'info, TokenAccount>>,
+    pub pool_share_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
     // Treasury token accounts -------------------------------------
     #[account(
       init_if_needed,
       payer = user,
       associated_token::mint = asset_token_mint,
-      associated_token::authority = treasury
+      associated_token::authority = treasury,
+      associated_token::token_program = asset_token_program
     )]
-    pub treasury_asset_token_account: Box<Account<'info, TokenAccount>>,
+    pub treasury_asset_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
       init_if_needed,
       payer = user,
       associated_token::mint = share_token_mint,
-      associated_token::authority = treasury
+      associated_token::authority = treasury,
+      associated_token::token_program = share_token_program
     )]
-    pub treasury_share_token_account: Box<Account<'info, TokenAccount>>,
+    pub treasury_share_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
       seeds = ["treasury".as_bytes()],
       bump
@@ -64,35 +70,52 @@ pub struct ClosePool<'info> {
       init_if_needed,
       payer = user,
       associated_token::mint = asset_token_mint,
-      associated_token::authority = pool_creator
+      associated_token::authority = pool_creator,
+      associated_token::token_program = asset_token_program
     )]
-    pub creator_asset_token_account: Box<Account<'info, TokenAccount>>,
+    pub creator_asset_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
     #[account(
       init_if_needed,
       payer = user,
       associated_token::mint = share_token_mint,
-      associated_token::authority = pool_creator
+      associated_token::authority = pool_creator,
+      associated_token::token_program = share_token_program
     )]
-    pub creator_share_token_account: Box<Account<'info, TokenAccount>>,
-    #[account(constraint = pool.creator == pool_creator.key() @PoolError::InvalidCreator)]
+    pub creator_share_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, constraint = pool.creator == pool_creator.key() @PoolError::InvalidCreator)]
     pub pool_creator: SystemAccount<'info>,
     // Swap fee recipient ----------------------------------------------
     #[account(
       init_if_needed, 
       payer = user, 
       associated_token::mint = asset_token_mint, 
-      associated_token::authority = swap_fee_recipient
+      associated_token::authority = swap_fee_recipient,
+      associated_token::token_program = asset_token_program
     )]
-    pub swap_fee_recipient_asset_token_account: Box<Account<'info, TokenAccount>>,
+    pub swap_fee_recipient_asset_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
     #[account(
       init_if_needed, 
       payer = user, 
       associated_token::mint = share_token_mint, 
-      associated_token::authority = swap_fee_recipient
+      associated_token::authority = swap_fee_recipient,
+      associated_token::token_program = share_token_program
     )]
-    pub swap_fee_recipient_share_token_account: Box<Account<'info, TokenAccount>>,
-    #[account(constraint = treasury.swap_fee_recipient == swap_fee_recipient.key() @PoolError::InvalidSwapFeeRecipient)]
+    pub swap_fee_recipient_share_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut, constraint = treasury.swap_fee_recipient == swap_fee_recipient.key() @PoolError::InvalidSwapFeeRecipient)]
     pub swap_fee_recipient: SystemAccount<'info>,
+    // Native SOL payouts ----------------------------------------------
+    // Transient wSOL account used to unwrap the assets paid out in native SOL pools, closed within the instruction.
+    // When provided, the asset token accounts of the recipients are not needed.
+    #[account(
+      init,
+      payer = user,
+      seeds = ["native_unwrap".as_bytes(), pool.key().as_ref(), user.key().as_ref()],
+      bump,
+      token::mint = asset_token_mint,
+      token::authority = pool,
+      token::token_program = asset_token_program
+    )]
+    pub native_unwrap_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
     // Global pool config ----------------------------------------------
     #[account(
       seeds = ["owner_config".as_bytes()],
@@ -103,17 +126,21 @@ pub struct ClosePool<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
     pub associated_token_program: Program<'info, AssociatedToken>,
-    pub token_program: Program<'info, Token>,
+    pub asset_token_program: Interface<'info, TokenInterface>,
+    pub share_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
 pub struct RedeemTokens<'info> {
   // Token mints -----------------------------------------------------
-  pub asset_token_mint: Account<'info, Mint>,
-  pub share_token_mint: Account<'info, Mint>,
+  #[account(mint::token_program = asset_token_program)]
+  pub asset_token_mint: Box<InterfaceAccount<'info, Mint>>,
+  #[account(mint::token_program = share_token_program)]
+  pub share_token_mint: Box<InterfaceAccount<'info, Mint>>,
   // The pool --------------------------------------------------------
   #[account(
+    mut,
     seeds = [share_token_mint.key().as_ref(), asset_token_mint.key().as_ref(), pool.creator.key().as_ref(), pool.salt.as_bytes()], 
     bump = pool.bump
   )]
@@ -122,30 +149,35 @@ pub struct RedeemTokens<'info> {
   #[account(
     mut,
     associated_token::mint = asset_token_mint,
-    associated_token::authority = pool
+    associated_token::authority = pool,
+    associated_token::token_program = asset_token_program
   )]
-  pub pool_asset_token_account: Account<'info, TokenAccount>,
+  pub pool_asset_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
   #[account(
     mut,
     associated_token::mint = share_token_mint,
-    associated_token::authority = pool
+    associated_token::authority = pool,
+    associated_token::token_program = share_token_program
   )]
-  pub pool_share_token_account: Account<'info, TokenAccount>,
+  pub pool_share_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
   // User token accounts ---------------------------------------------
+  // The asset token account can be omitted in native SOL pools to receive the referral assets in lamports
   #[account(
     init_if_needed,
     payer = user,
     associated_token::mint = asset_token_mint, 
-    associated_token::authority = user)
+    associated_token::authority = user,
+    associated_token::token_program = asset_token_program)
   ]
-  pub user_asset_token_account: Account<'info, TokenAccount>,
+  pub user_asset_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
   #[account(
     init_if_needed,
     payer = user,
     associated_token::mint = share_token_mint, 
-    associated_token::authority = user)
+    associated_token::authority = user,
+    associated_token::token_program = share_token_program)
   ]
-  pub user_share_token_account: Account<'info, TokenAccount>,
+  pub user_share_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
   // The user's state in a pool --------------------------------------
   #[account(
     mut,
@@ -153,11 +185,142 @@ pub struct RedeemTokens<'info> {
     bump
   )]
   pub user_state_in_pool: Box<Account<'info, UserStateInPool>>,
+  // Native SOL payouts ----------------------------------------------
+  // Transient wSOL account used to unwrap the referral assets in native SOL pools, closed within the instruction
+  #[account(
+    init,
+    payer = user,
+    seeds = ["native_unwrap".as_bytes(), pool.key().as_ref(), user.key().as_ref()],
+    bump,
+    token::mint = asset_token_mint,
+    token::authority = pool,
+    token::token_program = asset_token_program
+  )]
+  pub native_unwrap_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+  // Miscs ----------------------------------------------------------
+  #[account(mut)]
+  pub user: Signer<'info>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub asset_token_program: Interface<'info, TokenInterface>,
+  pub share_token_program: Interface<'info, TokenInterface>,
+  pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RefundTokens<'info> {
+  // Token mints -----------------------------------------------------
+  #[account(mint::token_program = asset_token_program)]
+  pub asset_token_mint: Box<InterfaceAccount<'info, Mint>>,
+  #[account(mint::token_program = share_token_program)]
+  pub share_token_mint: Box<InterfaceAccount<'info, Mint>>,
+  // The pool --------------------------------------------------------
+  #[account(
+    mut,
+    seeds = [share_token_mint.key().as_ref(), asset_token_mint.key().as_ref(), pool.creator.key().as_ref(), pool.salt.as_bytes()], 
+    bump = pool.bump
+  )]
+  pub pool: Box<Account<'info, LiquidityBootstrappingPool>>,
+  // Pool token accounts ---------------------------------------------
+  #[account(
+    mut,
+    associated_token::mint = asset_token_mint,
+    associated_token::authority = pool,
+    associated_token::token_program = asset_token_program
+  )]
+  pub pool_asset_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+  // User token accounts ---------------------------------------------
+  // The asset token account can be omitted in native SOL pools to be refunded in lamports
+  #[account(
+    init_if_needed,
+    payer = user,
+    associated_token::mint = asset_token_mint, 
+    associated_token::authority = user,
+    associated_token::token_program = asset_token_program)
+  ]
+  pub user_asset_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+  // The user's state in a pool, closed by the refund as nothing is left to claim from the pool
+  #[account(
+    mut,
+    seeds = [user.key().as_ref(), pool.key().as_ref()],
+    bump,
+    close = user
+  )]
+  pub user_state_in_pool: Box<Account<'info, UserStateInPool>>,
+  // Native SOL payouts ----------------------------------------------
+  // Transient wSOL account used to unwrap the refunded assets in native SOL pools, closed within the instruction
+  #[account(
+    init,
+    payer = user,
+    seeds = ["native_unwrap".as_bytes(), pool.key().as_ref(), user.key().as_ref()],
+    bump,
+    token::mint = asset_token_mint,
+    token::authority = pool,
+    token::token_program = asset_token_program
+  )]
+  pub native_unwrap_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
   // Miscs ----------------------------------------------------------
   #[account(mut)]
   pub user: Signer<'info>,
   pub associated_token_program: Program<'info, AssociatedToken>,
-  pub token_program: Program<'info, Token>,
+  pub asset_token_program: Interface<'info, TokenInterface>,
+  pub share_token_program: Interface<'info, TokenInterface>,
+  pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimReferralRewards<'info> {
+  // Token mint ------------------------------------------------------
+  #[account(mint::token_program = asset_token_program)]
+  pub asset_token_mint: Box<InterfaceAccount<'info, Mint>>,
+  // The pool --------------------------------------------------------
+  #[account(
+    mut,
+    seeds = [pool.share_token.as_ref(), asset_token_mint.key().as_ref(), pool.creator.key().as_ref(), pool.salt.as_bytes()], 
+    bump = pool.bump
+  )]
+  pub pool: Box<Account<'info, LiquidityBootstrappingPool>>,
+  // Pool token account ----------------------------------------------
+  #[account(
+    mut,
+    associated_token::mint = asset_token_mint,
+    associated_token::authority = pool,
+    associated_token::token_program = asset_token_program
+  )]
+  pub pool_asset_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+  // Referrer token account ------------------------------------------
+  // The asset token account can be omitted in native SOL pools to be paid in lamports
+  #[account(
+    init_if_needed,
+    payer = referrer,
+    associated_token::mint = asset_token_mint, 
+    associated_token::authority = referrer,
+    associated_token::token_program = asset_token_program)
+  ]
+  pub referrer_asset_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+  // The referrer's state in a pool ----------------------------------
+  #[account(
+    mut,
+    seeds = [referrer.key().as_ref(), pool.key().as_ref()],
+    bump
+  )]
+  pub referrer_state_in_pool: Box<Account<'info, UserStateInPool>>,
+  // Native SOL payouts ----------------------------------------------
+  // Transient wSOL account used to unwrap the referral assets in native SOL pools, closed within the instruction
+  #[account(
+    init,
+    payer = referrer,
+    seeds = ["native_unwrap".as_bytes(), pool.key().as_ref(), referrer.key().as_ref()],
+    bump,
+    token::mint = asset_token_mint,
+    token::authority = pool,
+    token::token_program = asset_token_program
+  )]
+  pub native_unwrap_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+  // Miscs ----------------------------------------------------------
+  #[account(mut)]
+  pub referrer: Signer<'info>,
+  pub associated_token_program: Program<'info, AssociatedToken>,
+  pub asset_token_program: Interface<'info, TokenInterface>,
   pub system_program: Program<'info, System>,
 }
 
@@ -165,6 +328,11 @@ pub struct RedeemTokens<'info> {
 /// This function closes the pool after the sale has ended and distributes
 /// assets to the platform (treasury) and the creator/manager, and shares to the creator/manager for
 /// any unsold shares. Once closed, the pool cannot be used for further transactions.
+/// In pools whose asset is wrapped SOL, passing the `native_unwrap_account` pays the assets out in native SOL
+/// to the wallets of the fee recipients, the swap fee recipient and the creator/manager.
+/// If the sale raised less than its soft cap (`min_assets_raised`), the pool is closed in refund mode instead:
+/// no fees are taken, the creator/manager gets back all the shares and the assets it provided, and the assets
+/// paid by the buyers are left in the pool for them to `refund`.
 pub fn close_pool<'info>(ctx: Context<'_, '_, '_, 'info, ClosePool<'info>>) -> Result<()> {
     let pool = &mut ctx.accounts.pool;
     let treasury = &ctx.accounts.treasury;
@@ -173,72 +341,123 @@ pub fn close_pool<'info>(ctx: Context<'_, '_, '_, 'info, ClosePool<'info>>) -> R
         return Err(PoolError::ClosingDisallowed.into());
     }
     pool.closed = true;
+    let refunding = pool.total_assets_raised < pool.min_assets_raised;
+    pool.refunding = refunding;
     let total_assets = safe_math::safe_sub(ctx.accounts.pool_asset_token_account.amount, pool.total_swap_fees_asset)?;
-    let platform_fees = calculate_fee(total_assets, ctx.accounts.owner_config.platform_fee);
-    let total_assets_minus_fees = safe_math::safe_sub(safe_math::safe_sub(total_assets, platform_fees)?, pool.total_referred)?;
+    let (platform_fees, total_assets_minus_fees) = if refunding {
+        // Everything but the assets owed to the buyers, should sellers have been paid out of the initial assets
+        (0, ctx.accounts.pool_asset_token_account.amount.saturating_sub(pool.total_assets_paid))
+    } else {
+        let platform_fees = calculate_fee(total_assets, pool.platform_fee);
+        (platform_fees, safe_math::safe_sub(safe_math::safe_sub(total_assets, platform_fees)?, pool.total_referred)?)
+    };
+
+    let native_sol = ctx.accounts.native_unwrap_account.is_some();
+    if native_sol && !is_native_mint(&ctx.accounts.asset_token_mint.key()) {
+        return Err(PoolError::NativeSolDisallowed.into());
+    }
 
     if total_assets != 0 {
-        // Transfer platform fees and swap fees directly to the respective recipients
-        let fee_recipients_asset_token = retrieve_valid_keys(treasury.fee_recipients.clone(), ctx.remaining_accounts, &ctx.accounts.asset_token_mint.key())?;
-        fee_recipients_asset_token.iter().for_each(|recipient| {
-            let fees = calculate_fee(platform_fees, recipient.fee_percentage);
-            transfer_tokens_from(
-                ctx.accounts.token_program.to_account_info(),
-                ctx.accounts.pool_asset_token_account.to_account_info(),
-                recipient.account_info.to_account_info(),
-                pool.to_account_info(),
-                &[
-                    pool.share_token.as_ref(),
-                    pool.asset_token.as_ref(),
-                    pool.creator.as_ref(),
-                    pool.salt.as_bytes(),
-                    &[pool.bump],
-                ],
-                fees,
-            ).unwrap();
-        });
-   
-        // Transfer asset to swap fee recipient
-        transfer_tokens_from(
-            ctx.accounts.token_program.to_account_info(),
-            ctx.accounts.pool_asset_token_account.to_account_info(),
-            ctx.accounts
-                .swap_fee_recipient_asset_token_account
-                .to_account_info(),
-            pool.to_account_info(),
-            &[
-                pool.share_token.as_ref(),
-                pool.asset_token.as_ref(),
-                pool.creator.as_ref(),
-                pool.salt.as_bytes(),
-                &[pool.bump],
-            ],
-            pool.total_swap_fees_asset,
-        )?;
+        // Transfer platform fees and swap fees directly to the respective recipients, unless refunding
+        // In native SOL mode the recipients' wallets are expected instead of their ATAs
+        let mut asset_payouts: Vec<(AccountInfo<'info>, u64)> = Vec::new();
+        if !refunding {
+            let fee_recipients_asset_token = retrieve_valid_keys(treasury.fee_recipients.clone(), ctx.remaining_accounts, &ctx.accounts.asset_token_mint.key(), &ctx.accounts.asset_token_program.key(), native_sol)?;
+            asset_payouts.extend(fee_recipients_asset_token
+                .iter()
+                .map(|recipient| (recipient.account_info.to_account_info(), calculate_fee(platform_fees, recipient.fee_percentage))));
+        }
 
-        // Transfer share to swap fee recipient
-        transfer_tokens_from(
-            ctx.accounts.token_program.to_account_info(),
-            ctx.accounts.pool_share_token_account.to_account_info(),
-            ctx.accounts
-                .swap_fee_recipient_share_token_account
-                .to_account_info(),
-            pool.to_account_info(),
-            &[
-                pool.share_token.as_ref(),
-                pool.asset_token.as_ref(),
-                pool.creator.as_ref(),
-                pool.salt.as_bytes(),
-                &[pool.bump],
-            ],
-            pool.total_swap_fees_share,
-        )?;
+        if native_sol {
+            // Swap fees and remaining assets go to the swap fee recipient and pool creator/manager wallets
+            if !refunding {
+                asset_payouts.push((ctx.accounts.swap_fee_recipient.to_account_info(), pool.total_swap_fees_asset));
+            }
+            asset_payouts.push((ctx.accounts.pool_creator.to_account_info(), total_assets_minus_fees));
+        } else {
+            // Transfer asset to swap fee recipient and remaining assets to pool creator/manager
+            if !refunding {
+                let swap_fee_recipient_asset_token_account = ctx.accounts.swap_fee_recipient_asset_token_account.as_ref().ok_or(PoolError::MissingAssetTokenAccount)?;
+                asset_payouts.push((swap_fee_recipient_asset_token_account.to_account_info(), pool.total_swap_fees_asset));
+            }
+            let creator_asset_token_account = ctx.accounts.creator_asset_token_account.as_ref().ok_or(PoolError::MissingAssetTokenAccount)?;
+            asset_payouts.push((creator_asset_token_account.to_account_info(), total_assets_minus_fees));
+        }
 
-        // Transfer remaining assets to pool creator/manager
-        transfer_tokens_from(
-            ctx.accounts.token_program.to_account_info(),
-            ctx.accounts.pool_asset_token_account.to_account_info(),
-            ctx.accounts.creator_asset_token_account.to_account_info(),
+        let pool_seeds: &[&[u8]] = &[
+            pool.share_token.as_ref(),
+            pool.asset_token.as_ref(),
+            pool.creator.as_ref(),
+            pool.salt.as_bytes(),
+            &[pool.bump],
+        ];
+
+        match &ctx.accounts.native_unwrap_account {
+            Some(native_unwrap_account) => {
+                // Unwrap all the assets paid out into the pool account, then pay the recipients in lamports from it
+                let total_payouts = asset_payouts.iter().try_fold(0u64, |total, (_, amount)| safe_math::safe_add(total, *amount))?;
+                let unwrap_account_rent = native_unwrap_account.to_account_info().lamports();
+                transfer_tokens_from(
+                    ctx.accounts.asset_token_program.to_account_info(),
+                    ctx.accounts.pool_asset_token_account.to_account_info(),
+                    native_unwrap_account.to_account_info(),
+                    ctx.accounts.asset_token_mint.to_account_info(),
+                    pool.to_account_info(),
+                    pool_seeds,
+                    total_payouts,
+                    ctx.accounts.asset_token_mint.decimals,
+                )?;
+                unwrap_native(
+                    ctx.accounts.asset_token_program.to_account_info(),
+                    native_unwrap_account.to_account_info(),
+                    pool.to_account_info(),
+                    pool.to_account_info(),
+                    pool_seeds,
+                )?;
+
+                for (recipient, amount) in asset_payouts.iter() {
+                    transfer_lamports_from(&pool.to_account_info(), recipient, *amount)?;
+                }
+                // Refund the rent of the transient account to the caller
+                transfer_lamports_from(&pool.to_account_info(), &ctx.accounts.user.to_account_info(), unwrap_account_rent)?;
+            }
+            None => {
+                for (recipient, amount) in asset_payouts.iter() {
+                    transfer_tokens_from(
+                        ctx.accounts.asset_token_program.to_account_info(),
+                        ctx.accounts.pool_asset_token_account.to_account_info(),
+                        recipient.to_account_info(),
+                        ctx.accounts.asset_token_mint.to_account_info(),
+                        pool.to_account_info(),
+                        pool_seeds,
+                        *amount,
+                        ctx.accounts.asset_token_mint.decimals,
+                    )?;
+                }
+            }
+        }
+
+        // Transfer share to swap fee recipient
+        if !refunding {
+            transfer_tokens_from(
+                ctx.accounts.share_token_program.to_account_info(),
+                ctx.accounts.pool_share_token_account.to_account_info(),
+                ctx.accounts
+                    .swap_fee_recipient_share_token_account
+                    .to_account_info(),
+                ctx.accounts.share_token_mint.to_account_info(),
+                pool.to_account_info(),
+                pool_seeds,
+                pool.total_swap_fees_share,
+                ctx.accounts.share_token_mint.decimals,
+            )?;
+        }
+    } else if let Some(native_unwrap_account) = &ctx.accounts.native_unwrap_account {
+        // Nothing to pay out, close the transient account back to the caller
+        unwrap_native(
+            ctx.accounts.asset_token_program.to_account_info(),
+            native_unwrap_account.to_account_info(),
+            ctx.accounts.user.to_account_info(),
             pool.to_account_info(),
             &[
                 pool.share_token.as_ref(),
@@ -247,20 +466,23 @@ pub fn close_pool<'info>(ctx: Context<'_, '_, '_, 'info, ClosePool<'info>>) -> R
                 pool.salt.as_bytes(),
                 &[pool.bump],
             ],
-            total_assets_minus_fees,
         )?;
     }
 
+    // The swap fees in shares may have been paid out above
+    ctx.accounts.pool_share_token_account.reload()?;
     let total_shares = ctx.accounts.pool_share_token_account.amount;
-    let unsold_shares = total_shares - pool.total_purchased;
+    // When refunding, the purchases are void and all the shares go back to the creator/manager
+    let unsold_shares = if refunding { total_shares } else { total_shares - pool.total_purchased };
 
     if unsold_shares != 0 {
         transfer_tokens_from(
-            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.share_token_program.to_account_info(),
             ctx.accounts.pool_share_token_account.to_account_info(),
             ctx.accounts
                 .creator_share_token_account
                 .to_account_info(),
+            ctx.accounts.share_token_mint.to_account_info(),
             pool.to_account_info(),
             &[
                 pool.share_token.as_ref(),
@@ -270,14 +492,16 @@ pub fn close_pool<'info>(ctx: Context<'_, '_, '_, 'info, ClosePool<'info>>) -> R
                 &[pool.bump],
             ],
             unsold_shares,
+            ctx.accounts.share_token_mint.decimals,
         )?;
     }
 
     emit!(Close {
         assets: total_assets_minus_fees,
         platform_fees,
-        swap_fees_asset: pool.total_swap_fees_asset,
-        swap_fees_share: pool.total_swap_fees_share,
+        swap_fees_asset: if refunding { 0 } else { pool.total_swap_fees_asset },
+        swap_fees_share: if refunding { 0 } else { pool.total_swap_fees_share },
+        refunding,
     });
 
     Ok(())
@@ -285,27 +509,56 @@ pub fn close_pool<'info>(ctx: Context<'_, '_, '_, 'info, ClosePool<'info>>) -> R
 
 
 
+/// Redeem the purchased shares (and optionally the referral assets) of the user after the pool is closed.
+/// Shares are released according to the pool's vesting schedule: nothing is released before `vest_cliff`,
+/// shares unlock linearly between `vest_cliff` and `vest_end`, and everything is released after `vest_end`.
+/// This function can be called repeatedly as shares unlock. Once the vesting is over and nothing is left to claim,
+/// the user's state in the pool is closed and its rent returned to the user.
+/// In pools whose asset is wrapped SOL, the referral assets are unwrapped to the user's wallet when the
+/// `native_unwrap_account` is passed.
+/// * `ctx` - The program context
+/// * `referred` - Whether to also claim the assets earned from referrals
 pub fn redeem(ctx: Context<RedeemTokens>, referred: bool) -> Result<()> {
-    if !ctx.accounts.pool.closed {
+    // Buyers of a refunded sale get their assets back instead
+    if !ctx.accounts.pool.closed || ctx.accounts.pool.refunding {
         return Err(PoolError::RedeemingDisallowed.into());
     }
+    if (ctx.accounts.user_asset_token_account.is_none() || ctx.accounts.native_unwrap_account.is_some())
+        && !is_native_mint(&ctx.accounts.asset_token_mint.key())
+    {
+        return Err(PoolError::NativeSolDisallowed.into());
+    }
     let user_state_in_pool = &mut ctx.accounts.user_state_in_pool;
     let shares = user_state_in_pool.purchased_shares;
+    let redeemed_shares = user_state_in_pool.redeemed_shares;
+
+    // The user's total allocation is what is left to claim plus what has already been claimed
+    let vested_shares = compute_vested_shares(
+        safe_math::safe_add(shares, redeemed_shares)?,
+        Clock::get()?.unix_timestamp,
+        ctx.accounts.pool.vest_cliff,
+        ctx.accounts.pool.vest_end,
+    )?;
+    let claimable_shares = safe_sub(vested_shares, redeemed_shares)?;
 
     // Fall back to the remaining shares if there are not enough shares in the pool due to slippage/rounding errors/etc. Could be unlikely, but better to be safe.
-    let user_eligible_shares_to_claim = if ctx.accounts.pool_share_token_account.amount < shares {
+    let user_eligible_shares_to_claim = if ctx.accounts.pool_share_token_account.amount < claimable_shares {
         ctx.accounts.pool_share_token_account.amount
     } else {
-        shares
+        claimable_shares
     };
 
     user_state_in_pool.purchased_shares = safe_sub(shares, user_eligible_shares_to_claim)?;
+    user_state_in_pool.redeemed_shares = safe_math::safe_add(redeemed_shares, user_eligible_shares_to_claim)?;
+    // What is left of the totals is still owed, until the pool accounts are closed
+    ctx.accounts.pool.total_purchased = ctx.accounts.pool.total_purchased.saturating_sub(user_eligible_shares_to_claim);
     
-    if shares != 0 {
+    if user_eligible_shares_to_claim != 0 {
         transfer_tokens_from(
-            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.share_token_program.to_account_info(),
             ctx.accounts.pool_share_token_account.to_account_info(),
             ctx.accounts.user_share_token_account.to_account_info(),
+            ctx.accounts.share_token_mint.to_account_info(),
             ctx.accounts.pool.to_account_info(),
             &[
                 ctx.accounts.pool.share_token.as_ref(),
@@ -314,7 +567,8 @@ pub fn redeem(ctx: Context<RedeemTokens>, referred: bool) -> Result<()> {
                 ctx.accounts.pool.salt.as_bytes(),
                 &[ctx.accounts.pool.bump],
             ],
-            user_eligible_shares_to_claim
+            user_eligible_shares_to_claim,
+            ctx.accounts.share_token_mint.decimals,
         )?;
 
         emit!(Redeem {
@@ -332,10 +586,17 @@ pub fn redeem(ctx: Context<RedeemTokens>, referred: bool) -> Result<()> {
             assets
         };
         user_state_in_pool.referred_assets = safe_sub(assets, referrer_eligible_assets_to_claim)?;
+        ctx.accounts.pool.total_referred = ctx.accounts.pool.total_referred.saturating_sub(referrer_eligible_assets_to_claim);
+        // In native SOL mode the assets go through the transient wSOL account, unwrapped to the user below
+        let recipient_asset_token_account = match &ctx.accounts.native_unwrap_account {
+            Some(native_unwrap_account) => native_unwrap_account.to_account_info(),
+            None => ctx.accounts.user_asset_token_account.as_ref().ok_or(PoolError::MissingNativeUnwrapAccount)?.to_account_info(),
+        };
         transfer_tokens_from(
-            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.asset_token_program.to_account_info(),
             ctx.accounts.pool_asset_token_account.to_account_info(),
-            ctx.accounts.user_asset_token_account.to_account_info(),
+            recipient_asset_token_account,
+            ctx.accounts.asset_token_mint.to_account_info(),
             ctx.accounts.pool.to_account_info(),
             &[
                 ctx.accounts.pool.share_token.as_ref(),
@@ -344,21 +605,223 @@ pub fn redeem(ctx: Context<RedeemTokens>, referred: bool) -> Result<()> {
                 ctx.accounts.pool.salt.as_bytes(),
                 &[ctx.accounts.pool.bump],
             ],
-            referrer_eligible_assets_to_claim
+            referrer_eligible_assets_to_claim,
+            ctx.accounts.asset_token_mint.decimals,
         )?
     }
 
+    // The transient account is always closed to the user, unwrapping the claimed assets (if any)
+    if let Some(native_unwrap_account) = &ctx.accounts.native_unwrap_account {
+        unwrap_native(
+            ctx.accounts.asset_token_program.to_account_info(),
+            native_unwrap_account.to_account_info(),
+            ctx.accounts.user.to_account_info(),
+            ctx.accounts.pool.to_account_info(),
+            &[
+                ctx.accounts.pool.share_token.as_ref(),
+                ctx.accounts.pool.asset_token.as_ref(),
+                ctx.accounts.pool.creator.as_ref(),
+                ctx.accounts.pool.salt.as_bytes(),
+                &[ctx.accounts.pool.bump],
+            ],
+        )?;
+    }
+
+    // Nothing more can be claimed from the pool, return the rent of the user's state
+    if ctx.accounts.user_state_in_pool.purchased_shares == 0
+        && ctx.accounts.user_state_in_pool.referred_assets == 0
+        && Clock::get()?.unix_timestamp >= ctx.accounts.pool.vest_end
+    {
+        ctx.accounts.user_state_in_pool.close(ctx.accounts.user.to_account_info())?;
+    }
+
     Ok(())
 }
 
+// Claims paid out besides the redemption of shares
+pub mod claims {
+    use super::*;
+
+    /// Refund the assets paid by the user in a sale closed below its soft cap (`min_assets_raised`).
+    /// The purchased shares are void: they are returned to the creator/manager by `close_pool` and can no
+    /// longer be redeemed. Referral assets are not paid out either, as they are part of the refunded assets.
+    /// Nothing is left to claim from the pool afterwards, so the user's state in the pool is closed and its rent
+    /// returned to the user.
+    /// In pools whose asset is wrapped SOL, the assets are unwrapped to the user's wallet when the
+    /// `native_unwrap_account` is passed.
+    /// * `ctx` - The program context
+    pub fn refund(ctx: Context<RefundTokens>) -> Result<()> {
+        if !ctx.accounts.pool.closed || !ctx.accounts.pool.refunding {
+            return Err(PoolError::RefundingDisallowed.into());
+        }
+        if (ctx.accounts.user_asset_token_account.is_none() || ctx.accounts.native_unwrap_account.is_some())
+            && !is_native_mint(&ctx.accounts.asset_token_mint.key())
+        {
+            return Err(PoolError::NativeSolDisallowed.into());
+        }
+        let pool = &mut ctx.accounts.pool;
+        let user_state_in_pool = &mut ctx.accounts.user_state_in_pool;
+
+        // Fall back to the remaining assets if there are not enough assets in the pool due to rounding errors/etc.
+        let assets = user_state_in_pool.assets_paid.min(ctx.accounts.pool_asset_token_account.amount);
+        user_state_in_pool.assets_paid = 0;
+        user_state_in_pool.purchased_shares = 0;
+        user_state_in_pool.referred_assets = 0;
+        pool.total_assets_paid = pool.total_assets_paid.saturating_sub(assets);
+
+        let pool_seeds: &[&[u8]] = &[
+            pool.share_token.as_ref(),
+            pool.asset_token.as_ref(),
+            pool.creator.as_ref(),
+            pool.salt.as_bytes(),
+            &[pool.bump],
+        ];
+
+        if assets != 0 {
+            // In native SOL mode the assets go through the transient wSOL account, unwrapped to the user below
+            let recipient_asset_token_account = match &ctx.accounts.native_unwrap_account {
+                Some(native_unwrap_account) => native_unwrap_account.to_account_info(),
+                None => ctx.accounts.user_asset_token_account.as_ref().ok_or(PoolError::MissingNativeUnwrapAccount)?.to_account_info(),
+            };
+            transfer_tokens_from(
+                ctx.accounts.asset_token_program.to_account_info(),
+                ctx.accounts.pool_asset_token_account.to_account_info(),
+                recipient_asset_token_account,
+                ctx.accounts.asset_token_mint.to_account_info(),
+                pool.to_account_info(),
+                pool_seeds,
+                assets,
+                ctx.accounts.asset_token_mint.decimals,
+            )?;
+
+            emit!(Refund {
+                caller: *ctx.accounts.user.key,
+                assets,
+            });
+        }
+
+        // The transient account is always closed to the user, unwrapping the refunded assets (if any)
+        if let Some(native_unwrap_account) = &ctx.accounts.native_unwrap_account {
+            unwrap_native(
+                ctx.accounts.asset_token_program.to_account_info(),
+                native_unwrap_account.to_account_info(),
+                ctx.accounts.user.to_account_info(),
+                pool.to_account_info(),
+                pool_seeds,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Claim the assets earned by the referrer from referrals once the pool is closed, independently of the
+    /// redemption and vesting of its shares, so that referrers who never bought can be paid.
+    /// Once nothing is left to claim and the referrer holds no shares in the pool, its state in the pool is closed
+    /// and its rent returned to the referrer.
+    /// In pools whose asset is wrapped SOL, the assets are unwrapped to the referrer's wallet when the
+    /// `native_unwrap_account` is passed.
+    /// * `ctx` - The program context
+    pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+        // Referral assets are part of the refunded assets in refund mode
+        if !ctx.accounts.pool.closed || ctx.accounts.pool.refunding {
+            return Err(PoolError::ReferralClaimDisallowed.into());
+        }
+        if (ctx.accounts.referrer_asset_token_account.is_none()
+            || ctx.accounts.native_unwrap_account.is_some())
+            && !is_native_mint(&ctx.accounts.asset_token_mint.key())
+        {
+            return Err(PoolError::NativeSolDisallowed.into());
+        }
+        let referrer_state_in_pool = &mut ctx.accounts.referrer_state_in_pool;
+
+        // Fall back to the remaining assets if there are not enough assets in the pool due to rounding errors/etc.
+        let assets = referrer_state_in_pool
+            .referred_assets
+            .min(ctx.accounts.pool_asset_token_account.amount);
+        referrer_state_in_pool.referred_assets =
+            safe_sub(referrer_state_in_pool.referred_assets, assets)?;
+        ctx.accounts.pool.total_referred = ctx.accounts.pool.total_referred.saturating_sub(assets);
+
+        let pool = &ctx.accounts.pool;
+        let pool_seeds: &[&[u8]] = &[
+            pool.share_token.as_ref(),
+            pool.asset_token.as_ref(),
+            pool.creator.as_ref(),
+            pool.salt.as_bytes(),
+            &[pool.bump],
+        ];
+
+        if assets != 0 {
+            // In native SOL mode the assets go through the transient wSOL account, unwrapped to the referrer below
+            let recipient_asset_token_account = match &ctx.accounts.native_unwrap_account {
+                Some(native_unwrap_account) => native_unwrap_account.to_account_info(),
+                None => ctx
+                    .accounts
+                    .referrer_asset_token_account
+                    .as_ref()
+                    .ok_or(PoolError::MissingNativeUnwrapAccount)?
+                    .to_account_info(),
+            };
+            transfer_tokens_from(
+                ctx.accounts.asset_token_program.to_account_info(),
+                ctx.accounts.pool_asset_token_account.to_account_info(),
+                recipient_asset_token_account,
+                ctx.accounts.asset_token_mint.to_account_info(),
+                pool.to_account_info(),
+                pool_seeds,
+                assets,
+                ctx.accounts.asset_token_mint.decimals,
+            )?;
+
+            emit!(ReferralRewardsClaimed {
+                pool: pool.key(),
+                referrer: *ctx.accounts.referrer.key,
+                assets,
+            });
+        }
+
+        // The transient account is always closed to the referrer, unwrapping the claimed assets (if any)
+        if let Some(native_unwrap_account) = &ctx.accounts.native_unwrap_account {
+            unwrap_native(
+                ctx.accounts.asset_token_program.to_account_info(),
+                native_unwrap_account.to_account_info(),
+                ctx.accounts.referrer.to_account_info(),
+                pool.to_account_info(),
+                pool_seeds,
+            )?;
+        }
+
+        // Nothing more can be claimed from the pool, return the rent of the referrer's state
+        if ctx.accounts.referrer_state_in_pool.purchased_shares == 0
+            && ctx.accounts.referrer_state_in_pool.referred_assets == 0
+        {
+            ctx.accounts
+                .referrer_state_in_pool
+                .close(ctx.accounts.referrer.to_account_info())?;
+        }
+
+        Ok(())
+    }
+}
+
 /// Retrieve the valid keys from a list of AccountInfo that match the ATAs of a list of recipient pubkeys set in the treasury
 /// and return them along with their respective fee percentages.
 /// * `a` - A list of FeeMapping structs, representing fee recipients and their percentages.
 /// * `b` - A list of AccountInfo, representing remaining_accounts passed in the instruction.
-fn retrieve_valid_keys<'a>(a: Vec<FeeMapping>, b: &[AccountInfo<'a>], token_mint: &Pubkey) -> Result<Vec<FeeRecipient<'a>>> {
-    // Compute the ATAs for each Pubkey in `a` and map them to their fee percentages
+/// * `token_mint` - The mint of the ATAs.
+/// * `token_program` - The token program owning the mint (SPL Token or Token-2022).
+/// * `native_sol` - Whether the recipients are paid in native SOL, in which case their wallets are expected instead of their ATAs.
+fn retrieve_valid_keys<'a>(a: Vec<FeeMapping>, b: &[AccountInfo<'a>], token_mint: &Pubkey, token_program: &Pubkey, native_sol: bool) -> Result<Vec<FeeRecipient<'a>>> {
+    // Compute the ATAs (or wallets) for each Pubkey in `a` and map them to their fee percentages
     let ata_to_fee: HashMap<Pubkey, u16> = a.iter()
-        .map(|recipient| (get_associated_token_address(&recipient.user, token_mint), recipient.percentage))
+        .map(|recipient| {
+            let key = if native_sol {
+                recipient.user
+            } else {
+                get_associated_token_address_with_program_id(&recipient.user, token_mint, token_program)
+            };
+            (key, recipient.percentage)
+        })
         .collect();
 
     let mut seen_keys: HashSet<Pubkey> = HashSet::with_capacity(b.len());
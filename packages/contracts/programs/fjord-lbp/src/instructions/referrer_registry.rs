@@ -0,0 +1,66 @@
+use crate::{AccessControlError, FeeConfig, OwnerConfig, ReferrerRegistration};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(referrer: Pubkey)]
+pub struct RegisterReferrer<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + ReferrerRegistration::LEN,
+        seeds = ["referrer".as_bytes(), referrer.as_ref()],
+        bump
+    )]
+    pub referrer_registration: Account<'info, ReferrerRegistration>,
+    #[account(
+        seeds = ["owner_config".as_bytes()],
+        constraint = config.owner == owner.key() @AccessControlError::Unauthorized,
+        bump = config.bump
+    )]
+    pub config: Account<'info, OwnerConfig>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnregisterReferrer<'info> {
+    #[account(
+        mut,
+        close = owner,
+        seeds = ["referrer".as_bytes(), referrer_registration.referrer.as_ref()],
+        bump = referrer_registration.bump
+    )]
+    pub referrer_registration: Account<'info, ReferrerRegistration>,
+    #[account(
+        seeds = ["owner_config".as_bytes()],
+        constraint = config.owner == owner.key() @AccessControlError::Unauthorized,
+        bump = config.bump
+    )]
+    pub config: Account<'info, OwnerConfig>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+// Registry of the referrers allowed to earn referral fees
+pub mod registry {
+    use super::*;
+
+    pub fn register_referrer(ctx: Context<RegisterReferrer>, referrer: Pubkey) -> Result<()> {
+        let registration = &mut ctx.accounts.referrer_registration;
+        registration.referrer = referrer;
+        registration.bump = ctx.bumps.referrer_registration;
+        Ok(())
+    }
+
+    // The registration account is closed to the owner on exit
+    pub fn unregister_referrer(_ctx: Context<UnregisterReferrer>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Require purchases to name registered referrers for them to earn referral fees, or accept any referrer.
+    pub fn set_referrer_registry_required(ctx: Context<FeeConfig>, required: bool) -> Result<()> {
+        ctx.accounts.config.referrer_registry_required = required;
+        Ok(())
+    }
+}
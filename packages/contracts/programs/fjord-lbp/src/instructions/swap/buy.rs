@@ -1,10 +1,11 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
+    _credit_referral, get_transfer_fee, get_transfer_inverse_fee, is_native_mint,
     math::{calculate_fee, preview_assets_in, preview_shares_out},
-    safe_math, standard_checks, Buy, LiquidityBootstrappingPool, OwnerConfig, PoolError,
-    PreviewAmountArgs, SwapTokens, UserStateInPool,
+    safe_math, standard_checks, transfer_tokens, wrap_native, Buy, LiquidityBootstrappingPool,
+    PoolError, PreviewAmountArgs, SaleFinalized, SwapTokens, UserStateInPool, WhitelistProof,
 };
 
 /// Swap a specific amount of assets for a minimum number of shares with a referrer and Merkle proof.
@@ -15,14 +16,15 @@ use crate::{
 /// * `min_shares_out` - The minimum number of shares expected to be received
 /// * `merkle_proof` - The Merkle proof for the whitelist
 /// * `referrer` - The referrer's public key (optional)
-#[access_control(standard_checks::before_token_swap(&ctx, merkle_proof, false))]
 pub fn swap_exact_assets_for_shares(
     ctx: Context<SwapTokens>,
     assets_in: u64,
     min_shares_out: u64,
-    merkle_proof: Option<Vec<[u8; 32]>>,
-    _referrer: Option<Pubkey>,
+    merkle_proof: Option<WhitelistProof>,
+    referrer: Option<Pubkey>,
 ) -> Result<()> {
+    // Swap checks shared with sales, returning the whitelist allocation of the user
+    let max_allocation = standard_checks::before_token_swap(&ctx, &merkle_proof, &referrer, false)?;
     if min_shares_out == 0 {
         return Err(PoolError::ZeroSlippage.into());
     }
@@ -30,7 +32,11 @@ pub fn swap_exact_assets_for_shares(
     let pool_asset_token_account = &mut ctx.accounts.pool_asset_token_account;
     let pool_share_token_account = &mut ctx.accounts.pool_share_token_account;
 
-    let swap_fees = calculate_fee(assets_in, ctx.accounts.config.swap_fee);
+    // Only the assets left after the token's transfer fee (if any) reach the pool
+    let asset_transfer_fee =
+        get_transfer_fee(&ctx.accounts.asset_token_mint.to_account_info(), assets_in)?;
+    let assets_received = safe_math::safe_sub(assets_in, asset_transfer_fee)?;
+    let swap_fees = calculate_fee(assets_received, pool.swap_fee);
     let shares_out = preview_shares_out(
         PreviewAmountArgs {
             assets: pool_asset_token_account.amount,
@@ -46,10 +52,13 @@ pub fn swap_exact_assets_for_shares(
             sale_end_time: pool.sale_end_time,
             start_weight_basis_points: pool.start_weight_basis_points,
             end_weight_basis_points: pool.end_weight_basis_points,
+            weight_curve: pool.weight_curve,
+            weight_knots: pool.weight_knots,
+            weight_adjustment: pool.weight_adjustment,
             total_swap_fees_asset: pool.total_swap_fees_asset,
             total_swap_fees_share: pool.total_swap_fees_share,
         },
-        safe_math::safe_sub(assets_in, swap_fees)?,
+        safe_math::safe_sub(assets_received, swap_fees)?,
     )?;
     pool.total_swap_fees_asset = safe_math::safe_add(pool.total_swap_fees_asset, swap_fees)?;
 
@@ -60,17 +69,22 @@ pub fn swap_exact_assets_for_shares(
     _swap_assets_for_shares(
         pool,
         &mut ctx.accounts.user_state_in_pool,
-        &mut ctx.accounts.config,
         &ctx.accounts.user_asset_token_account,
         pool_asset_token_account,
+        &ctx.accounts.asset_token_mint,
         &mut ctx.accounts.user,
-        &ctx.accounts.token_program,
+        &ctx.accounts.asset_token_program,
+        &ctx.accounts.system_program,
         assets_in,
+        asset_transfer_fee,
         shares_out,
         pool_asset_token_account.amount,
         pool_share_token_account.amount,
         swap_fees,
+        max_allocation,
         &mut ctx.accounts.referrer_state_in_pool,
+        &mut ctx.accounts.second_level_referrer_state_in_pool,
+        referrer,
     )?;
 
     Ok(())
@@ -85,14 +99,15 @@ pub fn swap_exact_assets_for_shares(
 /// * `merkle_proof` - The Merkle proof for the whitelist
 /// * `referrer` - The referrer's public key (optional)
 ///
-#[access_control(standard_checks::before_token_swap(&ctx, merkle_proof, false))]
 pub fn swap_assets_for_exact_shares(
     ctx: Context<SwapTokens>,
     shares_out: u64,
     max_assets_in: u64,
-    merkle_proof: Option<Vec<[u8; 32]>>,
-    _referrer: Option<Pubkey>,
+    merkle_proof: Option<WhitelistProof>,
+    referrer: Option<Pubkey>,
 ) -> Result<()> {
+    // Swap checks shared with sales, returning the whitelist allocation of the user
+    let max_allocation = standard_checks::before_token_swap(&ctx, &merkle_proof, &referrer, false)?;
     if max_assets_in == 0 {
         return Err(PoolError::ZeroSlippage.into());
     }
@@ -115,15 +130,23 @@ pub fn swap_assets_for_exact_shares(
             sale_end_time: pool.sale_end_time,
             start_weight_basis_points: pool.start_weight_basis_points,
             end_weight_basis_points: pool.end_weight_basis_points,
+            weight_curve: pool.weight_curve,
+            weight_knots: pool.weight_knots,
+            weight_adjustment: pool.weight_adjustment,
             total_swap_fees_asset: pool.total_swap_fees_asset,
             total_swap_fees_share: pool.total_swap_fees_share,
         },
         shares_out,
     )?;
-    let swap_fees = calculate_fee(assets_in, ctx.accounts.config.swap_fee);
+    let swap_fees = calculate_fee(assets_in, pool.swap_fee);
     assets_in = safe_math::safe_add(assets_in, swap_fees)?;
     pool.total_swap_fees_asset = safe_math::safe_add(pool.total_swap_fees_asset, swap_fees)?;
 
+    // Gross up the amount sent by the user so that the pool receives `assets_in` after the token's transfer fee (if any)
+    let asset_transfer_fee =
+        get_transfer_inverse_fee(&ctx.accounts.asset_token_mint.to_account_info(), assets_in)?;
+    assets_in = safe_math::safe_add(assets_in, asset_transfer_fee)?;
+
     if assets_in > max_assets_in {
         return Err(PoolError::SlippageExceeded.into());
     }
@@ -131,17 +154,22 @@ pub fn swap_assets_for_exact_shares(
     _swap_assets_for_shares(
         pool,
         &mut ctx.accounts.user_state_in_pool,
-        &mut ctx.accounts.config,
         &ctx.accounts.user_asset_token_account,
         pool_asset_token_account,
+        &ctx.accounts.asset_token_mint,
         &mut ctx.accounts.user,
-        &ctx.accounts.token_program,
+        &ctx.accounts.asset_token_program,
+        &ctx.accounts.system_program,
         assets_in,
+        asset_transfer_fee,
         shares_out,
         pool_asset_token_account.amount,
         pool_share_token_account.amount,
         swap_fees,
+        max_allocation,
         &mut ctx.accounts.referrer_state_in_pool,
+        &mut ctx.accounts.second_level_referrer_state_in_pool,
+        referrer,
     )?;
 
     Ok(())
@@ -151,50 +179,110 @@ pub fn swap_assets_for_exact_shares(
 fn _swap_assets_for_shares<'info>(
     pool: &mut Account<'info, LiquidityBootstrappingPool>,
     user_state_in_pool: &mut Account<'info, UserStateInPool>,
-    global_pool_config: &mut Account<'info, OwnerConfig>,
-    user_asset_token_account: &Account<'info, TokenAccount>,
-    pool_asset_token_account: &mut Account<'info, TokenAccount>,
+    user_asset_token_account: &Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pool_asset_token_account: &mut InterfaceAccount<'info, TokenAccount>,
+    asset_token_mint: &InterfaceAccount<'info, Mint>,
     user: &mut Signer<'info>,
-    token_program: &Program<'info, Token>,
+    asset_token_program: &Interface<'info, TokenInterface>,
+    system_program: &Program<'info, System>,
     assets_in: u64,
+    asset_transfer_fee: u64,
     shares_out: u64,
     assets: u64,
     shares: u64,
     swap_fees: u64,
+    max_allocation: u64,
     referrer_state_in_pool: &mut Option<Account<'info, UserStateInPool>>,
+    second_level_referrer_state_in_pool: &mut Option<Box<Account<'info, UserStateInPool>>>,
+    referrer: Option<Pubkey>,
 ) -> Result<()> {
-    if safe_math::safe_sub(safe_math::safe_add(assets, assets_in)?, swap_fees)?
+    if assets_in < pool.min_assets_per_buy {
+        return Err(PoolError::AssetsInTooLow.into());
+    }
+    // The amount of assets that actually reaches the pool
+    let assets_received = safe_math::safe_sub(assets_in, asset_transfer_fee)?;
+    if safe_math::safe_sub(safe_math::safe_add(assets, assets_received)?, swap_fees)?
         >= pool.max_assets_in
     {
         return Err(PoolError::AssetsInExceeded.into());
     }
-
-    // Transfer assets from user to pool
-    let asset_transfer_instruction = Transfer {
-        from: user_asset_token_account.to_account_info(),
-        to: pool_asset_token_account.to_account_info(),
-        authority: user.to_account_info(),
-    };
-    let asset_cpi_ctx =
-        CpiContext::new(token_program.to_account_info(), asset_transfer_instruction);
-    token::transfer(asset_cpi_ctx, assets_in)?;
-
-    let total_purchased_after: u64 = pool.total_purchased + shares_out;
+    // The assets buying the shares, net of the swap fees
+    let assets_spent = safe_math::safe_sub(assets_received, swap_fees)?;
+    // The purchase cannot take the sale past its hard cap, the one reaching it ends the sale
+    let total_assets_raised = safe_math::safe_add(pool.total_assets_raised, assets_spent)?;
+    if pool.max_assets_raised != 0 && total_assets_raised > pool.max_assets_raised {
+        return Err(PoolError::HardCapExceeded.into());
+    }
+    let total_purchased_after = safe_math::safe_add(pool.total_purchased, shares_out)?;
     if (total_purchased_after >= pool.max_shares_out) || (total_purchased_after >= shares) {
         return Err(PoolError::SharesOutExceeded.into());
     }
-    pool.total_purchased = total_purchased_after;
-    user_state_in_pool.purchased_shares =
+
+    // Per-wallet allocation limits, on the assets spent by the user including this purchase
+    let user_assets_spent = safe_math::safe_add(user_state_in_pool.assets_spent, assets_spent)?;
+    let user_purchased_shares =
         safe_math::safe_add(user_state_in_pool.purchased_shares, shares_out)?;
+    if pool.max_assets_per_user != 0 && user_assets_spent > pool.max_assets_per_user {
+        return Err(PoolError::UserAssetsInExceeded.into());
+    }
+    if pool.max_shares_per_user != 0 && user_purchased_shares > pool.max_shares_per_user {
+        return Err(PoolError::UserSharesOutExceeded.into());
+    }
+    if max_allocation != 0 && user_assets_spent > max_allocation {
+        return Err(PoolError::WhitelistAllocationExceeded.into());
+    }
 
-    match referrer_state_in_pool.as_mut() {
-        Some(referrer_state) if global_pool_config.referral_fee != 0 => {
-            let assets_referred = calculate_fee(assets_in, global_pool_config.referral_fee);
-            pool.total_referred = safe_math::safe_add(pool.total_referred, assets_referred)?;
-            referrer_state.referred_assets =
-                safe_math::safe_add(referrer_state.referred_assets, assets_referred)?;
-        }
-        _ => {}
+    // Transfer assets from user to pool
+    match user_asset_token_account {
+        Some(user_asset_token_account) => transfer_tokens(
+            asset_token_program.to_account_info(),
+            user_asset_token_account.to_account_info(),
+            pool_asset_token_account.to_account_info(),
+            asset_token_mint.to_account_info(),
+            user.to_account_info(),
+            assets_in,
+            asset_token_mint.decimals,
+        )?,
+        // Wrap the user's lamports straight into the pool's wSOL account
+        None if is_native_mint(&asset_token_mint.key()) => wrap_native(
+            system_program.to_account_info(),
+            asset_token_program.to_account_info(),
+            user.to_account_info(),
+            pool_asset_token_account.to_account_info(),
+            assets_in,
+        )?,
+        None => return Err(PoolError::NativeSolDisallowed.into()),
+    }
+
+    pool.total_purchased = total_purchased_after;
+    pool.traded = true;
+    user_state_in_pool.purchased_shares = user_purchased_shares;
+    user_state_in_pool.assets_spent = user_assets_spent;
+    // Everything that reached the pool is owed back to the buyer if the sale fails
+    user_state_in_pool.assets_paid =
+        safe_math::safe_add(user_state_in_pool.assets_paid, assets_received)?;
+    pool.total_assets_paid = safe_math::safe_add(pool.total_assets_paid, assets_received)?;
+
+    _credit_referral(
+        pool,
+        user_state_in_pool,
+        referrer_state_in_pool,
+        second_level_referrer_state_in_pool,
+        referrer,
+        user.key(),
+        assets_received,
+        false,
+    )?;
+
+    // The sale ends as soon as the hard cap is reached so that the pool can be closed right away
+    pool.total_assets_raised = total_assets_raised;
+    if pool.max_assets_raised != 0 && total_assets_raised == pool.max_assets_raised {
+        pool.sale_end_time = Clock::get()?.unix_timestamp;
+        emit!(SaleFinalized {
+            pool: pool.key(),
+            total_assets_raised: pool.total_assets_raised,
+            sale_end_time: pool.sale_end_time,
+        });
     }
 
     emit!(Buy {
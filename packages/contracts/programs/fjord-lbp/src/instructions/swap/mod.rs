@@ -1,7 +1,9 @@
 pub mod buy;
+pub mod referral;
 pub mod sell;
 pub mod shared;
 
 pub use buy::*;
+pub use referral::*;
 pub use sell::*;
 pub use shared::*;
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    math::calculate_fee, referral_fee, safe_math, LiquidityBootstrappingPool, Referral,
+    UserStateInPool,
+};
+
+/// Credit the referrer of a purchase with the referral fee on the assets it brings to the pool, recording it
+/// as the referrer of the user, or debit it of the referral fee on the assets the user takes back when selling.
+/// The referral fee follows the schedule of the pool, at the tier reached by the volume the referrer brought,
+/// and is split with the referrer of the referrer when the schedule has a second level.
+/// Referrers are never debited more than the referral fees they earned.
+#[allow(clippy::too_many_arguments)]
+pub fn _credit_referral<'info>(
+    pool: &mut Account<'info, LiquidityBootstrappingPool>,
+    user_state_in_pool: &mut Account<'info, UserStateInPool>,
+    referrer_state_in_pool: &mut Option<Account<'info, UserStateInPool>>,
+    second_level_referrer_state_in_pool: &mut Option<Box<Account<'info, UserStateInPool>>>,
+    referrer: Option<Pubkey>,
+    user: Pubkey,
+    assets: u64,
+    is_sell: bool,
+) -> Result<()> {
+    let (Some(referrer), Some(referrer_state)) = (referrer, referrer_state_in_pool.as_mut()) else {
+        return Ok(());
+    };
+    user_state_in_pool.referrer = referrer;
+
+    // Sales are debited at the tier the referrer was at before the purchases they take back
+    if is_sell {
+        referrer_state.referred_volume = referrer_state.referred_volume.saturating_sub(assets);
+    }
+    let fee = calculate_fee(
+        assets,
+        referral_fee(
+            &pool.referral_schedule,
+            pool.referral_fee,
+            referrer_state.referred_volume,
+        ),
+    );
+    if !is_sell {
+        referrer_state.referred_volume =
+            safe_math::safe_add(referrer_state.referred_volume, assets)?;
+    }
+
+    let second_level = second_level_referrer_state_in_pool.as_mut();
+    let second_level_fee = match second_level {
+        Some(_) => calculate_fee(fee, pool.referral_schedule.second_level_basis_points),
+        None => 0,
+    };
+    let referred_assets = _move_referred_assets(
+        pool,
+        referrer_state,
+        safe_math::safe_sub(fee, second_level_fee)?,
+        is_sell,
+    )?;
+    let (second_level_referrer, second_level_referred_assets) = match second_level {
+        Some(second_level_state) => (
+            referrer_state.referrer,
+            _move_referred_assets(pool, second_level_state, second_level_fee, is_sell)?,
+        ),
+        None => (Pubkey::default(), 0),
+    };
+
+    emit!(Referral {
+        pool: pool.key(),
+        referrer,
+        user,
+        assets,
+        referred_assets,
+        second_level_referrer,
+        second_level_referred_assets,
+        is_sell,
+    });
+    Ok(())
+}
+
+/// Credit `fee` to the referred assets of a referrer, or debit it of at most its referred assets when selling.
+fn _move_referred_assets(
+    pool: &mut LiquidityBootstrappingPool,
+    referrer_state: &mut UserStateInPool,
+    fee: u64,
+    is_sell: bool,
+) -> Result<u64> {
+    if is_sell {
+        let fee = fee.min(referrer_state.referred_assets);
+        referrer_state.referred_assets = safe_math::safe_sub(referrer_state.referred_assets, fee)?;
+        pool.total_referred = safe_math::safe_sub(pool.total_referred, fee)?;
+        Ok(fee)
+    } else {
+        referrer_state.referred_assets = safe_math::safe_add(referrer_state.referred_assets, fee)?;
+        pool.total_referred = safe_math::safe_add(pool.total_referred, fee)?;
+        Ok(fee)
+    }
+}
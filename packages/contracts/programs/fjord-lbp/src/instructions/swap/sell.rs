@@ -1,10 +1,11 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
+    _credit_referral, get_transfer_fee, get_transfer_inverse_fee,
     math::{calculate_fee, preview_assets_out, preview_shares_in},
-    safe_math, standard_checks, transfer_tokens_from, LiquidityBootstrappingPool, OwnerConfig,
-    PoolError, PreviewAmountArgs, Sell, SwapTokens, UserStateInPool,
+    safe_math, standard_checks, transfer_tokens_from, unwrap_native, LiquidityBootstrappingPool,
+    PoolError, PreviewAmountArgs, Sell, SwapTokens, UserStateInPool, WhitelistProof,
 };
 
 /// Swap a specific number of shares for a maximum amount of assets.
@@ -14,14 +15,15 @@ use crate::{
 /// *`maxSharesIn` - The number of shares to be exchanged for assets.
 /// *`recipient` - The address to receive the assets.
 /// *`proof` - The Merkle proof for whitelisting.
-#[access_control(standard_checks::before_token_swap(&ctx, merkle_proof, true))]
 pub fn swap_shares_for_exact_assets(
     ctx: Context<SwapTokens>,
     assets_out: u64,
     max_shares_in: u64,
-    merkle_proof: Option<Vec<[u8; 32]>>,
-    _referrer: Option<Pubkey>,
+    merkle_proof: Option<WhitelistProof>,
+    referrer: Option<Pubkey>,
 ) -> Result<()> {
+    // Swap checks shared with purchases, the whitelist allocation only limits purchases
+    standard_checks::before_token_swap(&ctx, &merkle_proof, &referrer, true)?;
     if max_shares_in == 0 {
         return Err(PoolError::ZeroSlippage.into());
     }
@@ -29,6 +31,11 @@ pub fn swap_shares_for_exact_assets(
     let pool_asset_token_account = &mut ctx.accounts.pool_asset_token_account;
     let pool_share_token_account = &mut ctx.accounts.pool_share_token_account;
 
+    // Gross up the amount sent by the pool so that the user receives `assets_out` after the token's transfer fee (if any)
+    let assets_out = safe_math::safe_add(
+        assets_out,
+        get_transfer_inverse_fee(&ctx.accounts.asset_token_mint.to_account_info(), assets_out)?,
+    )?;
     let mut shares_in = preview_shares_in(
         PreviewAmountArgs {
             assets: pool_asset_token_account.amount,
@@ -44,12 +51,15 @@ pub fn swap_shares_for_exact_assets(
             sale_end_time: pool.sale_end_time,
             start_weight_basis_points: pool.start_weight_basis_points,
             end_weight_basis_points: pool.end_weight_basis_points,
+            weight_curve: pool.weight_curve,
+            weight_knots: pool.weight_knots,
+            weight_adjustment: pool.weight_adjustment,
             total_swap_fees_asset: pool.total_swap_fees_asset,
             total_swap_fees_share: pool.total_swap_fees_share,
         },
         assets_out,
     )?;
-    let swap_fees = calculate_fee(shares_in, ctx.accounts.config.swap_fee);
+    let swap_fees = calculate_fee(shares_in, pool.swap_fee);
     shares_in = safe_math::safe_add(shares_in, swap_fees)?;
     pool.total_swap_fees_share = safe_math::safe_add(pool.total_swap_fees_share, swap_fees)?;
 
@@ -60,16 +70,20 @@ pub fn swap_shares_for_exact_assets(
     _swap_shares_for_assets(
         pool,
         &mut ctx.accounts.user_state_in_pool,
-        &mut ctx.accounts.config,
         &ctx.accounts.user_asset_token_account,
+        &ctx.accounts.native_unwrap_account,
         pool_asset_token_account,
+        &ctx.accounts.asset_token_mint,
         &mut ctx.accounts.user,
-        &ctx.accounts.token_program,
+        &ctx.accounts.asset_token_program,
         assets_out,
         shares_in,
         pool_asset_token_account.amount,
         pool_share_token_account.amount,
         swap_fees,
+        &mut ctx.accounts.referrer_state_in_pool,
+        &mut ctx.accounts.second_level_referrer_state_in_pool,
+        referrer,
     )?;
     Ok(())
 }
@@ -81,14 +95,15 @@ pub fn swap_shares_for_exact_assets(
 /// * `minAssetsOut` - The minimum amount of assets expected to be received.
 /// * `recipient` -  The address to receive the assets.
 /// * `proof` -  The Merkle proof for whitelisting.
-#[access_control(standard_checks::before_token_swap(&ctx, merkle_proof, true))]
 pub fn swap_exact_shares_for_assets(
     ctx: Context<SwapTokens>,
     shares_in: u64,
     min_assets_out: u64,
-    merkle_proof: Option<Vec<[u8; 32]>>,
-    _referrer: Option<Pubkey>,
+    merkle_proof: Option<WhitelistProof>,
+    referrer: Option<Pubkey>,
 ) -> Result<()> {
+    // Swap checks shared with purchases, the whitelist allocation only limits purchases
+    standard_checks::before_token_swap(&ctx, &merkle_proof, &referrer, true)?;
     if min_assets_out == 0 {
         return Err(PoolError::ZeroSlippage.into());
     }
@@ -96,7 +111,7 @@ pub fn swap_exact_shares_for_assets(
     let pool_asset_token_account = &mut ctx.accounts.pool_asset_token_account;
     let pool_share_token_account = &mut ctx.accounts.pool_share_token_account;
 
-    let swap_fees = calculate_fee(shares_in, ctx.accounts.config.swap_fee);
+    let swap_fees = calculate_fee(shares_in, pool.swap_fee);
     let assets_out = preview_assets_out(
         PreviewAmountArgs {
             assets: pool_asset_token_account.amount,
@@ -112,6 +127,9 @@ pub fn swap_exact_shares_for_assets(
             sale_end_time: pool.sale_end_time,
             start_weight_basis_points: pool.start_weight_basis_points,
             end_weight_basis_points: pool.end_weight_basis_points,
+            weight_curve: pool.weight_curve,
+            weight_knots: pool.weight_knots,
+            weight_adjustment: pool.weight_adjustment,
             total_swap_fees_asset: pool.total_swap_fees_asset,
             total_swap_fees_share: pool.total_swap_fees_share,
         },
@@ -119,23 +137,30 @@ pub fn swap_exact_shares_for_assets(
     )?;
     pool.total_swap_fees_share = safe_math::safe_add(pool.total_swap_fees_share, swap_fees)?;
 
-    if assets_out < min_assets_out {
+    // Only the assets left after the token's transfer fee (if any) reach the user
+    let asset_transfer_fee =
+        get_transfer_fee(&ctx.accounts.asset_token_mint.to_account_info(), assets_out)?;
+    if safe_math::safe_sub(assets_out, asset_transfer_fee)? < min_assets_out {
         return Err(PoolError::SlippageExceeded.into());
     }
 
     _swap_shares_for_assets(
         pool,
         &mut ctx.accounts.user_state_in_pool,
-        &mut ctx.accounts.config,
         &ctx.accounts.user_asset_token_account,
+        &ctx.accounts.native_unwrap_account,
         pool_asset_token_account,
+        &ctx.accounts.asset_token_mint,
         &mut ctx.accounts.user,
-        &ctx.accounts.token_program,
+        &ctx.accounts.asset_token_program,
         assets_out,
         shares_in,
         pool_asset_token_account.amount,
         pool_share_token_account.amount,
         swap_fees,
+        &mut ctx.accounts.referrer_state_in_pool,
+        &mut ctx.accounts.second_level_referrer_state_in_pool,
+        referrer,
     )?;
 
     Ok(())
@@ -145,16 +170,20 @@ pub fn swap_exact_shares_for_assets(
 fn _swap_shares_for_assets<'info>(
     pool: &mut Account<'info, LiquidityBootstrappingPool>,
     user_state_in_pool: &mut Account<'info, UserStateInPool>,
-    _global_pool_config: &mut Account<'info, OwnerConfig>,
-    user_asset_token_account: &Account<'info, TokenAccount>,
-    pool_asset_token_account: &mut Account<'info, TokenAccount>,
+    user_asset_token_account: &Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    native_unwrap_account: &Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pool_asset_token_account: &mut InterfaceAccount<'info, TokenAccount>,
+    asset_token_mint: &InterfaceAccount<'info, Mint>,
     user: &mut Signer<'info>,
-    token_program: &Program<'info, Token>,
+    asset_token_program: &Interface<'info, TokenInterface>,
     assets_out: u64,
     shares_in: u64,
     assets: u64,
     shares: u64,
     swap_fees: u64,
+    referrer_state_in_pool: &mut Option<Account<'info, UserStateInPool>>,
+    second_level_referrer_state_in_pool: &mut Option<Box<Account<'info, UserStateInPool>>>,
+    referrer: Option<Pubkey>,
 ) -> Result<()> {
     if assets >= pool.max_assets_in {
         return Err(PoolError::AssetsInExceeded.into());
@@ -169,22 +198,70 @@ fn _swap_shares_for_assets<'info>(
     user_state_in_pool.purchased_shares =
         safe_math::safe_sub(user_state_in_pool.purchased_shares, shares_in)?;
     pool.total_purchased = safe_math::safe_sub(total_purchased_before, shares_in)?;
+    // Sellers may get back more than was raised, out of the initial assets of the pool
+    pool.total_assets_raised = pool.total_assets_raised.saturating_sub(assets_out);
+    // Assets received from sales are no longer owed to the seller, up to what it paid
+    let assets_repaid = assets_out.min(user_state_in_pool.assets_paid);
+    user_state_in_pool.assets_paid =
+        safe_math::safe_sub(user_state_in_pool.assets_paid, assets_repaid)?;
+    pool.total_assets_paid = safe_math::safe_sub(pool.total_assets_paid, assets_repaid)?;
+    // Assets received from sales free up the allocation of the seller
+    user_state_in_pool.assets_spent = user_state_in_pool.assets_spent.saturating_sub(assets_out);
 
-    transfer_tokens_from(
-        token_program.to_account_info(),
-        pool_asset_token_account.to_account_info(),
-        user_asset_token_account.to_account_info(),
-        pool.to_account_info(),
-        &[
-            pool.share_token.as_ref(),
-            pool.asset_token.as_ref(),
-            pool.creator.as_ref(),
-            pool.salt.as_bytes(),
-            &[pool.bump],
-        ],
+    _credit_referral(
+        pool,
+        user_state_in_pool,
+        referrer_state_in_pool,
+        second_level_referrer_state_in_pool,
+        referrer,
+        user.key(),
         assets_out,
+        true,
     )?;
 
+    let pool_seeds: &[&[u8]] = &[
+        pool.share_token.as_ref(),
+        pool.asset_token.as_ref(),
+        pool.creator.as_ref(),
+        pool.salt.as_bytes(),
+        &[pool.bump],
+    ];
+    match native_unwrap_account {
+        // Unwrap the assets through the transient wSOL account, which is closed to the user
+        Some(native_unwrap_account) => {
+            transfer_tokens_from(
+                asset_token_program.to_account_info(),
+                pool_asset_token_account.to_account_info(),
+                native_unwrap_account.to_account_info(),
+                asset_token_mint.to_account_info(),
+                pool.to_account_info(),
+                pool_seeds,
+                assets_out,
+                asset_token_mint.decimals,
+            )?;
+            unwrap_native(
+                asset_token_program.to_account_info(),
+                native_unwrap_account.to_account_info(),
+                user.to_account_info(),
+                pool.to_account_info(),
+                pool_seeds,
+            )?;
+        }
+        None => transfer_tokens_from(
+            asset_token_program.to_account_info(),
+            pool_asset_token_account.to_account_info(),
+            user_asset_token_account
+                .as_ref()
+                .ok_or(PoolError::MissingNativeUnwrapAccount)?
+                .to_account_info(),
+            asset_token_mint.to_account_info(),
+            pool.to_account_info(),
+            pool_seeds,
+            assets_out,
+            asset_token_mint.decimals,
+        )?,
+    }
+
     emit!(Sell {
         user: user.key(),
         shares: shares_in,
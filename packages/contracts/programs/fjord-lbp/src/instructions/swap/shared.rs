@@ -1,17 +1,20 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::{Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
-  LiquidityBootstrappingPool, OwnerConfig, UserStateInPool,
+  LiquidityBootstrappingPool, OwnerConfig, UserStateInPool, WhitelistProof,
 };
 
 #[derive(Accounts)]
-#[instruction(assets_in: u64, min_shares_out: u64, merkle_proof: Option<Vec<[u8; 32]>>, referrer: Option<Pubkey>)]
+#[instruction(assets_in: u64, min_shares_out: u64, merkle_proof: Option<WhitelistProof>, referrer: Option<Pubkey>)]
 pub struct SwapTokens<'info> {
     // Token mints
-    pub asset_token_mint: Account<'info, Mint>,
-    pub share_token_mint: Account<'info, Mint>,
+    #[account(mint::token_program = asset_token_program)]
+    pub asset_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mint::token_program = share_token_program)]
+    pub share_token_mint: Box<InterfaceAccount<'info, Mint>>,
     // The pool
     #[account(
       mut,
@@ -23,30 +26,46 @@ pub struct SwapTokens<'info> {
     #[account(
       mut,
       associated_token::mint = asset_token_mint,
-      associated_token::authority = pool
+      associated_token::authority = pool,
+      associated_token::token_program = asset_token_program
     )]
-    pub pool_asset_token_account: Box<Account<'info, TokenAccount>>,
+    pub pool_asset_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
       mut,
       associated_token::mint = share_token_mint,
-      associated_token::authority = pool
+      associated_token::authority = pool,
+      associated_token::token_program = share_token_program
     )]
-    pub pool_share_token_account: Box<Account<'info, TokenAccount>>,
+    pub pool_share_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
     // The token accounts that the user uses to store tokens
+    // The asset token account can be omitted in native SOL pools to pay and get paid in lamports
     #[account(
       init_if_needed,
       payer = user,
       associated_token::mint = asset_token_mint, 
-      associated_token::authority = user)
+      associated_token::authority = user,
+      associated_token::token_program = asset_token_program)
     ]
-    pub user_asset_token_account: Box<Account<'info, TokenAccount>>,
+    pub user_asset_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
     #[account(
       init_if_needed,
       payer = user,
       associated_token::mint = share_token_mint, 
-      associated_token::authority = user)
+      associated_token::authority = user,
+      associated_token::token_program = share_token_program)
     ]
-    pub user_share_token_account: Box<Account<'info, TokenAccount>>,
+    pub user_share_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    // Transient wSOL account used to unwrap the assets sold in native SOL pools, closed within the instruction
+    #[account(
+      init,
+      payer = user,
+      seeds = ["native_unwrap".as_bytes(), pool.key().as_ref(), user.key().as_ref()],
+      bump,
+      token::mint = asset_token_mint,
+      token::authority = pool,
+      token::token_program = asset_token_program
+    )]
+    pub native_unwrap_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
     // Global pool config
     #[account(
       seeds = ["owner_config".as_bytes()],
@@ -71,10 +90,28 @@ pub struct SwapTokens<'info> {
       bump
     )]
     pub referrer_state_in_pool: Option<Account<'info, UserStateInPool>>,
+    // The state in the pool of the referrer of the referrer, earning the second-level split of the referral fee
+    #[account(
+      mut,
+      seeds = [referrer_state_in_pool.as_ref().map(|state| state.referrer).unwrap_or_default().as_ref(), pool.key().as_ref()],
+      bump
+    )]
+    pub second_level_referrer_state_in_pool: Option<Box<Account<'info, UserStateInPool>>>,
+    /// CHECK: Registration of the referrer, only read to check that it exists when the referrer registry is required
+    #[account(
+      seeds = ["referrer".as_bytes(), referrer.unwrap_or_default().key().as_ref()],
+      bump
+    )]
+    pub referrer_registration: Option<UncheckedAccount<'info>>,
     // Miscs
     #[account(mut)]
     pub user: Signer<'info>,
     pub associated_token_program: Program<'info, AssociatedToken>,
-    pub token_program: Program<'info, Token>,
+    // Token programs owning the asset and share mints (SPL Token or Token-2022)
+    pub asset_token_program: Interface<'info, TokenInterface>,
+    pub share_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
+    /// CHECK: Instructions sysvar, read to verify the whitelist attestation of the user
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: Option<UncheckedAccount<'info>>,
 }
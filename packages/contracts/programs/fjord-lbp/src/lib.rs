@@ -13,6 +13,9 @@ pub use instructions::*;
 pub use state::*;
 pub use utils::*;
 
+#[cfg(test)]
+mod tests;
+
 // Program Id for the Fjord LBP program. This is the address this program will be deployed to.
 declare_id!("7UTvQUzE1iThaXhXDg1FsVoqcv3MBAgwUCW7PEKzNbPH");
 
@@ -32,6 +35,9 @@ pub mod fjord_lbp {
         platform_fee: u16,
         referral_fee: u16,
         swap_fee: u16,
+        max_platform_fee: u16,
+        max_referral_fee: u16,
+        max_swap_fee: u16,
     ) -> Result<()> {
         ownable::initializer::initialize_owner_config(
             ctx,
@@ -42,6 +48,9 @@ pub mod fjord_lbp {
             platform_fee,
             referral_fee,
             swap_fee,
+            max_platform_fee,
+            max_referral_fee,
+            max_swap_fee,
         )
     }
 
@@ -57,13 +66,25 @@ pub mod fjord_lbp {
         max_share_price: u64,
         max_shares_out: u64,
         max_assets_in: u64,
+        min_assets_raised: u64,
+        max_assets_raised: u64,
+        max_assets_per_user: u64,
+        max_shares_per_user: u64,
+        min_assets_per_buy: u64,
         start_weight_basis_points: u16,
         end_weight_basis_points: u16,
+        weight_curve: WeightCurve,
+        weight_knots: [WeightKnot; MAX_WEIGHT_KNOTS],
         sale_start_time: i64,
         sale_end_time: i64,
         vest_cliff: i64,
         vest_end: i64,
         whitelist_merkle_root: [u8; 32],
+        whitelist_leaf_version: WhitelistLeafVersion,
+        whitelist_signer: Pubkey,
+        whitelist_tier_start_times: [i64; MAX_WHITELIST_TIERS],
+        public_sale_time: i64,
+        referral_schedule: ReferralSchedule,
         selling_allowed: bool,
     ) -> Result<()> {
         initialize_pool::create_pool(
@@ -76,13 +97,25 @@ pub mod fjord_lbp {
             max_share_price,
             max_shares_out,
             max_assets_in,
+            min_assets_raised,
+            max_assets_raised,
+            max_assets_per_user,
+            max_shares_per_user,
+            min_assets_per_buy,
             start_weight_basis_points,
             end_weight_basis_points,
+            weight_curve,
+            weight_knots,
             sale_start_time,
             sale_end_time,
             vest_cliff,
             vest_end,
             whitelist_merkle_root,
+            whitelist_leaf_version,
+            whitelist_signer,
+            whitelist_tier_start_times,
+            public_sale_time,
+            referral_schedule,
             selling_allowed,
         )
     }
@@ -92,7 +125,7 @@ pub mod fjord_lbp {
         ctx: Context<SwapTokens>,
         assets_in: u64,
         min_shares_out: u64,
-        merkle_proof: Option<Vec<[u8; 32]>>,
+        merkle_proof: Option<WhitelistProof>,
         referrer: Option<Pubkey>,
     ) -> Result<()> {
         swap::buy::swap_exact_assets_for_shares(
@@ -108,7 +141,7 @@ pub mod fjord_lbp {
         ctx: Context<SwapTokens>,
         shares_out: u64,
         max_assets_in: u64,
-        merkle_proof: Option<Vec<[u8; 32]>>,
+        merkle_proof: Option<WhitelistProof>,
         referrer: Option<Pubkey>,
     ) -> Result<()> {
         swap::buy::swap_assets_for_exact_shares(
@@ -125,7 +158,7 @@ pub mod fjord_lbp {
         ctx: Context<SwapTokens>,
         shares_in: u64,
         min_assets_out: u64,
-        merkle_proof: Option<Vec<[u8; 32]>>,
+        merkle_proof: Option<WhitelistProof>,
         referrer: Option<Pubkey>,
     ) -> Result<()> {
         swap::sell::swap_exact_shares_for_assets(
@@ -141,7 +174,7 @@ pub mod fjord_lbp {
         ctx: Context<SwapTokens>,
         assets_out: u64,
         max_shares_in: u64,
-        merkle_proof: Option<Vec<[u8; 32]>>,
+        merkle_proof: Option<WhitelistProof>,
         referrer: Option<Pubkey>,
     ) -> Result<()> {
         swap::sell::swap_shares_for_exact_assets(
@@ -162,6 +195,14 @@ pub mod fjord_lbp {
         redemption::redeem(ctx, referred)
     }
 
+    pub fn refund(ctx: Context<RefundTokens>) -> Result<()> {
+        claims::refund(ctx)
+    }
+
+    pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+        claims::claim_referral_rewards(ctx)
+    }
+
     // View functions -----------------------------------------------------
     pub fn preview_assets_in(ctx: Context<ReturnPreviewContext>, shares_out: u64) -> Result<u64> {
         let mut assets_in = math::preview_assets_in(
@@ -179,6 +220,9 @@ pub mod fjord_lbp {
                 sale_end_time: ctx.accounts.pool.sale_end_time,
                 start_weight_basis_points: ctx.accounts.pool.start_weight_basis_points,
                 end_weight_basis_points: ctx.accounts.pool.end_weight_basis_points,
+                weight_curve: ctx.accounts.pool.weight_curve,
+                weight_knots: ctx.accounts.pool.weight_knots,
+                weight_adjustment: ctx.accounts.pool.weight_adjustment,
                 total_swap_fees_asset: ctx.accounts.pool.total_swap_fees_asset,
                 total_swap_fees_share: ctx.accounts.pool.total_swap_fees_share,
             },
@@ -186,13 +230,21 @@ pub mod fjord_lbp {
         )?;
         assets_in = safe_math::safe_add(
             assets_in,
-            math::calculate_fee(assets_in, ctx.accounts.config.swap_fee),
+            math::calculate_fee(assets_in, ctx.accounts.pool.swap_fee),
+        )?;
+        assets_in = safe_math::safe_add(
+            assets_in,
+            get_transfer_inverse_fee(&ctx.accounts.asset_token_mint.to_account_info(), assets_in)?,
         )?;
         emit!(PreviewAssetsIn { assets_in });
         Ok(assets_in)
     }
 
     pub fn preview_shares_in(ctx: Context<ReturnPreviewContext>, assets_out: u64) -> Result<u64> {
+        let assets_out = safe_math::safe_add(
+            assets_out,
+            get_transfer_inverse_fee(&ctx.accounts.asset_token_mint.to_account_info(), assets_out)?,
+        )?;
         let mut shares_in = math::preview_shares_in(
             PreviewAmountArgs {
                 assets: ctx.accounts.pool_asset_token_account.amount,
@@ -208,6 +260,9 @@ pub mod fjord_lbp {
                 sale_end_time: ctx.accounts.pool.sale_end_time,
                 start_weight_basis_points: ctx.accounts.pool.start_weight_basis_points,
                 end_weight_basis_points: ctx.accounts.pool.end_weight_basis_points,
+                weight_curve: ctx.accounts.pool.weight_curve,
+                weight_knots: ctx.accounts.pool.weight_knots,
+                weight_adjustment: ctx.accounts.pool.weight_adjustment,
                 total_swap_fees_asset: ctx.accounts.pool.total_swap_fees_asset,
                 total_swap_fees_share: ctx.accounts.pool.total_swap_fees_share,
             },
@@ -215,13 +270,17 @@ pub mod fjord_lbp {
         )?;
         shares_in = safe_math::safe_add(
             shares_in,
-            math::calculate_fee(shares_in, ctx.accounts.config.swap_fee),
+            math::calculate_fee(shares_in, ctx.accounts.pool.swap_fee),
         )?;
         emit!(PreviewSharesIn { shares_in });
         Ok(shares_in)
     }
 
     pub fn preview_shares_out(ctx: Context<ReturnPreviewContext>, assets_in: u64) -> Result<u64> {
+        let assets_in = safe_math::safe_sub(
+            assets_in,
+            get_transfer_fee(&ctx.accounts.asset_token_mint.to_account_info(), assets_in)?,
+        )?;
         let shares_out = math::preview_shares_out(
             PreviewAmountArgs {
                 assets: ctx.accounts.pool_asset_token_account.amount,
@@ -237,12 +296,15 @@ pub mod fjord_lbp {
                 sale_end_time: ctx.accounts.pool.sale_end_time,
                 start_weight_basis_points: ctx.accounts.pool.start_weight_basis_points,
                 end_weight_basis_points: ctx.accounts.pool.end_weight_basis_points,
+                weight_curve: ctx.accounts.pool.weight_curve,
+                weight_knots: ctx.accounts.pool.weight_knots,
+                weight_adjustment: ctx.accounts.pool.weight_adjustment,
                 total_swap_fees_asset: ctx.accounts.pool.total_swap_fees_asset,
                 total_swap_fees_share: ctx.accounts.pool.total_swap_fees_share,
             },
             safe_math::safe_sub(
                 assets_in,
-                math::calculate_fee(assets_in, ctx.accounts.config.swap_fee),
+                math::calculate_fee(assets_in, ctx.accounts.pool.swap_fee),
             )?,
         )?;
         emit!(PreviewSharesOut { shares_out });
@@ -250,7 +312,7 @@ pub mod fjord_lbp {
     }
 
     pub fn preview_assets_out(ctx: Context<ReturnPreviewContext>, shares_in: u64) -> Result<u64> {
-        let assets_out = math::preview_assets_out(
+        let mut assets_out = math::preview_assets_out(
             PreviewAmountArgs {
                 assets: ctx.accounts.pool_asset_token_account.amount,
                 virtual_assets: ctx.accounts.pool.virtual_assets,
@@ -265,14 +327,21 @@ pub mod fjord_lbp {
                 sale_end_time: ctx.accounts.pool.sale_end_time,
                 start_weight_basis_points: ctx.accounts.pool.start_weight_basis_points,
                 end_weight_basis_points: ctx.accounts.pool.end_weight_basis_points,
+                weight_curve: ctx.accounts.pool.weight_curve,
+                weight_knots: ctx.accounts.pool.weight_knots,
+                weight_adjustment: ctx.accounts.pool.weight_adjustment,
                 total_swap_fees_asset: ctx.accounts.pool.total_swap_fees_asset,
                 total_swap_fees_share: ctx.accounts.pool.total_swap_fees_share,
             },
             safe_math::safe_sub(
                 shares_in,
-                math::calculate_fee(shares_in, ctx.accounts.config.swap_fee),
+                math::calculate_fee(shares_in, ctx.accounts.pool.swap_fee),
             )?,
         )?;
+        assets_out = safe_math::safe_sub(
+            assets_out,
+            get_transfer_fee(&ctx.accounts.asset_token_mint.to_account_info(), assets_out)?,
+        )?;
         emit!(PreviewAssetsOut { assets_out });
         Ok(assets_out)
     }
@@ -294,6 +363,9 @@ pub mod fjord_lbp {
             sale_end_time: ctx.accounts.pool.sale_end_time,
             start_weight_basis_points: ctx.accounts.pool.start_weight_basis_points,
             end_weight_basis_points: ctx.accounts.pool.end_weight_basis_points,
+            weight_curve: ctx.accounts.pool.weight_curve,
+            weight_knots: ctx.accounts.pool.weight_knots,
+            weight_adjustment: ctx.accounts.pool.weight_adjustment,
             total_swap_fees_asset: ctx.accounts.pool.total_swap_fees_asset,
             total_swap_fees_share: ctx.accounts.pool.total_swap_fees_share,
         })?;
@@ -318,23 +390,130 @@ pub mod fjord_lbp {
         pool_management::toggle_pause(ctx)
     }
 
+    pub fn cancel_pool(ctx: Context<CancelPool>) -> Result<()> {
+        lifecycle::cancel_pool(ctx)
+    }
+
+    pub fn close_pool_accounts(ctx: Context<ClosePoolAccounts>) -> Result<()> {
+        lifecycle::close_pool_accounts(ctx)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_pool_config(
+        ctx: Context<UpdatePoolConfig>,
+        assets: u64,
+        shares: u64,
+        virtual_assets: Option<u64>,
+        virtual_shares: Option<u64>,
+        max_share_price: Option<u64>,
+        start_weight_basis_points: Option<u16>,
+        end_weight_basis_points: Option<u16>,
+        sale_start_time: Option<i64>,
+        sale_end_time: Option<i64>,
+        vest_cliff: Option<i64>,
+        vest_end: Option<i64>,
+        weight_curve: Option<WeightCurve>,
+        weight_knots: Option<[WeightKnot; MAX_WEIGHT_KNOTS]>,
+    ) -> Result<()> {
+        config::update_pool_config(
+            ctx,
+            assets,
+            shares,
+            virtual_assets,
+            virtual_shares,
+            max_share_price,
+            start_weight_basis_points,
+            end_weight_basis_points,
+            sale_start_time,
+            sale_end_time,
+            vest_cliff,
+            vest_end,
+            weight_curve,
+            weight_knots,
+        )
+    }
+
+    pub fn set_whitelist_merkle_root(
+        ctx: Context<OnlyPoolCreator>,
+        whitelist_merkle_root: [u8; 32],
+    ) -> Result<()> {
+        config::set_whitelist_merkle_root(ctx, whitelist_merkle_root)
+    }
+
+    pub fn set_public_sale_time(ctx: Context<OnlyPoolCreator>, public_sale_time: i64) -> Result<()> {
+        config::set_public_sale_time(ctx, public_sale_time)
+    }
+
+    pub fn set_weight_manager(ctx: Context<OnlyPoolCreator>, weight_manager: Pubkey) -> Result<()> {
+        config::set_weight_manager(ctx, weight_manager)
+    }
+
+    pub fn adjust_weights(
+        ctx: Context<AdjustWeights>,
+        end_weight_basis_points: Option<u16>,
+        paused: Option<bool>,
+    ) -> Result<()> {
+        config::adjust_weights(ctx, end_weight_basis_points, paused)
+    }
+
     // Owner Authority Controls -------------------------------------------
-    pub fn set_fees(
+    pub fn propose_fees(
         ctx: Context<FeeConfig>,
         platform_fee: Option<u16>,
         referral_fee: Option<u16>,
         swap_fee: Option<u16>,
+        fee_change_delay: Option<i64>,
     ) -> Result<()> {
-        setter::set_fees(ctx, platform_fee, referral_fee, swap_fee)
+        setter::propose_fees(ctx, platform_fee, referral_fee, swap_fee, fee_change_delay)
     }
 
-    pub fn set_treasury_fee_recipients(
+    pub fn execute_fees(ctx: Context<FeeConfig>) -> Result<()> {
+        setter::execute_fees(ctx)
+    }
+
+    pub fn lower_fee_ceilings(
+        ctx: Context<FeeConfig>,
+        max_platform_fee: Option<u16>,
+        max_referral_fee: Option<u16>,
+        max_swap_fee: Option<u16>,
+    ) -> Result<()> {
+        setter::lower_fee_ceilings(ctx, max_platform_fee, max_referral_fee, max_swap_fee)
+    }
+
+    pub fn propose_treasury_fee_recipients(
         ctx: Context<TreasuryFeeRecipientConfig>,
         swap_fee_recipient: Option<Pubkey>,
         fee_recipients: Vec<Pubkey>,
         fee_percentages: Vec<u16>,
     ) -> Result<()> {
-        setter::set_fee_recipients(ctx, swap_fee_recipient, fee_recipients, fee_percentages)
+        setter::propose_fee_recipients(ctx, swap_fee_recipient, fee_recipients, fee_percentages)
+    }
+
+    pub fn execute_treasury_fee_recipients(
+        ctx: Context<ExecuteTreasuryFeeRecipients>,
+    ) -> Result<()> {
+        setter::execute_fee_recipients(ctx)
+    }
+
+    pub fn set_pool_fees(
+        ctx: Context<PoolFeeConfig>,
+        platform_fee: Option<u16>,
+        referral_fee: Option<u16>,
+        swap_fee: Option<u16>,
+    ) -> Result<()> {
+        setter::set_pool_fees(ctx, platform_fee, referral_fee, swap_fee)
+    }
+
+    pub fn register_referrer(ctx: Context<RegisterReferrer>, referrer: Pubkey) -> Result<()> {
+        registry::register_referrer(ctx, referrer)
+    }
+
+    pub fn unregister_referrer(ctx: Context<UnregisterReferrer>) -> Result<()> {
+        registry::unregister_referrer(ctx)
+    }
+
+    pub fn set_referrer_registry_required(ctx: Context<FeeConfig>, required: bool) -> Result<()> {
+        registry::set_referrer_registry_required(ctx, required)
     }
 
     pub fn nominate_new_owner(ctx: Context<OnlyOwner>, new_owner_key: Pubkey) -> Result<()> {
@@ -344,4 +523,22 @@ pub mod fjord_lbp {
     pub fn accept_new_owner(ctx: Context<AcceptOwner>) -> Result<()> {
         ownable::access_control::accept_owner(ctx)
     }
+
+    // Migration ----------------------------------------------------------
+    pub fn migrate_owner_config(
+        ctx: Context<MigrateOwnerConfig>,
+        max_platform_fee: u16,
+        max_referral_fee: u16,
+        max_swap_fee: u16,
+    ) -> Result<()> {
+        migration::migrate_owner_config(ctx, max_platform_fee, max_referral_fee, max_swap_fee)
+    }
+
+    pub fn migrate_pool(ctx: Context<MigratePool>) -> Result<()> {
+        migration::migrate_pool(ctx)
+    }
+
+    pub fn migrate_user_state(ctx: Context<MigrateUserState>) -> Result<()> {
+        migration::migrate_user_state(ctx)
+    }
 }
@@ -1,7 +1,13 @@
 use anchor_lang::prelude::*;
 
+use crate::{
+    ReferralSchedule, WeightAdjustment, WeightCurve, WeightKnot, WhitelistLeafVersion,
+    MAX_WEIGHT_KNOTS, MAX_WHITELIST_TIERS,
+};
+
 /// Account storing the information of the liquidity bootstrapping pool
 #[account]
+#[derive(Default)]
 pub struct LiquidityBootstrappingPool {
     // Collateral token
     pub asset_token: Pubkey,
@@ -14,10 +20,28 @@ pub struct LiquidityBootstrappingPool {
     pub max_share_price: u64,
     pub max_shares_out: u64,
     pub max_assets_in: u64,
+    // Soft cap: minimum amount of assets to raise, the pool is closed in refund mode below it (0 to disable)
+    pub min_assets_raised: u64,
+    // Hard cap: purchases cannot raise more than this amount of assets, the sale ends once it is raised (0 to disable)
+    pub max_assets_raised: u64,
+    // Maximum amount of assets a single wallet can spend, net of the swap fees and of its sales (0 to disable)
+    pub max_assets_per_user: u64,
+    // Maximum number of shares a single wallet can hold (0 to disable)
+    pub max_shares_per_user: u64,
+    // Minimum amount of assets of a single purchase (0 to disable)
+    pub min_assets_per_buy: u64,
     // Percentage
     pub start_weight_basis_points: u16,
     // Percentage
     pub end_weight_basis_points: u16,
+    // Shape of the weight schedule between the start and end weights
+    pub weight_curve: WeightCurve,
+    // Knots of the piecewise weight schedule, unused knots last with a 0 timestamp
+    pub weight_knots: [WeightKnot; MAX_WEIGHT_KNOTS],
+    // Last adjustment of the weight schedule during the sale, the schedule resumes from it
+    pub weight_adjustment: WeightAdjustment,
+    // Wallet allowed to adjust the weight schedule along with the creator, disabled when default
+    pub weight_manager: Pubkey,
     // Timestamp
     pub sale_start_time: i64,
     // Timestamp
@@ -27,13 +51,25 @@ pub struct LiquidityBootstrappingPool {
     // Timestamp
     pub vest_end: i64,
     pub selling_allowed: bool,
+    // Referral commission schedule set by the creator
+    pub referral_schedule: ReferralSchedule,
+    // Fees snapshotted from the owner config at creation, only changed by agreement of the owner and the creator
+    pub platform_fee: u16,
+    pub referral_fee: u16,
+    pub swap_fee: u16,
 
-    // The total number of purchased shares in the pool
+    // The total number of purchased shares in the pool, less the redeemed ones once the pool is closed
     pub total_purchased: u64,
 
-    // The total number of assets referred in the pool
+    // The total number of assets referred in the pool, less the claimed ones once the pool is closed
     pub total_referred: u64,
 
+    // The net amount of assets raised by the sale (bought minus sold, swap fees excluded)
+    pub total_assets_raised: u64,
+
+    // The total amount of assets paid by the buyers net of their sales, owed back to them in refund mode
+    pub total_assets_paid: u64,
+
     // The total swap fee amount in asset charged to users
     pub total_swap_fees_asset: u64,
 
@@ -43,12 +79,30 @@ pub struct LiquidityBootstrappingPool {
     // Flag to indicate the liquidity pool is closed
     pub closed: bool,
 
+    // Flag to indicate the pool was closed below its soft cap and the buyers are refunded
+    pub refunding: bool,
+
     // Flag to indicate the swaps are paused
     pub paused: bool,
 
+    // Flag to indicate shares were ever bought in the pool, even if sold back since, so it can no longer be cancelled
+    pub traded: bool,
+
     // Whitelist
     pub whitelist_merkle_root: [u8; 32],
 
+    // Hashing scheme of the whitelist Merkle tree
+    pub whitelist_leaf_version: WhitelistLeafVersion,
+
+    // Signer of the whitelist attestations accepted instead of Merkle proofs, disabled when default
+    pub whitelist_signer: Pubkey,
+
+    // Opening time of each whitelist tier, from the start of the sale when 0 or earlier
+    pub whitelist_tier_start_times: [i64; MAX_WHITELIST_TIERS],
+
+    // Time after which anyone can trade without a whitelist proof, never when 0
+    pub public_sale_time: i64,
+
     // Bump seed
     pub bump: u8,
 
@@ -56,16 +110,36 @@ pub struct LiquidityBootstrappingPool {
     pub salt: String,
 }
 
+/// Marker left at the address of a pool once its accounts are closed, so that no pool can be initialized there
+/// again and inherit the user states left in it
+#[account]
+pub struct ClosedPool {}
+
+impl ClosedPool {
+    // The size length of the ClosedPool account, its discriminator only
+    pub const LEN: usize = 8;
+}
+
 /// Account storing the information of the user in the liquidity bootstrapping pool
 #[account]
 pub struct UserStateInPool {
     pub purchased_shares: u64,
     pub referred_assets: u64,
     pub redeemed_shares: u64,
+    // The assets paid for the purchased shares net of the assets received from sales, refunded if the sale fails
+    pub assets_paid: u64,
+    // The referrer of the first referred purchase of the user, credited by its later swaps (default when none)
+    pub referrer: Pubkey,
+    // The assets brought to the pool by the users the user referred, net of their sales, selecting its referral tier
+    pub referred_volume: u64,
+    // The assets spent on the purchased shares net of the swap fees and of the assets received from sales,
+    // held to the per-wallet and whitelist allocations
+    pub assets_spent: u64,
 }
 
 impl UserStateInPool {
     // The size length of the UserStateInPool account
-    // purchased_shares(8) + referred_assets(8) + redeemed_shares(8)
-    pub const LEN: usize = 8 + 8 + 8;
+    // purchased_shares(8) + referred_assets(8) + redeemed_shares(8) + assets_paid(8) + referrer(32) + referred_volume(8)
+    // + assets_spent(8)
+    pub const LEN: usize = 8 + 8 + 8 + 8 + 32 + 8 + 8;
 }
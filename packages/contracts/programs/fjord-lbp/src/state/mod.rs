@@ -1,7 +1,9 @@
 pub mod liquidity_bootstrapping_pool;
 pub mod owner_config;
+pub mod referrer_registration;
 pub mod treasury;
 
 pub use liquidity_bootstrapping_pool::*;
 pub use owner_config::*;
+pub use referrer_registration::*;
 pub use treasury::*;
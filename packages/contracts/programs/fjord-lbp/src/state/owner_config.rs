@@ -13,12 +13,40 @@ pub struct OwnerConfig {
     pub platform_fee: u16,
     pub referral_fee: u16,
     pub swap_fee: u16,
+    // Hard ceilings of the fees, which can only be lowered
+    pub max_platform_fee: u16,
+    pub max_referral_fee: u16,
+    pub max_swap_fee: u16,
+    // Delay between the proposal of a fee change and its execution, in seconds
+    pub fee_change_delay: i64,
+    // Fee change proposed by the owner, executable once the delay has elapsed
+    pub pending_fees: Option<PendingFees>,
+
+    // Whether only the referrers registered by the owner can earn referral fees
+    pub referrer_registry_required: bool,
 
     pub bump: u8,
 }
 
 impl OwnerConfig {
     // The size length of the OwnerConfig account
-    // pub key(32) + optional pending owner (1 + 32) + Pool fee settings + bump(1)
-    pub const LEN: usize = 32 + 1 + 32 + 32 + 2 + 2 + 2 + 1;
+    // pub key(32) + optional pending owner (1 + 32) + Pool fee settings + fee ceilings(2 + 2 + 2) + fee change delay(8)
+    // + optional pending fees(1 + PendingFees::LEN) + referrer registry flag(1) + bump(1)
+    pub const LEN: usize =
+        32 + 1 + 32 + 32 + 2 + 2 + 2 + 2 + 2 + 2 + 8 + 1 + PendingFees::LEN + 1 + 1;
+}
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PendingFees {
+    pub platform_fee: u16,
+    pub referral_fee: u16,
+    pub swap_fee: u16,
+    pub fee_change_delay: i64,
+    // Timestamp from which the change can be executed
+    pub executable_at: i64,
+}
+
+impl PendingFees {
+    // platform_fee(2) + referral_fee(2) + swap_fee(2) + fee_change_delay(8) + executable_at(8)
+    pub const LEN: usize = 2 + 2 + 2 + 8 + 8;
 }
@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+/// Account registering a referrer allowed to earn referral fees when the referrer registry is required
+#[account]
+pub struct ReferrerRegistration {
+    pub referrer: Pubkey,
+    // Bump seed
+    pub bump: u8,
+}
+
+impl ReferrerRegistration {
+    // The size length of the ReferrerRegistration account
+    // referrer(32) + bump(1)
+    pub const LEN: usize = 32 + 1;
+}
@@ -5,6 +5,18 @@ pub struct Treasury {
     // Swap fee recipient
     pub swap_fee_recipient: Pubkey,
     pub fee_recipients: Vec<FeeMapping>,
+    // Fee recipients proposed by the owner, executable once the fee change delay has elapsed
+    pub pending_fee_recipients: Option<PendingFeeRecipients>,
+}
+
+impl Treasury {
+    // The size length of the Treasury account holding `fee_recipients` recipients, and `pending_fee_recipients` proposed ones
+    // swap fee recipient(32) + fee recipients(4 + (32 + 2) * n) + optional pending change(1 + 32 + 4 + (32 + 2) * m + 8)
+    pub fn space(fee_recipients: usize, pending_fee_recipients: Option<usize>) -> usize {
+        32 + (4 + (32 + 2) * fee_recipients)
+            + 1
+            + pending_fee_recipients.map_or(0, |pending| 32 + (4 + (32 + 2) * pending) + 8)
+    }
 }
 
 #[derive(AnchorDeserialize, AnchorSerialize, Clone)]
@@ -13,3 +25,11 @@ pub struct FeeMapping {
     // Fee percentages must match the length of fee recipients
     pub percentage: u16,
 }
+
+#[derive(AnchorDeserialize, AnchorSerialize, Clone)]
+pub struct PendingFeeRecipients {
+    pub swap_fee_recipient: Pubkey,
+    pub fee_recipients: Vec<FeeMapping>,
+    // Timestamp from which the change can be executed
+    pub executable_at: i64,
+}
@@ -0,0 +1,268 @@
+use proptest::prelude::*;
+
+use crate::math::{
+    _scale_token, preview_assets_in, preview_assets_out, preview_shares_in, preview_shares_out,
+};
+use crate::{PreviewAmountArgs, WeightCurve};
+
+const SCALED_DECIMALS: u128 = 100_000_000;
+
+/// State of a pool whose tokens both have 8 decimals, so that amounts are not rescaled.
+#[derive(Clone, Debug)]
+struct Pool {
+    assets: u64,
+    shares: u64,
+    total_purchased: u64,
+    max_share_price: u64,
+    current_time: i64,
+    sale_end_time: i64,
+    start_weight_basis_points: u16,
+    end_weight_basis_points: u16,
+}
+
+impl Pool {
+    fn args(&self) -> PreviewAmountArgs {
+        self.args_with_decimals(8, 8)
+    }
+
+    fn args_with_decimals(&self, asset_decimals: u8, share_decimals: u8) -> PreviewAmountArgs {
+        PreviewAmountArgs {
+            assets: self.assets,
+            virtual_assets: 0,
+            asset_token_decimal: asset_decimals,
+            shares: self.shares,
+            virtual_shares: 0,
+            share_token_decimal: share_decimals,
+            total_purchased: self.total_purchased,
+            current_time: self.current_time,
+            max_share_price: self.max_share_price,
+            sale_start_time: 0,
+            sale_end_time: self.sale_end_time,
+            start_weight_basis_points: self.start_weight_basis_points,
+            end_weight_basis_points: self.end_weight_basis_points,
+            weight_curve: WeightCurve::Linear,
+            weight_knots: Default::default(),
+            weight_adjustment: Default::default(),
+            total_swap_fees_asset: 0,
+            total_swap_fees_share: 0,
+        }
+    }
+}
+
+/// A pool during its sale, with a `max_share_price` that is never reached.
+fn pool() -> impl Strategy<Value = Pool> {
+    (
+        1_000_000u64..=1_000_000_000_000_000_000,
+        1_000_000u64..=1_000_000_000_000_000_000,
+        1i64..=100_000_000,
+        0u64..=10_000,
+        100u16..=9900,
+        100u16..=9900,
+    )
+        .prop_flat_map(|(assets, shares, sale_end_time, elapsed, start, end)| {
+            (0..=sale_end_time).prop_map(move |current_time| Pool {
+                assets,
+                shares,
+                total_purchased: 0,
+                max_share_price: u64::MAX,
+                current_time: current_time.min(sale_end_time - elapsed as i64).max(0),
+                sale_end_time,
+                start_weight_basis_points: start,
+                end_weight_basis_points: end,
+            })
+        })
+}
+
+proptest! {
+    #[test]
+    fn buying_then_selling_never_profits(
+        pool in pool(),
+        assets_in_basis_points in 1u64..=3000,
+    ) {
+        let assets_in = pool.assets / 10_000 * assets_in_basis_points;
+        let shares_out = preview_shares_out(pool.args(), assets_in);
+        prop_assume!(matches!(shares_out, Ok(shares) if shares > 0));
+        let shares_out = shares_out.unwrap();
+
+        // The purchased shares stay in the pool until they are redeemed
+        let pool_after_buy = Pool {
+            assets: pool.assets + assets_in,
+            total_purchased: shares_out,
+            ..pool
+        };
+        // Selling more than `MAX_PERCENTAGE_IN` of the share reserve back is rejected
+        if let Ok(assets_out) = preview_assets_out(pool_after_buy.args(), shares_out) {
+            prop_assert!(assets_out <= assets_in);
+        }
+    }
+
+    #[test]
+    fn preview_assets_in_brackets_the_assets_in_of_preview_shares_out(
+        pool in pool(),
+        assets_in_basis_points in 1u64..=2000,
+    ) {
+        let assets_in = pool.assets / 10_000 * assets_in_basis_points;
+        let shares_out = preview_shares_out(pool.args(), assets_in);
+        prop_assume!(matches!(shares_out, Ok(shares) if shares > 0));
+        let shares_out = shares_out.unwrap();
+
+        // Both previews round in favour of the pool, so `assets_in` lies between the price of the
+        // shares it bought and the price of one more unit of shares, up to the WAD precision of the
+        // math relative to the reserves
+        let tolerance = pool.assets / 1_000_000_000 + 2;
+        if let Ok(assets_in_for_same) = preview_assets_in(pool.args(), shares_out) {
+            prop_assert!(assets_in_for_same <= assets_in + tolerance);
+        }
+        if let Ok(assets_in_for_more) = preview_assets_in(pool.args(), shares_out + 1) {
+            prop_assert!(assets_in_for_more + tolerance >= assets_in);
+        }
+    }
+
+    #[test]
+    fn share_price_never_exceeds_max_share_price(
+        pool in pool(),
+        // Spread the maximum price over several orders of magnitude around the pool price
+        max_share_price in (1u32..=12).prop_flat_map(|digits| 1u64..=10u64.pow(digits)),
+        amount_basis_points in 1u64..=3000,
+    ) {
+        let pool = Pool { max_share_price, ..pool };
+        let max_share_price = u128::from(max_share_price);
+        let price_is_capped = |assets: u64, shares: u64, rounding_shares: u128| {
+            u128::from(assets) * SCALED_DECIMALS
+                <= max_share_price * (u128::from(shares) + rounding_shares)
+        };
+
+        let assets = pool.assets / 10_000 * amount_basis_points;
+        let shares = pool.shares / 10_000 * amount_basis_points;
+        if let Ok(shares_out) = preview_shares_out(pool.args(), assets) {
+            // Rounding the shares down can only move the price by one unit of shares
+            prop_assert!(price_is_capped(assets, shares_out, 1));
+        }
+        if let Ok(assets_in) = preview_assets_in(pool.args(), shares) {
+            prop_assert!(price_is_capped(assets_in, shares, 0));
+        }
+
+        // Sell back into a pool where shares were bought
+        let pool = Pool { total_purchased: pool.shares / 2, ..pool };
+        let shares = shares / 2;
+        if let Ok(assets_out) = preview_assets_out(pool.args(), shares) {
+            prop_assert!(price_is_capped(assets_out, shares, 0));
+        }
+        if let Ok(shares_in) = preview_shares_in(pool.args(), assets / 2) {
+            prop_assert!(price_is_capped(assets / 2, shares_in, 1));
+        }
+    }
+
+    #[test]
+    fn linear_weights_move_monotonically_over_the_sale(
+        pool in pool(),
+        later in 0i64..=100_000_000,
+    ) {
+        let pool_later = Pool {
+            current_time: (pool.current_time + later).min(pool.sale_end_time),
+            ..pool.clone()
+        };
+        let weights = crate::math::compute_reserves_and_weights(&pool.args()).unwrap();
+        let weights_later = crate::math::compute_reserves_and_weights(&pool_later.args()).unwrap();
+
+        if pool.start_weight_basis_points <= pool.end_weight_basis_points {
+            prop_assert!(weights.asset_weight <= weights_later.asset_weight);
+        } else {
+            prop_assert!(weights.asset_weight >= weights_later.asset_weight);
+        }
+        prop_assert_eq!(weights.asset_weight + weights.share_weight, 10_000);
+    }
+
+    #[test]
+    fn scale_token_round_trips_every_decimals(
+        decimals in 0u8..=18,
+        amount in 0u128..=u128::from(u64::MAX),
+    ) {
+        let scaled = _scale_token(decimals, amount, true).unwrap();
+        let unscaled = _scale_token(decimals, scaled, false).unwrap();
+
+        if decimals <= 8 {
+            // Scaling up to 8 decimals is exact
+            prop_assert_eq!(scaled, amount * 10u128.pow(8 - u32::from(decimals)));
+            prop_assert_eq!(unscaled, amount);
+        } else {
+            // Scaling down truncates the digits beyond 8 decimals
+            let truncated = 10u128.pow(u32::from(decimals) - 8);
+            prop_assert_eq!(scaled, amount / truncated);
+            prop_assert!(unscaled <= amount);
+            prop_assert!(amount - unscaled < truncated);
+        }
+    }
+
+    #[test]
+    fn previews_do_not_depend_on_token_decimals(
+        pool in pool(),
+        asset_decimals in 0u8..=18,
+        share_decimals in 0u8..=18,
+        assets_in_basis_points in 1u64..=3000,
+    ) {
+        // Express the same pool in whole tokens with other decimals
+        let whole_tokens = |amount: u64, decimals: u8| {
+            u64::try_from(u128::from(amount / 100_000_000) * 10u128.pow(u32::from(decimals))).ok()
+        };
+        let (Some(assets), Some(shares)) = (
+            whole_tokens(pool.assets, asset_decimals),
+            whole_tokens(pool.shares, share_decimals),
+        ) else {
+            return Ok(());
+        };
+        let pool = Pool {
+            assets: pool.assets / 100_000_000 * 100_000_000,
+            shares: pool.shares / 100_000_000 * 100_000_000,
+            ..pool
+        };
+        prop_assume!(pool.assets > 0 && pool.shares > 0);
+        let rescaled_pool = Pool { assets, shares, ..pool.clone() };
+
+        let assets_in = pool.assets / 10_000 * assets_in_basis_points / 100_000_000 * 100_000_000;
+        let Some(rescaled_assets_in) = whole_tokens(assets_in, asset_decimals) else {
+            return Ok(());
+        };
+
+        let shares_out = preview_shares_out(pool.args(), assets_in);
+        let rescaled_shares_out = preview_shares_out(
+            rescaled_pool.args_with_decimals(asset_decimals, share_decimals),
+            rescaled_assets_in,
+        );
+        if let (Ok(shares_out), Ok(rescaled_shares_out)) = (shares_out, rescaled_shares_out) {
+            // Equal once truncated to the coarser of both decimals
+            let share_unit = 10u128.pow(8u32.saturating_sub(u32::from(share_decimals)));
+            let expected = u128::from(shares_out) / share_unit * share_unit;
+            let rescaled = u128::from(rescaled_shares_out) * 100_000_000
+                / 10u128.pow(u32::from(share_decimals))
+                / share_unit
+                * share_unit;
+            prop_assert_eq!(expected, rescaled);
+        }
+    }
+}
+
+#[test]
+fn buys_capped_at_the_max_share_price_are_not_priced_at_its_inverse() {
+    // A share costs 0.001 assets at the pool price, but at most 0.0005 assets
+    let pool = Pool {
+        assets: 1_000 * 100_000_000,
+        shares: 1_000_000 * 100_000_000,
+        total_purchased: 0,
+        max_share_price: 50_000,
+        current_time: 0,
+        sale_end_time: 100,
+        start_weight_basis_points: 5000,
+        end_weight_basis_points: 5000,
+    };
+
+    // One asset buys 2000 shares at the max share price, not 1 / 2000
+    assert_eq!(
+        preview_shares_out(pool.args(), 100_000_000).unwrap(),
+        2_000 * 100_000_000
+    );
+    assert_eq!(
+        preview_assets_in(pool.args(), 2_000 * 100_000_000).unwrap(),
+        100_000_000
+    );
+}
@@ -0,0 +1,9 @@
+//! Property-based tests of the pool math, checked against a high-precision reference, and of the pool configuration.
+
+mod liquidity_bootstrap;
+mod pool_config;
+mod reference;
+mod referral_schedule;
+mod safe_math;
+mod weight_curve;
+mod weighted_math;
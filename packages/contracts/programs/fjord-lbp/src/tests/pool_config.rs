@@ -0,0 +1,64 @@
+use proptest::prelude::*;
+
+use crate::{valid_min_assets_per_buy, LiquidityBootstrappingPool};
+
+/// A pool with the given minimum buy size and caps, and no bound on the assets in.
+fn pool(
+    min_assets_per_buy: u64,
+    max_assets_per_user: u64,
+    max_assets_raised: u64,
+) -> LiquidityBootstrappingPool {
+    LiquidityBootstrappingPool {
+        min_assets_per_buy,
+        max_assets_per_user,
+        max_assets_raised,
+        max_assets_in: u64::MAX,
+        ..Default::default()
+    }
+}
+
+proptest! {
+    #[test]
+    fn minimum_buys_up_to_the_caps_are_accepted(
+        min_assets_per_buy in 0u64..u64::MAX,
+        wallet_headroom in 0u64..=u64::MAX,
+        hard_cap_headroom in 0u64..=u64::MAX,
+    ) {
+        let max_assets_per_user = min_assets_per_buy.saturating_add(wallet_headroom).max(1);
+        let max_assets_raised = min_assets_per_buy.saturating_add(hard_cap_headroom).max(1);
+        prop_assert!(valid_min_assets_per_buy(&pool(min_assets_per_buy, max_assets_per_user, max_assets_raised)));
+        // Disabled caps never get in the way
+        prop_assert!(valid_min_assets_per_buy(&pool(min_assets_per_buy, 0, 0)));
+    }
+
+    #[test]
+    fn minimum_buys_above_the_wallet_cap_are_rejected(
+        max_assets_per_user in 1u64..u64::MAX - 1,
+        excess in 1u64..=u64::MAX,
+    ) {
+        let min_assets_per_buy = max_assets_per_user.saturating_add(excess).min(u64::MAX - 1);
+        prop_assert!(!valid_min_assets_per_buy(&pool(min_assets_per_buy, max_assets_per_user, 0)));
+    }
+
+    #[test]
+    fn minimum_buys_above_the_hard_cap_are_rejected(
+        max_assets_raised in 1u64..u64::MAX - 1,
+        excess in 1u64..=u64::MAX,
+    ) {
+        let min_assets_per_buy = max_assets_raised.saturating_add(excess).min(u64::MAX - 1);
+        prop_assert!(!valid_min_assets_per_buy(&pool(min_assets_per_buy, 0, max_assets_raised)));
+    }
+
+    #[test]
+    fn minimum_buys_reaching_the_maximum_assets_in_are_rejected(
+        max_assets_in in 1u64..=u64::MAX,
+        excess in 0u64..=u64::MAX,
+    ) {
+        let pool = LiquidityBootstrappingPool {
+            min_assets_per_buy: max_assets_in.saturating_add(excess),
+            max_assets_in,
+            ..Default::default()
+        };
+        prop_assert!(!valid_min_assets_per_buy(&pool));
+    }
+}
@@ -0,0 +1,118 @@
+//! High-precision reference implementation of the pool math.
+//! Values are big integers scaled by 1e40, far beyond the precision of the program.
+
+use num_bigint::BigInt;
+
+use crate::WAD;
+
+const PRECISION: u32 = 40;
+
+/// 1 scaled by 1e40.
+pub fn one() -> BigInt {
+    BigInt::from(10u8).pow(PRECISION)
+}
+
+/// Convert a value scaled by WAD to the reference scale.
+pub fn from_wad(x: u128) -> BigInt {
+    BigInt::from(x) * one() / WAD
+}
+
+/// Exact ratio `num / den` scaled by 1e40.
+pub fn ratio(num: u128, den: u128) -> BigInt {
+    BigInt::from(num) * one() / den
+}
+
+/// 2 * atanh(z) = 2 * (z + z^3/3 + z^5/5 + ...)
+fn two_atanh(z: &BigInt) -> BigInt {
+    let one = one();
+    let z_squared = z * z / &one;
+    let mut term = z.clone();
+    let mut sum = z.clone();
+    let mut n = 1u32;
+    while term != BigInt::from(0) {
+        term = term * &z_squared / &one;
+        n += 2;
+        sum += &term / n;
+    }
+    sum * 2
+}
+
+fn ln_2() -> BigInt {
+    two_atanh(&(one() / 3))
+}
+
+/// Natural logarithm of `x` (> 0).
+pub fn ln(x: &BigInt) -> BigInt {
+    let one = one();
+    let two = &one * 2;
+    let mut m = x.clone();
+    let mut k = 0i64;
+    while m >= two {
+        m /= 2;
+        k += 1;
+    }
+    while m < one {
+        m *= 2;
+        k -= 1;
+    }
+    two_atanh(&((&m - &one) * &one / (&m + &one))) + ln_2() * k
+}
+
+/// Exponential of `x`.
+pub fn exp(x: &BigInt) -> BigInt {
+    let one = one();
+    let ln_2 = ln_2();
+    let k = x / &ln_2;
+    let r = x - &k * &ln_2;
+    let mut term = one.clone();
+    let mut sum = one.clone();
+    let mut n = 0u32;
+    while term != BigInt::from(0) {
+        n += 1;
+        term = term * &r / (&one * n);
+        sum += &term;
+    }
+    let k = i64::try_from(k).unwrap();
+    if k >= 0 {
+        sum << k
+    } else {
+        sum >> -k
+    }
+}
+
+/// `x` to the power of `y`, both scaled by 1e40.
+pub fn pow(x: &BigInt, y: &BigInt) -> BigInt {
+    exp(&(ln(x) * y / one()))
+}
+
+/// Exact output of `get_amount_out`, scaled by 1e40:
+/// reserveOut * (1 - (reserveIn / (reserveIn + amountIn)) ^ (weightIn / weightOut))
+pub fn amount_out(
+    amount_in: u128,
+    reserve_in: u128,
+    reserve_out: u128,
+    weight_in: u128,
+    weight_out: u128,
+) -> BigInt {
+    let power = pow(
+        &ratio(reserve_in, reserve_in + amount_in),
+        &ratio(weight_in, weight_out),
+    );
+    BigInt::from(reserve_out) * (one() - power)
+}
+
+/// Exact input of `get_amount_in`, scaled by 1e40:
+/// reserveIn * ((reserveOut / (reserveOut - amountOut)) ^ (weightOut / weightIn) - 1)
+pub fn amount_in(
+    amount_out: u128,
+    reserve_in: u128,
+    reserve_out: u128,
+    weight_in: u128,
+    weight_out: u128,
+) -> BigInt {
+    let power = pow(
+        &ratio(reserve_out, reserve_out - amount_out),
+        &ratio(weight_out, weight_in),
+    );
+    BigInt::from(reserve_in) * (power - one())
+}
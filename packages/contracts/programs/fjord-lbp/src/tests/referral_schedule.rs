@@ -0,0 +1,104 @@
+use proptest::prelude::*;
+
+use crate::{
+    referral_fee, valid_referral_schedule, ReferralSchedule, ReferralTier, MAX_REFERRAL_TIERS,
+};
+
+/// A valid schedule of up to `MAX_REFERRAL_TIERS` tiers.
+fn schedule() -> impl Strategy<Value = ReferralSchedule> {
+    (
+        proptest::option::of(0u16..=10_000),
+        proptest::collection::btree_set(1u64..=u64::MAX, 0..=MAX_REFERRAL_TIERS),
+        proptest::collection::vec(0u16..=10_000, MAX_REFERRAL_TIERS),
+        0u16..=10_000,
+    )
+        .prop_map(
+            |(fee_basis_points, volumes, fees, second_level_basis_points)| {
+                let mut tiers = [ReferralTier::default(); MAX_REFERRAL_TIERS];
+                for (tier, (min_referred_volume, fee_basis_points)) in
+                    tiers.iter_mut().zip(volumes.into_iter().zip(fees))
+                {
+                    *tier = ReferralTier {
+                        min_referred_volume,
+                        fee_basis_points,
+                    };
+                }
+                ReferralSchedule {
+                    fee_basis_points,
+                    tiers,
+                    second_level_basis_points,
+                }
+            },
+        )
+}
+
+proptest! {
+    #[test]
+    fn referrers_earn_the_fee_of_the_highest_tier_reached(
+        schedule in schedule(),
+        global_referral_fee in 0u16..=10_000,
+        referred_volume: u64,
+    ) {
+        prop_assert!(valid_referral_schedule(&schedule, global_referral_fee, 0, 0));
+
+        let expected = schedule
+            .tiers
+            .iter()
+            .rev()
+            .find(|tier| tier.min_referred_volume != 0 && tier.min_referred_volume <= referred_volume)
+            .map(|tier| tier.fee_basis_points)
+            .unwrap_or(schedule.fee_basis_points.unwrap_or(global_referral_fee));
+        prop_assert_eq!(referral_fee(&schedule, global_referral_fee, referred_volume), expected);
+    }
+
+    #[test]
+    fn unordered_tiers_are_rejected(mut schedule in schedule()) {
+        let used = schedule.tiers.iter().filter(|tier| tier.min_referred_volume != 0).count();
+        prop_assume!(used >= 2);
+
+        schedule.tiers.swap(0, used - 1);
+        prop_assert!(!valid_referral_schedule(&schedule, 0, 0, 0));
+    }
+
+    #[test]
+    fn tiers_after_an_unused_tier_are_rejected(mut schedule in schedule()) {
+        let used = schedule.tiers.iter().filter(|tier| tier.min_referred_volume != 0).count();
+        prop_assume!((1..MAX_REFERRAL_TIERS).contains(&used));
+
+        schedule.tiers.swap(0, used);
+        prop_assert!(!valid_referral_schedule(&schedule, 0, 0, 0));
+    }
+}
+
+#[test]
+fn fees_above_100_percent_are_rejected() {
+    let schedule = ReferralSchedule {
+        fee_basis_points: Some(10_001),
+        ..ReferralSchedule::default()
+    };
+    assert!(!valid_referral_schedule(&schedule, 0, 0, 0));
+
+    let schedule = ReferralSchedule {
+        second_level_basis_points: 10_001,
+        ..ReferralSchedule::default()
+    };
+    assert!(!valid_referral_schedule(&schedule, 0, 0, 0));
+}
+
+#[test]
+fn fees_must_fit_along_with_the_platform_and_swap_fees() {
+    let mut schedule = ReferralSchedule::default();
+    assert!(valid_referral_schedule(&schedule, 9_800, 100, 100));
+    assert!(!valid_referral_schedule(&schedule, 9_801, 100, 100));
+
+    schedule.fee_basis_points = Some(9_801);
+    assert!(!valid_referral_schedule(&schedule, 0, 100, 100));
+
+    schedule.fee_basis_points = None;
+    schedule.tiers[0] = ReferralTier {
+        min_referred_volume: 1,
+        fee_basis_points: 9_800,
+    };
+    assert!(valid_referral_schedule(&schedule, 0, 100, 100));
+    assert!(!valid_referral_schedule(&schedule, 0, 100, 101));
+}
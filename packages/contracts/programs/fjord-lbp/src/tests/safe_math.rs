@@ -0,0 +1,87 @@
+use num_bigint::BigInt;
+use proptest::prelude::*;
+
+use super::reference;
+use crate::{exp_wad, ln_wad, pow_wad, pow_wad_up, SafeMathError, WAD};
+
+/// Convert a value of the reference scale to WAD, rounded down.
+fn to_wad(x: &BigInt) -> BigInt {
+    x * WAD / reference::one()
+}
+
+proptest! {
+    #[test]
+    fn pow_wad_brackets_the_exact_power(
+        x in WAD / 2..=3 * WAD / 2,
+        y in WAD / 100..=99 * WAD,
+    ) {
+        let exact = reference::pow(&reference::from_wad(x), &reference::from_wad(y));
+        let up = BigInt::from(pow_wad_up(x, y).unwrap()) * reference::one() / WAD;
+        prop_assert!(up >= exact);
+        // Within 2 units plus 1e-12 of relative error
+        prop_assert!(&up - &exact <= &exact / 1_000_000_000_000u64 + reference::from_wad(2));
+
+        match pow_wad(x, y) {
+            Ok(down) => {
+                let down = BigInt::from(down) * reference::one() / WAD;
+                prop_assert!(down <= exact);
+                prop_assert!(&exact - &down <= &exact / 1_000_000_000_000u64 + reference::from_wad(2));
+            }
+            Err(error) => prop_assert!(matches!(error, SafeMathError::ExponentiationOverflow)),
+        }
+    }
+
+    #[test]
+    fn ln_wad_is_within_one_unit(x in 1i64..=i64::MAX) {
+        let exact = to_wad(&reference::ln(&reference::from_wad(x as u128)));
+        let diff = BigInt::from(ln_wad(x).unwrap()) - exact;
+        prop_assert!(diff >= BigInt::from(-1) && diff <= BigInt::from(1));
+    }
+
+    #[test]
+    fn exp_wad_is_within_one_unit(x in -43 * WAD as i128..43 * WAD as i128) {
+        let exact = to_wad(&reference::exp(&(BigInt::from(x) * reference::one() / WAD)));
+        match exp_wad(x) {
+            Ok(result) => {
+                let diff = BigInt::from(result) - &exact;
+                let tolerance: BigInt = &exact / 1_000_000_000_000_000u64 + 1;
+                prop_assert!(diff >= -tolerance.clone() && diff <= tolerance);
+            }
+            // The result does not fit in an i64
+            Err(error) => {
+                prop_assert!(matches!(error, SafeMathError::ExponentiationOverflow));
+                prop_assert!(exact > BigInt::from(i64::MAX) - 1);
+            }
+        }
+    }
+}
+
+#[test]
+fn ln_wad_is_undefined_for_non_positive_values() {
+    assert!(matches!(ln_wad(0), Err(SafeMathError::LogarithmUndefined)));
+    assert!(matches!(ln_wad(-1), Err(SafeMathError::LogarithmUndefined)));
+}
+
+#[test]
+fn exp_wad_saturates_outside_of_its_domain() {
+    assert!(matches!(exp_wad(-43 * WAD as i128), Ok(0)));
+    assert!(matches!(
+        exp_wad(43 * WAD as i128),
+        Err(SafeMathError::ExponentiationOverflow)
+    ));
+}
+
+#[test]
+fn pow_wad_rejects_inputs_that_do_not_fit_in_an_i64() {
+    let too_large = i64::MAX as u128 + 1;
+    for (x, y) in [(too_large, WAD), (WAD, too_large)] {
+        assert!(matches!(
+            pow_wad(x, y),
+            Err(SafeMathError::ConversionOverflow)
+        ));
+        assert!(matches!(
+            pow_wad_up(x, y),
+            Err(SafeMathError::ConversionOverflow)
+        ));
+    }
+}
@@ -0,0 +1,187 @@
+use proptest::prelude::*;
+
+use crate::{
+    adjusted_weight, interpolate_weight, linear_interpolation, valid_weight_knots,
+    WeightAdjustment, WeightCurve, WeightKnot, MAX_WEIGHT_KNOTS,
+};
+
+const SALE_START_TIME: i64 = 1_000;
+
+/// A sale with a valid piecewise schedule of up to `MAX_WEIGHT_KNOTS` knots.
+fn schedule() -> impl Strategy<Value = (i64, [WeightKnot; MAX_WEIGHT_KNOTS])> {
+    (
+        (MAX_WEIGHT_KNOTS as i64 + 1)..=100_000_000,
+        proptest::collection::btree_set(1i64..=99_999_999, 0..=MAX_WEIGHT_KNOTS),
+        proptest::collection::vec(100u16..=9900, MAX_WEIGHT_KNOTS),
+    )
+        .prop_map(|(duration, offsets, weights)| {
+            let mut knots = [WeightKnot::default(); MAX_WEIGHT_KNOTS];
+            for (knot, (offset, weight)) in knots.iter_mut().zip(
+                offsets
+                    .into_iter()
+                    .filter(|&offset| offset < duration)
+                    .zip(weights),
+            ) {
+                *knot = WeightKnot {
+                    timestamp: SALE_START_TIME + offset,
+                    weight_basis_points: weight,
+                };
+            }
+            (SALE_START_TIME + duration, knots)
+        })
+}
+
+proptest! {
+    #[test]
+    fn piecewise_schedules_go_through_their_knots(
+        (sale_end_time, knots) in schedule(),
+        start in 100u16..=9900,
+        end in 100u16..=9900,
+        offset in 0i64..=200_000_000,
+    ) {
+        prop_assert!(valid_weight_knots(&knots, SALE_START_TIME, sale_end_time));
+        let weight_at = |current_time| {
+            interpolate_weight(WeightCurve::Piecewise, &knots, start, end, SALE_START_TIME, sale_end_time, current_time)
+                .unwrap()
+        };
+
+        prop_assert_eq!(weight_at(SALE_START_TIME - 1), u128::from(start));
+        prop_assert_eq!(weight_at(sale_end_time + offset), u128::from(end));
+        let mut previous = (SALE_START_TIME, start);
+        for knot in knots.iter().filter(|knot| knot.timestamp != 0) {
+            prop_assert_eq!(weight_at(knot.timestamp), u128::from(knot.weight_basis_points));
+            // Between two knots the weight moves linearly
+            let current_time = previous.0 + offset % (knot.timestamp - previous.0);
+            prop_assert_eq!(
+                weight_at(current_time),
+                linear_interpolation(
+                    previous.1.into(),
+                    knot.weight_basis_points.into(),
+                    (current_time - previous.0) as u128,
+                    (knot.timestamp - previous.0) as u128,
+                )
+                .unwrap()
+            );
+            previous = (knot.timestamp, knot.weight_basis_points);
+        }
+    }
+
+    #[test]
+    fn knots_are_ignored_by_other_curves(
+        (sale_end_time, knots) in schedule(),
+        start in 100u16..=9900,
+        end in 100u16..=9900,
+        offset in 0i64..=100_000_000,
+    ) {
+        let current_time = SALE_START_TIME + offset;
+        prop_assert_eq!(
+            interpolate_weight(WeightCurve::Linear, &knots, start, end, SALE_START_TIME, sale_end_time, current_time)
+                .unwrap(),
+            linear_interpolation(
+                start.into(),
+                end.into(),
+                offset as u128,
+                (sale_end_time - SALE_START_TIME) as u128,
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn adjusted_schedules_continue_from_the_current_weight(
+        (sale_end_time, knots) in schedule(),
+        start in 100u16..=9900,
+        end in 100u16..=9900,
+        new_end in 100u16..=9900,
+        offset in 0i64..=100_000_000,
+        later in 0i64..=100_000_000,
+    ) {
+        let adjusted_at = SALE_START_TIME + offset % (sale_end_time - SALE_START_TIME);
+        let weight = interpolate_weight(WeightCurve::Piecewise, &knots, start, end, SALE_START_TIME, sale_end_time, adjusted_at)
+            .unwrap();
+        let adjustment = WeightAdjustment {
+            timestamp: adjusted_at,
+            weight_basis_points: weight as u16,
+            paused: false,
+        };
+        let weight_at = |adjustment, current_time| {
+            adjusted_weight(WeightCurve::Piecewise, &knots, adjustment, start, new_end, SALE_START_TIME, sale_end_time, current_time)
+                .unwrap()
+        };
+
+        // No jump at the adjustment, then through the knots ahead to the new end weight
+        prop_assert_eq!(weight_at(adjustment, adjusted_at), weight);
+        for knot in knots.iter().filter(|knot| knot.timestamp > adjusted_at) {
+            prop_assert_eq!(weight_at(adjustment, knot.timestamp), u128::from(knot.weight_basis_points));
+        }
+        prop_assert_eq!(weight_at(adjustment, sale_end_time), u128::from(new_end));
+
+        // Paused schedules hold the weight of the adjustment
+        let paused = WeightAdjustment { paused: true, ..adjustment };
+        prop_assert_eq!(weight_at(paused, adjusted_at + later), weight);
+    }
+
+    #[test]
+    fn out_of_order_knots_are_invalid(
+        (sale_end_time, mut knots) in schedule(),
+        index in 0..MAX_WEIGHT_KNOTS,
+    ) {
+        let used = knots.iter().filter(|knot| knot.timestamp != 0).count();
+        prop_assume!(used >= 2);
+        let index = index % (used - 1);
+        knots.swap(index, index + 1);
+        prop_assert!(!valid_weight_knots(&knots, SALE_START_TIME, sale_end_time));
+    }
+}
+
+#[test]
+fn knots_must_be_within_the_sale_and_used_first() {
+    let knot = |timestamp, weight_basis_points| WeightKnot {
+        timestamp,
+        weight_basis_points,
+    };
+    let mut knots = [WeightKnot::default(); MAX_WEIGHT_KNOTS];
+    assert!(valid_weight_knots(&knots, SALE_START_TIME, 2_000));
+
+    for invalid in [
+        knot(SALE_START_TIME, 5000),
+        knot(2_000, 5000),
+        knot(1_500, 50),
+    ] {
+        knots[0] = invalid;
+        assert!(!valid_weight_knots(&knots, SALE_START_TIME, 2_000));
+    }
+
+    // Unused knots cannot come before used ones
+    knots[0] = WeightKnot::default();
+    knots[1] = knot(1_500, 5000);
+    assert!(!valid_weight_knots(&knots, SALE_START_TIME, 2_000));
+}
+
+#[test]
+fn sales_ending_at_the_adjustment_are_at_the_end_weight() {
+    let knots = [WeightKnot::default(); MAX_WEIGHT_KNOTS];
+    let adjustment = WeightAdjustment {
+        timestamp: 1_500,
+        weight_basis_points: 7000,
+        paused: false,
+    };
+    for curve in [
+        WeightCurve::Linear,
+        WeightCurve::Exponential { half_life: 100 },
+        WeightCurve::Piecewise,
+    ] {
+        // A hard cap can end the sale at the very time of the last adjustment
+        let weight = adjusted_weight(
+            curve,
+            &knots,
+            adjustment,
+            9000,
+            1000,
+            SALE_START_TIME,
+            1_500,
+            1_500,
+        );
+        assert_eq!(weight.unwrap(), 1000);
+    }
+}
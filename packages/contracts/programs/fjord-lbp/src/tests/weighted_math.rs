@@ -0,0 +1,135 @@
+use num_bigint::BigInt;
+use proptest::prelude::*;
+
+use super::reference;
+use crate::{
+    exponential_interpolation, get_amount_in, get_amount_out, linear_interpolation, SafeMathError,
+    WAD,
+};
+
+/// Reserves scaled to 8 decimals, from dust to the largest u64 supplies.
+fn reserve() -> impl Strategy<Value = u128> {
+    1_000u128..=1_000_000_000_000_000_000_000_000
+}
+
+/// Pool weights in basis points, as accepted by `initialize_pool`.
+fn weight() -> impl Strategy<Value = u128> {
+    100u128..=9900
+}
+
+proptest! {
+    #[test]
+    fn linear_interpolation_is_monotonic_in_time(
+        x in weight(),
+        y in weight(),
+        n in 1u128..=100_000_000,
+        i in 0u128..=200_000_000,
+        j in 0u128..=200_000_000,
+    ) {
+        let (before, after) = (i.min(j), i.max(j));
+        let weight_before = linear_interpolation(x, y, before, n).unwrap();
+        let weight_after = linear_interpolation(x, y, after, n).unwrap();
+
+        if x <= y {
+            prop_assert!(weight_before <= weight_after);
+        } else {
+            prop_assert!(weight_before >= weight_after);
+        }
+        prop_assert!(weight_before >= x.min(y) && weight_before <= x.max(y));
+        prop_assert_eq!(linear_interpolation(x, y, 0, n).unwrap(), x);
+        // The weight stops moving at the end of the sale
+        prop_assert_eq!(linear_interpolation(x, y, n + i, n).unwrap(), y);
+    }
+
+    #[test]
+    fn exponential_interpolation_is_monotonic_and_ahead_of_the_linear_schedule(
+        x in weight(),
+        y in weight(),
+        n in 1u128..=100_000_000,
+        i in 0u128..=200_000_000,
+        j in 0u128..=200_000_000,
+        half_life in 1u128..=200_000_000,
+    ) {
+        let (before, after) = (i.min(j), i.max(j));
+        let weight_before = exponential_interpolation(x, y, before, n, half_life).unwrap();
+        let weight_after = exponential_interpolation(x, y, after, n, half_life).unwrap();
+        let linear_weight = linear_interpolation(x, y, before, n).unwrap();
+
+        // The weight moves towards `y` at least as fast as with the linear schedule
+        if x <= y {
+            prop_assert!(weight_before <= weight_after);
+            prop_assert!(weight_before + 1 >= linear_weight);
+        } else {
+            prop_assert!(weight_before >= weight_after);
+            prop_assert!(weight_before <= linear_weight + 1);
+        }
+        prop_assert!(weight_before >= x.min(y) && weight_before <= x.max(y));
+        prop_assert_eq!(exponential_interpolation(x, y, 0, n, half_life).unwrap(), x);
+        prop_assert_eq!(exponential_interpolation(x, y, n + i, n, half_life).unwrap(), y);
+    }
+
+    #[test]
+    fn get_amount_out_never_exceeds_the_exact_amount(
+        reserve_in in reserve(),
+        reserve_out in reserve(),
+        amount_in_basis_points in 1u128..=3000,
+        weight_in in weight(),
+        weight_out in weight(),
+    ) {
+        let amount_in = reserve_in * amount_in_basis_points / 10_000;
+        let amount_out =
+            get_amount_out(amount_in, reserve_in, reserve_out, weight_in, weight_out).unwrap();
+
+        let exact = reference::amount_out(amount_in, reserve_in, reserve_out, weight_in, weight_out);
+        let amount_out = BigInt::from(amount_out) * reference::one();
+        prop_assert!(amount_out <= exact);
+        // The WAD rounding of the base and exponent is amplified by the exponent
+        let tolerance = (BigInt::from(reserve_out) * (weight_in + 2 * weight_out) * 4
+            / (weight_out * WAD)
+            + 2)
+            * reference::one();
+        prop_assert!(exact - amount_out <= tolerance);
+    }
+
+    #[test]
+    fn get_amount_in_never_falls_below_the_exact_amount(
+        reserve_in in reserve(),
+        reserve_out in reserve(),
+        amount_out_basis_points in 1u128..=3000,
+        weight_in in weight(),
+        weight_out in weight(),
+    ) {
+        let amount_out = reserve_out * amount_out_basis_points / 10_000;
+        prop_assume!(amount_out > 0);
+        let result = get_amount_in(amount_out, reserve_in, reserve_out, weight_in, weight_out);
+        // Steep weights can price the output beyond the range of the math
+        prop_assume!(!matches!(result, Err(SafeMathError::MultiplicationOverflow)));
+        let amount_in = BigInt::from(result.unwrap()) * reference::one();
+
+        let exact = reference::amount_in(amount_out, reserve_in, reserve_out, weight_in, weight_out);
+        prop_assert!(amount_in >= exact);
+        let power = &exact / reserve_in + reference::one();
+        let tolerance = BigInt::from(reserve_in) * (power + reference::one())
+            * (weight_out + 2 * weight_in) * 4
+            / (weight_in * WAD)
+            + reference::one() * 2;
+        prop_assert!(amount_in - exact <= tolerance);
+    }
+
+    #[test]
+    fn swaps_above_the_maximum_percentage_are_rejected(
+        reserve_in in reserve(),
+        reserve_out in reserve(),
+        excess in 1u128..=1_000_000,
+    ) {
+        let amount = |reserve: u128| reserve * 3 / 10 + excess;
+        prop_assert!(matches!(
+            get_amount_out(amount(reserve_in), reserve_in, reserve_out, 5000, 5000),
+            Err(SafeMathError::AmountInTooLarge)
+        ));
+        prop_assert!(matches!(
+            get_amount_in(amount(reserve_out), reserve_in, reserve_out, 5000, 5000),
+            Err(SafeMathError::AmountOutTooLarge)
+        ));
+    }
+}
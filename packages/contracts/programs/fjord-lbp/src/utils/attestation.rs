@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+
+use crate::PoolError;
+
+/// Approval of a user by the whitelist signer of a pool, signed off-chain and verified by the Ed25519 program.
+/// The message signed is the Borsh serialization of the attestation.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug, PartialEq, Eq)]
+pub struct WhitelistAttestation {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    // Time after which the attestation can no longer be used
+    pub expiry: i64,
+    // Maximum amount of assets the user can spend, net of the swap fees and of its sales (0 for no limit)
+    pub max_allocation: u64,
+}
+
+// Layout of the Ed25519 program instruction data: the number of signatures and a padding byte,
+// followed by the offsets of each signature, public key and message
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_SERIALIZED_SIZE: usize = 14;
+const PUBKEY_SERIALIZED_SIZE: usize = 32;
+
+/// Load the attestation verified by the Ed25519 program instruction preceding the current instruction.
+/// The instruction must verify a single signature of `signer`, with its public key and message stored in the
+/// instruction itself.
+pub fn load_whitelist_attestation(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+) -> Result<WhitelistAttestation> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    let Some(index) = current_index.checked_sub(1) else {
+        return Err(PoolError::InvalidWhitelistAttestation.into());
+    };
+    let instruction = load_instruction_at_checked(usize::from(index), instructions_sysvar)?;
+    if instruction.program_id != ed25519_program::ID {
+        return Err(PoolError::InvalidWhitelistAttestation.into());
+    }
+
+    let data = &instruction.data;
+    if data.len() < SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SERIALIZED_SIZE || data[0] != 1 {
+        return Err(PoolError::InvalidWhitelistAttestation.into());
+    }
+    let offsets = &data[SIGNATURE_OFFSETS_START..];
+    let read_offset = |at: usize| usize::from(u16::from_le_bytes([offsets[at], offsets[at + 1]]));
+    // Signature, public key and message instruction indexes, u16::MAX referring to the instruction itself
+    if [2, 6, 12]
+        .into_iter()
+        .any(|at| read_offset(at) != usize::from(u16::MAX))
+    {
+        return Err(PoolError::InvalidWhitelistAttestation.into());
+    }
+
+    let public_key_offset = read_offset(4);
+    let public_key = data.get(public_key_offset..public_key_offset + PUBKEY_SERIALIZED_SIZE);
+    if public_key != Some(signer.as_ref()) {
+        return Err(PoolError::InvalidWhitelistAttestation.into());
+    }
+    let message_offset = read_offset(8);
+    let message = data
+        .get(message_offset..message_offset + read_offset(10))
+        .ok_or(PoolError::InvalidWhitelistAttestation)?;
+    WhitelistAttestation::try_from_slice(message)
+        .map_err(|_| PoolError::InvalidWhitelistAttestation.into())
+}
@@ -1,16 +1,70 @@
+use anchor_lang::prelude::*;
 use anchor_lang::solana_program::keccak;
 
-pub fn merkle_verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: &[u8; 32]) -> bool {
-    let mut computed_hash = *leaf;
-    for proof_element in proof.iter() {
-        if computed_hash <= *proof_element {
-            // hash (current computed hash + current element of the proof)
-            computed_hash = keccak::hashv(&[&computed_hash, proof_element]).0
-        } else {
-            // hash (current element of the proof + current computed hash)
-            computed_hash = keccak::hashv(&[proof_element, &computed_hash]).0;
+/// Whitelist entry of a user along with its Merkle proof.
+/// The leaves of `Tiered` whitelist Merkle trees encode `(user, tier, max_allocation)`, see `whitelist_leaf`.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct WhitelistProof {
+    // Tier of the user, opening at `whitelist_tier_start_times[tier]` (always 0 in `Legacy` trees)
+    pub tier: u8,
+    // Maximum amount of assets the user can spend, net of the swap fees and of its sales (0 for no limit, always 0 in `Legacy` trees)
+    pub max_allocation: u64,
+    pub proof: Vec<[u8; 32]>,
+}
+
+/// Hashing scheme of the whitelist Merkle tree of a pool.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WhitelistLeafVersion {
+    /// Leaves hashing the base58 address of the user, as in the first pools: every user is in tier 0
+    /// without allocation. Internal nodes are hashed as sorted pairs.
+    #[default]
+    Legacy,
+    /// Leaves hashing the user along with its tier and allocation once, and internal nodes hashed as sorted pairs.
+    Tiered,
+    /// Leaves of OpenZeppelin's `StandardMerkleTree` of `[user, tier, max_allocation]` values typed
+    /// `["bytes32", "uint8", "uint64"]`: the ABI encoding of the values hashed twice, so that no internal node can
+    /// be passed off as a leaf (second preimage). Internal nodes are hashed as plain sorted pairs, as in
+    /// OpenZeppelin's `MerkleProof`, rather than with a domain-separation prefix: the double-hashed leaves already
+    /// provide the separation, and a prefix would break compatibility with OpenZeppelin's tooling.
+    Standard,
+}
+
+/// Leaf of the whitelist Merkle tree:
+/// keccak256(base58 user) for `Legacy` trees, which only whitelist users in tier 0 without allocation,
+/// keccak256(user || tier || max_allocation as little endian u64) for `Tiered` trees,
+/// and keccak256(keccak256(abi.encode(user, tier, max_allocation))) for `Standard` trees.
+pub fn whitelist_leaf(
+    version: WhitelistLeafVersion,
+    user: &Pubkey,
+    tier: u8,
+    max_allocation: u64,
+) -> [u8; 32] {
+    match version {
+        WhitelistLeafVersion::Legacy => keccak::hash(user.to_string().as_bytes()).0,
+        WhitelistLeafVersion::Tiered => {
+            keccak::hashv(&[user.as_ref(), &[tier], &max_allocation.to_le_bytes()]).0
+        }
+        WhitelistLeafVersion::Standard => {
+            // ABI encoding: every value takes a 32 bytes word, integers in big endian
+            let mut data = [0u8; 96];
+            data[..32].copy_from_slice(user.as_ref());
+            data[63] = tier;
+            data[88..].copy_from_slice(&max_allocation.to_be_bytes());
+            keccak::hash(&keccak::hash(&data).0).0
         }
     }
+}
+
+/// Parent of two nodes of the whitelist Merkle tree, hashed in sorted order.
+pub fn merkle_node(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    keccak::hashv(&[left, right]).0
+}
+
+pub fn merkle_verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: &[u8; 32]) -> bool {
+    let computed_hash = proof.iter().fold(*leaf, |computed_hash, proof_element| {
+        merkle_node(&computed_hash, proof_element)
+    });
     // check if the computed hash (root) is equal to the provided root
     computed_hash == *root
 }
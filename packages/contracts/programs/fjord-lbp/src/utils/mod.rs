@@ -1,13 +1,19 @@
+pub mod attestation;
 pub mod merkle;
 pub mod ownable;
+pub mod referral_schedule;
 pub mod safe_math;
 pub mod standard_checks;
 pub mod transfer;
+pub mod weight_curve;
 pub mod weighted_math_lib;
 
+pub use attestation::*;
 pub use merkle::*;
 pub use ownable::*;
+pub use referral_schedule::*;
 pub use safe_math::*;
 pub use standard_checks::*;
 pub use transfer::*;
+pub use weight_curve::*;
 pub use weighted_math_lib::*;
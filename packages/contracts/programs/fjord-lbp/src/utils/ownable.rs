@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::{program::FjordLbp, AccessControlError, OwnerConfig, PoolError, Treasury, FeeMapping};
+use crate::{program::FjordLbp, AccessControlError, OwnerConfig, PoolError, Treasury, FeeMapping, MIN_FEE_CHANGE_DELAY};
 use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::MAX_FEE_BASIS_POINTS;
 
 // Accounts for the initialize_owner instruction
@@ -19,7 +19,7 @@ pub struct InitializeOwner<'info> {
     payer = authority,
     seeds=["treasury".as_bytes()],
     constraint = fee_percentages.len() == fee_recipients.len() @PoolError::InvalidFeeRecipients,
-    space = 8 + 32 + (4 + (32 + 2) * fee_recipients.len()),
+    space = 8 + Treasury::space(fee_recipients.len(), None),
     bump
   )]
   pub treasury: Box<Account<'info, Treasury>>,
@@ -73,7 +73,10 @@ pub mod initializer {
     fee_percentages: Vec<u16>,
     platform_fee: u16,
     referral_fee: u16,
-    swap_fee: u16
+    swap_fee: u16,
+    max_platform_fee: u16,
+    max_referral_fee: u16,
+    max_swap_fee: u16
   ) -> Result<()> {
     let config = &mut ctx.accounts.config;
     let treasury = &mut ctx.accounts.treasury;
@@ -83,6 +86,14 @@ pub mod initializer {
       return Err(PoolError::MaxFeeExceeded.into());
     }
 
+    if max_platform_fee > MAX_FEE_BASIS_POINTS || max_referral_fee > MAX_FEE_BASIS_POINTS || max_swap_fee > MAX_FEE_BASIS_POINTS {
+      return Err(PoolError::MaxFeeExceeded.into());
+    }
+
+    if platform_fee > max_platform_fee || referral_fee > max_referral_fee || swap_fee > max_swap_fee {
+      return Err(PoolError::FeeCeilingExceeded.into());
+    }
+
     if fee_percentages.iter().any(|&fee| fee > MAX_FEE_BASIS_POINTS) {
       return Err(PoolError::MaxFeeExceeded.into());
     }
@@ -102,6 +113,11 @@ pub mod initializer {
     config.platform_fee = platform_fee;
     config.referral_fee = referral_fee;
     config.swap_fee = swap_fee;
+    // The ceilings set at deployment can only be lowered from there
+    config.max_platform_fee = max_platform_fee;
+    config.max_referral_fee = max_referral_fee;
+    config.max_swap_fee = max_swap_fee;
+    config.fee_change_delay = MIN_FEE_CHANGE_DELAY;
 
     treasury.swap_fee_recipient = swap_fee_recipient;
     for (i, recipient) in fee_recipients.iter().enumerate() {
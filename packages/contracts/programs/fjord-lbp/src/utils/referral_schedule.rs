@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::MAX_FEE_BASIS_POINTS;
+
+use crate::MAX_REFERRAL_TIERS;
+
+/// Volume tier of a referral schedule, unused when its `min_referred_volume` is 0.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ReferralTier {
+    // Assets the referrer must have brought to the pool for the tier to apply
+    pub min_referred_volume: u64,
+    pub fee_basis_points: u16,
+}
+
+/// Referral commission schedule of a pool, set by its creator at creation and paid out of its proceeds.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ReferralSchedule {
+    // Base referral fee set by the creator, the referral fee of the pool when None. Cleared once the owner and the
+    // creator override the referral fee of the pool, which then applies below the first tier
+    pub fee_basis_points: Option<u16>,
+    // Higher referral fees once referrers have brought enough assets, unused tiers last
+    pub tiers: [ReferralTier; MAX_REFERRAL_TIERS],
+    // Share of the referral fee going to the referrer of the referrer, in basis points of the fee
+    pub second_level_basis_points: u16,
+}
+
+impl ReferralSchedule {
+    /// Highest referral fee a referrer can earn, in basis points, the base fee being
+    /// `pool_referral_fee` when unset.
+    pub fn max_fee(&self, pool_referral_fee: u16) -> u16 {
+        self.tiers
+            .iter()
+            .map(|tier| tier.fee_basis_points)
+            .fold(self.fee_basis_points.unwrap_or(pool_referral_fee), u16::max)
+    }
+}
+
+/// Referral fee of a referrer having brought `referred_volume` assets to the pool, in basis points:
+/// the fee of the highest tier it reached, or the base fee below the first tier.
+pub fn referral_fee(
+    schedule: &ReferralSchedule,
+    pool_referral_fee: u16,
+    referred_volume: u64,
+) -> u16 {
+    schedule
+        .tiers
+        .iter()
+        .take_while(|tier| tier.min_referred_volume != 0)
+        .filter(|tier| referred_volume >= tier.min_referred_volume)
+        .last()
+        .map(|tier| tier.fee_basis_points)
+        .unwrap_or(schedule.fee_basis_points.unwrap_or(pool_referral_fee))
+}
+
+/// Whether the fees are within bounds, and the tiers in use come first in strictly increasing volume order.
+/// Referral fees are paid out of the proceeds along with the platform and swap fees of the pool,
+/// and every fee of the schedule must fit within 100% along with them.
+pub fn valid_referral_schedule(
+    schedule: &ReferralSchedule,
+    pool_referral_fee: u16,
+    platform_fee: u16,
+    swap_fee: u16,
+) -> bool {
+    let max_referral_fee =
+        MAX_FEE_BASIS_POINTS.saturating_sub(platform_fee.saturating_add(swap_fee));
+    let used = schedule
+        .tiers
+        .iter()
+        .take_while(|tier| tier.min_referred_volume != 0)
+        .count();
+    let mut previous = 0;
+    schedule.fee_basis_points.unwrap_or(pool_referral_fee) <= max_referral_fee
+        && schedule.second_level_basis_points <= MAX_FEE_BASIS_POINTS
+        && schedule.tiers[..used].iter().all(|tier| {
+            let valid =
+                tier.min_referred_volume > previous && tier.fee_basis_points <= max_referral_fee;
+            previous = tier.min_referred_volume;
+            valid
+        })
+        && schedule.tiers[used..]
+            .iter()
+            .all(|tier| *tier == ReferralTier::default())
+}
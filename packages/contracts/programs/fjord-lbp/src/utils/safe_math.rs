@@ -4,43 +4,66 @@ use crate::SafeMathError;
  * Clarification: WAD is usually an Ethereum term for 1e18 in Math Libraries.
  * It is just used here for consistency across Fjord's arithmetic logic.
  */
-pub const WAD: u64 = 1_000_000_000_000;
+pub const WAD: u128 = 1_000_000_000_000;
+
+/**
+ * Unsigned integers supported by `safe_add` and `safe_sub`:
+ * token amounts are stored as u64 while the pool math runs on u128.
+ */
+pub trait CheckedUint: Copy {
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_checked_uint {
+    ($($t:ty),*) => {
+        $(impl CheckedUint for $t {
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+        })*
+    };
+}
+
+impl_checked_uint!(u64, u128);
 
 /**
  * (x * y)/WAD
  */
-pub fn mul_wad(x: u64, y: u64) -> Result<u64, SafeMathError> {
-    let xy = u128::from(x)
-        .checked_mul(u128::from(y))
+pub fn mul_wad(x: u128, y: u128) -> Result<u128, SafeMathError> {
+    let xy = x
+        .checked_mul(y)
         .ok_or(SafeMathError::MultiplicationOverflow)?;
     let z = xy
-        .checked_div(u128::from(WAD))
+        .checked_div(WAD)
         .ok_or(SafeMathError::DivisionUnderflow)?;
-
-    u64::try_from(z).map_err(|_| SafeMathError::ConversionOverflow)
+    Ok(z)
 }
 
 /**
  * (x * y)/WAD but rounded up
  */
-pub fn mul_wad_up(x: u64, y: u64) -> Result<u64, SafeMathError> {
-    let xy = u128::from(x)
-        .checked_mul(u128::from(y))
+pub fn mul_wad_up(x: u128, y: u128) -> Result<u128, SafeMathError> {
+    let xy = x
+        .checked_mul(y)
         .ok_or(SafeMathError::MultiplicationOverflow)?;
     // Add WAD-1 before division to ensure rounding up
     let z = xy
-        .checked_add(u128::from(WAD) - 1)
+        .checked_add(WAD - 1)
         .ok_or(SafeMathError::AdditionOverflow)?
-        .checked_div(u128::from(WAD))
+        .checked_div(WAD)
         .ok_or(SafeMathError::DivisionUnderflow)?;
-
-    u64::try_from(z).map_err(|_| SafeMathError::ConversionOverflow)
+    Ok(z)
 }
 
 /**
  * x / y
  */
-pub fn div(x: u64, y: u64) -> Result<u64, SafeMathError> {
+pub fn div(x: u128, y: u128) -> Result<u128, SafeMathError> {
     let z = x.checked_div(y).ok_or(SafeMathError::DivisionUnderflow)?;
     Ok(z)
 }
@@ -48,7 +71,7 @@ pub fn div(x: u64, y: u64) -> Result<u64, SafeMathError> {
 /**
  * x * y
  */
-pub fn mul(x: u64, y: u64) -> Result<u64, SafeMathError> {
+pub fn mul(x: u128, y: u128) -> Result<u128, SafeMathError> {
     let z = x
         .checked_mul(y)
         .ok_or(SafeMathError::MultiplicationOverflow)?;
@@ -58,36 +81,34 @@ pub fn mul(x: u64, y: u64) -> Result<u64, SafeMathError> {
 /**
  * (x * WAD)/y
  */
-pub fn div_wad(x: u64, y: u64) -> Result<u64, SafeMathError> {
-    let xy = u128::from(x)
-        .checked_mul(u128::from(WAD))
+pub fn div_wad(x: u128, y: u128) -> Result<u128, SafeMathError> {
+    let xy = x
+        .checked_mul(WAD)
         .ok_or(SafeMathError::MultiplicationOverflow)?;
-    let z = xy
-        .checked_div(u128::from(y))
-        .ok_or(SafeMathError::DivisionUnderflow)?;
-    u64::try_from(z).map_err(|_| SafeMathError::ConversionOverflow)
+    let z = xy.checked_div(y).ok_or(SafeMathError::DivisionUnderflow)?;
+    Ok(z)
 }
 
 /**
  * (x * WAD)/y but rounded up
  */
-pub fn div_wad_up(x: u64, y: u64) -> Result<u64, SafeMathError> {
-    let xy = u128::from(x)
-        .checked_mul(u128::from(WAD))
+pub fn div_wad_up(x: u128, y: u128) -> Result<u128, SafeMathError> {
+    let xy = x
+        .checked_mul(WAD)
         .ok_or(SafeMathError::MultiplicationOverflow)?;
     // Add y-1 before division to ensure rounding up
     let z = xy
-        .checked_add(u128::from(y) - 1)
+        .checked_add(y.checked_sub(1).ok_or(SafeMathError::DivisionUnderflow)?)
         .ok_or(SafeMathError::AdditionOverflow)?
-        .checked_div(u128::from(y))
+        .checked_div(y)
         .ok_or(SafeMathError::DivisionUnderflow)?;
-    u64::try_from(z).map_err(|_| SafeMathError::ConversionOverflow)
+    Ok(z)
 }
 
 /**
  * x + y
  */
-pub fn safe_add(x: u64, y: u64) -> Result<u64, SafeMathError> {
+pub fn safe_add<T: CheckedUint>(x: T, y: T) -> Result<T, SafeMathError> {
     let z = x.checked_add(y).ok_or(SafeMathError::AdditionOverflow)?;
     Ok(z)
 }
@@ -95,7 +116,7 @@ pub fn safe_add(x: u64, y: u64) -> Result<u64, SafeMathError> {
 /**
  * x - y
  */
-pub fn safe_sub(x: u64, y: u64) -> Result<u64, SafeMathError> {
+pub fn safe_sub<T: CheckedUint>(x: T, y: T) -> Result<T, SafeMathError> {
     let z = x
         .checked_sub(y)
         .ok_or(SafeMathError::SubtractionUnderflow)?;
@@ -105,84 +126,181 @@ pub fn safe_sub(x: u64, y: u64) -> Result<u64, SafeMathError> {
 /**
  * (x * y)/z
  */
-pub fn mul_div(x: u64, y: u64, z: u64) -> Result<u64, SafeMathError> {
-    let xy = u128::from(x)
-        .checked_mul(u128::from(y))
+pub fn mul_div(x: u128, y: u128, z: u128) -> Result<u128, SafeMathError> {
+    let xy = x
+        .checked_mul(y)
         .ok_or(SafeMathError::MultiplicationOverflow)?;
-    let z = xy
-        .checked_div(u128::from(z))
-        .ok_or(SafeMathError::DivisionUnderflow)?;
-    u64::try_from(z).map_err(|_| SafeMathError::ConversionOverflow)
+    let z = xy.checked_div(z).ok_or(SafeMathError::DivisionUnderflow)?;
+    Ok(z)
 }
 
 /**
  * x ** y
  */
-pub fn safe_pow(x: u64, y: u32) -> Result<u64, SafeMathError> {
+pub fn safe_pow(x: u128, y: u32) -> Result<u128, SafeMathError> {
     let z = x
         .checked_pow(y)
         .ok_or(SafeMathError::ExponentiationOverflow)?;
     Ok(z)
 }
 
-/// @dev Equivalent to `x` to the power of `y` scaled by WAD.
-pub fn pow_wad(x: i64, y: i64) -> Result<i64, SafeMathError> {
-    let ln_result = ln_wad(x)?;
-    let exp_input = (ln_result * y as i128 / WAD as i128) as i128;
-
-    exp_wad(exp_input)
+/**
+ * Convert a result of the pool math back to a token amount
+ */
+pub fn to_u64(x: u128) -> Result<u64, SafeMathError> {
+    u64::try_from(x).map_err(|_| SafeMathError::ConversionOverflow)
 }
 
 /**
- * pow_wad but rounded up
+ * Scale of the intermediate results of the logarithm and exponential (1e18),
+ * 1e6 times more precise than WAD so that rounding to WAD absorbs their errors.
  */
-pub fn pow_wad_up(x: u64, y: u64) -> Result<u64, SafeMathError> {
-    let power = pow_wad(x as i64, y as i64)?;
+const HP_SCALE: i128 = 1_000_000_000_000_000_000;
+const WAD_TO_HP: i128 = HP_SCALE / WAD as i128;
 
-    // Convert power to u64, add small increment for rounding up.
-    let power_u64 = power as u64;
-    let increment = mul_wad_up(power_u64, 1000)?;
-    let rounded_up = power_u64
-        .checked_add(increment)
-        .and_then(|p| p.checked_add(1))
-        .ok_or(SafeMathError::AdditionOverflow)?;
+/**
+ * ln(2) scaled by 1e36
+ */
+const LN_2_E36: i128 = 693_147_180_559_945_309_417_232_121_458_176_568;
 
-    Ok(rounded_up)
-}
+/**
+ * Inputs of the exponential (scaled by 1e18) above which the result overflows and below which it rounds to 0.
+ * exp(43) ≈ 4.7e18 scaled by 1e18 still fits in an i128.
+ */
+const EXP_MAX_INPUT_HP: i128 = 43 * HP_SCALE;
 
-/// Approximates ln(x) for x scaled by WAD.
-pub fn ln_wad(x: i64) -> Result<i128, SafeMathError> {
+/**
+ * Upper bound of the absolute error of `ln_hp`, in units of 1e-18.
+ * Reducing x to [1, 2) and computing z cost at most 3 units, and each of the (at most 20) terms of the series
+ * loses less than 2 units to truncation, doubled by the final factor 2.
+ */
+const LN_HP_MAX_ERROR: i128 = 100;
+
+/**
+ * Upper bound of the relative error of `exp_hp`, in units of 1e-18.
+ * The range reduction loses at most 1 unit and each of the (at most 20) Taylor terms less than 2 units.
+ */
+const EXP_HP_MAX_RELATIVE_ERROR: i128 = 50;
+
+/// Natural logarithm of `x`, with `x` and the result scaled by 1e18.
+/// The result is within `LN_HP_MAX_ERROR` units of the exact value.
+fn ln_hp(x: i128) -> Result<i128, SafeMathError> {
     if x <= 0 {
         return Err(SafeMathError::LogarithmUndefined);
     }
-    // Calculate the natural logarithm using f64, then convert to i128 for high precision.
-    let ln_value = ((x as f64 / WAD as f64).ln() * WAD as f64) as i128;
 
-    Ok(ln_value)
-}
+    // Reduce x to m * 2^k with m in [1, 2)
+    let mut k = x.ilog2() as i32 - HP_SCALE.ilog2() as i32;
+    let mut m = if k >= 0 { x >> k } else { x << -k };
+    while m >= 2 * HP_SCALE {
+        m >>= 1;
+        k += 1;
+    }
+    while m < HP_SCALE {
+        m <<= 1;
+        k -= 1;
+    }
 
-pub fn exp_wad(x: i128) -> Result<i64, SafeMathError> {
-    // Approximately ~log(i64:MAX_VALUE) in WAD (1e9 scale)
-    let max_min_input = 43 * WAD as i128;
+    // ln(m) = 2 * atanh(z) = 2 * (z + z^3/3 + z^5/5 + ...) with z = (m - 1) / (m + 1) in [0, 1/3)
+    let z = (m - HP_SCALE) * HP_SCALE / (m + HP_SCALE);
+    let z_squared = z * z / HP_SCALE;
+    let mut term = z;
+    let mut sum = z;
+    let mut n = 1;
+    while term != 0 {
+        term = term * z_squared / HP_SCALE;
+        n += 2;
+        sum += term / n;
+    }
 
-    // Early exit for overflow/underflow conditions
-    if x >= max_min_input {
+    Ok(i128::from(k) * LN_2_E36 / HP_SCALE + 2 * sum)
+}
+
+/// Exponential of `x`, with `x` and the result scaled by 1e18.
+/// The relative error of the result is below `EXP_HP_MAX_RELATIVE_ERROR` units, plus 1 unit of truncation.
+fn exp_hp(x: i128) -> Result<i128, SafeMathError> {
+    if x >= EXP_MAX_INPUT_HP {
         return Err(SafeMathError::ExponentiationOverflow);
     }
-
-    // Exp small x approximates to 0
-    if x <= -max_min_input {
+    if x <= -EXP_MAX_INPUT_HP {
         return Ok(0);
     }
 
-    // Scale x from WAD to natural exponential scale
-    let scaled_x = (x as f64 / WAD as f64).exp();
-    let result_scaled = (scaled_x * WAD as f64).round() as i128;
+    // Reduce x to k * ln(2) + r with |r| <= ln(2) / 2
+    let half_ln_2 = if x >= 0 { LN_2_E36 / 2 } else { -LN_2_E36 / 2 };
+    let k = (x * HP_SCALE + half_ln_2) / LN_2_E36;
+    let r = x - k * LN_2_E36 / HP_SCALE;
 
-    // Ensures the result fits within i64 range.
-    if result_scaled > i64::MAX as i128 || result_scaled < i64::MIN as i128 {
-        return Err(SafeMathError::ExponentiationOverflow);
+    // exp(r) = 1 + r + r^2/2! + r^3/3! + ...
+    let mut term = HP_SCALE;
+    let mut sum = HP_SCALE;
+    let mut n = 0;
+    while term != 0 {
+        n += 1;
+        term = term * r / (HP_SCALE * n);
+        sum += term;
     }
 
-    Ok(result_scaled as i64)
+    // exp(x) = exp(r) * 2^k
+    Ok(if k >= 0 { sum << k } else { sum >> -k })
+}
+
+/// Compute `x` to the power of `y` (both scaled by WAD) as exp(y * ln(x)).
+/// Returns the result scaled by 1e18 along with an upper bound of its absolute error.
+/// `x` and `y` must fit in an i64, otherwise `ConversionOverflow` is returned.
+fn _pow_hp(x: u128, y: u128) -> Result<(i128, i128), SafeMathError> {
+    let x = i64::try_from(x).map_err(|_| SafeMathError::ConversionOverflow)?;
+    let y = i64::try_from(y).map_err(|_| SafeMathError::ConversionOverflow)?;
+    let ln_x = ln_hp(i128::from(x) * WAD_TO_HP)?;
+    let exponent = ln_x
+        .checked_mul(i128::from(y))
+        .ok_or(SafeMathError::MultiplicationOverflow)?
+        / WAD as i128;
+    let power = exp_hp(exponent)?;
+
+    // The error of the logarithm is scaled by |y| in the exponent, which turns into a relative error of the
+    // exponential (exp(x + e) ≈ exp(x) * (1 + e) for tiny e). The bound is doubled to stay conservative.
+    let relative_error = 2
+        * (LN_HP_MAX_ERROR * (i128::from(y).abs() / WAD as i128 + 1)
+            + 1
+            + EXP_HP_MAX_RELATIVE_ERROR);
+    let max_error = (power / HP_SCALE + 1) * relative_error + 2;
+
+    Ok((power, max_error))
+}
+
+/// @dev Equivalent to `x` to the power of `y` scaled by WAD, rounded down.
+/// The result is never above the exact value, and below it by at most ~1e-13 relative error plus 1 unit.
+pub fn pow_wad(x: u128, y: u128) -> Result<u128, SafeMathError> {
+    let (power, max_error) = _pow_hp(x, y)?;
+    let rounded_down = (power - max_error).max(0) / WAD_TO_HP;
+
+    u128::try_from(rounded_down).map_err(|_| SafeMathError::ExponentiationOverflow)
+}
+
+/// pow_wad but rounded up.
+/// The result is never below the exact value, and above it by at most ~1e-13 relative error plus 1 unit.
+/// Used by the weighted math so that rounding always favours the pool.
+pub fn pow_wad_up(x: u128, y: u128) -> Result<u128, SafeMathError> {
+    let (power, max_error) = _pow_hp(x, y)?;
+    let rounded_up = (power + max_error + WAD_TO_HP - 1) / WAD_TO_HP;
+
+    u128::try_from(rounded_up).map_err(|_| SafeMathError::ExponentiationOverflow)
+}
+
+/// Natural logarithm of `x` scaled by WAD, rounded down.
+/// Computed with integer arithmetic only, within 1 unit of the exact value.
+pub fn ln_wad(x: i64) -> Result<i128, SafeMathError> {
+    let ln_value = ln_hp(i128::from(x) * WAD_TO_HP)?;
+
+    Ok(ln_value.div_euclid(WAD_TO_HP))
+}
+
+/// Exponential of `x` scaled by WAD, rounded down.
+/// Computed with integer arithmetic only, within 1 unit plus ~1e-16 relative error of the exact value.
+pub fn exp_wad(x: i128) -> Result<i64, SafeMathError> {
+    let result = exp_hp(x.saturating_mul(WAD_TO_HP))? / WAD_TO_HP;
+
+    // Ensures the result fits within i64 range.
+    i64::try_from(result).map_err(|_| SafeMathError::ExponentiationOverflow)
 }
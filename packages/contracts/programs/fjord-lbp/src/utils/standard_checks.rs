@@ -1,26 +1,92 @@
-use crate::{PoolError, SwapTokens};
+use crate::{PoolError, SwapTokens, WhitelistProof};
 use anchor_lang::prelude::*;
 
 pub mod merkle {
-    use solana_program::keccak;
-
     pub use super::*;
-    use crate::merkle_verify;
+    use crate::{
+        load_whitelist_attestation, merkle_verify, whitelist_leaf, WhitelistAttestation,
+        WhitelistLeafVersion, MAX_WHITELIST_TIERS,
+    };
 
+    /// Check that the user is whitelisted, by a Merkle proof or an attestation of the whitelist signer,
+    /// and return its maximum allocation (0 for no limit).
     pub fn _only_white_listed(
         ctx: &Context<SwapTokens>,
-        merkle_proof: Option<Vec<[u8; 32]>>,
-    ) -> Result<()> {
-        // if merkle root is not an empty array and merkle proof is provided, check if the user is in the whitelist
+        merkle_proof: &Option<WhitelistProof>,
+    ) -> Result<u64> {
+        // if neither a merkle root nor a whitelist signer is set, everyone can trade
         let merkle_root = ctx.accounts.pool.whitelist_merkle_root;
-        let node = keccak::hashv(&[&ctx.accounts.user.key().to_string().as_bytes()]);
-        if merkle_root != [0u8; 32]
-            && (merkle_proof.is_none()
-                || !merkle_verify(&merkle_proof.unwrap()[..], &merkle_root, &node.0))
+        let whitelist_signer = ctx.accounts.pool.whitelist_signer;
+        if merkle_root == [0u8; 32] && whitelist_signer == Pubkey::default() {
+            return Ok(0);
+        }
+        // Anyone can trade once the public sale starts
+        let public_sale_time = ctx.accounts.pool.public_sale_time;
+        let current_time = Clock::get()?.unix_timestamp;
+        if public_sale_time != 0 && current_time >= public_sale_time {
+            return Ok(0);
+        }
+
+        let (tier, max_allocation) = match merkle_proof {
+            // Check that the user is in the whitelist with the given tier and allocation
+            Some(merkle_proof) if merkle_root != [0u8; 32] => {
+                let version = ctx.accounts.pool.whitelist_leaf_version;
+                // Legacy leaves do not encode any tier or allocation
+                if version == WhitelistLeafVersion::Legacy
+                    && (merkle_proof.tier != 0 || merkle_proof.max_allocation != 0)
+                {
+                    return Err(PoolError::WhitelistProof.into());
+                }
+                let leaf = whitelist_leaf(
+                    version,
+                    &ctx.accounts.user.key(),
+                    merkle_proof.tier,
+                    merkle_proof.max_allocation,
+                );
+                if !merkle_verify(&merkle_proof.proof, &merkle_root, &leaf) {
+                    return Err(PoolError::WhitelistProof.into());
+                }
+                (merkle_proof.tier, merkle_proof.max_allocation)
+            }
+            // Users attested by the whitelist signer belong to the first tier
+            _ if whitelist_signer != Pubkey::default() => {
+                (0, _whitelist_attestation(ctx, current_time)?.max_allocation)
+            }
+            _ => return Err(PoolError::WhitelistProof.into()),
+        };
+
+        // Each tier can only trade once it opens
+        let tier = usize::from(tier);
+        if tier >= MAX_WHITELIST_TIERS {
+            return Err(PoolError::InvalidWhitelistTier.into());
+        }
+        if current_time < ctx.accounts.pool.whitelist_tier_start_times[tier] {
+            return Err(PoolError::WhitelistTierNotOpen.into());
+        }
+        Ok(max_allocation)
+    }
+
+    /// Attestation of the user for this pool by the whitelist signer, verified by the preceding Ed25519 instruction.
+    fn _whitelist_attestation(
+        ctx: &Context<SwapTokens>,
+        current_time: i64,
+    ) -> Result<WhitelistAttestation> {
+        let Some(instructions_sysvar) = &ctx.accounts.instructions_sysvar else {
+            return Err(PoolError::InvalidWhitelistAttestation.into());
+        };
+        let attestation = load_whitelist_attestation(
+            &instructions_sysvar.to_account_info(),
+            &ctx.accounts.pool.whitelist_signer,
+        )?;
+        if attestation.pool != ctx.accounts.pool.key()
+            || attestation.user != ctx.accounts.user.key()
         {
-            return Err(PoolError::WhitelistProof.into());
+            return Err(PoolError::InvalidWhitelistAttestation.into());
         }
-        Ok(())
+        if current_time > attestation.expiry {
+            return Err(PoolError::WhitelistAttestationExpired.into());
+        }
+        Ok(attestation)
     }
 }
 
@@ -28,9 +94,11 @@ pub mod sale {
     pub use super::*;
 
     pub fn _when_sale_active(ctx: &Context<SwapTokens>) -> Result<()> {
+        // Cancelled pools are closed before their sale ends
         let current_time = Clock::get()?.unix_timestamp;
         if current_time < ctx.accounts.pool.sale_start_time
             || current_time >= ctx.accounts.pool.sale_end_time
+            || ctx.accounts.pool.closed
         {
             return Err(PoolError::TradingDisallowed.into());
         }
@@ -52,16 +120,109 @@ pub mod sale {
     }
 }
 
+pub mod native {
+    pub use super::*;
+    use crate::is_native_mint;
+
+    pub fn _valid_native_accounts(ctx: &Context<SwapTokens>, is_sell: bool) -> Result<()> {
+        // Native SOL is used when the user's asset token account is omitted or assets are unwrapped
+        let user_asset_token_account = &ctx.accounts.user_asset_token_account;
+        let native_unwrap_account = &ctx.accounts.native_unwrap_account;
+        if (user_asset_token_account.is_none() || native_unwrap_account.is_some())
+            && !is_native_mint(&ctx.accounts.asset_token_mint.key())
+        {
+            return Err(PoolError::NativeSolDisallowed.into());
+        }
+        // The unwrap account must always be closed within the instruction, which only happens when selling
+        if !is_sell && native_unwrap_account.is_some() {
+            return Err(PoolError::UnexpectedNativeUnwrapAccount.into());
+        }
+        if is_sell && user_asset_token_account.is_none() && native_unwrap_account.is_none() {
+            return Err(PoolError::MissingNativeUnwrapAccount.into());
+        }
+        Ok(())
+    }
+}
+
+pub mod referrer {
+    pub use super::*;
+
+    /// Check that the referrer of a swap comes with its state in the pool, is not the user itself and is the
+    /// referrer recorded by the earlier purchases of the user, if any. Sales must name the recorded referrer
+    /// so that it is debited, and purchases can only name registered referrers when the registry is required.
+    /// The state of the referrer of the referrer must come along when the pool splits referral fees with it.
+    pub fn _valid_referrer(
+        ctx: &Context<SwapTokens>,
+        referrer: &Option<Pubkey>,
+        is_sell: bool,
+    ) -> Result<()> {
+        let recorded = ctx.accounts.user_state_in_pool.referrer;
+        let Some(referrer) = referrer else {
+            if ctx.accounts.referrer_state_in_pool.is_some()
+                || ctx.accounts.second_level_referrer_state_in_pool.is_some()
+                || (is_sell && recorded != Pubkey::default())
+            {
+                return Err(PoolError::InvalidReferrer.into());
+            }
+            return Ok(());
+        };
+        if *referrer == ctx.accounts.user.key() {
+            return Err(PoolError::SelfReferral.into());
+        }
+        if ctx.accounts.referrer_state_in_pool.is_none()
+            || ((is_sell || recorded != Pubkey::default()) && *referrer != recorded)
+        {
+            return Err(PoolError::InvalidReferrer.into());
+        }
+        if !is_sell && ctx.accounts.config.referrer_registry_required {
+            // Only the program creates accounts at the registration address, and only to register referrers
+            let registered = matches!(
+                &ctx.accounts.referrer_registration,
+                Some(registration) if *registration.owner == crate::ID
+            );
+            if !registered {
+                return Err(PoolError::UnregisteredReferrer.into());
+            }
+        }
+        // The referrer of the referrer comes with its state when it earns a second-level split, unless it is the user
+        let second_level = ctx
+            .accounts
+            .referrer_state_in_pool
+            .as_ref()
+            .map(|state| state.referrer)
+            .unwrap_or_default();
+        let second_level_earns =
+            second_level != Pubkey::default() && second_level != ctx.accounts.user.key();
+        let second_level_required = second_level_earns
+            && ctx
+                .accounts
+                .pool
+                .referral_schedule
+                .second_level_basis_points
+                != 0;
+        match &ctx.accounts.second_level_referrer_state_in_pool {
+            Some(_) if !second_level_earns => Err(PoolError::InvalidReferrer.into()),
+            None if second_level_required => Err(PoolError::InvalidReferrer.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Run the checks shared by every swap, at the start of each swap handler, and return the whitelist allocation
+/// of the user (0 for no limit) so that buys can enforce it without verifying the whitelist again.
 pub fn before_token_swap(
     ctx: &Context<SwapTokens>,
-    merkle_proof: Option<Vec<[u8; 32]>>,
+    merkle_proof: &Option<WhitelistProof>,
+    referrer: &Option<Pubkey>,
     is_sell: bool,
-) -> Result<()> {
+) -> Result<u64> {
     sale::_when_not_paused(ctx)?;
     sale::_when_sale_active(ctx)?;
-    merkle::_only_white_listed(ctx, merkle_proof)?;
+    let max_allocation = merkle::_only_white_listed(ctx, merkle_proof)?;
+    native::_valid_native_accounts(ctx, is_sell)?;
+    referrer::_valid_referrer(ctx, referrer, is_sell)?;
     if is_sell {
         sale::_when_selling_allowed(ctx)?
     };
-    Ok(())
+    Ok(max_allocation)
 }
@@ -1,17 +1,31 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Transfer};
+use anchor_lang::solana_program::program::invoke;
+use anchor_lang::system_program;
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    transfer_fee::{instruction::harvest_withheld_tokens_to_mint, TransferFeeConfig},
+    BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::Mint as MintState;
+use anchor_spl::token_interface::{self, CloseAccount, SyncNative, TransferChecked};
+
+use crate::{safe_add, safe_sub, SafeMathError};
 
 /// Transfer helper function to transfer tokens to an account from the pool.
+#[allow(clippy::too_many_arguments)]
 pub fn transfer_tokens_from<'a>(
     token_program: AccountInfo<'a>,
     from_account: AccountInfo<'a>,
     to_account: AccountInfo<'a>,
+    mint: AccountInfo<'a>,
     authority: AccountInfo<'a>,
     seeds: &[&[u8]],
     amount: u64,
+    decimals: u8,
 ) -> Result<()> {
-    let token_transfer_instruction = Transfer {
+    let token_transfer_instruction = TransferChecked {
         from: from_account.clone(),
+        mint: mint.clone(),
         to: to_account.clone(),
         authority: authority.clone(),
     };
@@ -23,5 +37,159 @@ pub fn transfer_tokens_from<'a>(
         signer_seeds,
     );
 
-    token::transfer(cpi_ctx, amount)
+    token_interface::transfer_checked(cpi_ctx, amount, decimals)
+}
+
+/// Transfer helper function to transfer tokens to an account from a signer of the instruction.
+pub fn transfer_tokens<'a>(
+    token_program: AccountInfo<'a>,
+    from_account: AccountInfo<'a>,
+    to_account: AccountInfo<'a>,
+    mint: AccountInfo<'a>,
+    authority: AccountInfo<'a>,
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    let token_transfer_instruction = TransferChecked {
+        from: from_account,
+        mint,
+        to: to_account,
+        authority,
+    };
+    let cpi_ctx = CpiContext::new(token_program, token_transfer_instruction);
+
+    token_interface::transfer_checked(cpi_ctx, amount, decimals)
+}
+
+/// Whether `mint` is the wrapped SOL mint of either the SPL Token or the Token-2022 program.
+pub fn is_native_mint(mint: &Pubkey) -> bool {
+    *mint == spl_token::native_mint::ID
+        || *mint == anchor_spl::token_2022::spl_token_2022::native_mint::ID
+}
+
+/// Wrap lamports of a signer of the instruction into a native (wSOL) token account.
+pub fn wrap_native<'a>(
+    system_program: AccountInfo<'a>,
+    token_program: AccountInfo<'a>,
+    from: AccountInfo<'a>,
+    to_account: AccountInfo<'a>,
+    amount: u64,
+) -> Result<()> {
+    system_program::transfer(
+        CpiContext::new(
+            system_program,
+            system_program::Transfer {
+                from,
+                to: to_account.clone(),
+            },
+        ),
+        amount,
+    )?;
+
+    token_interface::sync_native(CpiContext::new(
+        token_program,
+        SyncNative {
+            account: to_account,
+        },
+    ))
+}
+
+/// Unwrap a native (wSOL) token account owned by the pool by closing it.
+/// All of its lamports (the wrapped amount and the rent) are released to `destination`.
+pub fn unwrap_native<'a>(
+    token_program: AccountInfo<'a>,
+    account: AccountInfo<'a>,
+    destination: AccountInfo<'a>,
+    authority: AccountInfo<'a>,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    let signer_seeds = &[seeds];
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program,
+        CloseAccount {
+            account,
+            destination,
+            authority,
+        },
+        signer_seeds,
+    );
+
+    token_interface::close_account(cpi_ctx)
+}
+
+/// Close a token account owned by the pool, releasing its rent to `destination`.
+/// The transfer fees withheld in Token-2022 accounts prevent closing them and are first harvested to the mint.
+pub fn close_token_account_from<'a>(
+    token_program: AccountInfo<'a>,
+    mint: AccountInfo<'a>,
+    account: AccountInfo<'a>,
+    destination: AccountInfo<'a>,
+    authority: AccountInfo<'a>,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    if _get_transfer_fee_config(&mint)?.is_some() {
+        let harvest_instruction =
+            harvest_withheld_tokens_to_mint(token_program.key, mint.key, &[account.key])?;
+        invoke(
+            &harvest_instruction,
+            &[mint, account.clone(), token_program.clone()],
+        )?;
+    }
+
+    let signer_seeds = &[seeds];
+    let cpi_ctx = CpiContext::new_with_signer(
+        token_program,
+        CloseAccount {
+            account,
+            destination,
+            authority,
+        },
+        signer_seeds,
+    );
+
+    token_interface::close_account(cpi_ctx)
+}
+
+/// Move lamports out of an account owned by this program (e.g. the pool) into any writable account.
+pub fn transfer_lamports_from(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    let from_lamports = safe_sub(from.lamports(), amount)?;
+    let to_lamports = safe_add(to.lamports(), amount)?;
+    **from.try_borrow_mut_lamports()? = from_lamports;
+    **to.try_borrow_mut_lamports()? = to_lamports;
+    Ok(())
+}
+
+/// Calculate the fee withheld by the Token-2022 transfer fee extension when sending `amount` tokens of `mint`.
+/// Returns 0 for legacy SPL mints and Token-2022 mints without the extension.
+pub fn get_transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    match _get_transfer_fee_config(mint)? {
+        Some(config) => Ok(config
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(SafeMathError::MultiplicationOverflow)?),
+        None => Ok(0),
+    }
+}
+
+/// Calculate the fee that must be added on top of `post_fee_amount` so that the recipient receives exactly
+/// `post_fee_amount` tokens of `mint` after the Token-2022 transfer fee is withheld.
+/// Returns 0 for legacy SPL mints and Token-2022 mints without the extension.
+pub fn get_transfer_inverse_fee(mint: &AccountInfo, post_fee_amount: u64) -> Result<u64> {
+    match _get_transfer_fee_config(mint)? {
+        Some(config) => Ok(config
+            .calculate_inverse_epoch_fee(Clock::get()?.epoch, post_fee_amount)
+            .ok_or(SafeMathError::MultiplicationOverflow)?),
+        None => Ok(0),
+    }
+}
+
+fn _get_transfer_fee_config(mint: &AccountInfo) -> Result<Option<TransferFeeConfig>> {
+    if *mint.owner != anchor_spl::token_2022::ID {
+        return Ok(None);
+    }
+    let mint_data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<MintState>::unpack(&mint_data)?;
+    Ok(mint_state
+        .get_extension::<TransferFeeConfig>()
+        .ok()
+        .copied())
 }
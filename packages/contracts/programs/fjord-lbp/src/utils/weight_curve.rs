@@ -0,0 +1,141 @@
+use anchor_lang::prelude::*;
+
+use crate::{exponential_interpolation, linear_interpolation, SafeMathError, MAX_WEIGHT_KNOTS};
+
+/// Shape of the asset weight schedule of a pool, from `start_weight_basis_points` at the start of the sale
+/// to `end_weight_basis_points` at its end.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WeightCurve {
+    /// Weight moving by the same amount every second.
+    #[default]
+    Linear,
+    /// Weight moving fast early and flattening later, its distance to the end weight halving every `half_life` seconds.
+    Exponential { half_life: i64 },
+    /// Weight moving linearly between the `weight_knots` of the pool.
+    Piecewise,
+}
+
+/// Point of a piecewise weight schedule, unused when its timestamp is 0.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WeightKnot {
+    pub timestamp: i64,
+    pub weight_basis_points: u16,
+}
+
+/// Last adjustment of the weight schedule of a pool during its sale, by its creator or weight manager.
+/// The schedule then resumes from `weight_basis_points` at `timestamp` instead of the start of the sale.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WeightAdjustment {
+    // Timestamp of the adjustment, 0 when never adjusted
+    pub timestamp: i64,
+    // Asset weight at the time of the adjustment
+    pub weight_basis_points: u16,
+    // Whether the asset weight is held at `weight_basis_points`
+    pub paused: bool,
+}
+
+/// Asset weight of a pool at `current_time`, in basis points.
+/// Piecewise schedules go through their knots past `sale_start_time`, between the start and end weights.
+pub fn interpolate_weight(
+    curve: WeightCurve,
+    knots: &[WeightKnot; MAX_WEIGHT_KNOTS],
+    start_weight_basis_points: u16,
+    end_weight_basis_points: u16,
+    sale_start_time: i64,
+    sale_end_time: i64,
+    current_time: i64,
+) -> std::result::Result<u128, SafeMathError> {
+    let mut from = (sale_start_time, start_weight_basis_points);
+    let knots = match curve {
+        WeightCurve::Piecewise => &knots[..],
+        _ => &[],
+    };
+    let points = knots
+        .iter()
+        .filter(|knot| knot.timestamp > sale_start_time)
+        .map(|knot| (knot.timestamp, knot.weight_basis_points))
+        .chain([(sale_end_time, end_weight_basis_points)]);
+
+    for to in points {
+        if current_time < to.0 || to.0 == sale_end_time {
+            // The sale can end at the very time the schedule resumes from, like a hard cap hit on an adjustment
+            if to.0 <= from.0 {
+                return Ok(to.1.into());
+            }
+            let seconds_elapsed = (current_time.max(from.0) - from.0) as u128;
+            let total_seconds = (to.0 - from.0) as u128;
+            return match curve {
+                WeightCurve::Exponential { half_life } => exponential_interpolation(
+                    from.1.into(),
+                    to.1.into(),
+                    seconds_elapsed,
+                    total_seconds,
+                    half_life as u128,
+                ),
+                _ => {
+                    linear_interpolation(from.1.into(), to.1.into(), seconds_elapsed, total_seconds)
+                }
+            };
+        }
+        from = to;
+    }
+    Ok(end_weight_basis_points.into())
+}
+
+/// Asset weight of a pool at `current_time` after the last adjustment of its schedule, in basis points.
+/// Held while paused, it otherwise moves from the weight at the last adjustment to the end weight,
+/// through the knots past the adjustment, so that the weight never jumps.
+#[allow(clippy::too_many_arguments)]
+pub fn adjusted_weight(
+    curve: WeightCurve,
+    knots: &[WeightKnot; MAX_WEIGHT_KNOTS],
+    adjustment: WeightAdjustment,
+    start_weight_basis_points: u16,
+    end_weight_basis_points: u16,
+    sale_start_time: i64,
+    sale_end_time: i64,
+    current_time: i64,
+) -> std::result::Result<u128, SafeMathError> {
+    if adjustment.paused {
+        return Ok(adjustment.weight_basis_points.into());
+    }
+    if adjustment.timestamp == 0 {
+        return interpolate_weight(
+            curve,
+            knots,
+            start_weight_basis_points,
+            end_weight_basis_points,
+            sale_start_time,
+            sale_end_time,
+            current_time,
+        );
+    }
+    interpolate_weight(
+        curve,
+        knots,
+        adjustment.weight_basis_points,
+        end_weight_basis_points,
+        adjustment.timestamp,
+        sale_end_time,
+        current_time,
+    )
+}
+
+/// Whether the knots in use come first, in strictly increasing order within the sale, with valid weights.
+pub fn valid_weight_knots(
+    knots: &[WeightKnot; MAX_WEIGHT_KNOTS],
+    sale_start_time: i64,
+    sale_end_time: i64,
+) -> bool {
+    let used = knots.iter().take_while(|knot| knot.timestamp != 0).count();
+    let mut previous = sale_start_time;
+    knots[..used].iter().all(|knot| {
+        let valid = knot.timestamp > previous
+            && knot.timestamp < sale_end_time
+            && (100..=9900).contains(&knot.weight_basis_points);
+        previous = knot.timestamp;
+        valid
+    }) && knots[used..]
+        .iter()
+        .all(|knot| *knot == WeightKnot::default())
+}
@@ -3,11 +3,11 @@ use std::cmp::min;
 use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::MAX_FEE_BASIS_POINTS;
 
 use crate::{
-    div_wad, div_wad_up, mul_div, mul_wad, mul_wad_up, pow_wad_up, safe_add, safe_sub,
+    div_wad, div_wad_up, exp_wad, mul_div, mul_wad, mul_wad_up, pow_wad_up, safe_add, safe_sub,
     SafeMathError, MAX_PERCENTAGE_IN, MAX_PERCENTAGE_OUT, WAD,
 };
 
-pub fn linear_interpolation(x: u64, y: u64, i: u64, n: u64) -> Result<u64, SafeMathError> {
+pub fn linear_interpolation(x: u128, y: u128, i: u128, n: u128) -> Result<u128, SafeMathError> {
     // -----------------------------------------------------------------------
     //
     //         ⎛ |x - y| ⎞
@@ -21,6 +21,50 @@ pub fn linear_interpolation(x: u64, y: u64, i: u64, n: u64) -> Result<u64, SafeM
     }
 }
 
+/// ln(2) scaled by WAD.
+const LN_2_WAD: u128 = 693_147_180_560;
+
+/// Interpolate from `x` to `y` over `n` steps at step `i`, with the distance left to `y` halving every `half_life` steps.
+/// The weight moves fast early and flattens later, and still reaches `y` at step `n`.
+pub fn exponential_interpolation(
+    x: u128,
+    y: u128,
+    i: u128,
+    n: u128,
+    half_life: u128,
+) -> Result<u128, SafeMathError> {
+    // -----------------------------------------------------------------------
+    //
+    //                  -i/h     -n/h
+    //              ⎛  2     -  2     ⎞
+    // x ± |x - y| ⋅ ⎜1 - ───────────── ⎟
+    //              ⎜          -n/h    ⎟
+    //              ⎝     1 - 2        ⎠
+    // -----------------------------------------------------------------------
+    let decay = |step: u128| -> Result<u128, SafeMathError> {
+        let exponent = i128::try_from(mul_div(step, LN_2_WAD, half_life)?)
+            .map_err(|_| SafeMathError::ConversionOverflow)?;
+        u128::try_from(exp_wad(-exponent)?).map_err(|_| SafeMathError::ConversionOverflow)
+    };
+    let decay_end = decay(n)?;
+    // Too slow a decay to move within the sale, fall back to the linear schedule
+    if decay_end >= WAD {
+        return linear_interpolation(x, y, i, n);
+    }
+    let moved = mul_div(
+        x.abs_diff(y),
+        WAD - decay(min(i, n))?.min(WAD),
+        WAD - decay_end,
+    )?
+    .min(x.abs_diff(y));
+
+    if x > y {
+        Ok(x - moved)
+    } else {
+        Ok(x + moved)
+    }
+}
+
 /// Calculate the amount of output asset received by providing a specific amount of input asset to the pool.
 /// * `amount_in` - The amount of input asset provided.
 /// * `reserve_in` - The reserve of the input asset in the pool.
@@ -28,12 +72,12 @@ pub fn linear_interpolation(x: u64, y: u64, i: u64, n: u64) -> Result<u64, SafeM
 /// * `weight_in` - The weight of the input asset in the pool.
 /// * `weight_out` - The weight of the output asset in the pool.
 pub fn get_amount_out(
-    amount_in: u64,
-    reserve_in: u64,
-    reserve_out: u64,
-    weight_in: u64,
-    weight_out: u64,
-) -> Result<u64, SafeMathError> {
+    amount_in: u128,
+    reserve_in: u128,
+    reserve_out: u128,
+    weight_in: u128,
+    weight_out: u128,
+) -> Result<u128, SafeMathError> {
     // -----------------------------------------------------------------------
     //
     //             ⎛                          ⎛weightIn ⎞⎞
@@ -47,8 +91,8 @@ pub fn get_amount_out(
     if amount_in
         > mul_div(
             reserve_in,
-            MAX_PERCENTAGE_IN as u64,
-            MAX_FEE_BASIS_POINTS as u64,
+            MAX_PERCENTAGE_IN as u128,
+            MAX_FEE_BASIS_POINTS as u128,
         )?
     {
         Err(SafeMathError::AmountInTooLarge)
@@ -70,12 +114,12 @@ pub fn get_amount_out(
 /// * `weight_in` - The weight of the input asset in the pool.
 /// * `weight_out` - The weight of the output asset in the pool.
 pub fn get_amount_in(
-    amount_out: u64,
-    reserve_in: u64,
-    reserve_out: u64,
-    weight_in: u64,
-    weight_out: u64,
-) -> Result<u64, SafeMathError> {
+    amount_out: u128,
+    reserve_in: u128,
+    reserve_out: u128,
+    weight_in: u128,
+    weight_out: u128,
+) -> Result<u128, SafeMathError> {
     // -----------------------------------------------------------------------
     //
     //             ⎛                          ⎛weightIn ⎞⎞
@@ -90,8 +134,8 @@ pub fn get_amount_in(
     if amount_out
         > mul_div(
             reserve_out,
-            MAX_PERCENTAGE_OUT as u64,
-            MAX_FEE_BASIS_POINTS as u64,
+            MAX_PERCENTAGE_OUT as u128,
+            MAX_FEE_BASIS_POINTS as u128,
         )?
     {
         Err(SafeMathError::AmountOutTooLarge)
@@ -107,12 +151,12 @@ pub fn get_amount_in(
 }
 
 fn _get_amount_out(
-    amount_in: u64,
-    reserve_in: u64,
-    reserve_out: u64,
-    weight_in: u64,
-    weight_out: u64,
-) -> Result<u64, SafeMathError> {
+    amount_in: u128,
+    reserve_in: u128,
+    reserve_out: u128,
+    weight_in: u128,
+    weight_out: u128,
+) -> Result<u128, SafeMathError> {
     mul_wad(
         reserve_out,
         safe_sub(
@@ -126,12 +170,12 @@ fn _get_amount_out(
 }
 
 fn _get_amount_in(
-    amount_out: u64,
-    reserve_in: u64,
-    reserve_out: u64,
-    weight_in: u64,
-    weight_out: u64,
-) -> Result<u64, SafeMathError> {
+    amount_out: u128,
+    reserve_in: u128,
+    reserve_out: u128,
+    weight_in: u128,
+    weight_out: u128,
+) -> Result<u128, SafeMathError> {
     mul_wad_up(
         reserve_in,
         safe_sub(
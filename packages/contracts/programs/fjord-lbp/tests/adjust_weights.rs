@@ -0,0 +1,108 @@
+//! Adjustments of the weight schedule by the creator or its weight manager during the sale.
+
+mod harness;
+
+use fjord_lbp::{Buy, PoolError, WeightsAdjusted};
+use fjord_lbp_sdk::PoolKeys;
+use harness::*;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::TransactionError;
+
+async fn adjust_weights(
+    ctx: &mut TestContext,
+    keys: &PoolKeys,
+    authority: &Keypair,
+    end_weight_basis_points: Option<u16>,
+    paused: Option<bool>,
+) -> Result<Vec<String>, TransactionError> {
+    ctx.process(
+        &[fjord_lbp_sdk::adjust_weights(
+            keys,
+            &authority.pubkey(),
+            end_weight_basis_points,
+            paused,
+        )],
+        &[authority],
+    )
+    .await
+}
+
+#[tokio::test]
+async fn weights_can_be_paused_and_retargeted_during_the_sale() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool(&mut ctx).await;
+    let keys = &sale.keys;
+    let bob = ctx.fund_wallet(Keypair::new()).await;
+    ctx.process(
+        &[fjord_lbp_sdk::set_weight_manager(keys, bob.pubkey())],
+        &[&sale.creator],
+    )
+    .await
+    .unwrap();
+
+    // Only during the sale
+    let result = adjust_weights(&mut ctx, keys, &bob, None, Some(true)).await;
+    assert_error(result, PoolError::WeightAdjustmentDisallowed.into());
+
+    // By the creator or the weight manager only
+    let midpoint = sale.sale_start_time + ONE_DAY;
+    ctx.warp_to(midpoint).await;
+    let carol = ctx.fund_wallet(Keypair::new()).await;
+    let result = adjust_weights(&mut ctx, keys, &carol, None, Some(true)).await;
+    assert_error(result, PoolError::UnauthorizedWeightAdjustment.into());
+
+    // The weight manager holds the weight where it is, 7000 basis points halfway through
+    let logs = adjust_weights(&mut ctx, keys, &bob, None, Some(true))
+        .await
+        .unwrap();
+    let adjusted = &events::<WeightsAdjusted>(&logs)[0];
+    assert_eq!(adjusted.authority, bob.pubkey());
+    assert_eq!(adjusted.weight_basis_points, 7000);
+    assert_eq!(adjusted.end_weight_basis_points, 5000);
+    assert!(adjusted.paused);
+    let resumed_at = midpoint + ONE_DAY / 2;
+    let paused = snapshot(&mut ctx, keys).await;
+    assert_eq!(
+        paused
+            .reserves_and_weights(resumed_at)
+            .unwrap()
+            .asset_weight,
+        7000
+    );
+
+    let result = adjust_weights(&mut ctx, keys, &sale.creator, Some(9950), None).await;
+    assert_error(result, PoolError::InvalidWeightConfig.into());
+
+    // The creator resumes towards a lower end weight, from 7000 basis points over the rest of the sale
+    ctx.warp_to(resumed_at).await;
+    let logs = adjust_weights(&mut ctx, keys, &sale.creator, Some(3000), Some(false))
+        .await
+        .unwrap();
+    let adjusted = &events::<WeightsAdjusted>(&logs)[0];
+    assert_eq!(adjusted.weight_basis_points, 7000);
+    assert!(!adjusted.paused);
+    let snapshot = snapshot(&mut ctx, keys).await;
+    let weight_at = |current_time| {
+        snapshot
+            .reserves_and_weights(current_time)
+            .unwrap()
+            .asset_weight
+    };
+    assert_eq!(weight_at(resumed_at), 7000);
+    assert_eq!(weight_at(resumed_at + ONE_DAY / 4), 5000);
+    assert_eq!(weight_at(sale.sale_end_time), 3000);
+
+    // Swaps are priced on the adjusted schedule
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    let current_time = resumed_at + ONE_DAY / 4;
+    ctx.warp_to(current_time).await;
+    let expected_shares = snapshot
+        .preview_shares_out(100_000_000, current_time)
+        .unwrap();
+    let logs = buy(&mut ctx, keys, &alice, 100_000_000).await.unwrap();
+    assert_eq!(events::<Buy>(&logs)[0].shares, expected_shares);
+
+    ctx.warp_to(sale.sale_end_time).await;
+    let result = adjust_weights(&mut ctx, keys, &sale.creator, None, Some(true)).await;
+    assert_error(result, PoolError::WeightAdjustmentDisallowed.into());
+}
@@ -0,0 +1,131 @@
+//! Cancellation of a pool by its creator, returning the deposits and the rent of the pool and its token accounts.
+
+mod harness;
+
+use fjord_lbp::{Cancel, LiquidityBootstrappingPool, PoolError};
+use fjord_lbp_sdk::PoolKeys;
+use harness::*;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::TransactionError;
+
+async fn cancel_pool(
+    ctx: &mut TestContext,
+    keys: &PoolKeys,
+    creator: &Keypair,
+) -> Result<Vec<String>, TransactionError> {
+    ctx.process(&[fjord_lbp_sdk::cancel_pool(keys)], &[creator])
+        .await
+}
+
+#[tokio::test]
+async fn creator_can_cancel_before_the_sale() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool(&mut ctx).await;
+    let keys = &sale.keys;
+    let creator = sale.creator.pubkey();
+    let rent = ctx.lamports(keys.pool).await
+        + ctx.lamports(keys.pool_asset_token_account()).await
+        + ctx.lamports(keys.pool_share_token_account()).await;
+    let creator_lamports = ctx.lamports(creator).await;
+
+    // Only the creator can cancel its pool
+    let carol = ctx.fund_wallet(Keypair::new()).await;
+    let result = cancel_pool(
+        &mut ctx,
+        &PoolKeys {
+            creator: carol.pubkey(),
+            ..keys.clone()
+        },
+        &carol,
+    )
+    .await;
+    assert_error(
+        result,
+        anchor_lang::error::ErrorCode::ConstraintSeeds.into(),
+    );
+
+    let logs = cancel_pool(&mut ctx, keys, &sale.creator).await.unwrap();
+    let cancel = &events::<Cancel>(&logs)[0];
+    assert_eq!(cancel.pool, keys.pool);
+    assert_eq!(cancel.assets, POOL_ASSETS);
+    assert_eq!(cancel.shares, POOL_SHARES);
+
+    assert_eq!(
+        ctx.token_balance(keys.asset_token_account(&creator)).await,
+        POOL_ASSETS
+    );
+    assert_eq!(
+        ctx.token_balance(keys.share_token_account(&creator)).await,
+        POOL_SHARES
+    );
+    assert!(ctx
+        .get_account(keys.pool_asset_token_account())
+        .await
+        .is_none());
+    assert!(ctx
+        .get_account(keys.pool_share_token_account())
+        .await
+        .is_none());
+    assert!(ctx.get_account(keys.pool).await.is_none());
+    assert_eq!(ctx.lamports(creator).await, creator_lamports + rent);
+}
+
+#[tokio::test]
+async fn pools_with_purchases_cannot_be_cancelled() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool(&mut ctx).await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    ctx.warp_to(sale.sale_start_time).await;
+    buy(&mut ctx, keys, &alice, 100_000_000).await.unwrap();
+
+    let result = cancel_pool(&mut ctx, keys, &sale.creator).await;
+    assert_error(result, PoolError::CancellingDisallowed.into());
+}
+
+#[tokio::test]
+async fn pools_whose_purchases_were_sold_back_cannot_be_cancelled() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool(&mut ctx).await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    ctx.warp_to(sale.sale_start_time).await;
+    buy(&mut ctx, keys, &alice, 10_000_000).await.unwrap();
+
+    // Selling everything back leaves no purchased shares, but the swap fees are owed to the treasury
+    let shares = user_state(&mut ctx, keys, &alice).await.purchased_shares;
+    ctx.process(
+        &[fjord_lbp_sdk::swap_exact_shares_for_assets(
+            keys,
+            &alice.pubkey(),
+            shares,
+            1,
+            None,
+            None,
+            false,
+        )],
+        &[&alice],
+    )
+    .await
+    .unwrap();
+    let pool = ctx.account::<LiquidityBootstrappingPool>(keys.pool).await;
+    assert_eq!(pool.total_purchased, 0);
+
+    let result = cancel_pool(&mut ctx, keys, &sale.creator).await;
+    assert_error(result, PoolError::CancellingDisallowed.into());
+}
+
+#[tokio::test]
+async fn started_sales_without_purchases_can_be_cancelled() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool(&mut ctx).await;
+    let keys = &sale.keys;
+    ctx.warp_to(sale.sale_start_time + ONE_DAY).await;
+
+    cancel_pool(&mut ctx, keys, &sale.creator).await.unwrap();
+    assert_eq!(
+        ctx.token_balance(keys.share_token_account(&sale.creator.pubkey()))
+            .await,
+        POOL_SHARES
+    );
+}
@@ -0,0 +1,178 @@
+//! Timelocked changes of the global fees and treasury fee recipients, bounded by ceilings that can only be lowered.
+
+mod harness;
+
+use fjord_lbp::{
+    FeeCeilingsLowered, FeeRecipientsExecuted, FeeRecipientsProposed, FeesExecuted, FeesProposed,
+    OwnerConfig, PoolError, Treasury, MIN_FEE_CHANGE_DELAY,
+};
+use fjord_lbp_sdk::{find_owner_config_address, find_treasury_address};
+use harness::*;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+use solana_sdk::transaction::TransactionError;
+
+async fn as_owner(
+    ctx: &mut TestContext,
+    instruction: Instruction,
+) -> Result<Vec<String>, TransactionError> {
+    let owner = ctx.owner.insecure_clone();
+    ctx.process(&[instruction], &[&owner]).await
+}
+
+#[tokio::test]
+async fn fee_changes_are_executed_after_the_delay() {
+    let mut ctx = TestContext::new().await;
+    let owner = ctx.owner.pubkey();
+    let now = ctx.now().await;
+
+    let result = as_owner(&mut ctx, fjord_lbp_sdk::execute_fees(&owner)).await;
+    assert_error(result, PoolError::FeeChangeNotProposed.into());
+
+    let result = as_owner(
+        &mut ctx,
+        fjord_lbp_sdk::propose_fees(&owner, None, None, None, Some(0)),
+    )
+    .await;
+    assert_error(result, PoolError::InvalidFeeChangeDelay.into());
+
+    let logs = as_owner(
+        &mut ctx,
+        fjord_lbp_sdk::propose_fees(&owner, Some(200), None, Some(300), Some(2 * ONE_DAY)),
+    )
+    .await
+    .unwrap();
+    let proposed = &events::<FeesProposed>(&logs)[0];
+    assert_eq!(proposed.platform_fee, 200);
+    assert_eq!(proposed.referral_fee, REFERRAL_FEE);
+    assert_eq!(proposed.swap_fee, 300);
+    assert_eq!(proposed.executable_at, now + MIN_FEE_CHANGE_DELAY);
+
+    ctx.warp_to(proposed.executable_at - 1).await;
+    let result = as_owner(&mut ctx, fjord_lbp_sdk::execute_fees(&owner)).await;
+    assert_error(result, PoolError::FeeChangeNotReady.into());
+
+    ctx.warp_to(proposed.executable_at).await;
+    let logs = as_owner(&mut ctx, fjord_lbp_sdk::execute_fees(&owner))
+        .await
+        .unwrap();
+    let executed = &events::<FeesExecuted>(&logs)[0];
+    assert_eq!(executed.swap_fee, 300);
+    let config = ctx
+        .account::<OwnerConfig>(find_owner_config_address().0)
+        .await;
+    assert_eq!(config.platform_fee, 200);
+    assert_eq!(config.swap_fee, 300);
+    assert_eq!(config.fee_change_delay, 2 * ONE_DAY);
+    assert!(config.pending_fees.is_none());
+
+    // Later proposals wait for the new delay
+    let logs = as_owner(
+        &mut ctx,
+        fjord_lbp_sdk::propose_fees(&owner, None, None, Some(100), None),
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        events::<FeesProposed>(&logs)[0].executable_at,
+        proposed.executable_at + 2 * ONE_DAY
+    );
+}
+
+#[tokio::test]
+async fn fee_ceilings_can_only_be_lowered() {
+    let mut ctx = TestContext::new().await;
+    let owner = ctx.owner.pubkey();
+    as_owner(
+        &mut ctx,
+        fjord_lbp_sdk::propose_fees(&owner, None, None, Some(500), None),
+    )
+    .await
+    .unwrap();
+
+    // Lowering a ceiling lowers the fee above it right away
+    let logs = as_owner(
+        &mut ctx,
+        fjord_lbp_sdk::lower_fee_ceilings(&owner, None, None, Some(50)),
+    )
+    .await
+    .unwrap();
+    let lowered = &events::<FeeCeilingsLowered>(&logs)[0];
+    assert_eq!(lowered.max_platform_fee, 10_000);
+    assert_eq!(lowered.max_swap_fee, 50);
+    let config = ctx
+        .account::<OwnerConfig>(find_owner_config_address().0)
+        .await;
+    assert_eq!(config.swap_fee, 50);
+    assert_eq!(config.platform_fee, PLATFORM_FEE);
+
+    let result = as_owner(
+        &mut ctx,
+        fjord_lbp_sdk::lower_fee_ceilings(&owner, None, None, Some(60)),
+    )
+    .await;
+    assert_error(result, PoolError::FeeCeilingRaised.into());
+    let result = as_owner(
+        &mut ctx,
+        fjord_lbp_sdk::propose_fees(&owner, None, None, Some(60), None),
+    )
+    .await;
+    assert_error(result, PoolError::FeeCeilingExceeded.into());
+
+    // The change proposed before the ceiling was lowered no longer fits under it
+    let now = ctx.now().await;
+    ctx.warp_to(now + MIN_FEE_CHANGE_DELAY).await;
+    let result = as_owner(&mut ctx, fjord_lbp_sdk::execute_fees(&owner)).await;
+    assert_error(result, PoolError::FeeCeilingExceeded.into());
+}
+
+#[tokio::test]
+async fn treasury_fee_recipient_changes_are_executed_after_the_delay() {
+    let mut ctx = TestContext::new().await;
+    // The owner pays for the resizing of the treasury
+    let owner = ctx.fund_wallet(ctx.owner.insecure_clone()).await.pubkey();
+    let swap_fee_recipient = Pubkey::new_unique();
+    let fee_recipients: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+
+    let logs = as_owner(
+        &mut ctx,
+        fjord_lbp_sdk::propose_treasury_fee_recipients(
+            &owner,
+            Some(swap_fee_recipient),
+            fee_recipients.clone(),
+            vec![5_000, 3_000, 2_000],
+        ),
+    )
+    .await
+    .unwrap();
+    let proposed = &events::<FeeRecipientsProposed>(&logs)[0];
+    assert_eq!(proposed.swap_fee_recipient, swap_fee_recipient);
+    assert_eq!(proposed.fee_recipients.len(), 3);
+    // The current recipients keep being paid meanwhile
+    let treasury = ctx.account::<Treasury>(find_treasury_address().0).await;
+    assert_eq!(treasury.swap_fee_recipient, ctx.treasury.swap_fee_recipient);
+
+    let result = as_owner(
+        &mut ctx,
+        fjord_lbp_sdk::execute_treasury_fee_recipients(&owner),
+    )
+    .await;
+    assert_error(result, PoolError::FeeChangeNotReady.into());
+
+    ctx.warp_to(proposed.executable_at).await;
+    let logs = as_owner(
+        &mut ctx,
+        fjord_lbp_sdk::execute_treasury_fee_recipients(&owner),
+    )
+    .await
+    .unwrap();
+    let executed = &events::<FeeRecipientsExecuted>(&logs)[0];
+    assert_eq!(executed.swap_fee_recipient, swap_fee_recipient);
+    let treasury = ctx.account::<Treasury>(find_treasury_address().0).await;
+    assert_eq!(treasury.swap_fee_recipient, swap_fee_recipient);
+    assert_eq!(treasury.fee_recipients[2].percentage, 2_000);
+    assert!(treasury.pending_fee_recipients.is_none());
+    let account = ctx.get_account(find_treasury_address().0).await.unwrap();
+    assert_eq!(account.data.len(), 8 + Treasury::space(3, None));
+}
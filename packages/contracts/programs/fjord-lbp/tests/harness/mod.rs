@@ -0,0 +1,499 @@
+//! In-process test harness running the program natively on `solana-program-test`, without a validator.
+//!
+//! The owner configuration and treasury are seeded as accounts (like the bankrun tests do) since
+//! `initialize_owner_config` needs the program to be deployed by the upgradeable loader.
+
+#![allow(dead_code)]
+
+use std::sync::Once;
+
+use anchor_lang::prelude::{AccountInfo, Clock, Pubkey};
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+use anchor_lang::solana_program::{program_pack::Pack, system_instruction};
+use anchor_lang::{AccountDeserialize, AccountSerialize, Event};
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::instruction::initialize_transfer_fee_config;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::MAX_FEE_BASIS_POINTS;
+use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
+use anchor_spl::token_interface::TokenAccount;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use fjord_lbp::{FeeMapping, OwnerConfig, Treasury, MIN_FEE_CHANGE_DELAY};
+use fjord_lbp_sdk::{
+    find_owner_config_address, find_treasury_address, get_token_account_address, PoolKeys,
+};
+use solana_program_test::{processor, ProgramTest, ProgramTestContext};
+use solana_sdk::account::Account;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+mod pool;
+
+pub use anchor_spl::token::spl_token::native_mint::ID as NATIVE_MINT;
+pub use anchor_spl::token::ID as TOKEN_PROGRAM_ID;
+pub use anchor_spl::token_2022::ID as TOKEN_2022_PROGRAM_ID;
+pub use pool::*;
+
+// Fees of the seeded owner configuration, in basis points (same as the bankrun tests)
+pub const PLATFORM_FEE: u16 = 100;
+pub const REFERRAL_FEE: u16 = 100;
+pub const SWAP_FEE: u16 = 100;
+
+// Lamports given to every funded wallet
+const WALLET_LAMPORTS: u64 = 10_000_000_000;
+
+/// The native processor hands over accounts that only live for the call, while the Anchor entrypoint
+/// expects them to outlive the account infos. Leaking them is fine for tests.
+fn process_instruction<'a>(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo<'a>],
+    data: &[u8],
+) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    fjord_lbp::entry(program_id, accounts, data)
+}
+
+/// Syscall stubs of `solana-program-test`, forwarding `sol_log_data` to the transaction logs.
+/// The native stubs print the data to stdout instead, which would lose the events emitted by the program.
+struct EventLogStubs {
+    inner: Box<dyn SyscallStubs>,
+}
+
+impl SyscallStubs for EventLogStubs {
+    fn sol_log(&self, message: &str) {
+        self.inner.sol_log(message)
+    }
+    fn sol_log_compute_units(&self) {
+        self.inner.sol_log_compute_units()
+    }
+    fn sol_remaining_compute_units(&self) -> u64 {
+        self.inner.sol_remaining_compute_units()
+    }
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        self.inner
+            .sol_invoke_signed(instruction, account_infos, signers_seeds)
+    }
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_clock_sysvar(var_addr)
+    }
+    fn sol_get_epoch_schedule_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_epoch_schedule_sysvar(var_addr)
+    }
+    fn sol_get_fees_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_fees_sysvar(var_addr)
+    }
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_rent_sysvar(var_addr)
+    }
+    fn sol_get_epoch_rewards_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_epoch_rewards_sysvar(var_addr)
+    }
+    fn sol_get_last_restart_slot(&self, var_addr: *mut u8) -> u64 {
+        self.inner.sol_get_last_restart_slot(var_addr)
+    }
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        self.inner.sol_get_return_data()
+    }
+    fn sol_set_return_data(&self, data: &[u8]) {
+        self.inner.sol_set_return_data(data)
+    }
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        let fields: Vec<String> = fields.iter().map(|field| STANDARD.encode(field)).collect();
+        self.inner
+            .sol_log(&format!("Program data: {}", fields.join(" ")));
+    }
+    fn sol_get_processed_sibling_instruction(&self, index: usize) -> Option<Instruction> {
+        self.inner.sol_get_processed_sibling_instruction(index)
+    }
+    fn sol_get_stack_height(&self) -> u64 {
+        self.inner.sol_get_stack_height()
+    }
+}
+
+// Placeholder while swapping the stubs
+struct DefaultStubs;
+
+impl SyscallStubs for DefaultStubs {}
+
+/// Program test context with the owner configuration and treasury in place.
+pub struct TestContext {
+    pub context: ProgramTestContext,
+    pub owner: Keypair,
+    pub treasury: Treasury,
+}
+
+impl TestContext {
+    pub async fn new() -> Self {
+        let owner = Keypair::new();
+        let treasury = Treasury {
+            swap_fee_recipient: Pubkey::new_unique(),
+            fee_recipients: vec![
+                FeeMapping {
+                    user: Pubkey::new_unique(),
+                    percentage: 5000,
+                },
+                FeeMapping {
+                    user: Pubkey::new_unique(),
+                    percentage: 5000,
+                },
+            ],
+            pending_fee_recipients: None,
+        };
+
+        let mut program_test =
+            ProgramTest::new("fjord_lbp", fjord_lbp::ID, processor!(process_instruction));
+        let (owner_config_address, owner_config_bump) = find_owner_config_address();
+        program_test.add_account(
+            owner_config_address,
+            program_account(
+                &OwnerConfig {
+                    owner: owner.pubkey(),
+                    pending_owner: None,
+                    treasury: find_treasury_address().0,
+                    platform_fee: PLATFORM_FEE,
+                    referral_fee: REFERRAL_FEE,
+                    swap_fee: SWAP_FEE,
+                    max_platform_fee: MAX_FEE_BASIS_POINTS,
+                    max_referral_fee: MAX_FEE_BASIS_POINTS,
+                    max_swap_fee: MAX_FEE_BASIS_POINTS,
+                    fee_change_delay: MIN_FEE_CHANGE_DELAY,
+                    pending_fees: None,
+                    referrer_registry_required: false,
+                    bump: owner_config_bump,
+                },
+                8 + OwnerConfig::LEN,
+            ),
+        );
+        program_test.add_account(
+            find_treasury_address().0,
+            program_account(
+                &treasury,
+                8 + Treasury::space(treasury.fee_recipients.len(), None),
+            ),
+        );
+
+        let context = program_test.start_with_context().await;
+
+        // The stubs are installed when the first bank is set up, wrap them once they exist
+        static WRAP_STUBS: Once = Once::new();
+        WRAP_STUBS.call_once(|| {
+            let inner = set_syscall_stubs(Box::new(DefaultStubs));
+            set_syscall_stubs(Box::new(EventLogStubs { inner }));
+        });
+
+        Self {
+            context,
+            owner,
+            treasury,
+        }
+    }
+
+    pub fn payer(&self) -> Pubkey {
+        self.context.payer.pubkey()
+    }
+
+    /// Sign with the payer and `signers`, send the transaction and return its logs.
+    pub async fn process(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<Vec<String>, TransactionError> {
+        let blockhash = self
+            .context
+            .banks_client
+            .get_latest_blockhash()
+            .await
+            .unwrap();
+        let mut all_signers = vec![&self.context.payer];
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&self.context.payer.pubkey()),
+            &all_signers,
+            blockhash,
+        );
+
+        let result = self
+            .context
+            .banks_client
+            .process_transaction_with_metadata(transaction)
+            .await
+            .unwrap();
+        result.result?;
+        Ok(result.metadata.map(|m| m.log_messages).unwrap_or_default())
+    }
+
+    /// Fetch and deserialize an account owned by the program (or a token account).
+    pub async fn account<T: AccountDeserialize>(&mut self, address: Pubkey) -> T {
+        let account = self.get_account(address).await.unwrap();
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub async fn get_account(&mut self, address: Pubkey) -> Option<Account> {
+        self.context
+            .banks_client
+            .get_account(address)
+            .await
+            .unwrap()
+    }
+
+    /// Overwrite the account at `address`, e.g. with an account laid out by an earlier release of the program.
+    pub fn set_account(&mut self, address: Pubkey, account: Account) {
+        self.context.set_account(&address, &account.into());
+    }
+
+    pub async fn lamports(&mut self, address: Pubkey) -> u64 {
+        self.get_account(address)
+            .await
+            .map_or(0, |account| account.lamports)
+    }
+
+    /// Balance of a token account, 0 if it does not exist yet.
+    pub async fn token_balance(&mut self, address: Pubkey) -> u64 {
+        match self.get_account(address).await {
+            Some(account) => {
+                TokenAccount::try_deserialize(&mut account.data.as_slice())
+                    .unwrap()
+                    .amount
+            }
+            None => 0,
+        }
+    }
+
+    // Clock ----------------------------------------------------------
+
+    pub async fn clock(&mut self) -> Clock {
+        self.context.banks_client.get_sysvar().await.unwrap()
+    }
+
+    pub async fn now(&mut self) -> i64 {
+        self.clock().await.unix_timestamp
+    }
+
+    /// Move the clock to `unix_timestamp`, on a new slot so that repeated transactions get a new blockhash.
+    pub async fn warp_to(&mut self, unix_timestamp: i64) {
+        let slot = self.clock().await.slot;
+        self.context.warp_to_slot(slot + 1).unwrap();
+        let mut clock = self.clock().await;
+        clock.unix_timestamp = unix_timestamp;
+        self.context.set_sysvar(&clock);
+    }
+
+    // Tokens ---------------------------------------------------------
+
+    /// Create a mint owned by `token_program` (SPL Token or Token-2022) with the payer as authority.
+    pub async fn create_mint(&mut self, decimals: u8, token_program: &Pubkey) -> Pubkey {
+        let mint = Keypair::new();
+        let payer = self.payer();
+        let rent = self.context.banks_client.get_rent().await.unwrap();
+        let space = spl_token_2022::state::Mint::LEN;
+        self.process(
+            &[
+                system_instruction::create_account(
+                    &payer,
+                    &mint.pubkey(),
+                    rent.minimum_balance(space),
+                    space as u64,
+                    token_program,
+                ),
+                spl_token_2022::instruction::initialize_mint2(
+                    token_program,
+                    &mint.pubkey(),
+                    &payer,
+                    None,
+                    decimals,
+                )
+                .unwrap(),
+            ],
+            &[&mint],
+        )
+        .await
+        .unwrap();
+        mint.pubkey()
+    }
+
+    /// Create a Token-2022 mint withholding `basis_points` of every transfer, with the payer as authority.
+    pub async fn create_transfer_fee_mint(&mut self, decimals: u8, basis_points: u16) -> Pubkey {
+        let mint = Keypair::new();
+        let payer = self.payer();
+        let rent = self.context.banks_client.get_rent().await.unwrap();
+        let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+            ExtensionType::TransferFeeConfig,
+        ])
+        .unwrap();
+        self.process(
+            &[
+                system_instruction::create_account(
+                    &payer,
+                    &mint.pubkey(),
+                    rent.minimum_balance(space),
+                    space as u64,
+                    &TOKEN_2022_PROGRAM_ID,
+                ),
+                initialize_transfer_fee_config(
+                    &TOKEN_2022_PROGRAM_ID,
+                    &mint.pubkey(),
+                    Some(&payer),
+                    Some(&payer),
+                    basis_points,
+                    u64::MAX,
+                )
+                .unwrap(),
+                spl_token_2022::instruction::initialize_mint2(
+                    &TOKEN_2022_PROGRAM_ID,
+                    &mint.pubkey(),
+                    &payer,
+                    None,
+                    decimals,
+                )
+                .unwrap(),
+            ],
+            &[&mint],
+        )
+        .await
+        .unwrap();
+        mint.pubkey()
+    }
+
+    /// Create the associated token account of `owner` for `mint` if it does not exist yet.
+    pub async fn create_token_account(
+        &mut self,
+        owner: &Pubkey,
+        mint: &Pubkey,
+        token_program: &Pubkey,
+    ) -> Pubkey {
+        let payer = self.payer();
+        self.process(
+            &[
+                spl_associated_token_account::instruction::create_associated_token_account_idempotent(
+                    &payer,
+                    owner,
+                    mint,
+                    token_program,
+                ),
+            ],
+            &[],
+        )
+        .await
+        .unwrap();
+        get_token_account_address(owner, mint, token_program)
+    }
+
+    /// Mint `amount` tokens to the associated token account of `owner`, creating it if needed.
+    /// Wrapped SOL is minted by wrapping lamports of the payer.
+    pub async fn mint_to(
+        &mut self,
+        mint: &Pubkey,
+        token_program: &Pubkey,
+        owner: &Pubkey,
+        amount: u64,
+    ) -> Pubkey {
+        let token_account = self.create_token_account(owner, mint, token_program).await;
+        let payer = self.payer();
+        let instructions = if *mint == NATIVE_MINT {
+            vec![
+                system_instruction::transfer(&payer, &token_account, amount),
+                spl_token_2022::instruction::sync_native(token_program, &token_account).unwrap(),
+            ]
+        } else {
+            vec![spl_token_2022::instruction::mint_to(
+                token_program,
+                mint,
+                &token_account,
+                &payer,
+                &[],
+                amount,
+            )
+            .unwrap()]
+        };
+        self.process(&instructions, &[]).await.unwrap();
+        token_account
+    }
+
+    /// Give `wallet` lamports for the transaction fees and rent.
+    pub async fn fund_wallet(&mut self, wallet: Keypair) -> Keypair {
+        let payer = self.payer();
+        self.process(
+            &[system_instruction::transfer(
+                &payer,
+                &wallet.pubkey(),
+                WALLET_LAMPORTS,
+            )],
+            &[],
+        )
+        .await
+        .unwrap();
+        wallet
+    }
+
+    /// Create a funded wallet holding `assets` of the pool's asset token.
+    pub async fn fund_user(&mut self, keys: &PoolKeys, assets: u64) -> Keypair {
+        self.fund_user_with(keys, Keypair::new(), assets).await
+    }
+
+    /// Fund a wallet known in advance (e.g. whitelisted at the pool creation) with `assets` of the pool's asset token.
+    pub async fn fund_user_with(&mut self, keys: &PoolKeys, user: Keypair, assets: u64) -> Keypair {
+        let user = self.fund_wallet(user).await;
+        self.mint_to(
+            &keys.asset_token_mint,
+            &keys.asset_token_program,
+            &user.pubkey(),
+            assets,
+        )
+        .await;
+        user
+    }
+
+    /// Create the asset token accounts of the platform fee recipients, passed to `close_pool` as is.
+    pub async fn create_fee_recipient_accounts(&mut self, keys: &PoolKeys) {
+        for recipient in self.treasury.fee_recipients.clone() {
+            self.create_token_account(
+                &recipient.user,
+                &keys.asset_token_mint,
+                &keys.asset_token_program,
+            )
+            .await;
+        }
+    }
+}
+
+/// Serialize an Anchor account (discriminator included) into a rent exempt account owned by the program.
+fn program_account<T: AccountSerialize>(data: &T, space: usize) -> Account {
+    let mut buffer = Vec::with_capacity(space);
+    data.try_serialize(&mut buffer).unwrap();
+    buffer.resize(space, 0);
+    Account {
+        lamports: solana_sdk::rent::Rent::default().minimum_balance(space),
+        data: buffer,
+        owner: fjord_lbp::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+/// Decode the events of type `T` emitted in `logs`.
+pub fn events<T: Event>(logs: &[String]) -> Vec<T> {
+    logs.iter()
+        .filter_map(|log| log.split("Program data: ").nth(1))
+        .filter_map(|data| STANDARD.decode(data).ok())
+        .filter(|data| data.starts_with(&T::discriminator()))
+        .map(|data| T::try_from_slice(&data[8..]).unwrap())
+        .collect()
+}
+
+/// Assert that a transaction failed with the custom error `code`.
+pub fn assert_error(result: Result<Vec<String>, TransactionError>, code: u32) {
+    use anchor_lang::solana_program::instruction::InstructionError;
+    match result {
+        Err(TransactionError::InstructionError(_, InstructionError::Custom(actual))) => {
+            assert_eq!(actual, code, "unexpected error code")
+        }
+        other => panic!("expected custom error {code}, got {other:?}"),
+    }
+}
@@ -0,0 +1,218 @@
+//! Pool fixtures shared by the instruction tests.
+
+use fjord_lbp::{
+    instruction, LiquidityBootstrappingPool, PoolCreatedEvent, ReferralSchedule, UserStateInPool,
+    WeightCurve, WeightKnot, WhitelistLeafVersion, MAX_WEIGHT_KNOTS, MAX_WHITELIST_TIERS,
+};
+use fjord_lbp_sdk::{find_user_state_address, PoolKeys, PoolSnapshot};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::TransactionError;
+
+use super::{events, TestContext, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID};
+
+pub const ONE_DAY: i64 = 86_400;
+pub const ASSET_DECIMALS: u8 = 6;
+pub const SHARE_DECIMALS: u8 = 9;
+// 1,000 assets and 1,000,000 shares provided by the creator
+pub const POOL_ASSETS: u64 = 1_000_000_000;
+pub const POOL_SHARES: u64 = 1_000_000_000_000_000;
+// 10,000 assets given to each buyer
+pub const USER_ASSETS: u64 = 10_000_000_000;
+
+/// A created pool along with its creator and schedule.
+pub struct Sale {
+    pub keys: PoolKeys,
+    pub creator: Keypair,
+    pub sale_start_time: i64,
+    pub sale_end_time: i64,
+    pub vest_cliff: i64,
+    pub vest_end: i64,
+}
+
+/// Arguments of a pool whose sale starts in one day and lasts two days, vesting over two days after a one day cliff.
+pub fn pool_args(now: i64) -> instruction::InitializePool {
+    let sale_start_time = now + ONE_DAY;
+    let sale_end_time = sale_start_time + 2 * ONE_DAY;
+    let vest_cliff = sale_end_time + ONE_DAY;
+    instruction::InitializePool {
+        salt: "pool".to_string(),
+        assets: POOL_ASSETS,
+        shares: POOL_SHARES,
+        virtual_assets: 0,
+        virtual_shares: 0,
+        max_share_price: u64::MAX,
+        max_shares_out: POOL_SHARES,
+        max_assets_in: u64::MAX,
+        min_assets_raised: 0,
+        max_assets_raised: 0,
+        max_assets_per_user: 0,
+        max_shares_per_user: 0,
+        min_assets_per_buy: 0,
+        start_weight_basis_points: 9000,
+        end_weight_basis_points: 5000,
+        weight_curve: WeightCurve::Linear,
+        weight_knots: [WeightKnot::default(); MAX_WEIGHT_KNOTS],
+        sale_start_time,
+        sale_end_time,
+        vest_cliff,
+        vest_end: vest_cliff + 2 * ONE_DAY,
+        whitelist_merkle_root: [0; 32],
+        whitelist_leaf_version: WhitelistLeafVersion::Legacy,
+        whitelist_signer: Pubkey::default(),
+        whitelist_tier_start_times: [0; MAX_WHITELIST_TIERS],
+        public_sale_time: 0,
+        referral_schedule: ReferralSchedule::default(),
+        selling_allowed: true,
+    }
+}
+
+/// Create a pool selling Token-2022 shares for SPL Token assets with the default `pool_args`.
+pub async fn create_pool(ctx: &mut TestContext) -> Sale {
+    create_pool_with(ctx, |_| {}).await
+}
+
+/// Create a pool with the default `pool_args` changed by `configure`.
+pub async fn create_pool_with(
+    ctx: &mut TestContext,
+    configure: impl FnOnce(&mut instruction::InitializePool),
+) -> Sale {
+    let asset_token_mint = ctx.create_mint(ASSET_DECIMALS, &TOKEN_PROGRAM_ID).await;
+    create_pool_for(ctx, asset_token_mint, configure).await
+}
+
+/// Create a pool selling for `asset_token_mint`, an SPL Token mint such as `NATIVE_MINT`,
+/// with the default `pool_args` changed by `configure`.
+pub async fn create_pool_for(
+    ctx: &mut TestContext,
+    asset_token_mint: Pubkey,
+    configure: impl FnOnce(&mut instruction::InitializePool),
+) -> Sale {
+    let (sale, result) = try_create_pool_for(ctx, asset_token_mint, configure).await;
+    let logs = result.unwrap();
+    assert_eq!(events::<PoolCreatedEvent>(&logs)[0].pool, sale.keys.pool);
+    sale
+}
+
+/// Attempt to create a pool like `create_pool_for`, returning the outcome of the creation along with the sale.
+pub async fn try_create_pool_for(
+    ctx: &mut TestContext,
+    asset_token_mint: Pubkey,
+    configure: impl FnOnce(&mut instruction::InitializePool),
+) -> (Sale, Result<Vec<String>, TransactionError>) {
+    let share_token_mint = ctx
+        .create_mint(SHARE_DECIMALS, &TOKEN_2022_PROGRAM_ID)
+        .await;
+    try_create_pool_selling(ctx, asset_token_mint, share_token_mint, configure).await
+}
+
+/// Attempt to create a pool like `try_create_pool_for`, selling `share_token_mint`, a Token-2022 mint
+/// such as one with a transfer fee.
+pub async fn try_create_pool_selling(
+    ctx: &mut TestContext,
+    asset_token_mint: Pubkey,
+    share_token_mint: Pubkey,
+    configure: impl FnOnce(&mut instruction::InitializePool),
+) -> (Sale, Result<Vec<String>, TransactionError>) {
+    let mut args = pool_args(ctx.now().await);
+    configure(&mut args);
+
+    let creator = ctx.fund_wallet(Keypair::new()).await;
+    let keys = PoolKeys::new(
+        asset_token_mint,
+        share_token_mint,
+        creator.pubkey(),
+        &args.salt,
+        TOKEN_PROGRAM_ID,
+        TOKEN_2022_PROGRAM_ID,
+    );
+    ctx.mint_to(
+        &asset_token_mint,
+        &TOKEN_PROGRAM_ID,
+        &creator.pubkey(),
+        args.assets,
+    )
+    .await;
+    ctx.mint_to(
+        &share_token_mint,
+        &TOKEN_2022_PROGRAM_ID,
+        &creator.pubkey(),
+        args.shares,
+    )
+    .await;
+
+    let sale = Sale {
+        keys,
+        creator,
+        sale_start_time: args.sale_start_time,
+        sale_end_time: args.sale_end_time,
+        vest_cliff: args.vest_cliff,
+        vest_end: args.vest_end,
+    };
+    let result = ctx
+        .process(
+            &[fjord_lbp_sdk::initialize_pool(&sale.keys, args)],
+            &[&sale.creator],
+        )
+        .await;
+    (sale, result)
+}
+
+/// Snapshot of the pool to preview the swaps with the SDK.
+pub async fn snapshot(ctx: &mut TestContext, keys: &PoolKeys) -> PoolSnapshot {
+    PoolSnapshot {
+        pool: ctx.account::<LiquidityBootstrappingPool>(keys.pool).await,
+        assets: ctx.token_balance(keys.pool_asset_token_account()).await,
+        shares: ctx.token_balance(keys.pool_share_token_account()).await,
+        asset_token_decimals: ASSET_DECIMALS,
+        share_token_decimals: SHARE_DECIMALS,
+        asset_transfer_fee: None,
+    }
+}
+
+pub async fn user_state(ctx: &mut TestContext, keys: &PoolKeys, user: &Keypair) -> UserStateInPool {
+    ctx.account(find_user_state_address(&user.pubkey(), &keys.pool).0)
+        .await
+}
+
+/// Buy shares with exactly `assets_in` assets, without slippage protection.
+pub async fn buy(
+    ctx: &mut TestContext,
+    keys: &PoolKeys,
+    user: &Keypair,
+    assets_in: u64,
+) -> Result<Vec<String>, TransactionError> {
+    ctx.process(
+        &[fjord_lbp_sdk::swap_exact_assets_for_shares(
+            keys,
+            &user.pubkey(),
+            assets_in,
+            1,
+            None,
+            None,
+            false,
+        )],
+        &[user],
+    )
+    .await
+}
+
+/// Close the pool, paying the fee recipients of the treasury in tokens.
+pub async fn close_pool(
+    ctx: &mut TestContext,
+    keys: &PoolKeys,
+    user: &Keypair,
+) -> Result<Vec<String>, TransactionError> {
+    ctx.create_fee_recipient_accounts(keys).await;
+    let treasury = ctx.treasury.clone();
+    ctx.process(
+        &[fjord_lbp_sdk::close_pool(
+            keys,
+            &user.pubkey(),
+            &treasury,
+            false,
+        )],
+        &[user],
+    )
+    .await
+}
@@ -0,0 +1,320 @@
+//! Full lifecycle of a pool: creation, the four swaps during the sale, closing and vested redemptions.
+
+mod harness;
+
+use fjord_lbp::{Buy, Close, LiquidityBootstrappingPool, PoolError, Redeem, Sell};
+use fjord_lbp_sdk::find_user_state_address;
+use harness::*;
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn pool_lifecycle() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool(&mut ctx).await;
+    let keys = &sale.keys;
+    assert_eq!(
+        ctx.token_balance(keys.pool_asset_token_account()).await,
+        POOL_ASSETS
+    );
+    assert_eq!(
+        ctx.token_balance(keys.pool_share_token_account()).await,
+        POOL_SHARES
+    );
+
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    let bob = ctx.fund_user(keys, USER_ASSETS).await;
+    ctx.warp_to(sale.sale_start_time + ONE_DAY / 2).await;
+
+    // Buy with exactly 100 assets
+    let now = ctx.now().await;
+    let assets_in = 100_000_000;
+    let expected_shares = snapshot(&mut ctx, keys)
+        .await
+        .preview_shares_out(assets_in, now)
+        .unwrap();
+    let logs = ctx
+        .process(
+            &[fjord_lbp_sdk::swap_exact_assets_for_shares(
+                keys,
+                &alice.pubkey(),
+                assets_in,
+                expected_shares,
+                None,
+                None,
+                false,
+            )],
+            &[&alice],
+        )
+        .await
+        .unwrap();
+    let buy = &events::<Buy>(&logs)[0];
+    assert_eq!(buy.user, alice.pubkey());
+    assert_eq!(buy.assets, assets_in);
+    assert_eq!(buy.shares, expected_shares);
+    assert_eq!(
+        ctx.token_balance(keys.asset_token_account(&alice.pubkey()))
+            .await,
+        USER_ASSETS - assets_in
+    );
+    assert_eq!(
+        user_state(&mut ctx, keys, &alice).await.purchased_shares,
+        expected_shares
+    );
+
+    // Buy exactly 50,000 shares
+    let shares_out = 50_000_000_000_000;
+    let expected_assets = snapshot(&mut ctx, keys)
+        .await
+        .preview_assets_in(shares_out, now)
+        .unwrap();
+    let logs = ctx
+        .process(
+            &[fjord_lbp_sdk::swap_assets_for_exact_shares(
+                keys,
+                &bob.pubkey(),
+                shares_out,
+                expected_assets,
+                None,
+                None,
+                false,
+            )],
+            &[&bob],
+        )
+        .await
+        .unwrap();
+    let buy = &events::<Buy>(&logs)[0];
+    assert_eq!(buy.shares, shares_out);
+    assert_eq!(buy.assets, expected_assets);
+    assert_eq!(
+        ctx.token_balance(keys.asset_token_account(&bob.pubkey()))
+            .await,
+        USER_ASSETS - expected_assets
+    );
+    assert_eq!(
+        user_state(&mut ctx, keys, &bob).await.purchased_shares,
+        shares_out
+    );
+
+    // Later in the sale, sell a quarter of the purchased shares back
+    ctx.warp_to(sale.sale_start_time + ONE_DAY).await;
+    let now = ctx.now().await;
+    let shares_in = expected_shares / 4;
+    let expected_assets_out = snapshot(&mut ctx, keys)
+        .await
+        .preview_assets_out(shares_in, now)
+        .unwrap();
+    let logs = ctx
+        .process(
+            &[fjord_lbp_sdk::swap_exact_shares_for_assets(
+                keys,
+                &alice.pubkey(),
+                shares_in,
+                expected_assets_out,
+                None,
+                None,
+                false,
+            )],
+            &[&alice],
+        )
+        .await
+        .unwrap();
+    let sell = &events::<Sell>(&logs)[0];
+    assert_eq!(sell.user, alice.pubkey());
+    assert_eq!(sell.shares, shares_in);
+    assert_eq!(sell.assets, expected_assets_out);
+
+    // Sell shares for exactly 10 assets
+    let assets_out = 10_000_000;
+    let expected_shares_in = snapshot(&mut ctx, keys)
+        .await
+        .preview_shares_in(assets_out, now)
+        .unwrap();
+    let logs = ctx
+        .process(
+            &[fjord_lbp_sdk::swap_shares_for_exact_assets(
+                keys,
+                &alice.pubkey(),
+                assets_out,
+                expected_shares_in,
+                None,
+                None,
+                false,
+            )],
+            &[&alice],
+        )
+        .await
+        .unwrap();
+    let sell = &events::<Sell>(&logs)[0];
+    assert_eq!(sell.assets, assets_out);
+    assert_eq!(sell.shares, expected_shares_in);
+    assert_eq!(
+        ctx.token_balance(keys.asset_token_account(&alice.pubkey()))
+            .await,
+        USER_ASSETS - assets_in + expected_assets_out + assets_out
+    );
+    let alice_shares = user_state(&mut ctx, keys, &alice).await.purchased_shares;
+    assert_eq!(
+        alice_shares,
+        expected_shares - shares_in - expected_shares_in
+    );
+
+    // Close the pool once the sale is over
+    ctx.warp_to(sale.sale_end_time).await;
+    let pool = ctx.account::<LiquidityBootstrappingPool>(keys.pool).await;
+    let pool_assets = ctx.token_balance(keys.pool_asset_token_account()).await;
+    let logs = close_pool(&mut ctx, keys, &sale.creator).await.unwrap();
+    let close = &events::<Close>(&logs)[0];
+    let total_assets = pool_assets - pool.total_swap_fees_asset;
+    let platform_fees = total_assets * u64::from(PLATFORM_FEE) / 10_000;
+    assert_eq!(close.platform_fees, platform_fees);
+    assert_eq!(close.assets, total_assets - platform_fees);
+    assert_eq!(close.swap_fees_asset, pool.total_swap_fees_asset);
+    assert_eq!(close.swap_fees_share, pool.total_swap_fees_share);
+    assert_eq!(
+        ctx.token_balance(keys.asset_token_account(&sale.creator.pubkey()))
+            .await,
+        total_assets - platform_fees
+    );
+    assert_eq!(
+        ctx.token_balance(keys.asset_token_account(&ctx.treasury.swap_fee_recipient))
+            .await,
+        pool.total_swap_fees_asset
+    );
+    for recipient in ctx.treasury.fee_recipients.clone() {
+        assert_eq!(
+            ctx.token_balance(keys.asset_token_account(&recipient.user))
+                .await,
+            platform_fees / 2
+        );
+    }
+    assert_eq!(
+        ctx.token_balance(keys.share_token_account(&sale.creator.pubkey()))
+            .await,
+        POOL_SHARES - pool.total_purchased - pool.total_swap_fees_share
+    );
+    assert!(
+        ctx.account::<LiquidityBootstrappingPool>(keys.pool)
+            .await
+            .closed
+    );
+
+    // Nothing is released before the cliff
+    ctx.warp_to(sale.vest_cliff - 1).await;
+    let logs = ctx
+        .process(
+            &[fjord_lbp_sdk::redeem(keys, &alice.pubkey(), false, false)],
+            &[&alice],
+        )
+        .await
+        .unwrap();
+    assert!(events::<Redeem>(&logs).is_empty());
+    assert_eq!(
+        ctx.token_balance(keys.share_token_account(&alice.pubkey()))
+            .await,
+        0
+    );
+
+    // Half of the shares are released halfway through the vesting
+    ctx.warp_to((sale.vest_cliff + sale.vest_end) / 2).await;
+    let logs = ctx
+        .process(
+            &[fjord_lbp_sdk::redeem(keys, &alice.pubkey(), false, false)],
+            &[&alice],
+        )
+        .await
+        .unwrap();
+    let redeem = &events::<Redeem>(&logs)[0];
+    assert_eq!(redeem.caller, alice.pubkey());
+    assert_eq!(redeem.shares, alice_shares / 2);
+
+    // Everything is released after the vesting ends, and the states of the users are closed
+    ctx.warp_to(sale.vest_end).await;
+    for user in [&alice, &bob] {
+        ctx.process(
+            &[fjord_lbp_sdk::redeem(keys, &user.pubkey(), false, false)],
+            &[user],
+        )
+        .await
+        .unwrap();
+        assert!(ctx
+            .get_account(find_user_state_address(&user.pubkey(), &keys.pool).0)
+            .await
+            .is_none());
+    }
+    assert_eq!(
+        ctx.token_balance(keys.share_token_account(&alice.pubkey()))
+            .await,
+        alice_shares
+    );
+    assert_eq!(
+        ctx.token_balance(keys.share_token_account(&bob.pubkey()))
+            .await,
+        shares_out
+    );
+}
+
+#[tokio::test]
+async fn buys_are_capped_at_the_max_share_price() {
+    let mut ctx = TestContext::new().await;
+    // Shares start at about 0.00011 assets, but cost at most 0.00005 assets
+    let sale = create_pool_with(&mut ctx, |args| args.max_share_price = 5_000).await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    ctx.warp_to(sale.sale_start_time).await;
+
+    // 10 assets less the 1% swap fee buy 198,000 shares at the max share price
+    let logs = buy(&mut ctx, keys, &alice, 10_000_000).await.unwrap();
+    assert_eq!(events::<Buy>(&logs)[0].shares, 198_000_000_000_000);
+
+    // 100,000 shares cost 5 assets plus the 1% swap fee
+    let logs = ctx
+        .process(
+            &[fjord_lbp_sdk::swap_assets_for_exact_shares(
+                keys,
+                &alice.pubkey(),
+                100_000_000_000_000,
+                u64::MAX,
+                None,
+                None,
+                false,
+            )],
+            &[&alice],
+        )
+        .await
+        .unwrap();
+    assert_eq!(events::<Buy>(&logs)[0].assets, 5_050_000);
+    assert_eq!(
+        user_state(&mut ctx, keys, &alice).await.purchased_shares,
+        298_000_000_000_000
+    );
+}
+
+#[tokio::test]
+async fn sale_window_is_enforced() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool(&mut ctx).await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+
+    // Swaps are rejected before the sale starts
+    let result = buy(&mut ctx, keys, &alice, 1_000_000).await;
+    assert_error(result, PoolError::TradingDisallowed.into());
+
+    // The pool cannot be closed or redeemed from during the sale
+    ctx.warp_to(sale.sale_start_time).await;
+    buy(&mut ctx, keys, &alice, 1_000_000).await.unwrap();
+    let result = close_pool(&mut ctx, keys, &alice).await;
+    assert_error(result, PoolError::ClosingDisallowed.into());
+    let result = ctx
+        .process(
+            &[fjord_lbp_sdk::redeem(keys, &alice.pubkey(), false, false)],
+            &[&alice],
+        )
+        .await;
+    assert_error(result, PoolError::RedeemingDisallowed.into());
+
+    // Swaps are rejected once the sale is over
+    ctx.warp_to(sale.sale_end_time).await;
+    let result = buy(&mut ctx, keys, &alice, 1_000_000).await;
+    assert_error(result, PoolError::TradingDisallowed.into());
+}
@@ -0,0 +1,436 @@
+//! Migration of the accounts laid out by the first release of the program, too short for the fields added since.
+
+mod harness;
+
+use anchor_lang::prelude::borsh;
+use anchor_lang::{AnchorSerialize, Discriminator};
+use fjord_lbp::{
+    AccessControlError, FeeMapping, LiquidityBootstrappingPool, OwnerConfig, PoolError, Treasury,
+    UserStateInPool, WhitelistLeafVersion, MIN_FEE_CHANGE_DELAY,
+};
+use fjord_lbp_sdk::{
+    find_owner_config_address, find_treasury_address, find_user_state_address, PoolKeys,
+};
+use harness::*;
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+
+// Accounts as laid out by the first release, sized as it allocated them
+
+#[derive(AnchorSerialize)]
+struct FirstOwnerConfig {
+    owner: Pubkey,
+    pending_owner: Option<Pubkey>,
+    treasury: Pubkey,
+    platform_fee: u16,
+    referral_fee: u16,
+    swap_fee: u16,
+    bump: u8,
+}
+
+const FIRST_OWNER_CONFIG_SPACE: usize = 8 + 32 + 1 + 32 + 32 + 2 + 2 + 2 + 1;
+
+#[derive(AnchorSerialize)]
+struct FirstTreasury {
+    swap_fee_recipient: Pubkey,
+    fee_recipients: Vec<FeeMapping>,
+}
+
+#[derive(AnchorSerialize)]
+struct FirstPool {
+    asset_token: Pubkey,
+    share_token: Pubkey,
+    creator: Pubkey,
+    virtual_assets: u64,
+    virtual_shares: u64,
+    max_share_price: u64,
+    max_shares_out: u64,
+    max_assets_in: u64,
+    start_weight_basis_points: u16,
+    end_weight_basis_points: u16,
+    sale_start_time: i64,
+    sale_end_time: i64,
+    vest_cliff: i64,
+    vest_end: i64,
+    selling_allowed: bool,
+    total_purchased: u64,
+    total_referred: u64,
+    total_swap_fees_asset: u64,
+    total_swap_fees_share: u64,
+    closed: bool,
+    paused: bool,
+    whitelist_merkle_root: [u8; 32],
+    bump: u8,
+    salt: String,
+}
+
+// 8 + size_of::<LiquidityBootstrappingPool>() of the first release
+const FIRST_POOL_SPACE: usize = 8 + 264;
+
+#[derive(AnchorSerialize)]
+struct FirstUserState {
+    purchased_shares: u64,
+    referred_assets: u64,
+    redeemed_shares: u64,
+}
+
+const FIRST_USER_STATE_SPACE: usize = 8 + 8 + 8 + 8;
+
+/// Account of the program holding `data` after the discriminator of `T`, in `space` bytes.
+fn first_account<T: Discriminator>(data: &impl AnchorSerialize, space: usize) -> Account {
+    let mut buffer = T::DISCRIMINATOR.to_vec();
+    data.serialize(&mut buffer).unwrap();
+    buffer.resize(space, 0);
+    Account {
+        lamports: solana_sdk::rent::Rent::default().minimum_balance(space),
+        data: buffer,
+        owner: fjord_lbp::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn first_pool(pool: &LiquidityBootstrappingPool) -> FirstPool {
+    FirstPool {
+        asset_token: pool.asset_token,
+        share_token: pool.share_token,
+        creator: pool.creator,
+        virtual_assets: pool.virtual_assets,
+        virtual_shares: pool.virtual_shares,
+        max_share_price: pool.max_share_price,
+        max_shares_out: pool.max_shares_out,
+        max_assets_in: pool.max_assets_in,
+        start_weight_basis_points: pool.start_weight_basis_points,
+        end_weight_basis_points: pool.end_weight_basis_points,
+        sale_start_time: pool.sale_start_time,
+        sale_end_time: pool.sale_end_time,
+        vest_cliff: pool.vest_cliff,
+        vest_end: pool.vest_end,
+        selling_allowed: pool.selling_allowed,
+        total_purchased: pool.total_purchased,
+        total_referred: pool.total_referred,
+        total_swap_fees_asset: pool.total_swap_fees_asset,
+        total_swap_fees_share: pool.total_swap_fees_share,
+        closed: pool.closed,
+        paused: pool.paused,
+        whitelist_merkle_root: pool.whitelist_merkle_root,
+        bump: pool.bump,
+        salt: pool.salt.clone(),
+    }
+}
+
+#[tokio::test]
+async fn owner_config_and_treasury_are_migrated_by_the_owner() {
+    let mut ctx = TestContext::new().await;
+    let owner = ctx.owner.insecure_clone();
+    let (config_address, config_bump) = find_owner_config_address();
+    ctx.set_account(
+        config_address,
+        first_account::<OwnerConfig>(
+            &FirstOwnerConfig {
+                owner: owner.pubkey(),
+                pending_owner: None,
+                treasury: find_treasury_address().0,
+                platform_fee: PLATFORM_FEE,
+                referral_fee: REFERRAL_FEE,
+                swap_fee: SWAP_FEE,
+                bump: config_bump,
+            },
+            FIRST_OWNER_CONFIG_SPACE,
+        ),
+    );
+    let treasury = ctx.treasury.clone();
+    ctx.set_account(
+        find_treasury_address().0,
+        first_account::<Treasury>(
+            &FirstTreasury {
+                swap_fee_recipient: treasury.swap_fee_recipient,
+                fee_recipients: treasury.fee_recipients.clone(),
+            },
+            8 + 32 + 4 + (32 + 2) * treasury.fee_recipients.len(),
+        ),
+    );
+    let payer = ctx.payer();
+    ctx.process(
+        &[system_instruction::transfer(
+            &payer,
+            &owner.pubkey(),
+            1_000_000_000,
+        )],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    let intruder = ctx.fund_wallet(Keypair::new()).await;
+    let result = ctx
+        .process(
+            &[fjord_lbp_sdk::migrate_owner_config(
+                &intruder.pubkey(),
+                1000,
+                1000,
+                1000,
+            )],
+            &[&intruder],
+        )
+        .await;
+    assert_error(result, AccessControlError::Unauthorized.into());
+    let result = ctx
+        .process(
+            &[fjord_lbp_sdk::migrate_owner_config(
+                &owner.pubkey(),
+                1000,
+                REFERRAL_FEE - 1,
+                1000,
+            )],
+            &[&owner],
+        )
+        .await;
+    assert_error(result, PoolError::FeeCeilingExceeded.into());
+
+    ctx.process(
+        &[fjord_lbp_sdk::migrate_owner_config(
+            &owner.pubkey(),
+            1000,
+            2000,
+            3000,
+        )],
+        &[&owner],
+    )
+    .await
+    .unwrap();
+    let config = ctx.account::<OwnerConfig>(config_address).await;
+    assert_eq!(config.owner, owner.pubkey());
+    assert_eq!(config.treasury, find_treasury_address().0);
+    assert_eq!(
+        (config.platform_fee, config.referral_fee, config.swap_fee),
+        (PLATFORM_FEE, REFERRAL_FEE, SWAP_FEE)
+    );
+    assert_eq!(
+        (
+            config.max_platform_fee,
+            config.max_referral_fee,
+            config.max_swap_fee
+        ),
+        (1000, 2000, 3000)
+    );
+    assert_eq!(config.fee_change_delay, MIN_FEE_CHANGE_DELAY);
+    assert_eq!(config.pending_fees, None);
+    assert_eq!(config.bump, config_bump);
+    let migrated_treasury = ctx.account::<Treasury>(find_treasury_address().0).await;
+    assert_eq!(
+        migrated_treasury.swap_fee_recipient,
+        treasury.swap_fee_recipient
+    );
+    assert_eq!(migrated_treasury.fee_recipients.len(), 2);
+    assert!(migrated_treasury.pending_fee_recipients.is_none());
+
+    // Pools are created and traded against the migrated accounts
+    let sale = create_pool(&mut ctx).await;
+    let user = ctx.fund_user(&sale.keys, USER_ASSETS).await;
+    ctx.warp_to(sale.sale_start_time).await;
+    buy(&mut ctx, &sale.keys, &user, 1_000_000).await.unwrap();
+
+    let result = ctx
+        .process(
+            &[fjord_lbp_sdk::migrate_owner_config(
+                &owner.pubkey(),
+                1000,
+                2000,
+                3000,
+            )],
+            &[&owner],
+        )
+        .await;
+    assert_error(result, PoolError::AccountAlreadyMigrated.into());
+}
+
+#[tokio::test]
+async fn pools_and_user_states_are_migrated_by_anyone() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool(&mut ctx).await;
+    let keys = &sale.keys;
+    let user = ctx.fund_user(keys, USER_ASSETS).await;
+    ctx.warp_to(sale.sale_start_time).await;
+    buy(&mut ctx, keys, &user, 1_000_000).await.unwrap();
+
+    // Lay the pool and the user state out as the first release did
+    let pool = ctx.account::<LiquidityBootstrappingPool>(keys.pool).await;
+    ctx.set_account(
+        keys.pool,
+        first_account::<LiquidityBootstrappingPool>(&first_pool(&pool), FIRST_POOL_SPACE),
+    );
+    let state = user_state(&mut ctx, keys, &user).await;
+    let user_state_address = find_user_state_address(&user.pubkey(), &keys.pool).0;
+    ctx.set_account(
+        user_state_address,
+        first_account::<UserStateInPool>(
+            &FirstUserState {
+                purchased_shares: state.purchased_shares,
+                referred_assets: state.referred_assets,
+                redeemed_shares: state.redeemed_shares,
+            },
+            FIRST_USER_STATE_SPACE,
+        ),
+    );
+    ctx.warp_to(sale.sale_start_time + 1).await;
+    let result = buy(&mut ctx, keys, &user, 1_000_000).await;
+    assert_error(
+        result,
+        anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into(),
+    );
+
+    let payer = ctx.fund_wallet(Keypair::new()).await;
+    ctx.process(
+        &[fjord_lbp_sdk::migrate_pool(keys, &payer.pubkey())],
+        &[&payer],
+    )
+    .await
+    .unwrap();
+    let migrated = ctx.account::<LiquidityBootstrappingPool>(keys.pool).await;
+    assert_eq!(migrated.creator, pool.creator);
+    assert_eq!(migrated.salt, pool.salt);
+    assert_eq!(migrated.bump, pool.bump);
+    assert_eq!(migrated.total_purchased, pool.total_purchased);
+    assert_eq!(migrated.total_swap_fees_asset, pool.total_swap_fees_asset);
+    assert_eq!(
+        (
+            migrated.platform_fee,
+            migrated.referral_fee,
+            migrated.swap_fee
+        ),
+        (PLATFORM_FEE, REFERRAL_FEE, SWAP_FEE)
+    );
+    assert!(migrated.traded);
+    assert_eq!(migrated.max_assets_raised, 0);
+    assert_eq!(
+        migrated.whitelist_leaf_version,
+        WhitelistLeafVersion::Legacy
+    );
+    ctx.warp_to(sale.sale_start_time + 2).await;
+    let result = buy(&mut ctx, keys, &user, 1_000_000).await;
+    assert_error(
+        result,
+        anchor_lang::error::ErrorCode::AccountDidNotDeserialize.into(),
+    );
+
+    ctx.process(
+        &[fjord_lbp_sdk::migrate_user_state(
+            keys,
+            &user.pubkey(),
+            &payer.pubkey(),
+        )],
+        &[&payer],
+    )
+    .await
+    .unwrap();
+    let migrated_state = user_state(&mut ctx, keys, &user).await;
+    assert_eq!(migrated_state.purchased_shares, state.purchased_shares);
+    assert_eq!(migrated_state.referrer, Pubkey::default());
+    buy(&mut ctx, keys, &user, 2_000_000).await.unwrap();
+    assert!(user_state(&mut ctx, keys, &user).await.purchased_shares > state.purchased_shares);
+
+    for instruction in [
+        fjord_lbp_sdk::migrate_pool(keys, &payer.pubkey()),
+        fjord_lbp_sdk::migrate_user_state(keys, &user.pubkey(), &payer.pubkey()),
+    ] {
+        let result = ctx.process(&[instruction], &[&payer]).await;
+        assert_error(result, PoolError::AccountAlreadyMigrated.into());
+    }
+}
+
+/// Lay the pool out as the first release did and migrate it.
+async fn migrate_first_pool(ctx: &mut TestContext, keys: &PoolKeys) -> LiquidityBootstrappingPool {
+    let pool = ctx.account::<LiquidityBootstrappingPool>(keys.pool).await;
+    ctx.set_account(
+        keys.pool,
+        first_account::<LiquidityBootstrappingPool>(&first_pool(&pool), FIRST_POOL_SPACE),
+    );
+    let payer = ctx.fund_wallet(Keypair::new()).await;
+    ctx.process(
+        &[fjord_lbp_sdk::migrate_pool(keys, &payer.pubkey())],
+        &[&payer],
+    )
+    .await
+    .unwrap();
+    ctx.account::<LiquidityBootstrappingPool>(keys.pool).await
+}
+
+#[tokio::test]
+async fn migrated_pools_snapshot_the_fees_of_the_config() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool(&mut ctx).await;
+    let keys = &sale.keys;
+    let owner = ctx.owner.insecure_clone();
+
+    // The first release charged the fees of the config at the time of each swap, not at creation
+    let now = ctx.now().await;
+    ctx.process(
+        &[fjord_lbp_sdk::propose_fees(
+            &owner.pubkey(),
+            None,
+            None,
+            Some(2 * SWAP_FEE),
+            None,
+        )],
+        &[&owner],
+    )
+    .await
+    .unwrap();
+    ctx.warp_to(now + MIN_FEE_CHANGE_DELAY).await;
+    ctx.process(&[fjord_lbp_sdk::execute_fees(&owner.pubkey())], &[&owner])
+        .await
+        .unwrap();
+    let migrated = migrate_first_pool(&mut ctx, keys).await;
+    assert_eq!(
+        (
+            migrated.platform_fee,
+            migrated.referral_fee,
+            migrated.swap_fee
+        ),
+        (PLATFORM_FEE, REFERRAL_FEE, 2 * SWAP_FEE)
+    );
+
+    // Later changes of the config no longer apply to the pool
+    let now = ctx.now().await;
+    ctx.process(
+        &[fjord_lbp_sdk::propose_fees(
+            &owner.pubkey(),
+            None,
+            None,
+            Some(SWAP_FEE),
+            None,
+        )],
+        &[&owner],
+    )
+    .await
+    .unwrap();
+    ctx.warp_to(now + MIN_FEE_CHANGE_DELAY).await;
+    ctx.process(&[fjord_lbp_sdk::execute_fees(&owner.pubkey())], &[&owner])
+        .await
+        .unwrap();
+    let pool = ctx.account::<LiquidityBootstrappingPool>(keys.pool).await;
+    assert_eq!(pool.swap_fee, 2 * SWAP_FEE);
+}
+
+#[tokio::test]
+async fn migrated_pools_count_as_traded_once_their_sale_starts() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool(&mut ctx).await;
+    let keys = &sale.keys;
+
+    assert!(!migrate_first_pool(&mut ctx, keys).await.traded);
+
+    // Even without any shares held nor fees charged, purchases may have been sold back since the sale started
+    ctx.warp_to(sale.sale_start_time).await;
+    let migrated = migrate_first_pool(&mut ctx, keys).await;
+    assert_eq!(migrated.total_purchased, 0);
+    assert_eq!(migrated.total_swap_fees_asset, 0);
+    assert!(migrated.traded);
+    let result = ctx
+        .process(&[fjord_lbp_sdk::cancel_pool(keys)], &[&sale.creator])
+        .await;
+    assert_error(result, PoolError::CancellingDisallowed.into());
+}
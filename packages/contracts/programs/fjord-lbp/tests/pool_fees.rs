@@ -0,0 +1,233 @@
+//! Fees snapshotted into the pools at creation and their overrides agreed by the owner and the creator.
+
+mod harness;
+
+use fjord_lbp::{
+    AccessControlError, Buy, LiquidityBootstrappingPool, PoolError, PoolFeesUpdated, Referral,
+    ReferralTier, MIN_FEE_CHANGE_DELAY,
+};
+use fjord_lbp_sdk::PoolKeys;
+use harness::*;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::TransactionError;
+
+async fn set_pool_fees(
+    ctx: &mut TestContext,
+    keys: &PoolKeys,
+    creator: &Keypair,
+    platform_fee: Option<u16>,
+    referral_fee: Option<u16>,
+    swap_fee: Option<u16>,
+) -> Result<Vec<String>, TransactionError> {
+    let owner = ctx.owner.insecure_clone();
+    let keys = PoolKeys {
+        creator: creator.pubkey(),
+        ..keys.clone()
+    };
+    ctx.process(
+        &[fjord_lbp_sdk::set_pool_fees(
+            &keys,
+            &owner.pubkey(),
+            platform_fee,
+            referral_fee,
+            swap_fee,
+        )],
+        &[&owner, creator],
+    )
+    .await
+}
+
+#[tokio::test]
+async fn global_fee_changes_do_not_apply_to_existing_pools() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool(&mut ctx).await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    let owner = ctx.owner.insecure_clone();
+    ctx.process(
+        &[fjord_lbp_sdk::propose_fees(
+            &owner.pubkey(),
+            Some(0),
+            Some(0),
+            Some(500),
+            None,
+        )],
+        &[&owner],
+    )
+    .await
+    .unwrap();
+    // Halfway through the sale, once the fee change delay has elapsed
+    ctx.warp_to(sale.sale_start_time + MIN_FEE_CHANGE_DELAY)
+        .await;
+    ctx.process(&[fjord_lbp_sdk::execute_fees(&owner.pubkey())], &[&owner])
+        .await
+        .unwrap();
+
+    let pool = ctx.account::<LiquidityBootstrappingPool>(keys.pool).await;
+    assert_eq!(pool.platform_fee, PLATFORM_FEE);
+    assert_eq!(pool.referral_fee, REFERRAL_FEE);
+    assert_eq!(pool.swap_fee, SWAP_FEE);
+
+    let logs = buy(&mut ctx, keys, &alice, 100_000_000).await.unwrap();
+    let buy = &events::<Buy>(&logs)[0];
+    assert_eq!(buy.swap_fee, 100_000_000 * u64::from(SWAP_FEE) / 10_000);
+
+    // Pools created afterwards take the new fees
+    let sale = create_pool(&mut ctx).await;
+    let pool = ctx
+        .account::<LiquidityBootstrappingPool>(sale.keys.pool)
+        .await;
+    assert_eq!(pool.swap_fee, 500);
+}
+
+#[tokio::test]
+async fn pool_fees_are_overridden_by_the_owner_and_the_creator() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool(&mut ctx).await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    let mallory = ctx.fund_wallet(Keypair::new()).await;
+
+    let result = set_pool_fees(&mut ctx, keys, &mallory, None, None, Some(0)).await;
+    assert_error(result, AccessControlError::Unauthorized.into());
+    let result = set_pool_fees(
+        &mut ctx,
+        keys,
+        &sale.creator,
+        Some(5_000),
+        None,
+        Some(5_001),
+    )
+    .await;
+    assert_error(result, PoolError::MaxFeeExceeded.into());
+
+    let logs = set_pool_fees(&mut ctx, keys, &sale.creator, None, Some(300), Some(0))
+        .await
+        .unwrap();
+    let updated = &events::<PoolFeesUpdated>(&logs)[0];
+    assert_eq!(updated.pool, keys.pool);
+    assert_eq!(updated.platform_fee, PLATFORM_FEE);
+    assert_eq!(updated.referral_fee, 300);
+    assert_eq!(updated.swap_fee, 0);
+
+    ctx.warp_to(sale.sale_start_time).await;
+    let logs = buy(&mut ctx, keys, &alice, 100_000_000).await.unwrap();
+    assert_eq!(events::<Buy>(&logs)[0].swap_fee, 0);
+
+    ctx.warp_to(sale.sale_end_time).await;
+    close_pool(&mut ctx, keys, &sale.creator).await.unwrap();
+    let result = set_pool_fees(&mut ctx, keys, &sale.creator, None, None, Some(100)).await;
+    assert_error(result, PoolError::PoolFeesUpdateDisallowed.into());
+}
+
+#[tokio::test]
+async fn referral_fee_overrides_replace_the_base_fee_of_the_referral_schedule() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool_with(&mut ctx, |args| {
+        args.referral_schedule.fee_basis_points = Some(500);
+    })
+    .await;
+    let keys = &sale.keys;
+    let user = ctx.fund_user(keys, USER_ASSETS).await;
+    let referrer = Keypair::new().pubkey();
+    ctx.warp_to(sale.sale_start_time).await;
+
+    let buy_referred = |assets_in| {
+        fjord_lbp_sdk::swap_exact_assets_for_shares(
+            keys,
+            &user.pubkey(),
+            assets_in,
+            1,
+            None,
+            Some(referrer.into()),
+            false,
+        )
+    };
+    let logs = ctx
+        .process(&[buy_referred(100_000_000)], &[&user])
+        .await
+        .unwrap();
+    assert_eq!(events::<Referral>(&logs)[0].referred_assets, 5_000_000);
+
+    // Omitting the referral fee keeps the base fee of the schedule
+    set_pool_fees(&mut ctx, keys, &sale.creator, Some(200), None, None)
+        .await
+        .unwrap();
+    let pool = ctx.account::<LiquidityBootstrappingPool>(keys.pool).await;
+    assert_eq!(pool.referral_schedule.fee_basis_points, Some(500));
+
+    set_pool_fees(&mut ctx, keys, &sale.creator, None, Some(200), None)
+        .await
+        .unwrap();
+    let pool = ctx.account::<LiquidityBootstrappingPool>(keys.pool).await;
+    assert_eq!(pool.referral_fee, 200);
+    assert_eq!(pool.referral_schedule.fee_basis_points, None);
+    let logs = ctx
+        .process(&[buy_referred(200_000_000)], &[&user])
+        .await
+        .unwrap();
+    assert_eq!(events::<Referral>(&logs)[0].referred_assets, 4_000_000);
+}
+
+#[tokio::test]
+async fn pool_fees_must_leave_room_for_the_referral_schedule() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool_with(&mut ctx, |args| {
+        args.referral_schedule.fee_basis_points = Some(9_000);
+    })
+    .await;
+    let keys = &sale.keys;
+
+    let result = set_pool_fees(&mut ctx, keys, &sale.creator, Some(1_000), None, None).await;
+    assert_error(result, PoolError::InvalidReferralSchedule.into());
+    set_pool_fees(&mut ctx, keys, &sale.creator, Some(900), None, None)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn referral_schedules_are_held_to_the_referral_fee_ceiling() {
+    let mut ctx = TestContext::new().await;
+    let owner = ctx.owner.insecure_clone();
+    ctx.process(
+        &[fjord_lbp_sdk::lower_fee_ceilings(
+            &owner.pubkey(),
+            None,
+            Some(500),
+            None,
+        )],
+        &[&owner],
+    )
+    .await
+    .unwrap();
+
+    let asset_token_mint = ctx.create_mint(ASSET_DECIMALS, &TOKEN_PROGRAM_ID).await;
+    let (_, result) = try_create_pool_for(&mut ctx, asset_token_mint, |args| {
+        args.referral_schedule.tiers[0] = ReferralTier {
+            min_referred_volume: 100_000_000,
+            fee_basis_points: 501,
+        };
+    })
+    .await;
+    assert_error(result, PoolError::FeeCeilingExceeded.into());
+
+    let sale = create_pool_with(&mut ctx, |args| {
+        args.referral_schedule.fee_basis_points = Some(500);
+    })
+    .await;
+
+    // Overrides of the fees of a pool are held to the current ceilings, its schedule included
+    ctx.process(
+        &[fjord_lbp_sdk::lower_fee_ceilings(
+            &owner.pubkey(),
+            None,
+            Some(400),
+            None,
+        )],
+        &[&owner],
+    )
+    .await
+    .unwrap();
+    let result = set_pool_fees(&mut ctx, &sale.keys, &sale.creator, None, None, Some(0)).await;
+    assert_error(result, PoolError::FeeCeilingExceeded.into());
+}
@@ -0,0 +1,386 @@
+//! Rent of the user states and of the pool accounts returned once a pool is closed and paid out, or cancelled.
+
+mod harness;
+
+use anchor_lang::solana_program::system_instruction::SystemError;
+use anchor_lang::Discriminator;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::{
+    TransferFeeAmount, TransferFeeConfig,
+};
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::state::{Account, Mint};
+use fjord_lbp::{
+    Close, ClosedPool, LiquidityBootstrappingPool, PoolAccountsClosed, PoolError,
+    CLOSE_POOL_ACCOUNTS_GRACE_PERIOD,
+};
+use fjord_lbp_sdk::{find_user_state_address, PoolKeys};
+use harness::*;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::TransactionError;
+
+async fn redeem(
+    ctx: &mut TestContext,
+    keys: &PoolKeys,
+    user: &Keypair,
+) -> Result<Vec<String>, TransactionError> {
+    ctx.process(
+        &[fjord_lbp_sdk::redeem(keys, &user.pubkey(), false, false)],
+        &[user],
+    )
+    .await
+}
+
+/// Check that the pool was shrunk to a closed pool marker, returning the rent left in it.
+async fn assert_closed_pool(ctx: &mut TestContext, keys: &PoolKeys) -> u64 {
+    let marker = ctx.get_account(keys.pool).await.unwrap();
+    assert_eq!(marker.owner, fjord_lbp::ID);
+    assert_eq!(marker.data, ClosedPool::DISCRIMINATOR);
+    marker.lamports
+}
+
+async fn close_pool_accounts(
+    ctx: &mut TestContext,
+    keys: &PoolKeys,
+    creator: &Keypair,
+) -> Result<Vec<String>, TransactionError> {
+    ctx.process(
+        &[fjord_lbp_sdk::close_pool_accounts(keys, false)],
+        &[creator],
+    )
+    .await
+}
+
+#[tokio::test]
+async fn user_states_are_closed_once_fully_redeemed() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool(&mut ctx).await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    let state = find_user_state_address(&alice.pubkey(), &keys.pool).0;
+    ctx.warp_to(sale.sale_start_time).await;
+    buy(&mut ctx, keys, &alice, 100_000_000).await.unwrap();
+    ctx.warp_to(sale.sale_end_time).await;
+    close_pool(&mut ctx, keys, &sale.creator).await.unwrap();
+
+    // The state is kept while shares are still vesting
+    ctx.warp_to((sale.vest_cliff + sale.vest_end) / 2).await;
+    redeem(&mut ctx, keys, &alice).await.unwrap();
+    assert_ne!(user_state(&mut ctx, keys, &alice).await.purchased_shares, 0);
+
+    ctx.warp_to(sale.vest_end).await;
+    let rent = ctx.lamports(state).await;
+    let lamports = ctx.lamports(alice.pubkey()).await;
+    redeem(&mut ctx, keys, &alice).await.unwrap();
+    assert!(ctx.get_account(state).await.is_none());
+    assert_eq!(ctx.lamports(alice.pubkey()).await, lamports + rent);
+}
+
+#[tokio::test]
+async fn creator_can_close_the_emptied_pool() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool_with(&mut ctx, |args| {
+        args.vest_cliff = 0;
+        args.vest_end = 0;
+    })
+    .await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    ctx.warp_to(sale.sale_start_time).await;
+    buy(&mut ctx, keys, &alice, 100_000_000).await.unwrap();
+
+    // The pool must be closed first
+    let result = close_pool_accounts(&mut ctx, keys, &sale.creator).await;
+    assert_error(result, PoolError::PoolAccountsClosingDisallowed.into());
+
+    ctx.warp_to(sale.sale_end_time).await;
+    close_pool(&mut ctx, keys, &sale.creator).await.unwrap();
+
+    // And the purchased shares redeemed
+    let result = close_pool_accounts(&mut ctx, keys, &sale.creator).await;
+    assert_error(result, PoolError::PoolAccountsClosingDisallowed.into());
+    redeem(&mut ctx, keys, &alice).await.unwrap();
+
+    // Only the creator can close its pool
+    let carol = ctx.fund_wallet(Keypair::new()).await;
+    let result = close_pool_accounts(
+        &mut ctx,
+        &PoolKeys {
+            creator: carol.pubkey(),
+            ..keys.clone()
+        },
+        &carol,
+    )
+    .await;
+    assert_error(
+        result,
+        anchor_lang::error::ErrorCode::ConstraintSeeds.into(),
+    );
+
+    let creator = sale.creator.pubkey();
+    let rent = ctx.lamports(keys.pool).await
+        + ctx.lamports(keys.pool_asset_token_account()).await
+        + ctx.lamports(keys.pool_share_token_account()).await;
+    let lamports = ctx.lamports(creator).await;
+    let logs = close_pool_accounts(&mut ctx, keys, &sale.creator)
+        .await
+        .unwrap();
+    assert_eq!(events::<PoolAccountsClosed>(&logs)[0].pool, keys.pool);
+    for account in [
+        keys.pool_asset_token_account(),
+        keys.pool_share_token_account(),
+    ] {
+        assert!(ctx.get_account(account).await.is_none());
+    }
+    // The pool is shrunk to a marker holding the rent of its discriminator only
+    let marker = assert_closed_pool(&mut ctx, keys).await;
+    assert_eq!(ctx.lamports(creator).await, lamports + rent - marker);
+}
+
+#[tokio::test]
+async fn leftovers_are_swept_to_the_creator_once_everything_is_claimed() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool_with(&mut ctx, |args| {
+        args.vest_cliff = 0;
+        args.vest_end = 0;
+    })
+    .await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    let bob = ctx.fund_wallet(Keypair::new()).await;
+    ctx.warp_to(sale.sale_start_time).await;
+    ctx.process(
+        &[fjord_lbp_sdk::swap_exact_assets_for_shares(
+            keys,
+            &alice.pubkey(),
+            100_000_200,
+            1,
+            None,
+            Some(bob.pubkey().into()),
+            false,
+        )],
+        &[&alice],
+    )
+    .await
+    .unwrap();
+
+    // The odd platform fee is split evenly between the two fee recipients, leaving a unit in the pool
+    ctx.warp_to(sale.sale_end_time).await;
+    let logs = close_pool(&mut ctx, keys, &sale.creator).await.unwrap();
+    assert_eq!(events::<Close>(&logs)[0].platform_fees % 2, 1);
+
+    // The referral assets of bob are still owed once alice has redeemed
+    redeem(&mut ctx, keys, &alice).await.unwrap();
+    let result = close_pool_accounts(&mut ctx, keys, &sale.creator).await;
+    assert_error(result, PoolError::PoolAccountsClosingDisallowed.into());
+    ctx.process(
+        &[fjord_lbp_sdk::claim_referral_rewards(
+            keys,
+            &bob.pubkey(),
+            false,
+        )],
+        &[&bob],
+    )
+    .await
+    .unwrap();
+    let pool = ctx.account::<LiquidityBootstrappingPool>(keys.pool).await;
+    assert_eq!((pool.total_purchased, pool.total_referred), (0, 0));
+
+    let creator = sale.creator.pubkey();
+    let assets = ctx.token_balance(keys.pool_asset_token_account()).await;
+    let shares = ctx.token_balance(keys.pool_share_token_account()).await;
+    assert_ne!(assets, 0);
+    let creator_assets = ctx.token_balance(keys.asset_token_account(&creator)).await;
+    let creator_shares = ctx.token_balance(keys.share_token_account(&creator)).await;
+    let logs = close_pool_accounts(&mut ctx, keys, &sale.creator)
+        .await
+        .unwrap();
+    let closed = &events::<PoolAccountsClosed>(&logs)[0];
+    assert_eq!((closed.assets, closed.shares), (assets, shares));
+    assert_eq!(
+        ctx.token_balance(keys.asset_token_account(&creator)).await,
+        creator_assets + assets
+    );
+    assert_eq!(
+        ctx.token_balance(keys.share_token_account(&creator)).await,
+        creator_shares + shares
+    );
+    assert_closed_pool(&mut ctx, keys).await;
+}
+
+#[tokio::test]
+async fn unclaimed_shares_are_swept_after_the_grace_period() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool(&mut ctx).await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    let bob = ctx.fund_user(keys, USER_ASSETS).await;
+    ctx.warp_to(sale.sale_start_time).await;
+    buy(&mut ctx, keys, &alice, 100_000_000).await.unwrap();
+    buy(&mut ctx, keys, &bob, 100_000_000).await.unwrap();
+    ctx.warp_to(sale.sale_end_time).await;
+    close_pool(&mut ctx, keys, &sale.creator).await.unwrap();
+
+    // Bob never redeems his shares, which keep the pool open until the grace period is over
+    ctx.warp_to(sale.vest_end).await;
+    redeem(&mut ctx, keys, &alice).await.unwrap();
+    let bob_shares = user_state(&mut ctx, keys, &bob).await.purchased_shares;
+    let pool = ctx.account::<LiquidityBootstrappingPool>(keys.pool).await;
+    assert_eq!(pool.total_purchased, bob_shares);
+    ctx.warp_to(sale.vest_end + CLOSE_POOL_ACCOUNTS_GRACE_PERIOD - 1)
+        .await;
+    let result = close_pool_accounts(&mut ctx, keys, &sale.creator).await;
+    assert_error(result, PoolError::PoolAccountsClosingDisallowed.into());
+
+    ctx.warp_to(sale.vest_end + CLOSE_POOL_ACCOUNTS_GRACE_PERIOD)
+        .await;
+    let creator = sale.creator.pubkey();
+    let creator_shares = ctx.token_balance(keys.share_token_account(&creator)).await;
+    let logs = close_pool_accounts(&mut ctx, keys, &sale.creator)
+        .await
+        .unwrap();
+    let shares = events::<PoolAccountsClosed>(&logs)[0].shares;
+    assert!(shares >= bob_shares);
+    assert_eq!(
+        ctx.token_balance(keys.share_token_account(&creator)).await,
+        creator_shares + shares
+    );
+    assert_closed_pool(&mut ctx, keys).await;
+
+    // The state of bob is left behind, but can no longer be used with the marker in place of the pool
+    let result = redeem(&mut ctx, keys, &bob).await;
+    assert_error(
+        result,
+        anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into(),
+    );
+    let mut args = pool_args(ctx.now().await);
+    args.salt = "pool".to_string();
+    for (mint, token_program, amount) in [
+        (keys.asset_token_mint, keys.asset_token_program, args.assets),
+        (keys.share_token_mint, keys.share_token_program, args.shares),
+    ] {
+        ctx.mint_to(&mint, &token_program, &creator, amount).await;
+    }
+    let result = ctx
+        .process(
+            &[fjord_lbp_sdk::initialize_pool(keys, args)],
+            &[&sale.creator],
+        )
+        .await;
+    // The system program refuses to allocate the pool account, already in use by the marker
+    assert_error(result, SystemError::AccountAlreadyInUse as u32);
+    assert_closed_pool(&mut ctx, keys).await;
+}
+
+#[tokio::test]
+async fn native_sol_leftovers_are_unwrapped_to_the_creator() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool_for(&mut ctx, NATIVE_MINT, |args| {
+        args.vest_cliff = 0;
+        args.vest_end = 0;
+    })
+    .await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    ctx.warp_to(sale.sale_start_time).await;
+    buy(&mut ctx, keys, &alice, 100_000_200).await.unwrap();
+    ctx.warp_to(sale.sale_end_time).await;
+    close_pool(&mut ctx, keys, &sale.creator).await.unwrap();
+    redeem(&mut ctx, keys, &alice).await.unwrap();
+
+    let creator = sale.creator.pubkey();
+    let assets = ctx.token_balance(keys.pool_asset_token_account()).await;
+    assert_ne!(assets, 0);
+    let rent = ctx.lamports(keys.pool).await
+        + ctx.lamports(keys.pool_asset_token_account()).await
+        + ctx.lamports(keys.pool_share_token_account()).await;
+    let lamports = ctx.lamports(creator).await;
+    ctx.process(
+        &[fjord_lbp_sdk::close_pool_accounts(keys, true)],
+        &[&sale.creator],
+    )
+    .await
+    .unwrap();
+    // The lamports of the wSOL account include its remaining assets
+    let marker = assert_closed_pool(&mut ctx, keys).await;
+    assert_eq!(ctx.lamports(creator).await, lamports + rent - marker);
+    assert!(ctx
+        .get_account(keys.pool_asset_token_account())
+        .await
+        .is_none());
+}
+
+#[tokio::test]
+async fn withheld_transfer_fees_are_harvested_before_closing() {
+    let mut ctx = TestContext::new().await;
+    let asset_token_mint = ctx.create_mint(ASSET_DECIMALS, &TOKEN_PROGRAM_ID).await;
+    let share_token_mint = ctx.create_transfer_fee_mint(SHARE_DECIMALS, 100).await;
+    let (sale, result) =
+        try_create_pool_selling(&mut ctx, asset_token_mint, share_token_mint, |args| {
+            args.vest_cliff = 0;
+            args.vest_end = 0;
+        })
+        .await;
+    result.unwrap();
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    ctx.warp_to(sale.sale_start_time).await;
+    buy(&mut ctx, keys, &alice, 100_000_000).await.unwrap();
+    ctx.warp_to(sale.sale_end_time).await;
+    close_pool(&mut ctx, keys, &sale.creator).await.unwrap();
+    redeem(&mut ctx, keys, &alice).await.unwrap();
+
+    // The deposit of the shares left a fee withheld in the pool share account
+    let withheld = |account: &solana_sdk::account::Account| {
+        StateWithExtensions::<Account>::unpack(&account.data)
+            .unwrap()
+            .get_extension::<TransferFeeAmount>()
+            .unwrap()
+            .withheld_amount
+    };
+    let pool_share_token_account = ctx
+        .get_account(keys.pool_share_token_account())
+        .await
+        .unwrap();
+    assert_ne!(u64::from(withheld(&pool_share_token_account)), 0);
+
+    close_pool_accounts(&mut ctx, keys, &sale.creator)
+        .await
+        .unwrap();
+    assert!(ctx
+        .get_account(keys.pool_share_token_account())
+        .await
+        .is_none());
+    let mint = ctx.get_account(share_token_mint).await.unwrap();
+    let harvested = StateWithExtensions::<Mint>::unpack(&mint.data)
+        .unwrap()
+        .get_extension::<TransferFeeConfig>()
+        .unwrap()
+        .withheld_amount;
+    assert_ne!(u64::from(harvested), 0);
+}
+
+#[tokio::test]
+async fn cancelled_pools_leave_no_rent_to_reclaim() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool(&mut ctx).await;
+    let keys = &sale.keys;
+    let creator = sale.creator.pubkey();
+    let rent = ctx.lamports(keys.pool).await
+        + ctx.lamports(keys.pool_asset_token_account()).await
+        + ctx.lamports(keys.pool_share_token_account()).await;
+    let lamports = ctx.lamports(creator).await;
+
+    // Cancelling closes the pool and its token accounts at once
+    ctx.process(&[fjord_lbp_sdk::cancel_pool(keys)], &[&sale.creator])
+        .await
+        .unwrap();
+    assert_eq!(ctx.lamports(creator).await, lamports + rent);
+
+    let result = close_pool_accounts(&mut ctx, keys, &sale.creator).await;
+    assert_error(
+        result,
+        anchor_lang::error::ErrorCode::AccountNotInitialized.into(),
+    );
+}
@@ -0,0 +1,341 @@
+//! Referrals of swaps: validation of the referrers, their recording per user, their registry and their rewards.
+
+mod harness;
+
+use fjord_lbp::{
+    PoolError, Referral, ReferralRewardsClaimed, ReferralSchedule, ReferralTier, UserStateInPool,
+};
+use fjord_lbp_sdk::{find_native_unwrap_address, find_user_state_address, PoolKeys, SwapReferrer};
+use harness::*;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::TransactionError;
+
+async fn buy_referred(
+    ctx: &mut TestContext,
+    keys: &PoolKeys,
+    user: &Keypair,
+    assets_in: u64,
+    referrer: impl Into<SwapReferrer>,
+) -> Result<Vec<String>, TransactionError> {
+    ctx.process(
+        &[fjord_lbp_sdk::swap_exact_assets_for_shares(
+            keys,
+            &user.pubkey(),
+            assets_in,
+            1,
+            None,
+            Some(referrer.into()),
+            false,
+        )],
+        &[user],
+    )
+    .await
+}
+
+async fn sell_referred(
+    ctx: &mut TestContext,
+    keys: &PoolKeys,
+    user: &Keypair,
+    shares_in: u64,
+    referrer: Option<SwapReferrer>,
+) -> Result<Vec<String>, TransactionError> {
+    ctx.process(
+        &[fjord_lbp_sdk::swap_exact_shares_for_assets(
+            keys,
+            &user.pubkey(),
+            shares_in,
+            1,
+            None,
+            referrer,
+            false,
+        )],
+        &[user],
+    )
+    .await
+}
+
+#[tokio::test]
+async fn referrers_are_recorded_and_debited_on_sales() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool(&mut ctx).await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    let bob = Keypair::new().pubkey();
+    let carol = Keypair::new().pubkey();
+    ctx.warp_to(sale.sale_start_time).await;
+
+    let result = buy_referred(&mut ctx, keys, &alice, 100_000_000, alice.pubkey()).await;
+    assert_error(result, PoolError::SelfReferral.into());
+
+    let logs = buy_referred(&mut ctx, keys, &alice, 100_000_000, bob)
+        .await
+        .unwrap();
+    let referral = &events::<Referral>(&logs)[0];
+    assert_eq!(referral.pool, keys.pool);
+    assert_eq!(referral.referrer, bob);
+    assert_eq!(referral.user, alice.pubkey());
+    assert_eq!(referral.assets, 100_000_000);
+    assert_eq!(referral.referred_assets, 1_000_000);
+    assert!(!referral.is_sell);
+    let alice_state = user_state(&mut ctx, keys, &alice).await;
+    assert_eq!(alice_state.referrer, bob);
+
+    // The referrer sticks to the user
+    let result = buy_referred(&mut ctx, keys, &alice, 100_000_000, carol).await;
+    assert_error(result, PoolError::InvalidReferrer.into());
+    let result = sell_referred(
+        &mut ctx,
+        keys,
+        &alice,
+        alice_state.purchased_shares / 10,
+        None,
+    )
+    .await;
+    assert_error(result, PoolError::InvalidReferrer.into());
+
+    // Selling debits the referrer of the referral fee on the assets taken back
+    let logs = sell_referred(
+        &mut ctx,
+        keys,
+        &alice,
+        alice_state.purchased_shares / 10,
+        Some(bob.into()),
+    )
+    .await
+    .unwrap();
+    let referral = &events::<Referral>(&logs)[0];
+    assert!(referral.is_sell);
+    assert!(referral.referred_assets > 0);
+    let bob_state = ctx
+        .account::<UserStateInPool>(find_user_state_address(&bob, &keys.pool).0)
+        .await;
+    assert_eq!(
+        bob_state.referred_assets,
+        1_000_000 - referral.referred_assets
+    );
+}
+
+#[tokio::test]
+async fn required_registry_only_accepts_registered_referrers() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool(&mut ctx).await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    let bob = Keypair::new().pubkey();
+    // The owner pays for the registrations
+    let owner = ctx.fund_wallet(ctx.owner.insecure_clone()).await;
+    ctx.process(
+        &[fjord_lbp_sdk::set_referrer_registry_required(
+            &owner.pubkey(),
+            true,
+        )],
+        &[&owner],
+    )
+    .await
+    .unwrap();
+    ctx.warp_to(sale.sale_start_time).await;
+
+    let result = buy_referred(&mut ctx, keys, &alice, 100_000_000, bob).await;
+    assert_error(result, PoolError::UnregisteredReferrer.into());
+
+    ctx.process(
+        &[fjord_lbp_sdk::register_referrer(&owner.pubkey(), bob)],
+        &[&owner],
+    )
+    .await
+    .unwrap();
+    buy_referred(&mut ctx, keys, &alice, 100_000_000, bob)
+        .await
+        .unwrap();
+
+    // Unregistered referrers stop being accepted for new purchases
+    ctx.process(
+        &[fjord_lbp_sdk::unregister_referrer(&owner.pubkey(), &bob)],
+        &[&owner],
+    )
+    .await
+    .unwrap();
+    let result = buy_referred(&mut ctx, keys, &alice, 100_000_000, bob).await;
+    assert_error(result, PoolError::UnregisteredReferrer.into());
+}
+
+#[tokio::test]
+async fn referral_schedules_apply_volume_tiers_and_second_level_splits() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool_with(&mut ctx, |args| {
+        args.referral_schedule = ReferralSchedule {
+            fee_basis_points: Some(200),
+            second_level_basis_points: 2_000,
+            ..ReferralSchedule::default()
+        };
+        args.referral_schedule.tiers[0] = ReferralTier {
+            min_referred_volume: 100_000_000,
+            fee_basis_points: 500,
+        };
+    })
+    .await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    let bob = ctx.fund_user(keys, USER_ASSETS).await;
+    let carol = Keypair::new().pubkey();
+    ctx.warp_to(sale.sale_start_time).await;
+
+    // Carol refers Bob at the fee of the pool, with no second level
+    let logs = buy_referred(&mut ctx, keys, &bob, 100_000_000, carol)
+        .await
+        .unwrap();
+    let referral = &events::<Referral>(&logs)[0];
+    assert_eq!(referral.referred_assets, 2_000_000);
+    assert_eq!(referral.second_level_referrer, Pubkey::default());
+
+    // Bob refers Alice, splitting the referral fee with Carol, whose state must come along
+    let result = buy_referred(&mut ctx, keys, &alice, 100_000_000, bob.pubkey()).await;
+    assert_error(result, PoolError::InvalidReferrer.into());
+    let referrer = SwapReferrer {
+        referrer: bob.pubkey(),
+        second_level_referrer: Some(carol),
+    };
+    let logs = buy_referred(&mut ctx, keys, &alice, 100_000_000, referrer)
+        .await
+        .unwrap();
+    let referral = &events::<Referral>(&logs)[0];
+    assert_eq!(referral.referred_assets, 1_600_000);
+    assert_eq!(referral.second_level_referrer, carol);
+    assert_eq!(referral.second_level_referred_assets, 400_000);
+
+    // Having brought 100 assets, Bob reached the higher tier
+    ctx.warp_to(sale.sale_start_time + 1).await;
+    let logs = buy_referred(&mut ctx, keys, &alice, 100_000_000, referrer)
+        .await
+        .unwrap();
+    let referral = &events::<Referral>(&logs)[0];
+    assert_eq!(referral.referred_assets, 4_000_000);
+    assert_eq!(referral.second_level_referred_assets, 1_000_000);
+
+    let bob_state = user_state(&mut ctx, keys, &bob).await;
+    assert_eq!(bob_state.referred_volume, 200_000_000);
+    assert_eq!(bob_state.referred_assets, 5_600_000);
+    let carol_state = ctx
+        .account::<UserStateInPool>(find_user_state_address(&carol, &keys.pool).0)
+        .await;
+    assert_eq!(carol_state.referred_assets, 2_000_000 + 1_400_000);
+}
+
+#[tokio::test]
+async fn pools_close_at_the_highest_referral_fee_allowed() {
+    let mut ctx = TestContext::new().await;
+    let max_referral_fee = 10_000 - PLATFORM_FEE - SWAP_FEE;
+    // The proceeds are all the pool holds, with the reserve of assets all virtual
+    let sale = create_pool_with(&mut ctx, |args| {
+        args.assets = 0;
+        args.virtual_assets = POOL_ASSETS;
+        args.referral_schedule.fee_basis_points = Some(max_referral_fee);
+        args.referral_schedule.tiers[0] = ReferralTier {
+            min_referred_volume: 100_000_000,
+            fee_basis_points: max_referral_fee,
+        };
+    })
+    .await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    let bob = Keypair::new().pubkey();
+    ctx.warp_to(sale.sale_start_time).await;
+    buy_referred(&mut ctx, keys, &alice, 100_000_000, bob)
+        .await
+        .unwrap();
+    ctx.warp_to(sale.sale_start_time + 1).await;
+    let logs = buy_referred(&mut ctx, keys, &alice, 100_000_000, bob)
+        .await
+        .unwrap();
+    assert_eq!(events::<Referral>(&logs)[0].referred_assets, 98_000_000);
+
+    // The referral rewards are paid out of the proceeds along with the platform fee
+    ctx.warp_to(sale.sale_end_time).await;
+    close_pool(&mut ctx, keys, &sale.creator).await.unwrap();
+}
+
+#[tokio::test]
+async fn referrers_claim_their_rewards_once_the_pool_is_closed() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool(&mut ctx).await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    // Bob never buys, and pays for the asset token account the rewards go to
+    let bob = ctx.fund_wallet(Keypair::new()).await;
+    let claim = fjord_lbp_sdk::claim_referral_rewards(keys, &bob.pubkey(), false);
+    ctx.warp_to(sale.sale_start_time).await;
+    buy_referred(&mut ctx, keys, &alice, 100_000_000, bob.pubkey())
+        .await
+        .unwrap();
+
+    let result = ctx.process(std::slice::from_ref(&claim), &[&bob]).await;
+    assert_error(result, PoolError::ReferralClaimDisallowed.into());
+
+    ctx.warp_to(sale.sale_end_time).await;
+    close_pool(&mut ctx, keys, &sale.creator).await.unwrap();
+    let logs = ctx.process(&[claim], &[&bob]).await.unwrap();
+    let claimed = &events::<ReferralRewardsClaimed>(&logs)[0];
+    assert_eq!(claimed.pool, keys.pool);
+    assert_eq!(claimed.referrer, bob.pubkey());
+    assert_eq!(claimed.assets, 1_000_000);
+    assert_eq!(
+        ctx.token_balance(keys.asset_token_account(&bob.pubkey()))
+            .await,
+        1_000_000
+    );
+
+    // Having no shares to redeem, the state of the referrer is closed
+    let bob_state = find_user_state_address(&bob.pubkey(), &keys.pool).0;
+    assert!(ctx.get_account(bob_state).await.is_none());
+}
+
+#[tokio::test]
+async fn referrers_claim_their_rewards_in_native_sol() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool_for(&mut ctx, NATIVE_MINT, |_| {}).await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    let bob = ctx.fund_wallet(Keypair::new()).await;
+    ctx.warp_to(sale.sale_start_time).await;
+    buy_referred(&mut ctx, keys, &alice, 100_000_000, bob.pubkey())
+        .await
+        .unwrap();
+    ctx.warp_to(sale.sale_end_time).await;
+    close_pool(&mut ctx, keys, &sale.creator).await.unwrap();
+
+    // Paid straight to the wallet, without an asset token account, along with the rent of the closed state
+    let bob_state = find_user_state_address(&bob.pubkey(), &keys.pool).0;
+    let lamports = ctx.lamports(bob.pubkey()).await + ctx.lamports(bob_state).await;
+    let claim = fjord_lbp_sdk::claim_referral_rewards(keys, &bob.pubkey(), true);
+    let logs = ctx.process(&[claim], &[&bob]).await.unwrap();
+    assert_eq!(events::<ReferralRewardsClaimed>(&logs)[0].assets, 1_000_000);
+    assert_eq!(ctx.lamports(bob.pubkey()).await, lamports + 1_000_000);
+    assert!(ctx
+        .get_account(keys.asset_token_account(&bob.pubkey()))
+        .await
+        .is_none());
+    assert!(ctx
+        .get_account(find_native_unwrap_address(&keys.pool, &bob.pubkey()).0)
+        .await
+        .is_none());
+}
+
+#[tokio::test]
+async fn native_sol_claims_need_a_native_sol_pool() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool(&mut ctx).await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    let bob = ctx.fund_wallet(Keypair::new()).await;
+    ctx.warp_to(sale.sale_start_time).await;
+    buy_referred(&mut ctx, keys, &alice, 100_000_000, bob.pubkey())
+        .await
+        .unwrap();
+    ctx.warp_to(sale.sale_end_time).await;
+    close_pool(&mut ctx, keys, &sale.creator).await.unwrap();
+
+    let claim = fjord_lbp_sdk::claim_referral_rewards(keys, &bob.pubkey(), true);
+    let result = ctx.process(&[claim], &[&bob]).await;
+    assert_error(result, PoolError::NativeSolDisallowed.into());
+}
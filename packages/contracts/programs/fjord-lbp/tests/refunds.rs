@@ -0,0 +1,120 @@
+//! Refunds of the buyers of a sale closed below its soft cap (`min_assets_raised`).
+
+mod harness;
+
+use fjord_lbp::{LiquidityBootstrappingPool, PoolError, Refund};
+use fjord_lbp_sdk::find_user_state_address;
+use harness::*;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::TransactionError;
+
+// More than the buyers of these tests ever pay
+const SOFT_CAP: u64 = 5_000_000_000;
+
+async fn refund(
+    ctx: &mut TestContext,
+    keys: &fjord_lbp_sdk::PoolKeys,
+    user: &Keypair,
+) -> Result<Vec<String>, TransactionError> {
+    ctx.process(
+        &[fjord_lbp_sdk::refund(keys, &user.pubkey(), false)],
+        &[user],
+    )
+    .await
+}
+
+#[tokio::test]
+async fn buyers_are_refunded_what_they_paid() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool_with(&mut ctx, |args| args.min_assets_raised = SOFT_CAP).await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    let bob = ctx.fund_user(keys, USER_ASSETS).await;
+    ctx.warp_to(sale.sale_start_time + ONE_DAY / 2).await;
+
+    buy(&mut ctx, keys, &alice, 100_000_000).await.unwrap();
+    buy(&mut ctx, keys, &bob, 200_000_000).await.unwrap();
+    assert_eq!(
+        user_state(&mut ctx, keys, &bob).await.assets_paid,
+        200_000_000
+    );
+
+    // Alice sells part of her shares back, which she is no longer owed
+    let shares = user_state(&mut ctx, keys, &alice).await.purchased_shares;
+    ctx.process(
+        &[fjord_lbp_sdk::swap_exact_shares_for_assets(
+            keys,
+            &alice.pubkey(),
+            shares / 10,
+            1,
+            None,
+            None,
+            false,
+        )],
+        &[&alice],
+    )
+    .await
+    .unwrap();
+    let alice_paid = USER_ASSETS
+        - ctx
+            .token_balance(keys.asset_token_account(&alice.pubkey()))
+            .await;
+    assert_eq!(
+        user_state(&mut ctx, keys, &alice).await.assets_paid,
+        alice_paid
+    );
+    let pool = ctx.account::<LiquidityBootstrappingPool>(keys.pool).await;
+    assert_eq!(pool.total_assets_paid, alice_paid + 200_000_000);
+
+    // Refunds are only available once the pool is closed
+    let result = refund(&mut ctx, keys, &alice).await;
+    assert_error(result, PoolError::RefundingDisallowed.into());
+
+    ctx.warp_to(sale.sale_end_time).await;
+    close_pool(&mut ctx, keys, &sale.creator).await.unwrap();
+    assert_eq!(
+        ctx.token_balance(keys.asset_token_account(&sale.creator.pubkey()))
+            .await,
+        POOL_ASSETS
+    );
+
+    for (user, paid) in [(&alice, alice_paid), (&bob, 200_000_000)] {
+        let logs = refund(&mut ctx, keys, user).await.unwrap();
+        let refund = &events::<Refund>(&logs)[0];
+        assert_eq!(refund.caller, user.pubkey());
+        assert_eq!(refund.assets, paid);
+        assert_eq!(
+            ctx.token_balance(keys.asset_token_account(&user.pubkey()))
+                .await,
+            USER_ASSETS
+        );
+        // The state of the refunded user is closed, returning its rent
+        let state = find_user_state_address(&user.pubkey(), &keys.pool).0;
+        assert!(ctx.get_account(state).await.is_none());
+    }
+    assert_eq!(ctx.token_balance(keys.pool_asset_token_account()).await, 0);
+
+    // Nothing is left to refund, on a new slot so that the refund is not taken for a replay
+    let now = ctx.now().await;
+    ctx.warp_to(now + 1).await;
+    let result = refund(&mut ctx, keys, &bob).await;
+    assert_error(
+        result,
+        anchor_lang::error::ErrorCode::AccountNotInitialized.into(),
+    );
+}
+
+#[tokio::test]
+async fn refunds_are_disallowed_when_the_soft_cap_is_reached() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool(&mut ctx).await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    ctx.warp_to(sale.sale_start_time).await;
+    buy(&mut ctx, keys, &alice, 100_000_000).await.unwrap();
+
+    ctx.warp_to(sale.sale_end_time).await;
+    close_pool(&mut ctx, keys, &sale.creator).await.unwrap();
+    let result = refund(&mut ctx, keys, &alice).await;
+    assert_error(result, PoolError::RefundingDisallowed.into());
+}
@@ -0,0 +1,182 @@
+//! Soft cap (`min_assets_raised`) and hard cap (`max_assets_raised`) of the sale.
+
+mod harness;
+
+use fjord_lbp::{Close, LiquidityBootstrappingPool, PoolError, SaleFinalized};
+use harness::*;
+use solana_sdk::signature::Signer;
+
+// 100 assets, of which 99 are raised after the 1% swap fee
+const BUY_ASSETS: u64 = 100_000_000;
+const BUY_ASSETS_RAISED: u64 = 99_000_000;
+
+#[tokio::test]
+async fn hard_cap_ends_the_sale() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool_with(&mut ctx, |args| args.max_assets_raised = 150_000_000).await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    let bob = ctx.fund_user(keys, USER_ASSETS).await;
+    ctx.warp_to(sale.sale_start_time + ONE_DAY / 2).await;
+
+    let logs = buy(&mut ctx, keys, &alice, BUY_ASSETS).await.unwrap();
+    assert!(events::<SaleFinalized>(&logs).is_empty());
+
+    // The purchase raising the 51 assets left, after the 1% swap fee, reaches the hard cap and ends the sale
+    let now = ctx.now().await;
+    let logs = buy(&mut ctx, keys, &bob, 51_515_151).await.unwrap();
+    let finalized = &events::<SaleFinalized>(&logs)[0];
+    assert_eq!(finalized.pool, keys.pool);
+    assert_eq!(finalized.total_assets_raised, 150_000_000);
+    assert_eq!(finalized.sale_end_time, now);
+    let pool = ctx.account::<LiquidityBootstrappingPool>(keys.pool).await;
+    assert_eq!(pool.sale_end_time, now);
+    assert_eq!(pool.total_assets_raised, 150_000_000);
+
+    let result = buy(&mut ctx, keys, &alice, BUY_ASSETS).await;
+    assert_error(result, PoolError::TradingDisallowed.into());
+
+    // The pool can be closed right away
+    let logs = close_pool(&mut ctx, keys, &sale.creator).await.unwrap();
+    assert!(!events::<Close>(&logs)[0].refunding);
+}
+
+#[tokio::test]
+async fn purchases_crossing_the_hard_cap_are_rejected() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool_with(&mut ctx, |args| args.max_assets_raised = 150_000_000).await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    let bob = ctx.fund_user(keys, USER_ASSETS).await;
+    ctx.warp_to(sale.sale_start_time + ONE_DAY / 2).await;
+
+    // A single purchase above the hard cap is rejected outright
+    let result = buy(&mut ctx, keys, &alice, 2 * BUY_ASSETS).await;
+    assert_error(result, PoolError::HardCapExceeded.into());
+
+    // So is the one that would take the assets raised past it, by a single unit
+    buy(&mut ctx, keys, &alice, BUY_ASSETS).await.unwrap();
+    let result = buy(&mut ctx, keys, &bob, 51_515_152).await;
+    assert_error(result, PoolError::HardCapExceeded.into());
+
+    // Nothing was taken from the rejected buyer and the sale goes on
+    assert_eq!(
+        ctx.token_balance(keys.asset_token_account(&bob.pubkey()))
+            .await,
+        USER_ASSETS
+    );
+    let pool = ctx.account::<LiquidityBootstrappingPool>(keys.pool).await;
+    assert_eq!(pool.total_assets_raised, BUY_ASSETS_RAISED);
+    assert_eq!(pool.sale_end_time, sale.sale_end_time);
+}
+
+#[tokio::test]
+async fn sales_are_tracked_net_of_sells() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool(&mut ctx).await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    ctx.warp_to(sale.sale_start_time).await;
+
+    buy(&mut ctx, keys, &alice, BUY_ASSETS).await.unwrap();
+    ctx.warp_to(sale.sale_start_time + ONE_DAY).await;
+    let shares = user_state(&mut ctx, keys, &alice).await.purchased_shares;
+    let assets_before = ctx
+        .token_balance(keys.asset_token_account(&alice.pubkey()))
+        .await;
+    ctx.process(
+        &[fjord_lbp_sdk::swap_exact_shares_for_assets(
+            keys,
+            &alice.pubkey(),
+            shares / 10,
+            1,
+            None,
+            None,
+            false,
+        )],
+        &[&alice],
+    )
+    .await
+    .unwrap();
+    let assets_out = ctx
+        .token_balance(keys.asset_token_account(&alice.pubkey()))
+        .await
+        - assets_before;
+
+    let pool = ctx.account::<LiquidityBootstrappingPool>(keys.pool).await;
+    assert_eq!(pool.total_assets_raised, BUY_ASSETS_RAISED - assets_out);
+}
+
+#[tokio::test]
+async fn missing_the_soft_cap_closes_in_refund_mode() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool_with(&mut ctx, |args| args.min_assets_raised = 1_000_000_000).await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    ctx.warp_to(sale.sale_start_time).await;
+    buy(&mut ctx, keys, &alice, BUY_ASSETS).await.unwrap();
+
+    ctx.warp_to(sale.sale_end_time).await;
+    let logs = close_pool(&mut ctx, keys, &sale.creator).await.unwrap();
+    let close = &events::<Close>(&logs)[0];
+    assert!(close.refunding);
+    assert_eq!(close.platform_fees, 0);
+    assert_eq!(close.assets, POOL_ASSETS);
+    let pool = ctx.account::<LiquidityBootstrappingPool>(keys.pool).await;
+    assert!(pool.closed && pool.refunding);
+
+    // No fees are taken, the creator gets everything it provided back and the buyer's assets stay in the pool
+    let creator = sale.creator.pubkey();
+    assert_eq!(
+        ctx.token_balance(keys.asset_token_account(&creator)).await,
+        POOL_ASSETS
+    );
+    assert_eq!(
+        ctx.token_balance(keys.share_token_account(&creator)).await,
+        POOL_SHARES
+    );
+    assert_eq!(
+        ctx.token_balance(keys.pool_asset_token_account()).await,
+        BUY_ASSETS
+    );
+    assert_eq!(ctx.token_balance(keys.pool_share_token_account()).await, 0);
+    let swap_fee_recipient = ctx.treasury.swap_fee_recipient;
+    assert_eq!(
+        ctx.token_balance(keys.asset_token_account(&swap_fee_recipient))
+            .await,
+        0
+    );
+    for recipient in ctx.treasury.fee_recipients.clone() {
+        assert_eq!(
+            ctx.token_balance(keys.asset_token_account(&recipient.user))
+                .await,
+            0
+        );
+    }
+
+    // The purchased shares cannot be redeemed
+    ctx.warp_to(sale.vest_end).await;
+    let result = ctx
+        .process(
+            &[fjord_lbp_sdk::redeem(keys, &alice.pubkey(), false, false)],
+            &[&alice],
+        )
+        .await;
+    assert_error(result, PoolError::RedeemingDisallowed.into());
+}
+
+#[tokio::test]
+async fn reaching_the_soft_cap_closes_normally() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool_with(&mut ctx, |args| args.min_assets_raised = BUY_ASSETS_RAISED).await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    ctx.warp_to(sale.sale_start_time).await;
+    buy(&mut ctx, keys, &alice, BUY_ASSETS).await.unwrap();
+
+    ctx.warp_to(sale.sale_end_time).await;
+    let logs = close_pool(&mut ctx, keys, &sale.creator).await.unwrap();
+    let close = &events::<Close>(&logs)[0];
+    assert!(!close.refunding);
+    assert!(close.platform_fees > 0);
+}
@@ -0,0 +1,238 @@
+//! Updates of the pool configuration and reserves by its creator before the sale starts.
+
+mod harness;
+
+use fjord_lbp::{
+    instruction, LiquidityBootstrappingPool, PoolConfigUpdated, PoolError, WeightCurve, WeightKnot,
+    MAX_WEIGHT_KNOTS,
+};
+use fjord_lbp_sdk::PoolKeys;
+use harness::*;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::TransactionError;
+
+fn no_changes() -> instruction::UpdatePoolConfig {
+    instruction::UpdatePoolConfig {
+        assets: 0,
+        shares: 0,
+        virtual_assets: None,
+        virtual_shares: None,
+        max_share_price: None,
+        start_weight_basis_points: None,
+        end_weight_basis_points: None,
+        sale_start_time: None,
+        sale_end_time: None,
+        vest_cliff: None,
+        vest_end: None,
+        weight_curve: None,
+        weight_knots: None,
+    }
+}
+
+async fn update_pool_config(
+    ctx: &mut TestContext,
+    keys: &PoolKeys,
+    creator: &Keypair,
+    args: instruction::UpdatePoolConfig,
+) -> Result<Vec<String>, TransactionError> {
+    ctx.process(&[fjord_lbp_sdk::update_pool_config(keys, args)], &[creator])
+        .await
+}
+
+#[tokio::test]
+async fn creator_can_update_the_pool_before_the_sale() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool(&mut ctx).await;
+    let keys = &sale.keys;
+    ctx.mint_to(
+        &keys.share_token_mint,
+        &TOKEN_2022_PROGRAM_ID,
+        &sale.creator.pubkey(),
+        POOL_SHARES,
+    )
+    .await;
+
+    // Only the creator can update its pool
+    let carol = ctx.fund_user(keys, USER_ASSETS).await;
+    ctx.mint_to(
+        &keys.share_token_mint,
+        &TOKEN_2022_PROGRAM_ID,
+        &carol.pubkey(),
+        POOL_SHARES,
+    )
+    .await;
+    let result = update_pool_config(
+        &mut ctx,
+        &PoolKeys {
+            creator: carol.pubkey(),
+            ..keys.clone()
+        },
+        &carol,
+        no_changes(),
+    )
+    .await;
+    assert_error(
+        result,
+        anchor_lang::error::ErrorCode::ConstraintSeeds.into(),
+    );
+
+    let logs = update_pool_config(
+        &mut ctx,
+        keys,
+        &sale.creator,
+        instruction::UpdatePoolConfig {
+            shares: POOL_SHARES,
+            start_weight_basis_points: Some(9000),
+            end_weight_basis_points: Some(1000),
+            sale_end_time: Some(sale.sale_end_time + ONE_DAY),
+            ..no_changes()
+        },
+    )
+    .await
+    .unwrap();
+    let updated = &events::<PoolConfigUpdated>(&logs)[0];
+    assert_eq!(updated.pool, keys.pool);
+    assert_eq!(updated.assets, 0);
+    assert_eq!(updated.shares, POOL_SHARES);
+
+    let pool = ctx.account::<LiquidityBootstrappingPool>(keys.pool).await;
+    assert_eq!(pool.start_weight_basis_points, 9000);
+    assert_eq!(pool.end_weight_basis_points, 1000);
+    assert_eq!(pool.sale_start_time, sale.sale_start_time);
+    assert_eq!(pool.sale_end_time, sale.sale_end_time + ONE_DAY);
+    assert_eq!(
+        ctx.token_balance(keys.pool_share_token_account()).await,
+        2 * POOL_SHARES
+    );
+    assert_eq!(
+        ctx.token_balance(keys.pool_asset_token_account()).await,
+        POOL_ASSETS
+    );
+}
+
+#[tokio::test]
+async fn updates_are_validated_as_the_creation() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool(&mut ctx).await;
+    let keys = &sale.keys;
+
+    let result = update_pool_config(
+        &mut ctx,
+        keys,
+        &sale.creator,
+        instruction::UpdatePoolConfig {
+            start_weight_basis_points: Some(9950),
+            ..no_changes()
+        },
+    )
+    .await;
+    assert_error(result, PoolError::InvalidWeightConfig.into());
+
+    let result = update_pool_config(
+        &mut ctx,
+        keys,
+        &sale.creator,
+        instruction::UpdatePoolConfig {
+            sale_end_time: Some(sale.sale_start_time + ONE_DAY / 2),
+            ..no_changes()
+        },
+    )
+    .await;
+    assert_error(result, PoolError::SalePeriodLow.into());
+
+    // The creator has no tokens left after the creation
+    let result = update_pool_config(
+        &mut ctx,
+        keys,
+        &sale.creator,
+        instruction::UpdatePoolConfig {
+            assets: 1,
+            ..no_changes()
+        },
+    )
+    .await;
+    assert_error(result, PoolError::InsufficientAssets.into());
+}
+
+#[tokio::test]
+async fn sale_start_cannot_be_moved_into_the_past() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool(&mut ctx).await;
+    let now = ctx.now().await;
+
+    let result = update_pool_config(
+        &mut ctx,
+        &sale.keys,
+        &sale.creator,
+        instruction::UpdatePoolConfig {
+            sale_start_time: Some(now - 1),
+            ..no_changes()
+        },
+    )
+    .await;
+    assert_error(result, PoolError::SalePeriodLow.into());
+}
+
+#[tokio::test]
+async fn creator_can_update_the_weight_curve_before_the_sale() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool(&mut ctx).await;
+    let keys = &sale.keys;
+    let mut weight_knots = [WeightKnot::default(); MAX_WEIGHT_KNOTS];
+    weight_knots[0] = WeightKnot {
+        timestamp: sale.sale_start_time + ONE_DAY / 2,
+        weight_basis_points: 6000,
+    };
+
+    // Knots are validated against the sale period like at creation
+    let mut late_knots = weight_knots;
+    late_knots[0].timestamp = sale.sale_end_time;
+    let result = update_pool_config(
+        &mut ctx,
+        keys,
+        &sale.creator,
+        instruction::UpdatePoolConfig {
+            weight_curve: Some(WeightCurve::Piecewise),
+            weight_knots: Some(late_knots),
+            ..no_changes()
+        },
+    )
+    .await;
+    assert_error(result, PoolError::InvalidWeightConfig.into());
+
+    update_pool_config(
+        &mut ctx,
+        keys,
+        &sale.creator,
+        instruction::UpdatePoolConfig {
+            weight_curve: Some(WeightCurve::Piecewise),
+            weight_knots: Some(weight_knots),
+            ..no_changes()
+        },
+    )
+    .await
+    .unwrap();
+    let pool = ctx.account::<LiquidityBootstrappingPool>(keys.pool).await;
+    assert_eq!(pool.weight_curve, WeightCurve::Piecewise);
+    assert_eq!(pool.weight_knots, weight_knots);
+}
+
+#[tokio::test]
+async fn started_sales_cannot_be_updated() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool(&mut ctx).await;
+    let keys = &sale.keys;
+    ctx.warp_to(sale.sale_start_time).await;
+
+    let result = update_pool_config(
+        &mut ctx,
+        keys,
+        &sale.creator,
+        instruction::UpdatePoolConfig {
+            sale_end_time: Some(sale.sale_end_time + ONE_DAY),
+            ..no_changes()
+        },
+    )
+    .await;
+    assert_error(result, PoolError::UpdatingDisallowed.into());
+}
@@ -0,0 +1,87 @@
+//! Per-wallet allocation limits (`max_assets_per_user`, `max_shares_per_user`) and minimum buy size.
+
+mod harness;
+
+use fjord_lbp::PoolError;
+use harness::*;
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn purchases_below_the_minimum_are_rejected() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool_with(&mut ctx, |args| args.min_assets_per_buy = 10_000_000).await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    ctx.warp_to(sale.sale_start_time).await;
+
+    let result = buy(&mut ctx, keys, &alice, 9_999_999).await;
+    assert_error(result, PoolError::AssetsInTooLow.into());
+    buy(&mut ctx, keys, &alice, 10_000_000).await.unwrap();
+}
+
+#[tokio::test]
+async fn assets_per_user_are_capped() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool_with(&mut ctx, |args| args.max_assets_per_user = 99_000_000).await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    let bob = ctx.fund_user(keys, USER_ASSETS).await;
+    ctx.warp_to(sale.sale_start_time + ONE_DAY).await;
+
+    // The cap applies to the assets spent net of the 1% swap fee, along with the purchase being made
+    buy(&mut ctx, keys, &alice, 60_000_000).await.unwrap();
+    let result = buy(&mut ctx, keys, &alice, 40_000_001).await;
+    assert_error(result, PoolError::UserAssetsInExceeded.into());
+    buy(&mut ctx, keys, &alice, 40_000_000).await.unwrap();
+    let state = user_state(&mut ctx, keys, &alice).await;
+    assert_eq!(state.assets_spent, 99_000_000);
+    assert_eq!(state.assets_paid, 100_000_000);
+
+    // The limit applies to each wallet separately
+    buy(&mut ctx, keys, &bob, 100_000_000).await.unwrap();
+}
+
+#[tokio::test]
+async fn shares_per_user_are_capped() {
+    let mut ctx = TestContext::new().await;
+    let max_shares = POOL_SHARES / 100;
+    let sale = create_pool_with(&mut ctx, |args| args.max_shares_per_user = max_shares).await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    ctx.warp_to(sale.sale_start_time + ONE_DAY).await;
+
+    let result = ctx
+        .process(
+            &[fjord_lbp_sdk::swap_assets_for_exact_shares(
+                keys,
+                &alice.pubkey(),
+                max_shares + 1,
+                u64::MAX,
+                None,
+                None,
+                false,
+            )],
+            &[&alice],
+        )
+        .await;
+    assert_error(result, PoolError::UserSharesOutExceeded.into());
+
+    ctx.process(
+        &[fjord_lbp_sdk::swap_assets_for_exact_shares(
+            keys,
+            &alice.pubkey(),
+            max_shares,
+            u64::MAX,
+            None,
+            None,
+            false,
+        )],
+        &[&alice],
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        user_state(&mut ctx, keys, &alice).await.purchased_shares,
+        max_shares
+    );
+}
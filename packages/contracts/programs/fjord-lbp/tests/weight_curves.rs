@@ -0,0 +1,84 @@
+//! Exponential and piecewise weight schedules, priced on chain as previewed by the SDK.
+
+mod harness;
+
+use fjord_lbp::{Buy, WeightCurve, WeightKnot, MAX_WEIGHT_KNOTS};
+use harness::*;
+
+/// Buy at `current_time` and check the shares received against the preview of the SDK.
+async fn buy_at(ctx: &mut TestContext, sale: &Sale, current_time: i64) -> u64 {
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    ctx.warp_to(current_time).await;
+    let expected_shares = snapshot(ctx, keys)
+        .await
+        .preview_shares_out(100_000_000, current_time)
+        .unwrap();
+    let logs = buy(ctx, keys, &alice, 100_000_000).await.unwrap();
+    let shares = events::<Buy>(&logs)[0].shares;
+    assert_eq!(shares, expected_shares);
+    shares
+}
+
+#[tokio::test]
+async fn exponential_weights_move_faster_early() {
+    let mut ctx = TestContext::new().await;
+    let linear = create_pool(&mut ctx).await;
+    let exponential = create_pool_with(&mut ctx, |args| {
+        args.weight_curve = WeightCurve::Exponential {
+            half_life: ONE_DAY / 2,
+        }
+    })
+    .await;
+    let midpoint = (exponential.sale_start_time + exponential.sale_end_time) / 2;
+
+    // 9000 to 5000 basis points over 4 half-lives: (1 - 1/4) / (1 - 1/16) = 80% of the way halfway through
+    let snapshot = snapshot(&mut ctx, &exponential.keys).await;
+    let weights = snapshot.reserves_and_weights(midpoint).unwrap();
+    assert_eq!(weights.asset_weight, 5800);
+    assert_eq!(weights.share_weight, 10_000 - 5800);
+    assert_eq!(
+        snapshot
+            .reserves_and_weights(exponential.sale_end_time)
+            .unwrap()
+            .asset_weight,
+        5000
+    );
+
+    // The asset weight is already lower than with the linear schedule, pricing the shares higher
+    let linear_shares = buy_at(&mut ctx, &linear, midpoint).await;
+    let exponential_shares = buy_at(&mut ctx, &exponential, midpoint).await;
+    assert!(exponential_shares < linear_shares);
+}
+
+#[tokio::test]
+async fn piecewise_weights_go_through_their_knots() {
+    let mut ctx = TestContext::new().await;
+    let now = ctx.now().await;
+    let knot_time = pool_args(now).sale_start_time + ONE_DAY / 2;
+    let sale = create_pool_with(&mut ctx, |args| {
+        args.weight_curve = WeightCurve::Piecewise;
+        args.weight_knots = [WeightKnot::default(); MAX_WEIGHT_KNOTS];
+        args.weight_knots[0] = WeightKnot {
+            timestamp: knot_time,
+            weight_basis_points: 6000,
+        };
+    })
+    .await;
+    let snapshot = snapshot(&mut ctx, &sale.keys).await;
+    let weight_at = |current_time| {
+        snapshot
+            .reserves_and_weights(current_time)
+            .unwrap()
+            .asset_weight
+    };
+
+    // 9000 down to 6000 within half a day, then down to 5000 by the end of the sale
+    assert_eq!(weight_at(sale.sale_start_time), 9000);
+    assert_eq!(weight_at(sale.sale_start_time + ONE_DAY / 4), 7500);
+    assert_eq!(weight_at(knot_time), 6000);
+    assert_eq!(weight_at((knot_time + sale.sale_end_time) / 2), 5500);
+    assert_eq!(weight_at(sale.sale_end_time), 5000);
+
+    buy_at(&mut ctx, &sale, knot_time + ONE_DAY).await;
+}
@@ -0,0 +1,579 @@
+//! Whitelist: legacy Merkle leaves hashing the base58 address of the user, tiered Merkle leaves encoding
+//! `(user, tier, max_allocation)` in each leaf version, per-tier opening times, root rotation by the creator
+//! and the public phase of the sale, which the creator can reschedule.
+
+mod harness;
+
+use fjord_lbp::{
+    LiquidityBootstrappingPool, PoolError, PublicSaleTimeUpdated, WhitelistLeafVersion,
+    WhitelistProof, WhitelistUpdated,
+};
+use fjord_lbp_sdk::{PoolKeys, WhitelistEntry, WhitelistTree};
+use harness::*;
+use solana_sdk::signature::{keypair_from_seed, Keypair, Signer};
+use solana_sdk::transaction::TransactionError;
+
+// Assets spent net of the swap fee, paying 50 assets
+const TIER_1_ALLOCATION: u64 = 49_500_000;
+
+async fn buy_whitelisted(
+    ctx: &mut TestContext,
+    keys: &PoolKeys,
+    user: &Keypair,
+    assets_in: u64,
+    merkle_proof: Option<WhitelistProof>,
+) -> Result<Vec<String>, TransactionError> {
+    ctx.process(
+        &[fjord_lbp_sdk::swap_exact_assets_for_shares(
+            keys,
+            &user.pubkey(),
+            assets_in,
+            1,
+            merkle_proof,
+            None,
+            false,
+        )],
+        &[user],
+    )
+    .await
+}
+
+/// Whitelist of tier 0 (`alice`, no allocation) and tier 1 (`bob`, opening one day into the sale).
+struct Whitelist {
+    sale: Sale,
+    tree: WhitelistTree,
+    alice: Keypair,
+    bob: Keypair,
+}
+
+async fn create_whitelisted_pool(ctx: &mut TestContext) -> Whitelist {
+    let (alice, bob) = (Keypair::new(), Keypair::new());
+    let tree = WhitelistTree::new(
+        WhitelistLeafVersion::Tiered,
+        vec![
+            WhitelistEntry {
+                user: alice.pubkey(),
+                tier: 0,
+                max_allocation: 0,
+            },
+            WhitelistEntry {
+                user: bob.pubkey(),
+                tier: 1,
+                max_allocation: TIER_1_ALLOCATION,
+            },
+        ],
+    );
+    let root = tree.root();
+    let sale = create_pool_with(ctx, |args| {
+        args.whitelist_merkle_root = root;
+        args.whitelist_leaf_version = WhitelistLeafVersion::Tiered;
+        args.whitelist_tier_start_times[1] = args.sale_start_time + ONE_DAY;
+    })
+    .await;
+    let alice = ctx.fund_user_with(&sale.keys, alice, USER_ASSETS).await;
+    let bob = ctx.fund_user_with(&sale.keys, bob, USER_ASSETS).await;
+    Whitelist {
+        sale,
+        tree,
+        alice,
+        bob,
+    }
+}
+
+#[tokio::test]
+async fn only_whitelisted_entries_can_buy() {
+    let mut ctx = TestContext::new().await;
+    let Whitelist {
+        sale, tree, alice, ..
+    } = create_whitelisted_pool(&mut ctx).await;
+    let keys = &sale.keys;
+    let carol = ctx.fund_user(keys, USER_ASSETS).await;
+    ctx.warp_to(sale.sale_start_time).await;
+
+    let result = buy_whitelisted(&mut ctx, keys, &alice, 1_000_000, None).await;
+    assert_error(result, PoolError::WhitelistProof.into());
+    let result = buy_whitelisted(
+        &mut ctx,
+        keys,
+        &carol,
+        1_000_000,
+        tree.proof(&alice.pubkey()),
+    )
+    .await;
+    assert_error(result, PoolError::WhitelistProof.into());
+
+    // The tier and allocation are part of the leaf
+    let proof = tree.proof(&alice.pubkey()).unwrap();
+    for tampered in [
+        WhitelistProof {
+            tier: 1,
+            ..proof.clone()
+        },
+        WhitelistProof {
+            max_allocation: 1,
+            ..proof.clone()
+        },
+    ] {
+        let result = buy_whitelisted(&mut ctx, keys, &alice, 1_000_000, Some(tampered)).await;
+        assert_error(result, PoolError::WhitelistProof.into());
+    }
+
+    buy_whitelisted(&mut ctx, keys, &alice, 1_000_000, Some(proof))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn tiers_open_at_their_start_time() {
+    let mut ctx = TestContext::new().await;
+    let Whitelist {
+        sale, tree, bob, ..
+    } = create_whitelisted_pool(&mut ctx).await;
+    let keys = &sale.keys;
+
+    ctx.warp_to(sale.sale_start_time).await;
+    let result = buy_whitelisted(&mut ctx, keys, &bob, 1_000_000, tree.proof(&bob.pubkey())).await;
+    assert_error(result, PoolError::WhitelistTierNotOpen.into());
+
+    ctx.warp_to(sale.sale_start_time + ONE_DAY).await;
+    buy_whitelisted(&mut ctx, keys, &bob, 1_000_000, tree.proof(&bob.pubkey()))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn allocations_are_enforced() {
+    let mut ctx = TestContext::new().await;
+    let Whitelist {
+        sale, tree, bob, ..
+    } = create_whitelisted_pool(&mut ctx).await;
+    let keys = &sale.keys;
+    ctx.warp_to(sale.sale_start_time + ONE_DAY).await;
+
+    buy_whitelisted(&mut ctx, keys, &bob, 30_000_000, tree.proof(&bob.pubkey()))
+        .await
+        .unwrap();
+    let result = buy_whitelisted(&mut ctx, keys, &bob, 20_000_001, tree.proof(&bob.pubkey())).await;
+    assert_error(result, PoolError::WhitelistAllocationExceeded.into());
+    buy_whitelisted(&mut ctx, keys, &bob, 20_000_000, tree.proof(&bob.pubkey()))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn creator_can_rotate_the_root() {
+    let mut ctx = TestContext::new().await;
+    let Whitelist {
+        sale, tree, alice, ..
+    } = create_whitelisted_pool(&mut ctx).await;
+    let keys = &sale.keys;
+    let carol = ctx.fund_user(keys, USER_ASSETS).await;
+    let new_tree = WhitelistTree::new(
+        WhitelistLeafVersion::Tiered,
+        vec![WhitelistEntry {
+            user: carol.pubkey(),
+            tier: 0,
+            max_allocation: 0,
+        }],
+    );
+
+    // Only the creator can update the root
+    let result = ctx
+        .process(
+            &[fjord_lbp_sdk::set_whitelist_merkle_root(
+                &PoolKeys {
+                    creator: carol.pubkey(),
+                    ..keys.clone()
+                },
+                new_tree.root(),
+            )],
+            &[&carol],
+        )
+        .await;
+    assert_error(
+        result,
+        anchor_lang::error::ErrorCode::ConstraintSeeds.into(),
+    );
+
+    ctx.warp_to(sale.sale_start_time).await;
+    let logs = ctx
+        .process(
+            &[fjord_lbp_sdk::set_whitelist_merkle_root(
+                keys,
+                new_tree.root(),
+            )],
+            &[&sale.creator],
+        )
+        .await
+        .unwrap();
+    let updated = &events::<WhitelistUpdated>(&logs)[0];
+    assert_eq!(updated.pool, keys.pool);
+    assert_eq!(updated.whitelist_merkle_root, new_tree.root());
+
+    // Proofs of the previous whitelist no longer verify
+    let result = buy_whitelisted(
+        &mut ctx,
+        keys,
+        &alice,
+        1_000_000,
+        tree.proof(&alice.pubkey()),
+    )
+    .await;
+    assert_error(result, PoolError::WhitelistProof.into());
+    buy_whitelisted(
+        &mut ctx,
+        keys,
+        &carol,
+        1_000_000,
+        new_tree.proof(&carol.pubkey()),
+    )
+    .await
+    .unwrap();
+
+    // The whitelist is frozen once the sale ends
+    ctx.warp_to(sale.sale_end_time).await;
+    let result = ctx
+        .process(
+            &[fjord_lbp_sdk::set_whitelist_merkle_root(keys, tree.root())],
+            &[&sale.creator],
+        )
+        .await;
+    assert_error(result, PoolError::WhitelistUpdateDisallowed.into());
+    let pool = ctx.account::<LiquidityBootstrappingPool>(keys.pool).await;
+    assert_eq!(pool.whitelist_merkle_root, new_tree.root());
+}
+
+#[tokio::test]
+async fn public_sale_skips_the_whitelist() {
+    let mut ctx = TestContext::new().await;
+    let alice = Keypair::new();
+    let tree = WhitelistTree::new(
+        WhitelistLeafVersion::Legacy,
+        vec![WhitelistEntry {
+            user: alice.pubkey(),
+            tier: 0,
+            max_allocation: 0,
+        }],
+    );
+    let root = tree.root();
+    let sale = create_pool_with(&mut ctx, |args| {
+        args.whitelist_merkle_root = root;
+        args.public_sale_time = args.sale_start_time + ONE_DAY;
+    })
+    .await;
+    let keys = &sale.keys;
+    let carol = ctx.fund_user(keys, USER_ASSETS).await;
+
+    ctx.warp_to(sale.sale_start_time).await;
+    let result = buy_whitelisted(&mut ctx, keys, &carol, 1_000_000, None).await;
+    assert_error(result, PoolError::WhitelistProof.into());
+
+    ctx.warp_to(sale.sale_start_time + ONE_DAY).await;
+    buy_whitelisted(&mut ctx, keys, &carol, 1_000_000, None)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn creator_can_set_the_public_sale_time() {
+    let mut ctx = TestContext::new().await;
+    let Whitelist { sale, .. } = create_whitelisted_pool(&mut ctx).await;
+    let keys = &sale.keys;
+    let carol = ctx.fund_user(keys, USER_ASSETS).await;
+    let now = sale.sale_start_time + ONE_DAY;
+    ctx.warp_to(now).await;
+    let result = buy_whitelisted(&mut ctx, keys, &carol, 1_000_000, None).await;
+    assert_error(result, PoolError::WhitelistProof.into());
+
+    // Only the creator can set the time
+    let result = ctx
+        .process(
+            &[fjord_lbp_sdk::set_public_sale_time(
+                &PoolKeys {
+                    creator: carol.pubkey(),
+                    ..keys.clone()
+                },
+                now,
+            )],
+            &[&carol],
+        )
+        .await;
+    assert_error(
+        result,
+        anchor_lang::error::ErrorCode::ConstraintSeeds.into(),
+    );
+
+    // The public sale cannot open in the past nor once the sale ends
+    for public_sale_time in [now - 1, sale.sale_end_time] {
+        let result = ctx
+            .process(
+                &[fjord_lbp_sdk::set_public_sale_time(keys, public_sale_time)],
+                &[&sale.creator],
+            )
+            .await;
+        assert_error(result, PoolError::InvalidPublicSaleTime.into());
+    }
+
+    let logs = ctx
+        .process(
+            &[fjord_lbp_sdk::set_public_sale_time(keys, now)],
+            &[&sale.creator],
+        )
+        .await
+        .unwrap();
+    let updated = &events::<PublicSaleTimeUpdated>(&logs)[0];
+    assert_eq!(updated.pool, keys.pool);
+    assert_eq!(updated.public_sale_time, now);
+    buy_whitelisted(&mut ctx, keys, &carol, 1_000_000, None)
+        .await
+        .unwrap();
+
+    // Setting it back to 0 closes the public sale
+    ctx.process(
+        &[fjord_lbp_sdk::set_public_sale_time(keys, 0)],
+        &[&sale.creator],
+    )
+    .await
+    .unwrap();
+    let result = buy_whitelisted(&mut ctx, keys, &carol, 2_000_000, None).await;
+    assert_error(result, PoolError::WhitelistProof.into());
+
+    ctx.warp_to(sale.sale_end_time).await;
+    let result = ctx
+        .process(
+            &[fjord_lbp_sdk::set_public_sale_time(keys, 0)],
+            &[&sale.creator],
+        )
+        .await;
+    assert_error(result, PoolError::WhitelistUpdateDisallowed.into());
+    let pool = ctx.account::<LiquidityBootstrappingPool>(keys.pool).await;
+    assert_eq!(pool.public_sale_time, 0);
+}
+
+// Whitelist of the first pools, with its root and the proof of `legacy_user` as built by the baseline
+// `generateMerkleRoot` and `generateMerkleProof` helpers: merkletreejs over keccak256 of the base58 addresses,
+// with sorted pairs and the last node of odd levels promoted as is
+const LEGACY_WHITELIST: [&str; 3] = [
+    "11111111111111111111111111111111",
+    "GmaDrppBC7P5ARKV8g3djiwP89vz1jLK23V2GBjuAEGB",
+    "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+];
+const LEGACY_ROOT: [u8; 32] = [
+    188, 213, 202, 46, 208, 199, 159, 103, 125, 150, 149, 49, 100, 62, 130, 170, 144, 67, 80, 10,
+    107, 63, 34, 169, 116, 154, 217, 252, 27, 211, 47, 103,
+];
+const LEGACY_PROOF: [[u8; 32]; 2] = [
+    [
+        118, 29, 191, 82, 35, 174, 224, 85, 191, 76, 38, 78, 147, 159, 229, 209, 34, 184, 200, 134,
+        137, 226, 3, 196, 50, 80, 187, 243, 198, 150, 73, 37,
+    ],
+    [
+        60, 81, 33, 225, 193, 107, 205, 72, 116, 226, 163, 217, 90, 131, 123, 117, 244, 189, 176,
+        31, 212, 114, 174, 184, 177, 155, 76, 43, 244, 199, 117, 172,
+    ],
+];
+
+/// The second address of `LEGACY_WHITELIST`.
+fn legacy_user() -> Keypair {
+    keypair_from_seed(&[7; 32]).unwrap()
+}
+
+#[tokio::test]
+async fn legacy_pools_accept_proofs_of_the_first_whitelists() {
+    let mut ctx = TestContext::new().await;
+    let sale = create_pool_with(&mut ctx, |args| {
+        args.whitelist_merkle_root = LEGACY_ROOT;
+    })
+    .await;
+    let keys = &sale.keys;
+    let user = ctx.fund_user_with(keys, legacy_user(), USER_ASSETS).await;
+    ctx.warp_to(sale.sale_start_time).await;
+
+    // Legacy leaves encode no tier nor allocation
+    let proof = WhitelistProof {
+        tier: 0,
+        max_allocation: 0,
+        proof: LEGACY_PROOF.to_vec(),
+    };
+    for tampered in [
+        WhitelistProof {
+            tier: 1,
+            ..proof.clone()
+        },
+        WhitelistProof {
+            max_allocation: 1,
+            ..proof.clone()
+        },
+    ] {
+        let result = buy_whitelisted(&mut ctx, keys, &user, 1_000_000, Some(tampered)).await;
+        assert_error(result, PoolError::WhitelistProof.into());
+    }
+
+    buy_whitelisted(&mut ctx, keys, &user, 1_000_000, Some(proof))
+        .await
+        .unwrap();
+}
+
+#[test]
+fn legacy_trees_match_the_first_whitelists() {
+    assert_eq!(legacy_user().pubkey().to_string(), LEGACY_WHITELIST[1]);
+    let entries = LEGACY_WHITELIST
+        .iter()
+        .map(|user| WhitelistEntry {
+            user: user.parse().unwrap(),
+            tier: 0,
+            max_allocation: 0,
+        })
+        .collect();
+    let tree = WhitelistTree::new(WhitelistLeafVersion::Legacy, entries);
+    assert_eq!(tree.root(), LEGACY_ROOT);
+    assert_eq!(
+        tree.proof(&legacy_user().pubkey()).unwrap().proof,
+        LEGACY_PROOF
+    );
+}
+
+#[tokio::test]
+async fn proofs_are_verified_with_the_leaf_version_of_the_pool() {
+    let mut ctx = TestContext::new().await;
+    let alice = Keypair::new();
+    let entries = vec![
+        WhitelistEntry {
+            user: alice.pubkey(),
+            tier: 0,
+            max_allocation: 0,
+        },
+        WhitelistEntry {
+            user: Keypair::new().pubkey(),
+            tier: 0,
+            max_allocation: 0,
+        },
+    ];
+    let tiered_tree = WhitelistTree::new(WhitelistLeafVersion::Tiered, entries.clone());
+    let standard_tree = WhitelistTree::new(WhitelistLeafVersion::Standard, entries);
+    assert_ne!(tiered_tree.root(), standard_tree.root());
+
+    // A root generated with the tiered encoding does not verify in a standard pool
+    let root = tiered_tree.root();
+    let sale = create_pool_with(&mut ctx, |args| {
+        args.whitelist_merkle_root = root;
+        args.whitelist_leaf_version = WhitelistLeafVersion::Standard;
+    })
+    .await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user_with(keys, alice, USER_ASSETS).await;
+    ctx.warp_to(sale.sale_start_time).await;
+    let result = buy_whitelisted(
+        &mut ctx,
+        keys,
+        &alice,
+        1_000_000,
+        tiered_tree.proof(&alice.pubkey()),
+    )
+    .await;
+    assert_error(result, PoolError::WhitelistProof.into());
+
+    ctx.process(
+        &[fjord_lbp_sdk::set_whitelist_merkle_root(
+            keys,
+            standard_tree.root(),
+        )],
+        &[&sale.creator],
+    )
+    .await
+    .unwrap();
+    buy_whitelisted(
+        &mut ctx,
+        keys,
+        &alice,
+        1_000_000,
+        standard_tree.proof(&alice.pubkey()),
+    )
+    .await
+    .unwrap();
+}
+
+#[test]
+fn proofs_verify_against_the_root() {
+    for version in [
+        WhitelistLeafVersion::Legacy,
+        WhitelistLeafVersion::Tiered,
+        WhitelistLeafVersion::Standard,
+    ] {
+        for size in 1..=9 {
+            let entries: Vec<_> = (0..size)
+                .map(|i| WhitelistEntry {
+                    user: Keypair::new().pubkey(),
+                    tier: i % 4,
+                    max_allocation: u64::from(i) * 1_000_000,
+                })
+                .collect();
+            let tree = WhitelistTree::new(version, entries.clone());
+            for entry in entries {
+                let proof = tree.proof(&entry.user).unwrap();
+                let leaf = fjord_lbp::whitelist_leaf(
+                    version,
+                    &entry.user,
+                    proof.tier,
+                    proof.max_allocation,
+                );
+                assert!(fjord_lbp::merkle_verify(&proof.proof, &tree.root(), &leaf));
+            }
+        }
+        assert_eq!(WhitelistTree::new(version, vec![]).root(), [0; 32]);
+    }
+}
+
+/// Value of an OpenZeppelin `StandardMerkleTree` ABI encoded as a 32 bytes word, big endian and left padded.
+fn abi_word(value: &[u8]) -> [u8; 32] {
+    let mut word = [0; 32];
+    word[32 - value.len()..].copy_from_slice(value);
+    word
+}
+
+fn hex(value: &str) -> [u8; 32] {
+    let bytes: Vec<_> = (0..value.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&value[i..i + 2], 16).unwrap())
+        .collect();
+    bytes.try_into().unwrap()
+}
+
+#[test]
+fn standard_proofs_of_openzeppelin_trees_verify() {
+    use anchor_lang::solana_program::keccak::hash;
+
+    // Tree of the README of @openzeppelin/merkle-tree:
+    // StandardMerkleTree.of([["0x1111...1111", "5000000000000000000"], ["0x2222...2222", "2500000000000000000"]],
+    // ["address", "uint256"])
+    let root = hex("d4dee0beab2d53f2cc83e567171bd2820e49898130a22622b10ead383e90bd77");
+    let proof = hex("b92c48e9d7abe27fd8dfd6b5dfdbfb1c9a463f80c712b66f3a5180a090cccafc");
+    let leaf = |address: u8, amount: u64| {
+        let data = [abi_word(&[address; 20]), abi_word(&amount.to_be_bytes())].concat();
+        hash(&hash(&data).0).0
+    };
+
+    let first = leaf(0x11, 5_000_000_000_000_000_000);
+    let second = leaf(0x22, 2_500_000_000_000_000_000);
+    assert_eq!(second, proof);
+    assert!(fjord_lbp::merkle_verify(&[proof], &root, &first));
+    assert!(fjord_lbp::merkle_verify(&[first], &root, &second));
+}
+
+#[test]
+fn standard_leaves_abi_encode_the_entries() {
+    use anchor_lang::solana_program::keccak::hash;
+
+    // Leaves of a StandardMerkleTree of [user, tier, max_allocation] typed ["bytes32", "uint8", "uint64"]
+    let user = Keypair::new().pubkey();
+    let data = [
+        abi_word(user.as_ref()),
+        abi_word(&[3]),
+        abi_word(&1_000_000u64.to_be_bytes()),
+    ]
+    .concat();
+    assert_eq!(
+        fjord_lbp::whitelist_leaf(WhitelistLeafVersion::Standard, &user, 3, 1_000_000),
+        hash(&hash(&data).0).0
+    );
+}
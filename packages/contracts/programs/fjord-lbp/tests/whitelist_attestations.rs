@@ -0,0 +1,151 @@
+//! Whitelisting by attestations of the pool's whitelist signer, verified through the Ed25519 program.
+
+mod harness;
+
+use fjord_lbp::{PoolError, WhitelistAttestation};
+use fjord_lbp_sdk::PoolKeys;
+use harness::*;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::TransactionError;
+
+// Assets spent net of the swap fee, paying 50 assets
+const ALLOCATION: u64 = 49_500_000;
+
+fn attest(signer: &Keypair, attestation: &WhitelistAttestation) -> [u8; 64] {
+    signer
+        .sign_message(&fjord_lbp_sdk::whitelist_attestation_message(attestation))
+        .into()
+}
+
+/// Buy with `attestation` signed by `signer`, or without any attestation.
+async fn buy_attested(
+    ctx: &mut TestContext,
+    keys: &PoolKeys,
+    user: &Keypair,
+    assets_in: u64,
+    attestation: Option<(&Keypair, &WhitelistAttestation)>,
+) -> Result<Vec<String>, TransactionError> {
+    let mut instructions = Vec::new();
+    if let Some((signer, attestation)) = attestation {
+        instructions.push(fjord_lbp_sdk::verify_whitelist_attestation(
+            &signer.pubkey(),
+            &attest(signer, attestation),
+            attestation,
+        ));
+    }
+    instructions.push(fjord_lbp_sdk::swap_exact_assets_for_shares(
+        keys,
+        &user.pubkey(),
+        assets_in,
+        1,
+        None,
+        None,
+        false,
+    ));
+    ctx.process(&instructions, &[user]).await
+}
+
+#[tokio::test]
+async fn attested_users_can_buy_up_to_their_allocation() {
+    let mut ctx = TestContext::new().await;
+    let signer = Keypair::new();
+    let signer_key = signer.pubkey();
+    let sale = create_pool_with(&mut ctx, |args| args.whitelist_signer = signer_key).await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    ctx.warp_to(sale.sale_start_time).await;
+
+    let result = buy_attested(&mut ctx, keys, &alice, 1_000_000, None).await;
+    assert_error(result, PoolError::InvalidWhitelistAttestation.into());
+
+    let attestation = WhitelistAttestation {
+        pool: keys.pool,
+        user: alice.pubkey(),
+        expiry: sale.sale_end_time,
+        max_allocation: ALLOCATION,
+    };
+    let approval = Some((&signer, &attestation));
+    buy_attested(&mut ctx, keys, &alice, 30_000_000, approval)
+        .await
+        .unwrap();
+    let result = buy_attested(&mut ctx, keys, &alice, 20_000_001, approval).await;
+    assert_error(result, PoolError::WhitelistAllocationExceeded.into());
+    buy_attested(&mut ctx, keys, &alice, 20_000_000, approval)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn attestations_are_bound_to_the_signer_user_pool_and_expiry() {
+    let mut ctx = TestContext::new().await;
+    let signer = Keypair::new();
+    let signer_key = signer.pubkey();
+    let sale = create_pool_with(&mut ctx, |args| args.whitelist_signer = signer_key).await;
+    let other_sale = create_pool_with(&mut ctx, |args| args.whitelist_signer = signer_key).await;
+    let keys = &sale.keys;
+    let alice = ctx.fund_user(keys, USER_ASSETS).await;
+    let bob = ctx.fund_user(keys, USER_ASSETS).await;
+    ctx.warp_to(sale.sale_start_time).await;
+    let now = ctx.now().await;
+    let attestation = WhitelistAttestation {
+        pool: keys.pool,
+        user: alice.pubkey(),
+        expiry: now,
+        max_allocation: 0,
+    };
+
+    // Signed by another key
+    let result = buy_attested(
+        &mut ctx,
+        keys,
+        &alice,
+        1_000_000,
+        Some((&Keypair::new(), &attestation)),
+    )
+    .await;
+    assert_error(result, PoolError::InvalidWhitelistAttestation.into());
+
+    // Used by another user or in another pool
+    let result = buy_attested(
+        &mut ctx,
+        keys,
+        &bob,
+        1_000_000,
+        Some((&signer, &attestation)),
+    )
+    .await;
+    assert_error(result, PoolError::InvalidWhitelistAttestation.into());
+    let other_pool = WhitelistAttestation {
+        pool: other_sale.keys.pool,
+        ..attestation.clone()
+    };
+    let result = buy_attested(
+        &mut ctx,
+        keys,
+        &alice,
+        1_000_000,
+        Some((&signer, &other_pool)),
+    )
+    .await;
+    assert_error(result, PoolError::InvalidWhitelistAttestation.into());
+
+    buy_attested(
+        &mut ctx,
+        keys,
+        &alice,
+        1_000_000,
+        Some((&signer, &attestation)),
+    )
+    .await
+    .unwrap();
+    ctx.warp_to(now + 1).await;
+    let result = buy_attested(
+        &mut ctx,
+        keys,
+        &alice,
+        1_000_000,
+        Some((&signer, &attestation)),
+    )
+    .await;
+    assert_error(result, PoolError::WhitelistAttestationExpired.into());
+}
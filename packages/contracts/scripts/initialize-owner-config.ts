@@ -55,6 +55,13 @@ const ownerConfig: FjordLbpStruct<"initializeOwnerConfig"> = {
    * @dev Must be lower than 10000 (100%)
    */
   swapFee: 100,
+  /**
+   * The ceilings of the platform, referral and swap fees, in basis points
+   * @dev Must be at least the fees above, and can only be lowered afterwards
+   */
+  maxPlatformFee: 10000,
+  maxReferralFee: 10000,
+  maxSwapFee: 10000,
 };
 
 /**
@@ -0,0 +1,841 @@
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::{ed25519_program, sysvar};
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token;
+use fjord_lbp::{accounts, instruction};
+
+use crate::{
+    find_native_unwrap_address, find_owner_config_address, find_pool_address,
+    find_program_data_address, find_referrer_registration_address, find_treasury_address,
+    find_user_state_address, get_token_account_address, whitelist_attestation_message, Treasury,
+    WhitelistAttestation, WhitelistProof, ID,
+};
+
+/// Addresses of a pool and of the mints and token programs around it.
+#[derive(Clone, Debug)]
+pub struct PoolKeys {
+    pub pool: Pubkey,
+    pub creator: Pubkey,
+    pub asset_token_mint: Pubkey,
+    pub share_token_mint: Pubkey,
+    // Token programs owning the asset and share mints (SPL Token or Token-2022)
+    pub asset_token_program: Pubkey,
+    pub share_token_program: Pubkey,
+}
+
+impl PoolKeys {
+    pub fn new(
+        asset_token_mint: Pubkey,
+        share_token_mint: Pubkey,
+        creator: Pubkey,
+        salt: &str,
+        asset_token_program: Pubkey,
+        share_token_program: Pubkey,
+    ) -> Self {
+        let (pool, _) = find_pool_address(&share_token_mint, &asset_token_mint, &creator, salt);
+        Self {
+            pool,
+            creator,
+            asset_token_mint,
+            share_token_mint,
+            asset_token_program,
+            share_token_program,
+        }
+    }
+
+    /// Asset token account of `owner`.
+    pub fn asset_token_account(&self, owner: &Pubkey) -> Pubkey {
+        get_token_account_address(owner, &self.asset_token_mint, &self.asset_token_program)
+    }
+
+    /// Share token account of `owner`.
+    pub fn share_token_account(&self, owner: &Pubkey) -> Pubkey {
+        get_token_account_address(owner, &self.share_token_mint, &self.share_token_program)
+    }
+
+    pub fn pool_asset_token_account(&self) -> Pubkey {
+        self.asset_token_account(&self.pool)
+    }
+
+    pub fn pool_share_token_account(&self) -> Pubkey {
+        self.share_token_account(&self.pool)
+    }
+}
+
+fn build_instruction(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+// Initializer --------------------------------------------------------
+
+/// Build the `initialize_owner_config` instruction, signed by the program upgrade authority.
+pub fn initialize_owner_config(
+    authority: &Pubkey,
+    args: instruction::InitializeOwnerConfig,
+) -> Instruction {
+    build_instruction(
+        accounts::InitializeOwner {
+            config: find_owner_config_address().0,
+            treasury: find_treasury_address().0,
+            program: ID,
+            program_data: find_program_data_address().0,
+            authority: *authority,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+// Pool Creation ------------------------------------------------------
+
+/// Build the `initialize_pool` instruction. `keys` must be derived from the same salt as `args`.
+pub fn initialize_pool(keys: &PoolKeys, args: instruction::InitializePool) -> Instruction {
+    build_instruction(
+        accounts::InitializePool {
+            pool: keys.pool,
+            asset_token_mint: keys.asset_token_mint,
+            share_token_mint: keys.share_token_mint,
+            pool_share_token_account: keys.pool_share_token_account(),
+            pool_asset_token_account: keys.pool_asset_token_account(),
+            creator_asset_token_account: keys.asset_token_account(&keys.creator),
+            creator_share_token_account: keys.share_token_account(&keys.creator),
+            config: find_owner_config_address().0,
+            creator: keys.creator,
+            associated_token_program: associated_token::ID,
+            asset_token_program: keys.asset_token_program,
+            share_token_program: keys.share_token_program,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+// Swap functions -----------------------------------------------------
+
+/// Referrer of a swap, along with its own referrer in the pool when the pool splits referral fees with it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwapReferrer {
+    pub referrer: Pubkey,
+    /// The referrer recorded in the state of `referrer` in the pool, if any.
+    pub second_level_referrer: Option<Pubkey>,
+}
+
+impl From<Pubkey> for SwapReferrer {
+    fn from(referrer: Pubkey) -> Self {
+        Self {
+            referrer,
+            second_level_referrer: None,
+        }
+    }
+}
+
+/// Accounts of the swap instructions. With `native_sol`, the user pays or gets paid in lamports.
+fn swap_accounts(
+    keys: &PoolKeys,
+    user: &Pubkey,
+    referrer: Option<SwapReferrer>,
+    native_sol: bool,
+    is_sell: bool,
+) -> accounts::SwapTokens {
+    let second_level_referrer = referrer.and_then(|referrer| referrer.second_level_referrer);
+    let referrer = referrer.map(|referrer| referrer.referrer);
+    accounts::SwapTokens {
+        asset_token_mint: keys.asset_token_mint,
+        share_token_mint: keys.share_token_mint,
+        pool: keys.pool,
+        pool_asset_token_account: keys.pool_asset_token_account(),
+        pool_share_token_account: keys.pool_share_token_account(),
+        user_asset_token_account: (!native_sol).then(|| keys.asset_token_account(user)),
+        user_share_token_account: keys.share_token_account(user),
+        native_unwrap_account: (native_sol && is_sell)
+            .then(|| find_native_unwrap_address(&keys.pool, user).0),
+        config: find_owner_config_address().0,
+        user_state_in_pool: find_user_state_address(user, &keys.pool).0,
+        // The user state in pool cannot be passed twice, leave the program to reject self-referrals
+        referrer_state_in_pool: referrer
+            .filter(|referrer| referrer != user)
+            .map(|referrer| find_user_state_address(&referrer, &keys.pool).0),
+        second_level_referrer_state_in_pool: second_level_referrer
+            .filter(|second_level_referrer| second_level_referrer != user)
+            .map(|second_level_referrer| {
+                find_user_state_address(&second_level_referrer, &keys.pool).0
+            }),
+        referrer_registration: referrer
+            .map(|referrer| find_referrer_registration_address(&referrer).0),
+        user: *user,
+        associated_token_program: associated_token::ID,
+        asset_token_program: keys.asset_token_program,
+        share_token_program: keys.share_token_program,
+        system_program: system_program::ID,
+        instructions_sysvar: Some(sysvar::instructions::ID),
+    }
+}
+
+/// Build the `swap_exact_assets_for_shares` instruction.
+pub fn swap_exact_assets_for_shares(
+    keys: &PoolKeys,
+    user: &Pubkey,
+    assets_in: u64,
+    min_shares_out: u64,
+    merkle_proof: Option<WhitelistProof>,
+    referrer: Option<SwapReferrer>,
+    native_sol: bool,
+) -> Instruction {
+    build_instruction(
+        swap_accounts(keys, user, referrer, native_sol, false),
+        instruction::SwapExactAssetsForShares {
+            assets_in,
+            min_shares_out,
+            merkle_proof,
+            referrer: referrer.map(|referrer| referrer.referrer),
+        },
+    )
+}
+
+/// Build the `swap_assets_for_exact_shares` instruction.
+pub fn swap_assets_for_exact_shares(
+    keys: &PoolKeys,
+    user: &Pubkey,
+    shares_out: u64,
+    max_assets_in: u64,
+    merkle_proof: Option<WhitelistProof>,
+    referrer: Option<SwapReferrer>,
+    native_sol: bool,
+) -> Instruction {
+    build_instruction(
+        swap_accounts(keys, user, referrer, native_sol, false),
+        instruction::SwapAssetsForExactShares {
+            shares_out,
+            max_assets_in,
+            merkle_proof,
+            referrer: referrer.map(|referrer| referrer.referrer),
+        },
+    )
+}
+
+/// Build the `swap_exact_shares_for_assets` instruction.
+pub fn swap_exact_shares_for_assets(
+    keys: &PoolKeys,
+    user: &Pubkey,
+    shares_in: u64,
+    min_assets_out: u64,
+    merkle_proof: Option<WhitelistProof>,
+    referrer: Option<SwapReferrer>,
+    native_sol: bool,
+) -> Instruction {
+    build_instruction(
+        swap_accounts(keys, user, referrer, native_sol, true),
+        instruction::SwapExactSharesForAssets {
+            shares_in,
+            min_assets_out,
+            merkle_proof,
+            referrer: referrer.map(|referrer| referrer.referrer),
+        },
+    )
+}
+
+/// Build the `swap_shares_for_exact_assets` instruction.
+pub fn swap_shares_for_exact_assets(
+    keys: &PoolKeys,
+    user: &Pubkey,
+    assets_out: u64,
+    max_shares_in: u64,
+    merkle_proof: Option<WhitelistProof>,
+    referrer: Option<SwapReferrer>,
+    native_sol: bool,
+) -> Instruction {
+    build_instruction(
+        swap_accounts(keys, user, referrer, native_sol, true),
+        instruction::SwapSharesForExactAssets {
+            assets_out,
+            max_shares_in,
+            merkle_proof,
+            referrer: referrer.map(|referrer| referrer.referrer),
+        },
+    )
+}
+
+/// Build the Ed25519 program instruction verifying the `signature` of `attestation` by the whitelist `signer`
+/// of the pool, to place right before the swap of the attested user.
+pub fn verify_whitelist_attestation(
+    signer: &Pubkey,
+    signature: &[u8; 64],
+    attestation: &WhitelistAttestation,
+) -> Instruction {
+    // A single signature, followed by its offsets, public key, signature and message
+    const DATA_START: u16 = 16;
+    let message = whitelist_attestation_message(attestation);
+    let public_key_offset = DATA_START;
+    let signature_offset = public_key_offset + 32;
+    let message_offset = signature_offset + 64;
+    let mut data = vec![1, 0];
+    for offset in [
+        signature_offset,
+        u16::MAX,
+        public_key_offset,
+        u16::MAX,
+        message_offset,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&offset.to_le_bytes());
+    }
+    data.extend_from_slice(signer.as_ref());
+    data.extend_from_slice(signature);
+    data.extend_from_slice(&message);
+    Instruction {
+        program_id: ed25519_program::ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+// Redemption functions -----------------------------------------------
+
+/// Build the `close_pool` instruction, paying the fee recipients of `treasury`.
+/// With `native_sol`, the assets are paid out in lamports to the recipients' wallets.
+pub fn close_pool(
+    keys: &PoolKeys,
+    user: &Pubkey,
+    treasury: &Treasury,
+    native_sol: bool,
+) -> Instruction {
+    let treasury_address = find_treasury_address().0;
+    let swap_fee_recipient = treasury.swap_fee_recipient;
+    let mut instruction = build_instruction(
+        accounts::ClosePool {
+            asset_token_mint: keys.asset_token_mint,
+            share_token_mint: keys.share_token_mint,
+            pool: keys.pool,
+            pool_asset_token_account: keys.pool_asset_token_account(),
+            pool_share_token_account: keys.pool_share_token_account(),
+            treasury_asset_token_account: keys.asset_token_account(&treasury_address),
+            treasury_share_token_account: keys.share_token_account(&treasury_address),
+            treasury: treasury_address,
+            creator_asset_token_account: (!native_sol)
+                .then(|| keys.asset_token_account(&keys.creator)),
+            creator_share_token_account: keys.share_token_account(&keys.creator),
+            pool_creator: keys.creator,
+            swap_fee_recipient_asset_token_account: (!native_sol)
+                .then(|| keys.asset_token_account(&swap_fee_recipient)),
+            swap_fee_recipient_share_token_account: keys.share_token_account(&swap_fee_recipient),
+            swap_fee_recipient,
+            native_unwrap_account: native_sol
+                .then(|| find_native_unwrap_address(&keys.pool, user).0),
+            owner_config: find_owner_config_address().0,
+            user: *user,
+            associated_token_program: associated_token::ID,
+            asset_token_program: keys.asset_token_program,
+            share_token_program: keys.share_token_program,
+            system_program: system_program::ID,
+        },
+        instruction::ClosePool {},
+    );
+
+    // The platform fee recipients are passed as remaining accounts
+    instruction
+        .accounts
+        .extend(treasury.fee_recipients.iter().map(|recipient| {
+            let recipient_account = if native_sol {
+                recipient.user
+            } else {
+                keys.asset_token_account(&recipient.user)
+            };
+            AccountMeta::new(recipient_account, false)
+        }));
+    instruction
+}
+
+/// Build the `redeem` instruction. With `native_sol`, the referral assets are paid out in lamports.
+pub fn redeem(keys: &PoolKeys, user: &Pubkey, referred: bool, native_sol: bool) -> Instruction {
+    build_instruction(
+        accounts::RedeemTokens {
+            asset_token_mint: keys.asset_token_mint,
+            share_token_mint: keys.share_token_mint,
+            pool: keys.pool,
+            pool_asset_token_account: keys.pool_asset_token_account(),
+            pool_share_token_account: keys.pool_share_token_account(),
+            user_asset_token_account: (!native_sol).then(|| keys.asset_token_account(user)),
+            user_share_token_account: keys.share_token_account(user),
+            user_state_in_pool: find_user_state_address(user, &keys.pool).0,
+            native_unwrap_account: native_sol
+                .then(|| find_native_unwrap_address(&keys.pool, user).0),
+            user: *user,
+            associated_token_program: associated_token::ID,
+            asset_token_program: keys.asset_token_program,
+            share_token_program: keys.share_token_program,
+            system_program: system_program::ID,
+        },
+        instruction::Redeem { referred },
+    )
+}
+
+/// Build the `refund` instruction. With `native_sol`, the refunded assets are paid out in lamports.
+pub fn refund(keys: &PoolKeys, user: &Pubkey, native_sol: bool) -> Instruction {
+    build_instruction(
+        accounts::RefundTokens {
+            asset_token_mint: keys.asset_token_mint,
+            share_token_mint: keys.share_token_mint,
+            pool: keys.pool,
+            pool_asset_token_account: keys.pool_asset_token_account(),
+            user_asset_token_account: (!native_sol).then(|| keys.asset_token_account(user)),
+            user_state_in_pool: find_user_state_address(user, &keys.pool).0,
+            native_unwrap_account: native_sol
+                .then(|| find_native_unwrap_address(&keys.pool, user).0),
+            user: *user,
+            associated_token_program: associated_token::ID,
+            asset_token_program: keys.asset_token_program,
+            share_token_program: keys.share_token_program,
+            system_program: system_program::ID,
+        },
+        instruction::Refund {},
+    )
+}
+
+/// Build the `claim_referral_rewards` instruction, paying the referral assets of `referrer` to its ATA.
+/// With `native_sol`, the assets are paid out in lamports instead.
+pub fn claim_referral_rewards(keys: &PoolKeys, referrer: &Pubkey, native_sol: bool) -> Instruction {
+    build_instruction(
+        accounts::ClaimReferralRewards {
+            asset_token_mint: keys.asset_token_mint,
+            pool: keys.pool,
+            pool_asset_token_account: keys.pool_asset_token_account(),
+            referrer_asset_token_account: (!native_sol).then(|| keys.asset_token_account(referrer)),
+            referrer_state_in_pool: find_user_state_address(referrer, &keys.pool).0,
+            native_unwrap_account: native_sol
+                .then(|| find_native_unwrap_address(&keys.pool, referrer).0),
+            referrer: *referrer,
+            associated_token_program: associated_token::ID,
+            asset_token_program: keys.asset_token_program,
+            system_program: system_program::ID,
+        },
+        instruction::ClaimReferralRewards {},
+    )
+}
+
+// View functions -----------------------------------------------------
+
+fn preview_accounts(keys: &PoolKeys) -> accounts::ReturnPreviewContext {
+    accounts::ReturnPreviewContext {
+        asset_token_mint: keys.asset_token_mint,
+        share_token_mint: keys.share_token_mint,
+        pool: keys.pool,
+        pool_asset_token_account: keys.pool_asset_token_account(),
+        pool_share_token_account: keys.pool_share_token_account(),
+        config: find_owner_config_address().0,
+        asset_token_program: keys.asset_token_program,
+        share_token_program: keys.share_token_program,
+    }
+}
+
+/// Build the `preview_assets_in` instruction, meant to be simulated.
+pub fn preview_assets_in(keys: &PoolKeys, shares_out: u64) -> Instruction {
+    build_instruction(
+        preview_accounts(keys),
+        instruction::PreviewAssetsIn { shares_out },
+    )
+}
+
+/// Build the `preview_shares_in` instruction, meant to be simulated.
+pub fn preview_shares_in(keys: &PoolKeys, assets_out: u64) -> Instruction {
+    build_instruction(
+        preview_accounts(keys),
+        instruction::PreviewSharesIn { assets_out },
+    )
+}
+
+/// Build the `preview_shares_out` instruction, meant to be simulated.
+pub fn preview_shares_out(keys: &PoolKeys, assets_in: u64) -> Instruction {
+    build_instruction(
+        preview_accounts(keys),
+        instruction::PreviewSharesOut { assets_in },
+    )
+}
+
+/// Build the `preview_assets_out` instruction, meant to be simulated.
+pub fn preview_assets_out(keys: &PoolKeys, shares_in: u64) -> Instruction {
+    build_instruction(
+        preview_accounts(keys),
+        instruction::PreviewAssetsOut { shares_in },
+    )
+}
+
+/// Build the `reserves_and_weights` instruction, meant to be simulated.
+pub fn reserves_and_weights(keys: &PoolKeys) -> Instruction {
+    build_instruction(preview_accounts(keys), instruction::ReservesAndWeights {})
+}
+
+// Pool Management ----------------------------------------------------
+
+/// Build the `toggle_pause` instruction, signed by the pool creator.
+pub fn toggle_pause(keys: &PoolKeys) -> Instruction {
+    build_instruction(
+        accounts::OnlyPoolCreator {
+            pool: keys.pool,
+            asset_token_mint: keys.asset_token_mint,
+            share_token_mint: keys.share_token_mint,
+            creator: keys.creator,
+            token_program: keys.asset_token_program,
+            system_program: system_program::ID,
+        },
+        instruction::TogglePause {},
+    )
+}
+
+/// Build the `cancel_pool` instruction, signed by the pool creator.
+pub fn cancel_pool(keys: &PoolKeys) -> Instruction {
+    build_instruction(
+        accounts::CancelPool {
+            asset_token_mint: keys.asset_token_mint,
+            share_token_mint: keys.share_token_mint,
+            pool: keys.pool,
+            pool_asset_token_account: keys.pool_asset_token_account(),
+            pool_share_token_account: keys.pool_share_token_account(),
+            creator_asset_token_account: keys.asset_token_account(&keys.creator),
+            creator_share_token_account: keys.share_token_account(&keys.creator),
+            creator: keys.creator,
+            associated_token_program: associated_token::ID,
+            asset_token_program: keys.asset_token_program,
+            share_token_program: keys.share_token_program,
+            system_program: system_program::ID,
+        },
+        instruction::CancelPool {},
+    )
+}
+
+/// Build the `close_pool_accounts` instruction, signed by the pool creator.
+/// With `native_sol`, the assets left in the pool are paid out in lamports to the creator's wallet.
+pub fn close_pool_accounts(keys: &PoolKeys, native_sol: bool) -> Instruction {
+    build_instruction(
+        accounts::ClosePoolAccounts {
+            asset_token_mint: keys.asset_token_mint,
+            share_token_mint: keys.share_token_mint,
+            pool: keys.pool,
+            pool_asset_token_account: keys.pool_asset_token_account(),
+            pool_share_token_account: keys.pool_share_token_account(),
+            creator_asset_token_account: (!native_sol)
+                .then(|| keys.asset_token_account(&keys.creator)),
+            creator_share_token_account: keys.share_token_account(&keys.creator),
+            creator: keys.creator,
+            associated_token_program: associated_token::ID,
+            asset_token_program: keys.asset_token_program,
+            share_token_program: keys.share_token_program,
+            system_program: system_program::ID,
+        },
+        instruction::ClosePoolAccounts {},
+    )
+}
+
+/// Build the `update_pool_config` instruction, signed by the pool creator.
+pub fn update_pool_config(keys: &PoolKeys, args: instruction::UpdatePoolConfig) -> Instruction {
+    build_instruction(
+        accounts::UpdatePoolConfig {
+            asset_token_mint: keys.asset_token_mint,
+            share_token_mint: keys.share_token_mint,
+            pool: keys.pool,
+            pool_asset_token_account: keys.pool_asset_token_account(),
+            pool_share_token_account: keys.pool_share_token_account(),
+            creator_asset_token_account: keys.asset_token_account(&keys.creator),
+            creator_share_token_account: keys.share_token_account(&keys.creator),
+            creator: keys.creator,
+            asset_token_program: keys.asset_token_program,
+            share_token_program: keys.share_token_program,
+        },
+        args,
+    )
+}
+
+/// Build the `set_whitelist_merkle_root` instruction, signed by the pool creator.
+pub fn set_whitelist_merkle_root(keys: &PoolKeys, whitelist_merkle_root: [u8; 32]) -> Instruction {
+    build_instruction(
+        accounts::OnlyPoolCreator {
+            pool: keys.pool,
+            asset_token_mint: keys.asset_token_mint,
+            share_token_mint: keys.share_token_mint,
+            creator: keys.creator,
+            token_program: keys.asset_token_program,
+            system_program: system_program::ID,
+        },
+        instruction::SetWhitelistMerkleRoot {
+            whitelist_merkle_root,
+        },
+    )
+}
+
+/// Build the `set_public_sale_time` instruction, signed by the pool creator.
+pub fn set_public_sale_time(keys: &PoolKeys, public_sale_time: i64) -> Instruction {
+    build_instruction(
+        accounts::OnlyPoolCreator {
+            pool: keys.pool,
+            asset_token_mint: keys.asset_token_mint,
+            share_token_mint: keys.share_token_mint,
+            creator: keys.creator,
+            token_program: keys.asset_token_program,
+            system_program: system_program::ID,
+        },
+        instruction::SetPublicSaleTime { public_sale_time },
+    )
+}
+
+/// Build the `set_weight_manager` instruction, signed by the pool creator.
+pub fn set_weight_manager(keys: &PoolKeys, weight_manager: Pubkey) -> Instruction {
+    build_instruction(
+        accounts::OnlyPoolCreator {
+            pool: keys.pool,
+            asset_token_mint: keys.asset_token_mint,
+            share_token_mint: keys.share_token_mint,
+            creator: keys.creator,
+            token_program: keys.asset_token_program,
+            system_program: system_program::ID,
+        },
+        instruction::SetWeightManager { weight_manager },
+    )
+}
+
+/// Build the `adjust_weights` instruction, signed by `authority`, the pool creator or its weight manager.
+pub fn adjust_weights(
+    keys: &PoolKeys,
+    authority: &Pubkey,
+    end_weight_basis_points: Option<u16>,
+    paused: Option<bool>,
+) -> Instruction {
+    build_instruction(
+        accounts::AdjustWeights {
+            pool: keys.pool,
+            authority: *authority,
+        },
+        instruction::AdjustWeights {
+            end_weight_basis_points,
+            paused,
+        },
+    )
+}
+
+// Owner Authority Controls -------------------------------------------
+
+/// Build the `propose_fees` instruction, signed by the owner.
+pub fn propose_fees(
+    owner: &Pubkey,
+    platform_fee: Option<u16>,
+    referral_fee: Option<u16>,
+    swap_fee: Option<u16>,
+    fee_change_delay: Option<i64>,
+) -> Instruction {
+    build_instruction(
+        accounts::FeeConfig {
+            config: find_owner_config_address().0,
+            owner: *owner,
+            system_program: system_program::ID,
+        },
+        instruction::ProposeFees {
+            platform_fee,
+            referral_fee,
+            swap_fee,
+            fee_change_delay,
+        },
+    )
+}
+
+/// Build the `execute_fees` instruction, signed by the owner.
+pub fn execute_fees(owner: &Pubkey) -> Instruction {
+    build_instruction(
+        accounts::FeeConfig {
+            config: find_owner_config_address().0,
+            owner: *owner,
+            system_program: system_program::ID,
+        },
+        instruction::ExecuteFees {},
+    )
+}
+
+/// Build the `lower_fee_ceilings` instruction, signed by the owner.
+pub fn lower_fee_ceilings(
+    owner: &Pubkey,
+    max_platform_fee: Option<u16>,
+    max_referral_fee: Option<u16>,
+    max_swap_fee: Option<u16>,
+) -> Instruction {
+    build_instruction(
+        accounts::FeeConfig {
+            config: find_owner_config_address().0,
+            owner: *owner,
+            system_program: system_program::ID,
+        },
+        instruction::LowerFeeCeilings {
+            max_platform_fee,
+            max_referral_fee,
+            max_swap_fee,
+        },
+    )
+}
+
+/// Build the `propose_treasury_fee_recipients` instruction, signed by the owner.
+pub fn propose_treasury_fee_recipients(
+    owner: &Pubkey,
+    swap_fee_recipient: Option<Pubkey>,
+    fee_recipients: Vec<Pubkey>,
+    fee_percentages: Vec<u16>,
+) -> Instruction {
+    build_instruction(
+        accounts::TreasuryFeeRecipientConfig {
+            treasury: find_treasury_address().0,
+            config: find_owner_config_address().0,
+            owner: *owner,
+            system_program: system_program::ID,
+        },
+        instruction::ProposeTreasuryFeeRecipients {
+            swap_fee_recipient,
+            fee_recipients,
+            fee_percentages,
+        },
+    )
+}
+
+/// Build the `execute_treasury_fee_recipients` instruction, signed by the owner.
+pub fn execute_treasury_fee_recipients(owner: &Pubkey) -> Instruction {
+    build_instruction(
+        accounts::ExecuteTreasuryFeeRecipients {
+            treasury: find_treasury_address().0,
+            config: find_owner_config_address().0,
+            owner: *owner,
+            system_program: system_program::ID,
+        },
+        instruction::ExecuteTreasuryFeeRecipients {},
+    )
+}
+
+/// Build the `set_pool_fees` instruction, signed by both the owner and the creator of the pool.
+pub fn set_pool_fees(
+    keys: &PoolKeys,
+    owner: &Pubkey,
+    platform_fee: Option<u16>,
+    referral_fee: Option<u16>,
+    swap_fee: Option<u16>,
+) -> Instruction {
+    build_instruction(
+        accounts::PoolFeeConfig {
+            pool: keys.pool,
+            config: find_owner_config_address().0,
+            owner: *owner,
+            creator: keys.creator,
+        },
+        instruction::SetPoolFees {
+            platform_fee,
+            referral_fee,
+            swap_fee,
+        },
+    )
+}
+
+/// Build the `register_referrer` instruction, signed by the owner.
+pub fn register_referrer(owner: &Pubkey, referrer: Pubkey) -> Instruction {
+    build_instruction(
+        accounts::RegisterReferrer {
+            referrer_registration: find_referrer_registration_address(&referrer).0,
+            config: find_owner_config_address().0,
+            owner: *owner,
+            system_program: system_program::ID,
+        },
+        instruction::RegisterReferrer { referrer },
+    )
+}
+
+/// Build the `unregister_referrer` instruction, signed by the owner.
+pub fn unregister_referrer(owner: &Pubkey, referrer: &Pubkey) -> Instruction {
+    build_instruction(
+        accounts::UnregisterReferrer {
+            referrer_registration: find_referrer_registration_address(referrer).0,
+            config: find_owner_config_address().0,
+            owner: *owner,
+        },
+        instruction::UnregisterReferrer {},
+    )
+}
+
+/// Build the `set_referrer_registry_required` instruction, signed by the owner.
+pub fn set_referrer_registry_required(owner: &Pubkey, required: bool) -> Instruction {
+    build_instruction(
+        accounts::FeeConfig {
+            config: find_owner_config_address().0,
+            owner: *owner,
+            system_program: system_program::ID,
+        },
+        instruction::SetReferrerRegistryRequired { required },
+    )
+}
+
+/// Build the `nominate_new_owner` instruction, signed by the owner.
+pub fn nominate_new_owner(owner: &Pubkey, new_owner_key: Pubkey) -> Instruction {
+    build_instruction(
+        accounts::OnlyOwner {
+            config: find_owner_config_address().0,
+            owner: *owner,
+        },
+        instruction::NominateNewOwner { new_owner_key },
+    )
+}
+
+/// Build the `accept_new_owner` instruction, signed by the nominated owner.
+pub fn accept_new_owner(new_owner: &Pubkey) -> Instruction {
+    build_instruction(
+        accounts::AcceptOwner {
+            config: find_owner_config_address().0,
+            new_owner: *new_owner,
+        },
+        instruction::AcceptNewOwner {},
+    )
+}
+
+// Migration ----------------------------------------------------------
+
+/// Build the `migrate_owner_config` instruction, signed by the owner, migrating the treasury along with the config.
+pub fn migrate_owner_config(
+    owner: &Pubkey,
+    max_platform_fee: u16,
+    max_referral_fee: u16,
+    max_swap_fee: u16,
+) -> Instruction {
+    build_instruction(
+        accounts::MigrateOwnerConfig {
+            config: find_owner_config_address().0,
+            treasury: find_treasury_address().0,
+            owner: *owner,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateOwnerConfig {
+            max_platform_fee,
+            max_referral_fee,
+            max_swap_fee,
+        },
+    )
+}
+
+/// Build the `migrate_pool` instruction, signed by `payer`.
+pub fn migrate_pool(keys: &PoolKeys, payer: &Pubkey) -> Instruction {
+    build_instruction(
+        accounts::MigratePool {
+            pool: keys.pool,
+            config: find_owner_config_address().0,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::MigratePool {},
+    )
+}
+
+/// Build the `migrate_user_state` instruction for the state of `user` in the pool, signed by `payer`.
+pub fn migrate_user_state(keys: &PoolKeys, user: &Pubkey, payer: &Pubkey) -> Instruction {
+    build_instruction(
+        accounts::MigrateUserState {
+            user_state_in_pool: find_user_state_address(user, &keys.pool).0,
+            payer: *payer,
+            system_program: system_program::ID,
+        },
+        instruction::MigrateUserState {},
+    )
+}
@@ -0,0 +1,25 @@
+//! Off-chain SDK for the Fjord LBP program.
+//!
+//! Exposes the pool math over a plain snapshot of the pool, derives the program addresses, builds the
+//! whitelist Merkle trees and builds every instruction of the program with the right account metas,
+//! without needing a Solana runtime.
+
+pub mod instructions;
+pub mod math;
+pub mod pda;
+pub mod whitelist;
+
+pub use instructions::*;
+pub use math::*;
+pub use pda::*;
+pub use whitelist::*;
+
+#[cfg(test)]
+mod tests;
+
+pub use fjord_lbp::{
+    instruction as instruction_data, structs::ComputedReservesAndWeights, FeeMapping,
+    LiquidityBootstrappingPool, OwnerConfig, PreviewAmountArgs, ReferralSchedule, ReferralTier,
+    ReferrerRegistration, SafeMathError, Treasury, UserStateInPool, WeightAdjustment, WeightCurve,
+    WeightKnot, WhitelistAttestation, WhitelistLeafVersion, WhitelistProof, ID,
+};
@@ -0,0 +1,151 @@
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_fee::TransferFee;
+use fjord_lbp::math;
+use fjord_lbp::safe_math::{safe_add, safe_sub};
+
+use crate::{
+    ComputedReservesAndWeights, LiquidityBootstrappingPool, PreviewAmountArgs, SafeMathError,
+    UserStateInPool,
+};
+
+/// Snapshot of a pool and of the accounts around it, enough to run the pool math off-chain.
+/// The previews mirror the program's `preview_*` instructions, swap and transfer fees included.
+#[derive(Clone)]
+pub struct PoolSnapshot {
+    pub pool: LiquidityBootstrappingPool,
+    // Balance of the pool's asset token account
+    pub assets: u64,
+    // Balance of the pool's share token account
+    pub shares: u64,
+    pub asset_token_decimals: u8,
+    pub share_token_decimals: u8,
+    // Token-2022 transfer fee of the asset mint for the current epoch (if any)
+    pub asset_transfer_fee: Option<TransferFee>,
+}
+
+impl PoolSnapshot {
+    /// Build the arguments of the pool math at `current_time`.
+    pub fn preview_amount_args(&self, current_time: i64) -> PreviewAmountArgs {
+        PreviewAmountArgs {
+            assets: self.assets,
+            virtual_assets: self.pool.virtual_assets,
+            asset_token_decimal: self.asset_token_decimals,
+            shares: self.shares,
+            virtual_shares: self.pool.virtual_shares,
+            share_token_decimal: self.share_token_decimals,
+            total_purchased: self.pool.total_purchased,
+            max_share_price: self.pool.max_share_price,
+            current_time,
+            sale_start_time: self.pool.sale_start_time,
+            sale_end_time: self.pool.sale_end_time,
+            start_weight_basis_points: self.pool.start_weight_basis_points,
+            end_weight_basis_points: self.pool.end_weight_basis_points,
+            weight_curve: self.pool.weight_curve,
+            weight_knots: self.pool.weight_knots,
+            weight_adjustment: self.pool.weight_adjustment,
+            total_swap_fees_asset: self.pool.total_swap_fees_asset,
+            total_swap_fees_share: self.pool.total_swap_fees_share,
+        }
+    }
+
+    /// Assets the user has to send to buy exactly `shares_out` shares.
+    pub fn preview_assets_in(
+        &self,
+        shares_out: u64,
+        current_time: i64,
+    ) -> Result<u64, SafeMathError> {
+        let mut assets_in =
+            math::preview_assets_in(self.preview_amount_args(current_time), shares_out)?;
+        assets_in = safe_add(
+            assets_in,
+            math::calculate_fee(assets_in, self.pool.swap_fee),
+        )?;
+        safe_add(assets_in, self.transfer_inverse_fee(assets_in)?)
+    }
+
+    /// Shares the user has to sell to receive exactly `assets_out` assets.
+    pub fn preview_shares_in(
+        &self,
+        assets_out: u64,
+        current_time: i64,
+    ) -> Result<u64, SafeMathError> {
+        let assets_out = safe_add(assets_out, self.transfer_inverse_fee(assets_out)?)?;
+        let shares_in =
+            math::preview_shares_in(self.preview_amount_args(current_time), assets_out)?;
+        safe_add(
+            shares_in,
+            math::calculate_fee(shares_in, self.pool.swap_fee),
+        )
+    }
+
+    /// Shares received when buying with exactly `assets_in` assets.
+    pub fn preview_shares_out(
+        &self,
+        assets_in: u64,
+        current_time: i64,
+    ) -> Result<u64, SafeMathError> {
+        let assets_in = safe_sub(assets_in, self.transfer_fee(assets_in)?)?;
+        math::preview_shares_out(
+            self.preview_amount_args(current_time),
+            safe_sub(
+                assets_in,
+                math::calculate_fee(assets_in, self.pool.swap_fee),
+            )?,
+        )
+    }
+
+    /// Assets received when selling exactly `shares_in` shares.
+    pub fn preview_assets_out(
+        &self,
+        shares_in: u64,
+        current_time: i64,
+    ) -> Result<u64, SafeMathError> {
+        let assets_out = math::preview_assets_out(
+            self.preview_amount_args(current_time),
+            safe_sub(
+                shares_in,
+                math::calculate_fee(shares_in, self.pool.swap_fee),
+            )?,
+        )?;
+        safe_sub(assets_out, self.transfer_fee(assets_out)?)
+    }
+
+    /// Reserves and weights of the pool at `current_time`.
+    pub fn reserves_and_weights(
+        &self,
+        current_time: i64,
+    ) -> Result<ComputedReservesAndWeights, SafeMathError> {
+        math::compute_reserves_and_weights(&self.preview_amount_args(current_time))
+    }
+
+    /// Total shares of a user unlocked by the vesting schedule at `current_time`, redeemed shares included.
+    pub fn vested_shares(
+        &self,
+        user_state: &UserStateInPool,
+        current_time: i64,
+    ) -> Result<u64, SafeMathError> {
+        math::compute_vested_shares(
+            safe_add(user_state.purchased_shares, user_state.redeemed_shares)?,
+            current_time,
+            self.pool.vest_cliff,
+            self.pool.vest_end,
+        )
+    }
+
+    fn transfer_fee(&self, amount: u64) -> Result<u64, SafeMathError> {
+        match &self.asset_transfer_fee {
+            Some(fee) => fee
+                .calculate_fee(amount)
+                .ok_or(SafeMathError::MultiplicationOverflow),
+            None => Ok(0),
+        }
+    }
+
+    fn transfer_inverse_fee(&self, post_fee_amount: u64) -> Result<u64, SafeMathError> {
+        match &self.asset_transfer_fee {
+            Some(fee) => fee
+                .calculate_inverse_fee(post_fee_amount)
+                .ok_or(SafeMathError::MultiplicationOverflow),
+            None => Ok(0),
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+
+use crate::ID;
+
+/// Derive the address of a pool from its mints, creator and salt.
+pub fn find_pool_address(
+    share_token_mint: &Pubkey,
+    asset_token_mint: &Pubkey,
+    creator: &Pubkey,
+    salt: &str,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            share_token_mint.as_ref(),
+            asset_token_mint.as_ref(),
+            creator.as_ref(),
+            salt.as_bytes(),
+        ],
+        &ID,
+    )
+}
+
+/// Derive the address of the global owner configuration.
+pub fn find_owner_config_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&["owner_config".as_bytes()], &ID)
+}
+
+/// Derive the address of the treasury holding the fee recipients.
+pub fn find_treasury_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&["treasury".as_bytes()], &ID)
+}
+
+/// Derive the address of the state of a user (or referrer) in a pool.
+pub fn find_user_state_address(user: &Pubkey, pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[user.as_ref(), pool.as_ref()], &ID)
+}
+
+/// Derive the address of the registration of a referrer in the referrer registry.
+pub fn find_referrer_registration_address(referrer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&["referrer".as_bytes(), referrer.as_ref()], &ID)
+}
+
+/// Derive the address of the transient wSOL account used to unwrap native SOL for `user`.
+pub fn find_native_unwrap_address(pool: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &["native_unwrap".as_bytes(), pool.as_ref(), user.as_ref()],
+        &ID,
+    )
+}
+
+/// Derive the address of the program data account, needed to initialize the owner configuration.
+pub fn find_program_data_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ID.as_ref()], &bpf_loader_upgradeable::ID)
+}
+
+/// Derive the associated token account of `owner` for `mint`.
+pub fn get_token_account_address(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, mint, token_program)
+}
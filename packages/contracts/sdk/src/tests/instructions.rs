@@ -0,0 +1,284 @@
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::{system_program, AnchorDeserialize};
+use anchor_spl::{associated_token, token, token_2022};
+use fjord_lbp::instruction;
+
+use crate::{
+    find_native_unwrap_address, find_owner_config_address, find_referrer_registration_address,
+    find_treasury_address, find_user_state_address, FeeMapping, PoolKeys, SwapReferrer, Treasury,
+    ID,
+};
+
+fn keys() -> PoolKeys {
+    PoolKeys::new(
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        "salt",
+        token::ID,
+        token_2022::ID,
+    )
+}
+
+/// Meta of an account the program only reads.
+fn read(pubkey: Pubkey) -> AccountMeta {
+    AccountMeta::new_readonly(pubkey, false)
+}
+
+/// Meta of an account the program writes to.
+fn write(pubkey: Pubkey) -> AccountMeta {
+    AccountMeta::new(pubkey, false)
+}
+
+/// Meta of an optional account left out, which Anchor expects as the program id.
+fn omitted() -> AccountMeta {
+    AccountMeta::new_readonly(ID, false)
+}
+
+/// Decode the arguments of `instruction`, checking that its data starts with the sighash of `name`.
+fn arguments<T: AnchorDeserialize>(instruction: &Instruction, name: &str) -> T {
+    assert_eq!(instruction.program_id, ID);
+    let sighash = &hash(format!("global:{name}").as_bytes()).to_bytes()[..8];
+    assert_eq!(&instruction.data[..8], sighash);
+    T::try_from_slice(&instruction.data[8..]).unwrap()
+}
+
+#[test]
+fn swaps_pass_the_accounts_of_the_program_in_order() {
+    let keys = keys();
+    let user = Pubkey::new_unique();
+    let referrer = SwapReferrer {
+        referrer: Pubkey::new_unique(),
+        second_level_referrer: Some(Pubkey::new_unique()),
+    };
+    let instruction =
+        crate::swap_exact_assets_for_shares(&keys, &user, 1_000, 900, None, Some(referrer), false);
+
+    // Laid out as the `SwapTokens` accounts of the program
+    assert_eq!(
+        instruction.accounts,
+        vec![
+            read(keys.asset_token_mint),
+            read(keys.share_token_mint),
+            write(keys.pool),
+            write(keys.pool_asset_token_account()),
+            write(keys.pool_share_token_account()),
+            write(keys.asset_token_account(&user)),
+            write(keys.share_token_account(&user)),
+            omitted(),
+            read(find_owner_config_address().0),
+            write(find_user_state_address(&user, &keys.pool).0),
+            write(find_user_state_address(&referrer.referrer, &keys.pool).0),
+            write(find_user_state_address(&referrer.second_level_referrer.unwrap(), &keys.pool).0),
+            read(find_referrer_registration_address(&referrer.referrer).0),
+            AccountMeta::new(user, true),
+            read(associated_token::ID),
+            read(token::ID),
+            read(token_2022::ID),
+            read(system_program::ID),
+            read(sysvar::instructions::ID),
+        ]
+    );
+    let args: instruction::SwapExactAssetsForShares =
+        arguments(&instruction, "swap_exact_assets_for_shares");
+    assert_eq!((args.assets_in, args.min_shares_out), (1_000, 900));
+    assert!(args.merkle_proof.is_none());
+    assert_eq!(args.referrer, Some(referrer.referrer));
+}
+
+#[test]
+fn swaps_leave_out_the_accounts_they_do_not_use() {
+    let keys = keys();
+    let user = Pubkey::new_unique();
+
+    // Without referrer, none of the referral accounts are passed
+    let instruction =
+        crate::swap_exact_shares_for_assets(&keys, &user, 1_000, 1, None, None, false);
+    assert_eq!(
+        instruction.accounts[10..13],
+        [omitted(), omitted(), omitted()]
+    );
+    let args: instruction::SwapExactSharesForAssets =
+        arguments(&instruction, "swap_exact_shares_for_assets");
+    assert_eq!(args.referrer, None);
+
+    // The user state cannot be passed twice, self-referrals only pass the registration for the program to reject
+    let instruction = crate::swap_assets_for_exact_shares(
+        &keys,
+        &user,
+        1_000,
+        u64::MAX,
+        None,
+        Some(user.into()),
+        false,
+    );
+    assert_eq!(instruction.accounts[10..12], [omitted(), omitted()]);
+    assert_eq!(
+        instruction.accounts[12],
+        read(find_referrer_registration_address(&user).0)
+    );
+    let args: instruction::SwapAssetsForExactShares =
+        arguments(&instruction, "swap_assets_for_exact_shares");
+    assert_eq!(args.referrer, Some(user));
+
+    // Native SOL purchases pay in lamports, and sales unwrap the assets through the transient account
+    let instruction = crate::swap_exact_assets_for_shares(&keys, &user, 1_000, 1, None, None, true);
+    assert_eq!(instruction.accounts[5], omitted());
+    assert_eq!(instruction.accounts[7], omitted());
+    let instruction =
+        crate::swap_shares_for_exact_assets(&keys, &user, 1_000, u64::MAX, None, None, true);
+    assert_eq!(instruction.accounts[5], omitted());
+    assert_eq!(
+        instruction.accounts[7],
+        write(find_native_unwrap_address(&keys.pool, &user).0)
+    );
+    let args: instruction::SwapSharesForExactAssets =
+        arguments(&instruction, "swap_shares_for_exact_assets");
+    assert_eq!((args.assets_out, args.max_shares_in), (1_000, u64::MAX));
+}
+
+#[test]
+fn pools_are_closed_paying_the_fee_recipients_as_remaining_accounts() {
+    let keys = keys();
+    let user = Pubkey::new_unique();
+    let treasury = Treasury {
+        swap_fee_recipient: Pubkey::new_unique(),
+        fee_recipients: vec![
+            FeeMapping {
+                user: Pubkey::new_unique(),
+                percentage: 4_000,
+            },
+            FeeMapping {
+                user: Pubkey::new_unique(),
+                percentage: 6_000,
+            },
+        ],
+        pending_fee_recipients: None,
+    };
+    let recipients = [
+        treasury.fee_recipients[0].user,
+        treasury.fee_recipients[1].user,
+    ];
+
+    // Laid out as the `ClosePool` accounts of the program, followed by the token accounts of the recipients
+    let instruction = crate::close_pool(&keys, &user, &treasury, false);
+    let treasury_address = find_treasury_address().0;
+    assert_eq!(
+        instruction.accounts,
+        vec![
+            read(keys.asset_token_mint),
+            read(keys.share_token_mint),
+            write(keys.pool),
+            write(keys.pool_asset_token_account()),
+            write(keys.pool_share_token_account()),
+            write(keys.asset_token_account(&treasury_address)),
+            write(keys.share_token_account(&treasury_address)),
+            read(treasury_address),
+            write(keys.asset_token_account(&keys.creator)),
+            write(keys.share_token_account(&keys.creator)),
+            write(keys.creator),
+            write(keys.asset_token_account(&treasury.swap_fee_recipient)),
+            write(keys.share_token_account(&treasury.swap_fee_recipient)),
+            write(treasury.swap_fee_recipient),
+            omitted(),
+            read(find_owner_config_address().0),
+            AccountMeta::new(user, true),
+            read(associated_token::ID),
+            read(token::ID),
+            read(token_2022::ID),
+            read(system_program::ID),
+            write(keys.asset_token_account(&recipients[0])),
+            write(keys.asset_token_account(&recipients[1])),
+        ]
+    );
+    arguments::<instruction::ClosePool>(&instruction, "close_pool");
+
+    // Native SOL pools pay the creator, the swap fee recipient and the fee recipients in their wallets
+    let instruction = crate::close_pool(&keys, &user, &treasury, true);
+    assert_eq!(instruction.accounts[8], omitted());
+    assert_eq!(instruction.accounts[11], omitted());
+    assert_eq!(
+        instruction.accounts[14],
+        write(find_native_unwrap_address(&keys.pool, &user).0)
+    );
+    assert_eq!(
+        instruction.accounts[21..],
+        [write(recipients[0]), write(recipients[1])]
+    );
+}
+
+#[test]
+fn pool_fee_overrides_are_signed_by_the_owner_and_the_creator() {
+    let keys = keys();
+    let owner = Pubkey::new_unique();
+    let instruction = crate::set_pool_fees(&keys, &owner, Some(100), None, Some(0));
+
+    assert_eq!(
+        instruction.accounts,
+        vec![
+            write(keys.pool),
+            read(find_owner_config_address().0),
+            AccountMeta::new_readonly(owner, true),
+            AccountMeta::new_readonly(keys.creator, true),
+        ]
+    );
+    let args: instruction::SetPoolFees = arguments(&instruction, "set_pool_fees");
+    assert_eq!(
+        (args.platform_fee, args.referral_fee, args.swap_fee),
+        (Some(100), None, Some(0))
+    );
+}
+
+#[test]
+fn migrations_are_paid_by_their_signer() {
+    let keys = keys();
+    let owner = Pubkey::new_unique();
+    let user = Pubkey::new_unique();
+    let payer = Pubkey::new_unique();
+
+    let instruction = crate::migrate_owner_config(&owner, 1_000, 2_000, 3_000);
+    assert_eq!(
+        instruction.accounts,
+        vec![
+            write(find_owner_config_address().0),
+            write(find_treasury_address().0),
+            AccountMeta::new(owner, true),
+            read(system_program::ID),
+        ]
+    );
+    let args: instruction::MigrateOwnerConfig = arguments(&instruction, "migrate_owner_config");
+    assert_eq!(
+        (
+            args.max_platform_fee,
+            args.max_referral_fee,
+            args.max_swap_fee
+        ),
+        (1_000, 2_000, 3_000)
+    );
+
+    let instruction = crate::migrate_pool(&keys, &payer);
+    assert_eq!(
+        instruction.accounts,
+        vec![
+            write(keys.pool),
+            read(find_owner_config_address().0),
+            AccountMeta::new(payer, true),
+            read(system_program::ID),
+        ]
+    );
+    arguments::<instruction::MigratePool>(&instruction, "migrate_pool");
+
+    let instruction = crate::migrate_user_state(&keys, &user, &payer);
+    assert_eq!(
+        instruction.accounts,
+        vec![
+            write(find_user_state_address(&user, &keys.pool).0),
+            AccountMeta::new(payer, true),
+            read(system_program::ID),
+        ]
+    );
+    arguments::<instruction::MigrateUserState>(&instruction, "migrate_user_state");
+}
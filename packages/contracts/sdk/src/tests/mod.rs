@@ -0,0 +1,5 @@
+//! Tests of the addresses and instructions built by the SDK, checked against the seeds and account
+//! constraints of the program.
+
+mod instructions;
+mod pda;
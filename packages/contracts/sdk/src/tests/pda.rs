@@ -0,0 +1,110 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::{token, token_2022};
+
+use crate::{
+    find_native_unwrap_address, find_owner_config_address, find_pool_address,
+    find_program_data_address, find_referrer_registration_address, find_treasury_address,
+    find_user_state_address, PoolKeys, ID,
+};
+
+/// Check that `(address, bump)` is the canonical address of the program `program_id` for `seeds`.
+fn assert_derived(found: (Pubkey, u8), seeds: &[&[u8]], program_id: &Pubkey) {
+    let (address, bump) = found;
+    assert_eq!(
+        (address, bump),
+        Pubkey::find_program_address(seeds, program_id)
+    );
+    let bump = [bump];
+    let seeds = [seeds, &[&bump]].concat();
+    assert_eq!(
+        Pubkey::create_program_address(&seeds, program_id).unwrap(),
+        address
+    );
+}
+
+#[test]
+fn global_addresses_use_the_seeds_of_the_program() {
+    let user = Pubkey::new_unique();
+    let pool = Pubkey::new_unique();
+
+    assert_derived(find_owner_config_address(), &[b"owner_config"], &ID);
+    assert_derived(find_treasury_address(), &[b"treasury"], &ID);
+    assert_derived(
+        find_user_state_address(&user, &pool),
+        &[user.as_ref(), pool.as_ref()],
+        &ID,
+    );
+    assert_derived(
+        find_referrer_registration_address(&user),
+        &[b"referrer", user.as_ref()],
+        &ID,
+    );
+    assert_derived(
+        find_native_unwrap_address(&pool, &user),
+        &[b"native_unwrap", pool.as_ref(), user.as_ref()],
+        &ID,
+    );
+    assert_derived(
+        find_program_data_address(),
+        &[ID.as_ref()],
+        &bpf_loader_upgradeable::ID,
+    );
+}
+
+#[test]
+fn pool_keys_derive_the_pool_and_its_token_accounts() {
+    let asset_token_mint = Pubkey::new_unique();
+    let share_token_mint = Pubkey::new_unique();
+    let creator = Pubkey::new_unique();
+    let keys = PoolKeys::new(
+        asset_token_mint,
+        share_token_mint,
+        creator,
+        "salt",
+        token::ID,
+        token_2022::ID,
+    );
+
+    assert_derived(
+        find_pool_address(&share_token_mint, &asset_token_mint, &creator, "salt"),
+        &[
+            share_token_mint.as_ref(),
+            asset_token_mint.as_ref(),
+            creator.as_ref(),
+            b"salt",
+        ],
+        &ID,
+    );
+    assert_eq!(
+        keys.pool,
+        find_pool_address(&share_token_mint, &asset_token_mint, &creator, "salt").0
+    );
+    assert_ne!(
+        keys.pool,
+        find_pool_address(&share_token_mint, &asset_token_mint, &creator, "other").0
+    );
+
+    // Each token account belongs to the token program of its mint
+    assert_eq!(
+        keys.pool_asset_token_account(),
+        get_associated_token_address_with_program_id(&keys.pool, &asset_token_mint, &token::ID)
+    );
+    assert_eq!(
+        keys.pool_share_token_account(),
+        get_associated_token_address_with_program_id(
+            &keys.pool,
+            &share_token_mint,
+            &token_2022::ID
+        )
+    );
+    assert_eq!(
+        keys.asset_token_account(&creator),
+        get_associated_token_address_with_program_id(&creator, &asset_token_mint, &token::ID)
+    );
+    assert_eq!(
+        keys.share_token_account(&creator),
+        get_associated_token_address_with_program_id(&creator, &share_token_mint, &token_2022::ID)
+    );
+}
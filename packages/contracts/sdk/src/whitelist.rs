@@ -0,0 +1,91 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AnchorSerialize;
+use fjord_lbp::{merkle_node, whitelist_leaf, WhitelistLeafVersion};
+
+use crate::{WhitelistAttestation, WhitelistProof};
+
+/// A whitelisted user along with its tier and allocation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WhitelistEntry {
+    pub user: Pubkey,
+    pub tier: u8,
+    // Maximum amount of assets the user can spend, net of the swap fees and of its sales (0 for no limit)
+    pub max_allocation: u64,
+}
+
+/// Merkle tree of a pool's whitelist, verified on-chain by `merkle_verify` with the pool's `whitelist_leaf_version`.
+/// Pairs are hashed in sorted order and the last node of odd levels is promoted as is, which matches the
+/// trees built with `merkletreejs` and `sortPairs: true`. Trees built with OpenZeppelin's `StandardMerkleTree`
+/// are shaped differently, but their proofs verify just as well in `Standard` pools.
+pub struct WhitelistTree {
+    version: WhitelistLeafVersion,
+    entries: Vec<WhitelistEntry>,
+    // Leaves first, root last
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl WhitelistTree {
+    /// Build the tree of `entries`. The tiers and allocations of the entries are ignored by `Legacy` trees.
+    pub fn new(version: WhitelistLeafVersion, entries: Vec<WhitelistEntry>) -> Self {
+        let mut levels = vec![entries
+            .iter()
+            .map(|entry| whitelist_leaf(version, &entry.user, entry.tier, entry.max_allocation))
+            .collect::<Vec<_>>()];
+        while levels.last().unwrap().len() > 1 {
+            let level = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => merkle_node(left, right),
+                    [node] => *node,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(level);
+        }
+        Self {
+            version,
+            entries,
+            levels,
+        }
+    }
+
+    /// Root to store in the pool, all zeros for an empty whitelist (which disables it).
+    pub fn root(&self) -> [u8; 32] {
+        self.levels
+            .last()
+            .and_then(|level| level.first().copied())
+            .unwrap_or_default()
+    }
+
+    /// Whitelist proof of `user` to pass to the swaps, if whitelisted.
+    pub fn proof(&self, user: &Pubkey) -> Option<WhitelistProof> {
+        let mut index = self.entries.iter().position(|entry| entry.user == *user)?;
+        let entry = match self.version {
+            WhitelistLeafVersion::Legacy => WhitelistEntry {
+                tier: 0,
+                max_allocation: 0,
+                ..self.entries[index]
+            },
+            WhitelistLeafVersion::Tiered | WhitelistLeafVersion::Standard => self.entries[index],
+        };
+        let mut proof = Vec::new();
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(index ^ 1) {
+                proof.push(*sibling);
+            }
+            index /= 2;
+        }
+        Some(WhitelistProof {
+            tier: entry.tier,
+            max_allocation: entry.max_allocation,
+            proof,
+        })
+    }
+}
+
+/// Message to sign with the whitelist signer of a pool to attest a user, see `verify_whitelist_attestation`.
+pub fn whitelist_attestation_message(attestation: &WhitelistAttestation) -> Vec<u8> {
+    attestation.try_to_vec().unwrap()
+}
@@ -114,7 +114,7 @@ describe("Fjord LBP - Access Controls", () => {
       ).to.be.rejectedWith("Unauthorized");
     });
 
-    it("Should be able to change the treasury fee recipients as a owner", async () => {
+    it("Should be able to propose new treasury fee recipients as a owner", async () => {
       const newSwapFeeRecipient = Keypair.generate().publicKey;
       const newFeesPercentages = [];
       const newFeeRecipients = [];
@@ -124,7 +124,7 @@ describe("Fjord LBP - Access Controls", () => {
         newFeesPercentages.push(MAX_FEE_BASIS_POINTS / newRecipientsSize);
       }
       await program.methods
-        .setTreasuryFeeRecipients(
+        .proposeTreasuryFeeRecipients(
           newSwapFeeRecipient,
           newFeeRecipients,
           newFeesPercentages
@@ -133,16 +133,25 @@ describe("Fjord LBP - Access Controls", () => {
         .signers([creator])
         .rpc();
 
-      const treasury = await program.account.treasury.fetch(treasuryPda);
-
-      const newTreasuryFeeRecipients = treasury.feeRecipients.map(
-        (x) => x.user
-      );
-      const newTreasuryFeePercentages = treasury.feeRecipients.map(
-        (x) => x.percentage
+      // The new recipients are pending until the fee change delay has elapsed
+      const { pendingFeeRecipients } = await program.account.treasury.fetch(
+        treasuryPda
       );
+
+      const feeRecipients = pendingFeeRecipients?.feeRecipients ?? [];
+
+      const newTreasuryFeeRecipients = feeRecipients.map((x) => x.user);
+      const newTreasuryFeePercentages = feeRecipients.map((x) => x.percentage);
       expect(newTreasuryFeeRecipients).to.deep.eq(newFeeRecipients);
       expect(newTreasuryFeePercentages).to.deep.eq(newFeesPercentages);
+
+      await expect(
+        program.methods
+          .executeTreasuryFeeRecipients()
+          .accounts({ owner: creator.publicKey, treasury: treasuryPda })
+          .signers([creator])
+          .rpc()
+      ).to.be.rejectedWith("FeeChangeNotReady");
     });
 
     it("Should not be able to change the treasury fee recipients if not the same length as percentages", async () => {
@@ -155,7 +164,7 @@ describe("Fjord LBP - Access Controls", () => {
 
       await expect(
         program.methods
-          .setTreasuryFeeRecipients(
+          .proposeTreasuryFeeRecipients(
             newSwapFeeRecipient,
             newFeeRecipients,
             newFeesPercentages
@@ -177,7 +186,7 @@ describe("Fjord LBP - Access Controls", () => {
 
       await expect(
         program.methods
-          .setTreasuryFeeRecipients(
+          .proposeTreasuryFeeRecipients(
             newSwapFeeRecipient,
             newFeeRecipients,
             newFeesPercentages
@@ -199,7 +208,7 @@ describe("Fjord LBP - Access Controls", () => {
       }
       await expect(
         program.methods
-          .setTreasuryFeeRecipients(
+          .proposeTreasuryFeeRecipients(
             newSwapFeeRecipient,
             newFeeRecipients,
             newFeesPercentages
@@ -210,19 +219,22 @@ describe("Fjord LBP - Access Controls", () => {
       ).to.be.rejectedWith("MaxFeeExceeded");
     });
 
-    it("Should be able to set pool fees as a owner", async () => {
+    it("Should be able to propose new pool fees as a owner", async () => {
       const newPlatformFee = 300;
       const newReferralFee = 300;
       const newSwapFee = 300;
 
       await program.methods
-        .setFees(newPlatformFee, newReferralFee, newSwapFee)
+        .proposeFees(newPlatformFee, newReferralFee, newSwapFee, null)
         .accounts({ owner: creator.publicKey })
         .signers([creator])
         .rpc();
 
-      const { platformFee, swapFee, referralFee } =
-        await program.account.ownerConfig.fetch(configPda);
+      // The new fees are pending until the fee change delay has elapsed
+      const { pendingFees } = await program.account.ownerConfig.fetch(
+        configPda
+      );
+      const { platformFee, swapFee, referralFee } = pendingFees!;
 
       expect(platformFee).to.eq(newPlatformFee);
       expect(swapFee).to.eq(newSwapFee);
@@ -236,7 +248,7 @@ describe("Fjord LBP - Access Controls", () => {
 
       await expect(
         program.methods
-          .setFees(newPlatformFee, newReferralFee, newSwapFee)
+          .proposeFees(newPlatformFee, newReferralFee, newSwapFee, null)
           .accounts({ owner: testUserA.publicKey })
           .signers([testUserA])
           .rpc()
@@ -248,7 +260,7 @@ describe("Fjord LBP - Access Controls", () => {
 
       await expect(
         program.methods
-          .setFees(invalidFee, null, null)
+          .proposeFees(invalidFee, null, null, null)
           .accounts({ owner: creator.publicKey })
           .signers([creator])
           .rpc()
@@ -256,7 +268,7 @@ describe("Fjord LBP - Access Controls", () => {
 
       await expect(
         program.methods
-          .setFees(null, invalidFee, null)
+          .proposeFees(null, invalidFee, null, null)
           .accounts({ owner: creator.publicKey })
           .signers([creator])
           .rpc()
@@ -264,7 +276,7 @@ describe("Fjord LBP - Access Controls", () => {
 
       await expect(
         program.methods
-          .setFees(null, null, invalidFee)
+          .proposeFees(null, null, invalidFee, null)
           .accounts({ owner: creator.publicKey })
           .signers([creator])
           .rpc()
@@ -278,7 +290,7 @@ describe("Fjord LBP - Access Controls", () => {
 
       await expect(
         program.methods
-          .setFees(newPlatformFee, newReferralFee, newSwapFee)
+          .proposeFees(newPlatformFee, newReferralFee, newSwapFee, null)
           .accounts({ owner: creator.publicKey })
           .signers([creator])
           .rpc()
@@ -290,7 +302,7 @@ describe("Fjord LBP - Access Controls", () => {
       // Try using owner only functions
       await expect(
         program.methods
-          .setFees(200, null, null)
+          .proposeFees(200, null, null, null)
           .accounts({ owner: testUserA.publicKey })
           .signers([testUserA])
           .rpc()
@@ -327,15 +339,15 @@ describe("Fjord LBP - Access Controls", () => {
       // Try using owner only functions
       const newPlatformFee = 200;
       await program.methods
-        .setFees(newPlatformFee, null, null)
+        .proposeFees(newPlatformFee, null, null, null)
         .accounts({ owner: testUserA.publicKey })
         .signers([testUserA])
         .rpc();
 
-      const { platformFee } = await program.account.ownerConfig.fetch(
+      const { pendingFees } = await program.account.ownerConfig.fetch(
         configPda
       );
-      expect(platformFee).to.eq(newPlatformFee);
+      expect(pendingFees?.platformFee).to.eq(newPlatformFee);
     });
   });
 
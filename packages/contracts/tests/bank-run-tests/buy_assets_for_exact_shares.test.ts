@@ -4,6 +4,7 @@ import { findProgramAddressSync } from "@project-serum/anchor/dist/cjs/utils/pub
 import {
   MAX_FEE_BASIS_POINTS,
   getAssociatedTokenAddress,
+  TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import {
   Keypair,
@@ -252,6 +253,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
         creator: creator.publicKey,
         shareTokenMint,
         assetTokenMint,
+        assetTokenProgram: TOKEN_PROGRAM_ID,
+        shareTokenProgram: TOKEN_PROGRAM_ID,
         poolShareTokenAccount,
         poolAssetTokenAccount,
         creatorShareTokenAccount,
@@ -292,6 +295,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -315,6 +320,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           user: testUserA.publicKey,
           pool: poolPda,
           poolAssetTokenAccount,
@@ -323,7 +330,10 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
           userShareTokenAccount: shareTokenMintUserAccount,
           config: ownerConfigPda,
           referrerStateInPool: referrer,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
           userStateInPool: userPoolPda,
+          nativeUnwrapAccount: null,
         })
         .signers([testUserA])
         .rpc();
@@ -368,6 +378,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -391,6 +403,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           user: testUserA.publicKey,
           pool: poolPda,
           poolAssetTokenAccount,
@@ -399,7 +413,10 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
           userShareTokenAccount: shareTokenMintUserAccount,
           config: ownerConfigPda,
           referrerStateInPool: referrerPda,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
           userStateInPool: userPoolPda,
+          nativeUnwrapAccount: null,
         })
         .signers([testUserA])
         .rpc();
@@ -467,6 +484,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -490,6 +509,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           user: testUserA.publicKey,
           pool: poolPda,
           poolAssetTokenAccount,
@@ -498,7 +519,10 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
           userShareTokenAccount: shareTokenMintUserAccount,
           config: ownerConfigPda,
           referrerStateInPool: referrerPda,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
           userStateInPool: userPoolPda,
+          nativeUnwrapAccount: null,
         })
         .signers([testUserA])
         .rpc();
@@ -639,6 +663,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
           creator: creator.publicKey,
           shareTokenMint,
           assetTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           poolShareTokenAccount,
           poolAssetTokenAccount,
           creatorShareTokenAccount,
@@ -689,6 +715,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -712,6 +740,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           user: testUserB.publicKey,
           pool: poolPda,
           poolAssetTokenAccount,
@@ -720,7 +750,10 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
           userShareTokenAccount: shareTokenMintUserAccount,
           config: ownerConfigPda,
           referrerStateInPool: referrerPda,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
           userStateInPool: userPoolPda,
+          nativeUnwrapAccount: null,
         })
         .signers([testUserB])
         .rpc();
@@ -824,6 +857,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -841,6 +876,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -864,6 +901,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           user: testUserA.publicKey,
           pool: poolPda,
           poolAssetTokenAccount,
@@ -872,7 +911,10 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
           userShareTokenAccount: shareTokenMintUserAccount,
           config: ownerConfigPda,
           referrerStateInPool: referrerPda,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
           userStateInPool: userPoolPda,
+          nativeUnwrapAccount: null,
         })
 
         .signers([testUserA])
@@ -885,6 +927,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -938,6 +982,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -962,6 +1008,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -970,7 +1018,10 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrer,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
@@ -1052,6 +1103,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
           creator: creator.publicKey,
           shareTokenMint,
           assetTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           poolShareTokenAccount,
           poolAssetTokenAccount,
           creatorShareTokenAccount,
@@ -1096,6 +1149,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -1119,6 +1174,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserB.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -1127,7 +1184,10 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrerPda,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserB])
           .rpc()
@@ -1212,6 +1272,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
           creator: creator.publicKey,
           shareTokenMint,
           assetTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           poolShareTokenAccount,
           poolAssetTokenAccount,
           creatorShareTokenAccount,
@@ -1251,6 +1313,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -1275,6 +1339,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -1283,7 +1349,10 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrer,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
@@ -1322,6 +1391,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -1346,6 +1417,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -1354,7 +1427,10 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrer,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
@@ -1392,6 +1468,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -1416,6 +1494,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -1424,7 +1504,10 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrer,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserB])
           .rpc()
@@ -1446,6 +1529,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -1454,7 +1539,10 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrer,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([creator])
           .rpc()
@@ -1491,6 +1579,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -1515,6 +1605,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserB.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -1523,7 +1615,10 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrer,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserB])
           .rpc()
@@ -1562,6 +1657,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -1586,6 +1683,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount: assetTokenMintUserAccount,
@@ -1594,7 +1693,10 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrer,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
@@ -1633,6 +1735,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -1657,6 +1761,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -1665,7 +1771,10 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrer,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
@@ -1703,6 +1812,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -1727,6 +1838,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -1735,7 +1848,10 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
             userShareTokenAccount: assetTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrer,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
@@ -1773,6 +1889,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -1797,6 +1915,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -1805,7 +1925,10 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrer,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
@@ -1843,6 +1966,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -1867,6 +1992,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -1875,7 +2002,10 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrer,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
@@ -1960,6 +2090,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
           creator: creator.publicKey,
           shareTokenMint,
           assetTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           poolShareTokenAccount,
           poolAssetTokenAccount,
           creatorShareTokenAccount,
@@ -1998,6 +2130,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -2022,6 +2156,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -2030,7 +2166,10 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrer,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
@@ -2069,6 +2208,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -2095,6 +2236,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -2103,7 +2246,10 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrer,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
@@ -2148,6 +2294,8 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -2156,7 +2304,10 @@ describe("Fjord LBP - Buy `swapAssetsForExactShares`", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrer,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
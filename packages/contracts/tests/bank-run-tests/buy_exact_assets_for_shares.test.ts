@@ -4,6 +4,7 @@ import { findProgramAddressSync } from "@project-serum/anchor/dist/cjs/utils/pub
 import {
   MAX_FEE_BASIS_POINTS,
   getAssociatedTokenAddress,
+  TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import {
   Keypair,
@@ -246,6 +247,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
         creator: creator.publicKey,
         shareTokenMint,
         assetTokenMint,
+        assetTokenProgram: TOKEN_PROGRAM_ID,
+        shareTokenProgram: TOKEN_PROGRAM_ID,
         poolShareTokenAccount,
         poolAssetTokenAccount,
         creatorShareTokenAccount,
@@ -284,6 +287,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -306,6 +311,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           user: testUserA.publicKey,
           pool: poolPda,
           poolAssetTokenAccount,
@@ -314,7 +321,10 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
           userShareTokenAccount: shareTokenMintUserAccount,
           config: ownerConfigPda,
           referrerStateInPool: referrer,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
           userStateInPool: userPoolPda,
+          nativeUnwrapAccount: null,
         })
         .signers([testUserA])
         .rpc();
@@ -358,6 +368,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -380,6 +392,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           user: testUserA.publicKey,
           pool: poolPda,
           poolAssetTokenAccount,
@@ -388,7 +402,10 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
           userShareTokenAccount: shareTokenMintUserAccount,
           config: ownerConfigPda,
           referrerStateInPool: referrerPda,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
           userStateInPool: userPoolPda,
+          nativeUnwrapAccount: null,
         })
         .signers([testUserA])
         .rpc();
@@ -446,6 +463,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -468,6 +487,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           user: testUserA.publicKey,
           pool: poolPda,
           poolAssetTokenAccount,
@@ -476,7 +497,10 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
           userShareTokenAccount: shareTokenMintUserAccount,
           config: ownerConfigPda,
           referrerStateInPool: referrerPda,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
           userStateInPool: userPoolPda,
+          nativeUnwrapAccount: null,
         })
         .signers([testUserA])
         .rpc();
@@ -602,6 +626,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
           creator: creator.publicKey,
           shareTokenMint,
           assetTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           poolShareTokenAccount,
           poolAssetTokenAccount,
           creatorShareTokenAccount,
@@ -647,6 +673,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -669,6 +697,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           user: testUserB.publicKey,
           pool: poolPda,
           poolAssetTokenAccount,
@@ -677,7 +707,10 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
           userShareTokenAccount: shareTokenMintUserAccount,
           config: ownerConfigPda,
           referrerStateInPool: referrerPda,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
           userStateInPool: userPoolPda,
+          nativeUnwrapAccount: null,
         })
         .signers([testUserB])
         .rpc();
@@ -772,6 +805,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -798,6 +833,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           user: testUserA.publicKey,
           pool: poolPda,
           poolAssetTokenAccount,
@@ -806,7 +843,10 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
           userShareTokenAccount: shareTokenMintUserAccount,
           config: ownerConfigPda,
           referrerStateInPool: referrer,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
           userStateInPool: userPoolPda,
+          nativeUnwrapAccount: null,
         })
         .signers([testUserA])
         .rpc();
@@ -876,6 +916,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -889,6 +931,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -911,6 +955,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           user: testUserA.publicKey,
           pool: poolPda,
           poolAssetTokenAccount,
@@ -919,7 +965,10 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
           userShareTokenAccount: shareTokenMintUserAccount,
           config: ownerConfigPda,
           referrerStateInPool: referrer,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
           userStateInPool: userPoolPda,
+          nativeUnwrapAccount: null,
         })
         .signers([testUserA])
         .rpc();
@@ -931,6 +980,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -985,6 +1036,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -1008,6 +1061,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -1016,7 +1071,10 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrer,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
@@ -1098,6 +1156,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
           creator: creator.publicKey,
           shareTokenMint,
           assetTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           poolShareTokenAccount,
           poolAssetTokenAccount,
           creatorShareTokenAccount,
@@ -1141,6 +1201,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -1164,6 +1226,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserB.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -1172,7 +1236,10 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrerPda,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserB])
           .rpc()
@@ -1256,6 +1323,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
           creator: creator.publicKey,
           shareTokenMint,
           assetTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           poolShareTokenAccount,
           poolAssetTokenAccount,
           creatorShareTokenAccount,
@@ -1297,6 +1366,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -1320,6 +1391,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -1328,7 +1401,10 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrer,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
@@ -1362,6 +1438,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -1385,6 +1463,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -1393,7 +1473,10 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrer,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
@@ -1427,6 +1510,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -1450,6 +1535,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -1458,7 +1545,10 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrer,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserB])
           .rpc()
@@ -1480,6 +1570,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -1488,7 +1580,10 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrer,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([creator])
           .rpc()
@@ -1524,6 +1619,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -1547,6 +1644,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserB.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -1555,7 +1654,10 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrer,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserB])
           .rpc()
@@ -1591,6 +1693,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -1614,6 +1718,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount: assetTokenMintUserAccount,
@@ -1622,7 +1728,10 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrer,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
@@ -1658,6 +1767,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -1681,6 +1792,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -1689,7 +1802,10 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrer,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
@@ -1725,6 +1841,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -1748,6 +1866,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -1756,7 +1876,10 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
             userShareTokenAccount: assetTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrer,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
@@ -1792,6 +1915,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -1815,6 +1940,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -1823,7 +1950,10 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrer,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
@@ -1859,6 +1989,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -1882,6 +2014,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -1890,7 +2024,10 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrer,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
@@ -1974,6 +2111,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
           creator: creator.publicKey,
           shareTokenMint,
           assetTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           poolShareTokenAccount,
           poolAssetTokenAccount,
           creatorShareTokenAccount,
@@ -2013,6 +2152,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -2036,6 +2177,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -2044,7 +2187,10 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrer,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
@@ -2083,6 +2229,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -2108,6 +2256,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -2116,7 +2266,10 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrer,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
@@ -2160,6 +2313,8 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -2168,7 +2323,10 @@ describe("Fjord LBP - Buy `swapExactAssetsForShares`", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: referrer,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
@@ -0,0 +1,365 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { findProgramAddressSync } from "@project-serum/anchor/dist/cjs/utils/pubkey";
+import { getAssociatedTokenAddress, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import {
+  Keypair,
+  PublicKey,
+  Transaction,
+  LAMPORTS_PER_SOL,
+  SystemProgram,
+} from "@solana/web3.js";
+import { BankrunProvider } from "anchor-bankrun";
+import chai, { expect } from "chai";
+import chaiAsPromised from "chai-as-promised";
+import { beforeEach } from "mocha";
+import { BanksClient, ProgramTestContext, startAnchor } from "solana-bankrun";
+
+import { BN, BigNumber, generateRandomSalt } from "../../constants";
+import {
+  createMockOwnerConfig,
+  createMockpoolConfig,
+  getAccountBalance,
+  setup,
+  skipBlockTimestamp,
+} from "../../helpers";
+import { FjordLbp, IDL } from "../../target/types/fjord_lbp";
+import { ComputedReservesAndWeights } from "../../types";
+
+const MOCK_PK = new anchor.web3.PublicKey(
+  "BPFLoaderUpgradeab1e11111111111111111111111"
+);
+
+const GENERIC_BN = BN("1000000000000000000");
+
+// 10 trillion project tokens at 6 decimals: 1e19 raw units, which overflowed
+// a u64 once scaled up to the 8 decimals used by the pool math
+const SHARE_TOKEN_DECIMALS = 6;
+const SHARE_TOKEN_SUPPLY = 10_000_000_000_000;
+
+chai.use(chaiAsPromised);
+
+describe("Fjord LBP - Large supply", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const lbpProgramId = (anchor.workspace.FjordLbp as Program<FjordLbp>)
+    .programId;
+
+  let creator: Keypair = anchor.workspace.FjordLbp.provider.wallet.payer;
+  let testUserA: Keypair;
+
+  let shareTokenMint: PublicKey; // project token address
+  let assetTokenMint: PublicKey; // collateral token address
+
+  let assetTokenMintUserAccount: PublicKey | undefined;
+  let shareTokenMintUserAccount: PublicKey | undefined;
+
+  // Address of the deployed pool
+  let poolPda: PublicKey;
+  const treasuryPda: PublicKey = PublicKey.findProgramAddressSync(
+    [Buffer.from("treasury")],
+    lbpProgramId
+  )[0];
+
+  // A fixed account that holds the owner configuration of all the pools (e.g. fees)
+  let ownerConfigPda: PublicKey;
+
+  // Pool accounts that store the tokens
+  let poolShareTokenAccount: PublicKey;
+  let poolAssetTokenAccount: PublicKey;
+
+  // creator accounts that holds the tokens
+  let creatorShareTokenAccount: PublicKey;
+  let creatorAssetTokenAccount: PublicKey;
+
+  // Misc
+  let program: Program<FjordLbp> = anchor.workspace
+    .FjordLbp as Program<FjordLbp>;
+  let { connection } = program.provider;
+  let bankRunClient: BanksClient;
+  let bankRunCtx: ProgramTestContext;
+
+  const randomSalt = generateRandomSalt();
+
+  beforeEach(async () => {
+    testUserA = Keypair.generate();
+
+    // Setup owner configurations. This includes global pool fees, etc...
+    const ownerConfig = createMockOwnerConfig();
+    const [programDataAddress] = findProgramAddressSync(
+      [program.programId.toBytes()],
+      MOCK_PK
+    );
+
+    try {
+      // Initialize global pool settings
+      const tx = program.methods
+        .initializeOwnerConfig(...(Object.values(ownerConfig) as any))
+        .accounts({
+          program: program.programId,
+          programData: programDataAddress,
+          authority: creator.publicKey,
+        })
+        .signers([creator]);
+
+      const pubkeys = await tx.pubkeys();
+      ownerConfigPda = pubkeys.config as PublicKey;
+      await tx.rpc();
+    } catch {
+      // Do nothing
+    }
+
+    // Setup bankrun client [HACKY]
+    // Bankrun runs a fresh instance of the network which doesn't come with a valid program_data account that's needed in initializeOwnerConfig().
+    // So we must first start the anchor with our program, then initialize the owner config, then start the bankrun client with the ported over account.
+    const ownerConfigAcc = await connection.getAccountInfo(ownerConfigPda);
+    const treasuryAcc = await connection.getAccountInfo(treasuryPda);
+
+    bankRunCtx = await startAnchor(
+      "",
+      [],
+      [
+        {
+          address: ownerConfigPda,
+          info: ownerConfigAcc!,
+        },
+        {
+          address: treasuryPda,
+          info: treasuryAcc!,
+        },
+      ]
+    );
+    const provider = new BankrunProvider(bankRunCtx);
+    bankRunClient = bankRunCtx.banksClient;
+
+    program = new Program<FjordLbp>(IDL, lbpProgramId, provider);
+    connection = provider.connection;
+    creator = bankRunCtx.payer;
+
+    // Transfer some sol to testUserA from creator for fees
+    const transferTx = new Transaction();
+    transferTx.recentBlockhash = bankRunCtx.lastBlockhash;
+    transferTx.feePayer = creator.publicKey;
+    transferTx.add(
+      SystemProgram.transfer({
+        fromPubkey: creator.publicKey,
+        toPubkey: testUserA.publicKey,
+        lamports: 5 * LAMPORTS_PER_SOL,
+      })
+    );
+    transferTx.sign(creator);
+    await bankRunClient.processTransaction(transferTx);
+
+    ({
+      tokenAMint: shareTokenMint,
+      tokenBMint: assetTokenMint,
+      tokenAMintPayerAccount: creatorShareTokenAccount,
+      tokenBMintPayerAccount: creatorAssetTokenAccount,
+      tokenAUserAccount: shareTokenMintUserAccount,
+      tokenBUserAccount: assetTokenMintUserAccount,
+    } = await setup({
+      payer: creator,
+      connection,
+      testUser: testUserA,
+      bankRunClient,
+      decimalsTokenA: SHARE_TOKEN_DECIMALS,
+      supplyTokenA: SHARE_TOKEN_SUPPLY,
+    }));
+
+    const sharesAmount = await getAccountBalance(
+      bankRunClient,
+      creator.publicKey,
+      shareTokenMint
+    );
+    const assetsAmount = await getAccountBalance(
+      bankRunClient,
+      creator.publicKey,
+      assetTokenMint
+    );
+
+    // Get pool address
+    [poolPda] = findProgramAddressSync(
+      [
+        shareTokenMint.toBuffer(),
+        assetTokenMint.toBuffer(),
+        creator.publicKey.toBuffer(),
+        Buffer.from(randomSalt),
+      ],
+      program.programId
+    );
+
+    // Pre-compute the account addresses
+    // These will store the pool's tokens
+    poolShareTokenAccount = await getAssociatedTokenAddress(
+      shareTokenMint,
+      poolPda,
+      true
+    );
+    poolAssetTokenAccount = await getAssociatedTokenAddress(
+      assetTokenMint,
+      poolPda,
+      true
+    );
+
+    const poolParams = createMockpoolConfig({
+      salt: randomSalt,
+      assets: assetsAmount,
+      shares: sharesAmount,
+      maxSharePrice: GENERIC_BN,
+      maxAssetsIn: GENERIC_BN,
+      maxSharesOut: GENERIC_BN,
+    });
+
+    await program.methods
+      .initializePool(...(Object.values(poolParams) as any))
+      .accounts({
+        creator: creator.publicKey,
+        shareTokenMint,
+        assetTokenMint,
+        assetTokenProgram: TOKEN_PROGRAM_ID,
+        shareTokenProgram: TOKEN_PROGRAM_ID,
+        poolShareTokenAccount,
+        poolAssetTokenAccount,
+        creatorShareTokenAccount,
+        creatorAssetTokenAccount,
+      })
+      .signers([creator])
+      .rpc();
+
+    // Skip time by 1100 seconds to start the sale
+    await skipBlockTimestamp(bankRunCtx, 1100);
+  });
+
+  it("should compute reserves above the u64 range once scaled", async () => {
+    const { shareReserve } = await program.methods
+      .reservesAndWeights()
+      .accounts({
+        assetTokenMint,
+        shareTokenMint,
+        assetTokenProgram: TOKEN_PROGRAM_ID,
+        shareTokenProgram: TOKEN_PROGRAM_ID,
+        pool: poolPda,
+        poolAssetTokenAccount,
+        poolShareTokenAccount,
+      })
+      .simulate()
+      .then((data) => data.events[0].data as ComputedReservesAndWeights);
+
+    const poolShareBalance = await getAccountBalance(
+      bankRunClient,
+      poolPda,
+      shareTokenMint
+    );
+    expect(shareReserve.eq(poolShareBalance)).to.be.eq(true);
+    expect(shareReserve.gte(BN("10000000000000000000"))).to.be.eq(true);
+  });
+
+  it("should swap exact assets for shares in a pool with a large share supply", async () => {
+    const userPoolPda = findProgramAddressSync(
+      [testUserA.publicKey.toBuffer(), poolPda.toBuffer()],
+      program.programId
+    )[0];
+    const assetAmountIn = (
+      await getAccountBalance(
+        bankRunClient,
+        testUserA.publicKey,
+        assetTokenMint
+      )
+    ).div(BN(2));
+
+    const expectedSharesOut = await program.methods
+      .previewSharesOut(assetAmountIn)
+      .accounts({
+        assetTokenMint,
+        shareTokenMint,
+        assetTokenProgram: TOKEN_PROGRAM_ID,
+        shareTokenProgram: TOKEN_PROGRAM_ID,
+        pool: poolPda,
+        poolAssetTokenAccount,
+        poolShareTokenAccount,
+      })
+      .simulate()
+      .then((data) => data.events[0].data.sharesOut as BigNumber);
+    expect(expectedSharesOut.gt(BN(0))).to.be.eq(true);
+
+    await program.methods
+      .swapExactAssetsForShares(assetAmountIn, expectedSharesOut, null, null)
+      .accounts({
+        assetTokenMint,
+        shareTokenMint,
+        assetTokenProgram: TOKEN_PROGRAM_ID,
+        shareTokenProgram: TOKEN_PROGRAM_ID,
+        user: testUserA.publicKey,
+        pool: poolPda,
+        poolAssetTokenAccount,
+        poolShareTokenAccount,
+        userAssetTokenAccount: assetTokenMintUserAccount,
+        userShareTokenAccount: shareTokenMintUserAccount,
+        config: ownerConfigPda,
+        referrerStateInPool: null,
+        secondLevelReferrerStateInPool: null,
+        referrerRegistration: null,
+        userStateInPool: userPoolPda,
+        nativeUnwrapAccount: null,
+      })
+      .signers([testUserA])
+      .rpc();
+
+    const userState = await program.account.userStateInPool.fetch(
+      userPoolPda
+    );
+    expect(userState.purchasedShares.eq(expectedSharesOut)).to.be.eq(true);
+  });
+
+  it("should swap assets for exact shares in a pool with a large share supply", async () => {
+    const userPoolPda = findProgramAddressSync(
+      [testUserA.publicKey.toBuffer(), poolPda.toBuffer()],
+      program.programId
+    )[0];
+    // 1 billion project tokens
+    const sharesOut = BN("1000000000000000");
+
+    const expectedAssetsIn = await program.methods
+      .previewAssetsIn(sharesOut)
+      .accounts({
+        assetTokenMint,
+        shareTokenMint,
+        assetTokenProgram: TOKEN_PROGRAM_ID,
+        shareTokenProgram: TOKEN_PROGRAM_ID,
+        pool: poolPda,
+        poolAssetTokenAccount,
+        poolShareTokenAccount,
+      })
+      .simulate()
+      .then((data) => data.events[0].data.assetsIn as BigNumber);
+    expect(expectedAssetsIn.gt(BN(0))).to.be.eq(true);
+
+    await program.methods
+      .swapAssetsForExactShares(sharesOut, expectedAssetsIn, null, null)
+      .accounts({
+        assetTokenMint,
+        shareTokenMint,
+        assetTokenProgram: TOKEN_PROGRAM_ID,
+        shareTokenProgram: TOKEN_PROGRAM_ID,
+        user: testUserA.publicKey,
+        pool: poolPda,
+        poolAssetTokenAccount,
+        poolShareTokenAccount,
+        userAssetTokenAccount: assetTokenMintUserAccount,
+        userShareTokenAccount: shareTokenMintUserAccount,
+        config: ownerConfigPda,
+        referrerStateInPool: null,
+        secondLevelReferrerStateInPool: null,
+        referrerRegistration: null,
+        userStateInPool: userPoolPda,
+        nativeUnwrapAccount: null,
+      })
+      .signers([testUserA])
+      .rpc();
+
+    const userState = await program.account.userStateInPool.fetch(
+      userPoolPda
+    );
+    expect(userState.purchasedShares.eq(sharesOut)).to.be.eq(true);
+  });
+});
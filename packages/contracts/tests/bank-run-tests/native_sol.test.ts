@@ -0,0 +1,448 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { findProgramAddressSync } from "@project-serum/anchor/dist/cjs/utils/pubkey";
+import {
+  createAssociatedTokenAccountInstruction,
+  createSyncNativeInstruction,
+  getAssociatedTokenAddress,
+  NATIVE_MINT,
+  TOKEN_PROGRAM_ID,
+} from "@solana/spl-token";
+import {
+  Keypair,
+  PublicKey,
+  Transaction,
+  LAMPORTS_PER_SOL,
+  SystemProgram,
+} from "@solana/web3.js";
+import { BankrunProvider } from "anchor-bankrun";
+import chai, { expect } from "chai";
+import chaiAsPromised from "chai-as-promised";
+import { beforeEach } from "mocha";
+import { BanksClient, ProgramTestContext, startAnchor } from "solana-bankrun";
+
+import { BN, BigNumber, generateRandomSalt } from "../../constants";
+import {
+  createMockOwnerConfig,
+  createMockpoolConfig,
+  getAccountBalance,
+  setup,
+  skipBlockTimestamp,
+} from "../../helpers";
+import { FjordLbp, IDL } from "../../target/types/fjord_lbp";
+
+const MOCK_PK = new anchor.web3.PublicKey(
+  "BPFLoaderUpgradeab1e11111111111111111111111"
+);
+
+const GENERIC_BN = BN("1000000000000000000");
+
+// Wrapped SOL deposited by the creator in the pool
+const POOL_ASSETS = BN(10 * LAMPORTS_PER_SOL);
+
+chai.use(chaiAsPromised);
+
+describe("Fjord LBP - Native SOL", () => {
+  // Configure the client to use the local cluster.
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const lbpProgramId = (anchor.workspace.FjordLbp as Program<FjordLbp>)
+    .programId;
+
+  let creator: Keypair = anchor.workspace.FjordLbp.provider.wallet.payer;
+  let testUserA: Keypair;
+
+  let shareTokenMint: PublicKey; // project token address
+  const assetTokenMint: PublicKey = NATIVE_MINT; // collateral token address (wSOL)
+
+  let shareTokenMintUserAccount: PublicKey | undefined;
+
+  // Address of the deployed pool
+  let poolPda: PublicKey;
+  const treasuryPda: PublicKey = PublicKey.findProgramAddressSync(
+    [Buffer.from("treasury")],
+    lbpProgramId
+  )[0];
+
+  // A fixed account that holds the owner configuration of all the pools (e.g. fees)
+  let ownerConfigPda: PublicKey;
+
+  // Pool accounts that store the tokens
+  let poolShareTokenAccount: PublicKey;
+  let poolAssetTokenAccount: PublicKey;
+
+  // creator accounts that holds the tokens
+  let creatorShareTokenAccount: PublicKey;
+  let creatorAssetTokenAccount: PublicKey;
+
+  // Transient wSOL account of testUserA used to unwrap native SOL
+  let nativeUnwrapAccount: PublicKey;
+  let userPoolPda: PublicKey;
+
+  // Misc
+  let program: Program<FjordLbp> = anchor.workspace
+    .FjordLbp as Program<FjordLbp>;
+  let { connection } = program.provider;
+  let bankRunClient: BanksClient;
+  let bankRunCtx: ProgramTestContext;
+
+  const randomSalt = generateRandomSalt();
+
+  const getLamports = async (address: PublicKey) =>
+    BN((await bankRunClient.getBalance(address)).toString());
+
+  beforeEach(async () => {
+    testUserA = Keypair.generate();
+
+    // Setup owner configurations. This includes global pool fees, etc...
+    const ownerConfig = createMockOwnerConfig();
+    const [programDataAddress] = findProgramAddressSync(
+      [program.programId.toBytes()],
+      MOCK_PK
+    );
+
+    try {
+      // Initialize global pool settings
+      const tx = program.methods
+        .initializeOwnerConfig(...(Object.values(ownerConfig) as any))
+        .accounts({
+          program: program.programId,
+          programData: programDataAddress,
+          authority: creator.publicKey,
+        })
+        .signers([creator]);
+
+      const pubkeys = await tx.pubkeys();
+      ownerConfigPda = pubkeys.config as PublicKey;
+      await tx.rpc();
+    } catch {
+      // Do nothing
+    }
+
+    // Setup bankrun client [HACKY]
+    // Bankrun runs a fresh instance of the network which doesn't come with a valid program_data account that's needed in initializeOwnerConfig().
+    // So we must first start the anchor with our program, then initialize the owner config, then start the bankrun client with the ported over account.
+    const ownerConfigAcc = await connection.getAccountInfo(ownerConfigPda);
+    const treasuryAcc = await connection.getAccountInfo(treasuryPda);
+
+    bankRunCtx = await startAnchor(
+      "",
+      [],
+      [
+        {
+          address: ownerConfigPda,
+          info: ownerConfigAcc!,
+        },
+        {
+          address: treasuryPda,
+          info: treasuryAcc!,
+        },
+      ]
+    );
+    const provider = new BankrunProvider(bankRunCtx);
+    bankRunClient = bankRunCtx.banksClient;
+
+    program = new Program<FjordLbp>(IDL, lbpProgramId, provider);
+    connection = provider.connection;
+    creator = bankRunCtx.payer;
+
+    // Transfer some sol to testUserA from creator for fees and purchases
+    const transferTx = new Transaction();
+    transferTx.recentBlockhash = bankRunCtx.lastBlockhash;
+    transferTx.feePayer = creator.publicKey;
+    transferTx.add(
+      SystemProgram.transfer({
+        fromPubkey: creator.publicKey,
+        toPubkey: testUserA.publicKey,
+        lamports: 5 * LAMPORTS_PER_SOL,
+      })
+    );
+    transferTx.sign(creator);
+    await bankRunClient.processTransaction(transferTx);
+
+    ({
+      tokenAMint: shareTokenMint,
+      tokenAMintPayerAccount: creatorShareTokenAccount,
+      tokenAUserAccount: shareTokenMintUserAccount,
+    } = await setup({
+      payer: creator,
+      connection,
+      testUser: testUserA,
+      bankRunClient,
+    }));
+
+    // Wrap some SOL for the creator to deposit in the pool
+    creatorAssetTokenAccount = await getAssociatedTokenAddress(
+      NATIVE_MINT,
+      creator.publicKey
+    );
+    const wrapTx = new Transaction();
+    wrapTx.recentBlockhash = bankRunCtx.lastBlockhash;
+    wrapTx.feePayer = creator.publicKey;
+    wrapTx.add(
+      createAssociatedTokenAccountInstruction(
+        creator.publicKey,
+        creatorAssetTokenAccount,
+        creator.publicKey,
+        NATIVE_MINT
+      ),
+      SystemProgram.transfer({
+        fromPubkey: creator.publicKey,
+        toPubkey: creatorAssetTokenAccount,
+        lamports: BigInt(POOL_ASSETS.toString()),
+      }),
+      createSyncNativeInstruction(creatorAssetTokenAccount)
+    );
+    wrapTx.sign(creator);
+    await bankRunClient.processTransaction(wrapTx);
+
+    const sharesAmount = await getAccountBalance(
+      bankRunClient,
+      creator.publicKey,
+      shareTokenMint
+    );
+
+    // Get pool address
+    [poolPda] = findProgramAddressSync(
+      [
+        shareTokenMint.toBuffer(),
+        assetTokenMint.toBuffer(),
+        creator.publicKey.toBuffer(),
+        Buffer.from(randomSalt),
+      ],
+      program.programId
+    );
+
+    // Pre-compute the account addresses
+    // These will store the pool's tokens
+    poolShareTokenAccount = await getAssociatedTokenAddress(
+      shareTokenMint,
+      poolPda,
+      true
+    );
+    poolAssetTokenAccount = await getAssociatedTokenAddress(
+      assetTokenMint,
+      poolPda,
+      true
+    );
+
+    [nativeUnwrapAccount] = findProgramAddressSync(
+      [
+        Buffer.from("native_unwrap"),
+        poolPda.toBuffer(),
+        testUserA.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+    [userPoolPda] = findProgramAddressSync(
+      [testUserA.publicKey.toBuffer(), poolPda.toBuffer()],
+      program.programId
+    );
+
+    const poolParams = createMockpoolConfig({
+      salt: randomSalt,
+      assets: POOL_ASSETS,
+      shares: sharesAmount,
+      maxSharePrice: GENERIC_BN,
+      maxAssetsIn: GENERIC_BN,
+      maxSharesOut: GENERIC_BN,
+      sellingAllowed: true,
+    });
+
+    await program.methods
+      .initializePool(...(Object.values(poolParams) as any))
+      .accounts({
+        creator: creator.publicKey,
+        shareTokenMint,
+        assetTokenMint,
+        assetTokenProgram: TOKEN_PROGRAM_ID,
+        shareTokenProgram: TOKEN_PROGRAM_ID,
+        poolShareTokenAccount,
+        poolAssetTokenAccount,
+        creatorShareTokenAccount,
+        creatorAssetTokenAccount,
+      })
+      .signers([creator])
+      .rpc();
+
+    // Skip time by 1100 seconds to start the sale
+    await skipBlockTimestamp(bankRunCtx, 1100);
+  });
+
+  const buyWithNativeSol = async (assetsIn: BigNumber) => {
+    const expectedSharesOut = await program.methods
+      .previewSharesOut(assetsIn)
+      .accounts({
+        assetTokenMint,
+        shareTokenMint,
+        assetTokenProgram: TOKEN_PROGRAM_ID,
+        shareTokenProgram: TOKEN_PROGRAM_ID,
+        pool: poolPda,
+        poolAssetTokenAccount,
+        poolShareTokenAccount,
+      })
+      .simulate()
+      .then((data) => data.events[0].data.sharesOut as BigNumber);
+
+    await program.methods
+      .swapExactAssetsForShares(assetsIn, expectedSharesOut, null, null)
+      .accounts({
+        assetTokenMint,
+        shareTokenMint,
+        assetTokenProgram: TOKEN_PROGRAM_ID,
+        shareTokenProgram: TOKEN_PROGRAM_ID,
+        user: testUserA.publicKey,
+        pool: poolPda,
+        poolAssetTokenAccount,
+        poolShareTokenAccount,
+        userAssetTokenAccount: null,
+        userShareTokenAccount: shareTokenMintUserAccount,
+        config: ownerConfigPda,
+        referrerStateInPool: null,
+        secondLevelReferrerStateInPool: null,
+        referrerRegistration: null,
+        userStateInPool: userPoolPda,
+        nativeUnwrapAccount: null,
+      })
+      .signers([testUserA])
+      .rpc();
+
+    return expectedSharesOut;
+  };
+
+  it("should buy shares with native SOL wrapped into the pool", async () => {
+    const assetsIn = BN(LAMPORTS_PER_SOL);
+    const poolAssetBalanceBefore = await getAccountBalance(
+      bankRunClient,
+      poolPda,
+      assetTokenMint
+    );
+
+    const expectedSharesOut = await buyWithNativeSol(assetsIn);
+
+    const poolAssetBalanceAfter = await getAccountBalance(
+      bankRunClient,
+      poolPda,
+      assetTokenMint
+    );
+    expect(
+      poolAssetBalanceAfter.eq(poolAssetBalanceBefore.add(assetsIn))
+    ).to.be.eq(true);
+
+    const userState = await program.account.userStateInPool.fetch(
+      userPoolPda
+    );
+    expect(userState.purchasedShares.eq(expectedSharesOut)).to.be.eq(true);
+
+    // The user never needed a wSOL account
+    const userAssetTokenAccount = await getAssociatedTokenAddress(
+      NATIVE_MINT,
+      testUserA.publicKey
+    );
+    expect(await bankRunClient.getAccount(userAssetTokenAccount)).to.be.null;
+  });
+
+  it("should sell shares and unwrap the assets to the user's wallet", async () => {
+    const sharesIn = (await buyWithNativeSol(BN(LAMPORTS_PER_SOL))).div(BN(2));
+
+    const expectedAssetsOut = await program.methods
+      .previewAssetsOut(sharesIn)
+      .accounts({
+        assetTokenMint,
+        shareTokenMint,
+        assetTokenProgram: TOKEN_PROGRAM_ID,
+        shareTokenProgram: TOKEN_PROGRAM_ID,
+        pool: poolPda,
+        poolAssetTokenAccount,
+        poolShareTokenAccount,
+      })
+      .simulate()
+      .then((data) => data.events[0].data.assetsOut as BigNumber);
+    const userLamportsBefore = await getLamports(testUserA.publicKey);
+
+    await program.methods
+      .swapExactSharesForAssets(sharesIn, expectedAssetsOut, null, null)
+      .accounts({
+        assetTokenMint,
+        shareTokenMint,
+        assetTokenProgram: TOKEN_PROGRAM_ID,
+        shareTokenProgram: TOKEN_PROGRAM_ID,
+        user: testUserA.publicKey,
+        pool: poolPda,
+        poolAssetTokenAccount,
+        poolShareTokenAccount,
+        userAssetTokenAccount: null,
+        userShareTokenAccount: shareTokenMintUserAccount,
+        config: ownerConfigPda,
+        referrerStateInPool: null,
+        secondLevelReferrerStateInPool: null,
+        referrerRegistration: null,
+        userStateInPool: userPoolPda,
+        nativeUnwrapAccount,
+      })
+      .signers([testUserA])
+      .rpc();
+
+    // The rent of the transient account is refunded when it is closed
+    const userLamportsAfter = await getLamports(testUserA.publicKey);
+    expect(
+      userLamportsAfter.eq(userLamportsBefore.add(expectedAssetsOut))
+    ).to.be.eq(true);
+    expect(await bankRunClient.getAccount(nativeUnwrapAccount)).to.be.null;
+  });
+
+  it("should not be able to sell for native SOL without the native unwrap account", async () => {
+    const sharesIn = (await buyWithNativeSol(BN(LAMPORTS_PER_SOL))).div(BN(2));
+
+    await expect(
+      program.methods
+        .swapExactSharesForAssets(sharesIn, BN(1), null, null)
+        .accounts({
+          assetTokenMint,
+          shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
+          user: testUserA.publicKey,
+          pool: poolPda,
+          poolAssetTokenAccount,
+          poolShareTokenAccount,
+          userAssetTokenAccount: null,
+          userShareTokenAccount: shareTokenMintUserAccount,
+          config: ownerConfigPda,
+          referrerStateInPool: null,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
+          userStateInPool: userPoolPda,
+          nativeUnwrapAccount: null,
+        })
+        .signers([testUserA])
+        .rpc()
+    ).to.be.rejectedWith("MissingNativeUnwrapAccount");
+  });
+
+  it("should not be able to pass the native unwrap account when buying", async () => {
+    await expect(
+      program.methods
+        .swapExactAssetsForShares(BN(LAMPORTS_PER_SOL), BN(1), null, null)
+        .accounts({
+          assetTokenMint,
+          shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
+          user: testUserA.publicKey,
+          pool: poolPda,
+          poolAssetTokenAccount,
+          poolShareTokenAccount,
+          userAssetTokenAccount: null,
+          userShareTokenAccount: shareTokenMintUserAccount,
+          config: ownerConfigPda,
+          referrerStateInPool: null,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
+          userStateInPool: userPoolPda,
+          nativeUnwrapAccount,
+        })
+        .signers([testUserA])
+        .rpc()
+    ).to.be.rejectedWith("UnexpectedNativeUnwrapAccount");
+  });
+});
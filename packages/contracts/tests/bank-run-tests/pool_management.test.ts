@@ -1,7 +1,7 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { findProgramAddressSync } from "@project-serum/anchor/dist/cjs/utils/pubkey";
-import { getAssociatedTokenAddress } from "@solana/spl-token";
+import { getAssociatedTokenAddress, TOKEN_PROGRAM_ID } from "@solana/spl-token";
 import {
   Keypair,
   PublicKey,
@@ -216,6 +216,8 @@ describe("Fjord LBP - Pool Management", () => {
         creator: creator.publicKey,
         shareTokenMint,
         assetTokenMint,
+        assetTokenProgram: TOKEN_PROGRAM_ID,
+        shareTokenProgram: TOKEN_PROGRAM_ID,
         poolShareTokenAccount,
         poolAssetTokenAccount,
         creatorShareTokenAccount,
@@ -279,6 +281,8 @@ describe("Fjord LBP - Pool Management", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -287,7 +291,10 @@ describe("Fjord LBP - Pool Management", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: null,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
@@ -343,6 +350,179 @@ describe("Fjord LBP - Pool Management", () => {
 
       expect(poolAfterUnpause?.paused).to.eq(false);
     });
+
+    it("Should be able to update the whitelist merkle root", async () => {
+      const whitelistMerkleRoot = Array.from(
+        Keypair.generate().publicKey.toBytes()
+      );
+
+      await program.methods
+        .setWhitelistMerkleRoot(whitelistMerkleRoot)
+        .accounts({
+          creator: creator.publicKey,
+          pool: poolPda,
+          assetTokenMint,
+          shareTokenMint,
+        })
+        .signers([creator])
+        .rpc();
+
+      const { pool } = await getAllAccountState({
+        program,
+        poolPda,
+        bankRunClient,
+        shareTokenMint,
+        assetTokenMint,
+        user: testUserA.publicKey,
+        ownerConfigPda,
+        creator: creator.publicKey,
+      });
+
+      expect(pool?.whitelistMerkleRoot).to.deep.eq(whitelistMerkleRoot);
+    });
+
+    it("Should be able to update the pool config before the sale", async () => {
+      // Rewind to before the start of the sale
+      await skipBlockTimestamp(bankRunCtx, -1100);
+
+      await program.methods
+        .updatePoolConfig(
+          BN(0),
+          BN(0),
+          null,
+          null,
+          null,
+          9000,
+          1000,
+          null,
+          null,
+          null,
+          null,
+          null,
+          null
+        )
+        .accounts({
+          creator: creator.publicKey,
+          pool: poolPda,
+          assetTokenMint,
+          shareTokenMint,
+          poolAssetTokenAccount,
+          poolShareTokenAccount,
+          creatorAssetTokenAccount,
+          creatorShareTokenAccount,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      const { pool } = await getAllAccountState({
+        program,
+        poolPda,
+        bankRunClient,
+        shareTokenMint,
+        assetTokenMint,
+        user: testUserA.publicKey,
+        ownerConfigPda,
+        creator: creator.publicKey,
+      });
+
+      expect(pool?.startWeightBasisPoints).to.eq(9000);
+      expect(pool?.endWeightBasisPoints).to.eq(1000);
+    });
+
+    it("Should be able to pause and retarget the weights during the sale", async () => {
+      await program.methods
+        .adjustWeights(null, true)
+        .accounts({
+          authority: creator.publicKey,
+          pool: poolPda,
+        })
+        .signers([creator])
+        .rpc();
+
+      const { pool: poolPaused } = await getAllAccountState({
+        program,
+        poolPda,
+        bankRunClient,
+        shareTokenMint,
+        assetTokenMint,
+        user: testUserA.publicKey,
+        ownerConfigPda,
+        creator: creator.publicKey,
+      });
+
+      expect(poolPaused?.weightAdjustment.paused).to.eq(true);
+      const weightBasisPoints = poolPaused?.weightAdjustment.weightBasisPoints;
+
+      // The schedule resumes from the held weight towards the new end weight
+      await skipBlockTimestamp(bankRunCtx, 3600);
+      await program.methods
+        .adjustWeights(3000, false)
+        .accounts({
+          authority: creator.publicKey,
+          pool: poolPda,
+        })
+        .signers([creator])
+        .rpc();
+
+      const { pool: poolResumed } = await getAllAccountState({
+        program,
+        poolPda,
+        bankRunClient,
+        shareTokenMint,
+        assetTokenMint,
+        user: testUserA.publicKey,
+        ownerConfigPda,
+        creator: creator.publicKey,
+      });
+
+      expect(poolResumed?.weightAdjustment.paused).to.eq(false);
+      expect(poolResumed?.weightAdjustment.weightBasisPoints).to.eq(
+        weightBasisPoints
+      );
+      expect(poolResumed?.endWeightBasisPoints).to.eq(3000);
+    });
+
+    it("Should be able to cancel the pool before any purchase", async () => {
+      await program.methods
+        .cancelPool()
+        .accounts({
+          creator: creator.publicKey,
+          pool: poolPda,
+          assetTokenMint,
+          shareTokenMint,
+          poolAssetTokenAccount,
+          poolShareTokenAccount,
+          creatorAssetTokenAccount,
+          creatorShareTokenAccount,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([creator])
+        .rpc();
+
+      // The deposits are back with the creator and the pool and its token accounts are closed
+      const shareTokenBalanceCreator = await getAccountBalance(
+        bankRunClient,
+        creator.publicKey,
+        shareTokenMint
+      );
+      const assetTokenBalanceCreator = await getAccountBalance(
+        bankRunClient,
+        creator.publicKey,
+        assetTokenMint
+      );
+      expect(shareTokenBalanceCreator.toString()).to.eq(
+        initialProjectTokenBalanceCreator.toString()
+      );
+      expect(assetTokenBalanceCreator.toString()).to.eq(
+        initialCollateralTokenBalanceCreator.toString()
+      );
+      expect(await bankRunClient.getAccount(poolShareTokenAccount)).to.be.null;
+      expect(await bankRunClient.getAccount(poolAssetTokenAccount)).to.be.null;
+      expect(await bankRunClient.getAccount(poolPda)).to.be.null;
+    });
   });
 
   describe("Failure case", async () => {
@@ -360,5 +540,104 @@ describe("Fjord LBP - Pool Management", () => {
           .rpc()
       ).to.be.rejected;
     });
+
+    it("Should not be able to cancel the pool as a non pool-creator", async () => {
+      await expect(
+        program.methods
+          .cancelPool()
+          .accounts({
+            creator: testUserA.publicKey,
+            pool: poolPda,
+            assetTokenMint,
+            shareTokenMint,
+            poolAssetTokenAccount,
+            poolShareTokenAccount,
+            creatorAssetTokenAccount: assetTokenMintUserAccount,
+            creatorShareTokenAccount: shareTokenMintUserAccount,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([testUserA])
+          .rpc()
+      ).to.be.rejected;
+    });
+
+    it("Should not be able to update the pool config as a non pool-creator", async () => {
+      await expect(
+        program.methods
+          .updatePoolConfig(
+            BN(0),
+            BN(0),
+            null,
+            null,
+            null,
+            9000,
+            1000,
+            null,
+            null,
+            null,
+            null,
+            null,
+            null
+          )
+          .accounts({
+            creator: testUserA.publicKey,
+            pool: poolPda,
+            assetTokenMint,
+            shareTokenMint,
+            poolAssetTokenAccount,
+            poolShareTokenAccount,
+            creatorAssetTokenAccount: assetTokenMintUserAccount,
+            creatorShareTokenAccount: shareTokenMintUserAccount,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .signers([testUserA])
+          .rpc()
+      ).to.be.rejected;
+    });
+
+    it("Should not be able to adjust the weights as a non pool-creator", async () => {
+      await expect(
+        program.methods
+          .adjustWeights(3000, null)
+          .accounts({
+            authority: testUserA.publicKey,
+            pool: poolPda,
+          })
+          .signers([testUserA])
+          .rpc()
+      ).to.be.rejectedWith("UnauthorizedWeightAdjustment");
+    });
+
+    it("Should not be able to override the pool fees as a non-owner", async () => {
+      await expect(
+        program.methods
+          .setPoolFees(null, null, 0)
+          .accounts({
+            pool: poolPda,
+            config: ownerConfigPda,
+            owner: testUserA.publicKey,
+            creator: creator.publicKey,
+          })
+          .signers([testUserA])
+          .rpc()
+      ).to.be.rejectedWith("Unauthorized");
+    });
+
+    it("Should not be able to update the whitelist merkle root as a non pool-creator", async () => {
+      await expect(
+        program.methods
+          .setWhitelistMerkleRoot(new Array(32).fill(0))
+          .accounts({
+            creator: testUserA.publicKey,
+            pool: poolPda,
+            assetTokenMint,
+            shareTokenMint,
+          })
+          .signers([testUserA])
+          .rpc()
+      ).to.be.rejected;
+    });
   });
 });
@@ -6,6 +6,7 @@ import {
   createAssociatedTokenAccountInstruction,
   getAccount,
   MAX_FEE_BASIS_POINTS,
+  TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import {
   Keypair,
@@ -228,6 +229,8 @@ describe("Fjord LBP - Redeem", () => {
           creator: creator.publicKey,
           shareTokenMint,
           assetTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           poolShareTokenAccount,
           poolAssetTokenAccount,
           creatorShareTokenAccount,
@@ -262,6 +265,8 @@ describe("Fjord LBP - Redeem", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -276,6 +281,8 @@ describe("Fjord LBP - Redeem", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           user: testUserA.publicKey,
           pool: poolPda,
           poolAssetTokenAccount,
@@ -284,7 +291,10 @@ describe("Fjord LBP - Redeem", () => {
           userShareTokenAccount: shareTokenMintUserAccount,
           config: ownerConfigPda,
           referrerStateInPool: null,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
           userStateInPool: userPoolPda,
+          nativeUnwrapAccount: null,
         })
         .signers([testUserA])
         .rpc();
@@ -312,6 +322,8 @@ describe("Fjord LBP - Redeem", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -326,6 +338,8 @@ describe("Fjord LBP - Redeem", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           user: testUserA.publicKey,
           pool: poolPda,
           poolAssetTokenAccount,
@@ -334,7 +348,10 @@ describe("Fjord LBP - Redeem", () => {
           userShareTokenAccount: shareTokenMintUserAccount,
           config: ownerConfigPda,
           referrerStateInPool: null,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
           userStateInPool: userPoolPda,
+          nativeUnwrapAccount: null,
         })
         .signers([testUserA])
         .rpc();
@@ -449,6 +466,8 @@ describe("Fjord LBP - Redeem", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             pool: poolPda,
             poolAssetTokenAccount,
             poolShareTokenAccount,
@@ -463,6 +482,7 @@ describe("Fjord LBP - Redeem", () => {
             swapFeeRecipientAssetTokenAccount,
             swapFeeRecipientShareTokenAccount,
             swapFeeRecipient: treasury.swapFeeRecipient,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           // Creates the associated token accounts for the recipients if they don't exist
@@ -510,7 +530,10 @@ describe("Fjord LBP - Redeem", () => {
         // Expect most of the treasury balance to be emptied out as it is sent to relevant parties with some delta due to decimal precision.
         expect(treasuryShareBalanceAfter.toNumber()).to.be.closeTo(0, 1);
         expect(treasuryAssetBalanceAfter.toNumber()).to.be.closeTo(0, 1);
-        const unsoldShares = poolShareBalance.sub(pool.totalPurchased);
+        // The swap fees in shares are paid out of the pool before the unsold shares
+        const unsoldShares = poolShareBalance
+          .sub(pool.totalPurchased)
+          .sub(pool.totalSwapFeesShare);
         expect(
           creatorShareBalanceAfter.eq(
             creatorShareBalanceBefore.add(unsoldShares)
@@ -632,6 +655,8 @@ describe("Fjord LBP - Redeem", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             pool: poolPda,
             poolAssetTokenAccount,
             poolShareTokenAccount,
@@ -646,6 +671,7 @@ describe("Fjord LBP - Redeem", () => {
             swapFeeRecipientAssetTokenAccount,
             swapFeeRecipientShareTokenAccount,
             swapFeeRecipient: treasury.swapFeeRecipient,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           // Creates the associated token accounts for the recipients if they don't exist
@@ -661,6 +687,8 @@ describe("Fjord LBP - Redeem", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             pool: poolPda,
             poolAssetTokenAccount,
             poolShareTokenAccount,
@@ -668,6 +696,7 @@ describe("Fjord LBP - Redeem", () => {
             userAssetTokenAccount: assetTokenMintUserAccount,
             userShareTokenAccount: shareTokenMintUserAccount,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc();
@@ -698,6 +727,151 @@ describe("Fjord LBP - Redeem", () => {
           )
         ).to.be.eq(true);
       });
+
+      it("Should only release vested shares between the vesting cliff and end", async () => {
+        const { pool, treasury, userPoolAccount, userPoolPda } =
+          await getAllAccountState({
+            program,
+            poolPda,
+            bankRunClient,
+            shareTokenMint,
+            assetTokenMint,
+            user: testUserA.publicKey,
+            ownerConfigPda,
+            creator: creator.publicKey,
+          });
+        const totalShares: BigNumber = userPoolAccount?.purchasedShares!;
+
+        // Skip to the end of the sale, before the vesting cliff
+        const { unixTimestamp } = await bankRunClient.getClock();
+        await skipBlockTimestamp(
+          bankRunCtx,
+          pool.saleEndTime.toNumber() - Number(unixTimestamp) + 1
+        );
+
+        const { feeRecipients, swapFeeRecipient } = treasury;
+        const [
+          swapFeeRecipientAssetTokenAccount,
+          swapFeeRecipientShareTokenAccount,
+          treasuryAssetTokenAccount,
+          treasuryShareTokenAccount,
+        ] = await Promise.all([
+          getAssociatedTokenAddress(assetTokenMint, swapFeeRecipient),
+          getAssociatedTokenAddress(shareTokenMint, swapFeeRecipient),
+          getAssociatedTokenAddress(assetTokenMint, treasuryPda, true),
+          getAssociatedTokenAddress(shareTokenMint, treasuryPda, true),
+        ]);
+
+        const preInstructions = new Transaction();
+        const recipientAccountsSetup: Array<AccountMeta> = await Promise.all(
+          feeRecipients.map(async ({ user: recipient }) => {
+            const recipientAta = await getAssociatedTokenAddress(
+              assetTokenMint,
+              recipient,
+              true
+            );
+            try {
+              await getAccount(connection, recipientAta);
+            } catch {
+              preInstructions.add(
+                createAssociatedTokenAccountInstruction(
+                  testUserA.publicKey,
+                  recipientAta,
+                  recipient,
+                  assetTokenMint
+                )
+              );
+            }
+            return { pubkey: recipientAta, isWritable: true, isSigner: false };
+          })
+        );
+
+        await program.methods
+          .closePool()
+          .accounts({
+            assetTokenMint,
+            shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
+            pool: poolPda,
+            poolAssetTokenAccount,
+            poolShareTokenAccount,
+            treasuryAssetTokenAccount,
+            treasuryShareTokenAccount,
+            treasury: treasuryPda,
+            creatorAssetTokenAccount,
+            creatorShareTokenAccount,
+            ownerConfig: ownerConfigPda,
+            user: testUserA.publicKey,
+            poolCreator: creator.publicKey,
+            swapFeeRecipientAssetTokenAccount,
+            swapFeeRecipientShareTokenAccount,
+            swapFeeRecipient: treasury.swapFeeRecipient,
+            nativeUnwrapAccount: null,
+          })
+          .signers([testUserA])
+          .preInstructions(preInstructions.instructions)
+          .remainingAccounts(recipientAccountsSetup)
+          .rpc();
+
+        const redeem = () =>
+          program.methods
+            .redeem(false)
+            .accounts({
+              assetTokenMint,
+              shareTokenMint,
+              assetTokenProgram: TOKEN_PROGRAM_ID,
+              shareTokenProgram: TOKEN_PROGRAM_ID,
+              pool: poolPda,
+              poolAssetTokenAccount,
+              poolShareTokenAccount,
+              user: testUserA.publicKey,
+              userAssetTokenAccount: assetTokenMintUserAccount,
+              userShareTokenAccount: shareTokenMintUserAccount,
+              userStateInPool: userPoolPda,
+              nativeUnwrapAccount: null,
+            })
+            .signers([testUserA])
+            .rpc();
+
+        // Nothing is released before the cliff
+        await redeem();
+        let userState = await program.account.userStateInPool.fetch(
+          userPoolPda
+        );
+        expect(userState.redeemedShares.toNumber()).to.be.eq(0);
+        expect(userState.purchasedShares.eq(totalShares)).to.be.eq(true);
+
+        // Half of the shares are released halfway between the cliff and the end
+        const vestingMidpoint = pool.vestCliff.add(
+          pool.vestEnd.sub(pool.vestCliff).div(BN(2))
+        );
+        const { unixTimestamp: timestampAfterClose } =
+          await bankRunClient.getClock();
+        await skipBlockTimestamp(
+          bankRunCtx,
+          vestingMidpoint.toNumber() - Number(timestampAfterClose)
+        );
+        await redeem();
+        userState = await program.account.userStateInPool.fetch(userPoolPda);
+        expect(userState.redeemedShares.toNumber()).to.be.closeTo(
+          totalShares.div(BN(2)).toNumber(),
+          1
+        );
+        expect(
+          userState.redeemedShares
+            .add(userState.purchasedShares)
+            .eq(totalShares)
+        ).to.be.eq(true);
+
+        // Everything is released after the vesting end, closing the user state
+        await skipBlockTimestamp(
+          bankRunCtx,
+          pool.vestEnd.sub(vestingMidpoint).toNumber()
+        );
+        await redeem();
+        expect(await bankRunClient.getAccount(userPoolPda)).to.be.null;
+      });
     });
     describe("Failure case", async () => {
       it("Should not be able to close pool if sale period is not over", async () => {
@@ -772,6 +946,8 @@ describe("Fjord LBP - Redeem", () => {
             .accounts({
               assetTokenMint,
               shareTokenMint,
+              assetTokenProgram: TOKEN_PROGRAM_ID,
+              shareTokenProgram: TOKEN_PROGRAM_ID,
               pool: poolPda,
               poolAssetTokenAccount,
               poolShareTokenAccount,
@@ -786,6 +962,7 @@ describe("Fjord LBP - Redeem", () => {
               swapFeeRecipientAssetTokenAccount,
               swapFeeRecipientShareTokenAccount,
               swapFeeRecipient: treasury.swapFeeRecipient,
+              nativeUnwrapAccount: null,
             })
             .signers([testUserA])
             // Creates the associated token accounts for the recipients if they don't exist
@@ -870,6 +1047,8 @@ describe("Fjord LBP - Redeem", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             pool: poolPda,
             poolAssetTokenAccount,
             poolShareTokenAccount,
@@ -884,6 +1063,7 @@ describe("Fjord LBP - Redeem", () => {
             swapFeeRecipientAssetTokenAccount,
             swapFeeRecipientShareTokenAccount,
             swapFeeRecipient: treasury.swapFeeRecipient,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           // Creates the associated token accounts for the recipients if they don't exist
@@ -898,6 +1078,8 @@ describe("Fjord LBP - Redeem", () => {
             .accounts({
               assetTokenMint,
               shareTokenMint,
+              assetTokenProgram: TOKEN_PROGRAM_ID,
+              shareTokenProgram: TOKEN_PROGRAM_ID,
               pool: poolPda,
               poolAssetTokenAccount,
               poolShareTokenAccount,
@@ -912,6 +1094,7 @@ describe("Fjord LBP - Redeem", () => {
               swapFeeRecipientAssetTokenAccount,
               swapFeeRecipientShareTokenAccount,
               swapFeeRecipient: treasury.swapFeeRecipient,
+              nativeUnwrapAccount: null,
             })
             .signers([testUserA])
             // Pass all the recipient accounts to the program via remaining accounts
@@ -939,6 +1122,8 @@ describe("Fjord LBP - Redeem", () => {
             .accounts({
               assetTokenMint,
               shareTokenMint,
+              assetTokenProgram: TOKEN_PROGRAM_ID,
+              shareTokenProgram: TOKEN_PROGRAM_ID,
               pool: poolPda,
               poolAssetTokenAccount,
               poolShareTokenAccount,
@@ -946,12 +1131,43 @@ describe("Fjord LBP - Redeem", () => {
               userAssetTokenAccount: assetTokenMintUserAccount,
               userShareTokenAccount: shareTokenMintUserAccount,
               userStateInPool: userPoolPda,
+              nativeUnwrapAccount: null,
             })
             .signers([testUserA])
             .rpc()
         ).to.be.rejectedWith("RedeemingDisallowed");
       });
 
+      it("Should not be able to claim referral rewards if the pool is not closed", async () => {
+        const { userPoolPda } = await getAllAccountState({
+          program,
+          poolPda,
+          bankRunClient,
+          shareTokenMint,
+          assetTokenMint,
+          user: testUserA.publicKey,
+          ownerConfigPda,
+          creator: creator.publicKey,
+        });
+
+        await expect(
+          program.methods
+            .claimReferralRewards()
+            .accounts({
+              assetTokenMint,
+              assetTokenProgram: TOKEN_PROGRAM_ID,
+              pool: poolPda,
+              poolAssetTokenAccount,
+              referrer: testUserA.publicKey,
+              referrerAssetTokenAccount: assetTokenMintUserAccount,
+              referrerStateInPool: userPoolPda,
+              nativeUnwrapAccount: null,
+            })
+            .signers([testUserA])
+            .rpc()
+        ).to.be.rejectedWith("ReferralClaimDisallowed");
+      });
+
       it("should not allow duplicate fee recipients", async () => {
         const { pool, treasury } = await getAllAccountState({
           program,
@@ -1032,6 +1248,8 @@ describe("Fjord LBP - Redeem", () => {
             .accounts({
               assetTokenMint,
               shareTokenMint,
+              assetTokenProgram: TOKEN_PROGRAM_ID,
+              shareTokenProgram: TOKEN_PROGRAM_ID,
               pool: poolPda,
               poolAssetTokenAccount,
               poolShareTokenAccount,
@@ -1046,6 +1264,7 @@ describe("Fjord LBP - Redeem", () => {
               swapFeeRecipientAssetTokenAccount,
               swapFeeRecipientShareTokenAccount,
               swapFeeRecipient: treasury.swapFeeRecipient,
+              nativeUnwrapAccount: null,
             })
             .signers([testUserA])
             // Creates the associated token accounts for the recipients if they don't exist
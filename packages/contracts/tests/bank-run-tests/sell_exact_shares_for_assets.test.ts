@@ -4,6 +4,7 @@ import { findProgramAddressSync } from "@project-serum/anchor/dist/cjs/utils/pub
 import {
   MAX_FEE_BASIS_POINTS,
   getAssociatedTokenAddress,
+  TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import {
   Keypair,
@@ -256,6 +257,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           creator: creator.publicKey,
           shareTokenMint,
           assetTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           poolShareTokenAccount,
           poolAssetTokenAccount,
           creatorShareTokenAccount,
@@ -290,6 +293,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -304,6 +309,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           user: testUserA.publicKey,
           pool: poolPda,
           poolAssetTokenAccount,
@@ -312,7 +319,10 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           userShareTokenAccount: shareTokenMintUserAccount,
           config: ownerConfigPda,
           referrerStateInPool: null,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
           userStateInPool: initialUserPoolPda,
+          nativeUnwrapAccount: null,
         })
         .signers([testUserA])
         .rpc();
@@ -347,6 +357,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -361,6 +373,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           user: testUserA.publicKey,
           pool: poolPda,
           poolAssetTokenAccount,
@@ -369,7 +383,10 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           userShareTokenAccount: shareTokenMintUserAccount,
           config: ownerConfigPda,
           referrerStateInPool: null,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
           userStateInPool: userPoolPda,
+          nativeUnwrapAccount: null,
         })
         .signers([testUserA])
         .rpc();
@@ -410,7 +427,7 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
       );
     });
 
-    it("should be able to sell exact project tokens (shares) for collateral tokens (assets) with a referrer but no referrer fee is assigned", async () => {
+    it("should not be able to sell exact project tokens (shares) for collateral tokens (assets) with a referrer other than the referrer of the purchases", async () => {
       const { userPoolPda, userPoolAccount: userPoolAccountBefore } =
         await getAllAccountState({
           program,
@@ -443,6 +460,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -451,32 +470,31 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .simulate()
         .then((data) => data.events[0].data.assetsOut as BigNumber);
 
-      // Sell project token
-      await program.methods
-        .swapExactSharesForAssets(sharesIn, minAssetsOut, null, referrer)
-        .accounts({
-          assetTokenMint,
-          shareTokenMint,
-          user: testUserA.publicKey,
-          pool: poolPda,
-          poolAssetTokenAccount,
-          poolShareTokenAccount,
-          userAssetTokenAccount: assetTokenMintUserAccount,
-          userShareTokenAccount: shareTokenMintUserAccount,
-          config: ownerConfigPda,
-          referrerStateInPool: referrerPda,
-          userStateInPool: userPoolPda,
-        })
-        .signers([testUserA])
-        .rpc();
-
-      const referrerPoolAccount = await program.account.userStateInPool.fetch(
-        referrerPda!
-      );
-
-      expect(referrerPoolAccount.referredAssets.toString()).to.eq(
-        BN(0).toString()
-      );
+      // The purchases of the user were not referred, so no referrer can be named when selling
+      await expect(
+        program.methods
+          .swapExactSharesForAssets(sharesIn, minAssetsOut, null, referrer)
+          .accounts({
+            assetTokenMint,
+            shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
+            user: testUserA.publicKey,
+            pool: poolPda,
+            poolAssetTokenAccount,
+            poolShareTokenAccount,
+            userAssetTokenAccount: assetTokenMintUserAccount,
+            userShareTokenAccount: shareTokenMintUserAccount,
+            config: ownerConfigPda,
+            referrerStateInPool: referrerPda,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
+            userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
+          })
+          .signers([testUserA])
+          .rpc()
+      ).to.be.rejectedWith("InvalidReferrer");
     });
 
     it("should be able to swap exact project tokens (shares) for collateral tokens (assets) when the number of shares out is greater that that in the preview.", async () => {
@@ -505,6 +523,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -519,6 +539,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           user: testUserA.publicKey,
           pool: poolPda,
           poolAssetTokenAccount,
@@ -527,7 +549,10 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           userShareTokenAccount: shareTokenMintUserAccount,
           config: ownerConfigPda,
           referrerStateInPool: null,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
           userStateInPool: userPoolPda,
+          nativeUnwrapAccount: null,
         })
         .signers([testUserA])
         .rpc();
@@ -582,6 +607,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -595,6 +622,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -609,6 +638,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           user: testUserA.publicKey,
           pool: poolPda,
           poolAssetTokenAccount,
@@ -617,7 +648,10 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           userShareTokenAccount: shareTokenMintUserAccount,
           config: ownerConfigPda,
           referrerStateInPool: null,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
           userStateInPool: userPoolPda,
+          nativeUnwrapAccount: null,
         })
         .signers([testUserA])
         .rpc();
@@ -629,6 +663,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -669,6 +705,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -677,7 +715,10 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: null,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
@@ -709,6 +750,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           creator: creator.publicKey,
           shareTokenMint,
           assetTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           poolShareTokenAccount,
           poolAssetTokenAccount,
           creatorShareTokenAccount,
@@ -748,6 +791,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -767,6 +812,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           user: testUserA.publicKey,
           pool: poolPda,
           poolAssetTokenAccount,
@@ -775,7 +822,10 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           userShareTokenAccount: shareTokenMintUserAccount,
           config: ownerConfigPda,
           referrerStateInPool: null,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
           userStateInPool: initialUserPoolPda,
+          nativeUnwrapAccount: null,
         })
         .signers([testUserA])
         .rpc();
@@ -815,6 +865,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -829,6 +881,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           user: testUserA.publicKey,
           pool: poolPda,
           poolAssetTokenAccount,
@@ -837,7 +891,10 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           userShareTokenAccount: shareTokenMintUserAccount,
           config: ownerConfigPda,
           referrerStateInPool: null,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
           userStateInPool: userPoolPda,
+          nativeUnwrapAccount: null,
         })
         .signers([testUserA])
         .rpc();
@@ -903,6 +960,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           creator: creator.publicKey,
           shareTokenMint,
           assetTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           poolShareTokenAccount,
           poolAssetTokenAccount,
           creatorShareTokenAccount,
@@ -942,6 +1001,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -961,6 +1022,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           user: testUserA.publicKey,
           pool: poolPda,
           poolAssetTokenAccount,
@@ -969,7 +1032,10 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           userShareTokenAccount: shareTokenMintUserAccount,
           config: ownerConfigPda,
           referrerStateInPool: null,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
           userStateInPool: initialUserPoolPda,
+          nativeUnwrapAccount: null,
         })
         .signers([testUserA])
         .rpc();
@@ -998,6 +1064,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -1013,6 +1081,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -1021,7 +1091,10 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: null,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
@@ -1052,6 +1125,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           creator: creator.publicKey,
           shareTokenMint,
           assetTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           poolShareTokenAccount,
           poolAssetTokenAccount,
           creatorShareTokenAccount,
@@ -1086,6 +1161,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -1100,6 +1177,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           user: testUserA.publicKey,
           pool: poolPda,
           poolAssetTokenAccount,
@@ -1108,7 +1187,10 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           userShareTokenAccount: shareTokenMintUserAccount,
           config: ownerConfigPda,
           referrerStateInPool: null,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
           userStateInPool: userPoolPda,
+          nativeUnwrapAccount: null,
         })
         .signers([testUserA])
         .rpc();
@@ -1125,6 +1207,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -1133,6 +1217,9 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: null,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
@@ -1151,6 +1238,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -1159,6 +1248,9 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: null,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
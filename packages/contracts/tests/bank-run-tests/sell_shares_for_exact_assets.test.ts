@@ -4,6 +4,7 @@ import { findProgramAddressSync } from "@project-serum/anchor/dist/cjs/utils/pub
 import {
   MAX_FEE_BASIS_POINTS,
   getAssociatedTokenAddress,
+  TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import {
   Keypair,
@@ -255,6 +256,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           creator: creator.publicKey,
           shareTokenMint,
           assetTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           poolShareTokenAccount,
           poolAssetTokenAccount,
           creatorShareTokenAccount,
@@ -289,6 +292,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -303,6 +308,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           user: testUserA.publicKey,
           pool: poolPda,
           poolAssetTokenAccount,
@@ -311,7 +318,10 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           userShareTokenAccount: shareTokenMintUserAccount,
           config: ownerConfigPda,
           referrerStateInPool: null,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
           userStateInPool: initialUserPoolPda,
+          nativeUnwrapAccount: null,
         })
         .signers([testUserA])
         .rpc();
@@ -345,6 +355,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -359,6 +371,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           user: testUserA.publicKey,
           pool: poolPda,
           poolAssetTokenAccount,
@@ -367,7 +381,10 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           userShareTokenAccount: shareTokenMintUserAccount,
           config: ownerConfigPda,
           referrerStateInPool: null,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
           userStateInPool: userPoolPda,
+          nativeUnwrapAccount: null,
         })
         .signers([testUserA])
         .rpc();
@@ -410,7 +427,7 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
       );
     });
 
-    it("should be able to sell project tokens (shares) for exact collateral tokens (assets) with a referrer but no referrer fee is assigned", async () => {
+    it("should not be able to sell project tokens (shares) for exact collateral tokens (assets) with a referrer other than the referrer of the purchases", async () => {
       const { userPoolPda } = await getAllAccountState({
         program,
         poolPda,
@@ -442,6 +459,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -450,32 +469,31 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .simulate()
         .then((data) => data.events[0].data.sharesIn as BigNumber);
 
-      // Sell project token
-      await program.methods
-        .swapSharesForExactAssets(assetsToSell, maxSharesIn, null, referrer)
-        .accounts({
-          assetTokenMint,
-          shareTokenMint,
-          user: testUserA.publicKey,
-          pool: poolPda,
-          poolAssetTokenAccount,
-          poolShareTokenAccount,
-          userAssetTokenAccount: assetTokenMintUserAccount,
-          userShareTokenAccount: shareTokenMintUserAccount,
-          config: ownerConfigPda,
-          referrerStateInPool: referrerPda,
-          userStateInPool: userPoolPda,
-        })
-        .signers([testUserA])
-        .rpc();
-
-      const referrerPoolAccount = await program.account.userStateInPool.fetch(
-        referrerPda!
-      );
-
-      expect(referrerPoolAccount.referredAssets.toString()).to.eq(
-        BN(0).toString()
-      );
+      // The purchases of the user were not referred, so no referrer can be named when selling
+      await expect(
+        program.methods
+          .swapSharesForExactAssets(assetsToSell, maxSharesIn, null, referrer)
+          .accounts({
+            assetTokenMint,
+            shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
+            user: testUserA.publicKey,
+            pool: poolPda,
+            poolAssetTokenAccount,
+            poolShareTokenAccount,
+            userAssetTokenAccount: assetTokenMintUserAccount,
+            userShareTokenAccount: shareTokenMintUserAccount,
+            config: ownerConfigPda,
+            referrerStateInPool: referrerPda,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
+            userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
+          })
+          .signers([testUserA])
+          .rpc()
+      ).to.be.rejectedWith("InvalidReferrer");
     });
 
     it("should update reserves and weights", async () => {
@@ -502,6 +520,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -515,6 +535,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -529,6 +551,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           user: testUserA.publicKey,
           pool: poolPda,
           poolAssetTokenAccount,
@@ -537,7 +561,10 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           userShareTokenAccount: shareTokenMintUserAccount,
           config: ownerConfigPda,
           referrerStateInPool: null,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
           userStateInPool: userPoolPda,
+          nativeUnwrapAccount: null,
         })
         .signers([testUserA])
         .rpc();
@@ -549,6 +576,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -573,6 +602,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -581,6 +612,9 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: null,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
@@ -612,6 +646,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           creator: creator.publicKey,
           shareTokenMint,
           assetTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           poolShareTokenAccount,
           poolAssetTokenAccount,
           creatorShareTokenAccount,
@@ -651,6 +687,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -670,6 +708,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           user: testUserA.publicKey,
           pool: poolPda,
           poolAssetTokenAccount,
@@ -678,7 +718,10 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           userShareTokenAccount: shareTokenMintUserAccount,
           config: ownerConfigPda,
           referrerStateInPool: null,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
           userStateInPool: initialUserPoolPda,
+          nativeUnwrapAccount: null,
         })
         .signers([testUserA])
         .rpc();
@@ -717,6 +760,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -731,6 +776,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           user: testUserA.publicKey,
           pool: poolPda,
           poolAssetTokenAccount,
@@ -739,7 +786,10 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           userShareTokenAccount: shareTokenMintUserAccount,
           config: ownerConfigPda,
           referrerStateInPool: null,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
           userStateInPool: userPoolPda,
+          nativeUnwrapAccount: null,
         })
         .signers([testUserA])
         .rpc();
@@ -807,6 +857,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           creator: creator.publicKey,
           shareTokenMint,
           assetTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           poolShareTokenAccount,
           poolAssetTokenAccount,
           creatorShareTokenAccount,
@@ -846,6 +898,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -865,6 +919,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           user: testUserA.publicKey,
           pool: poolPda,
           poolAssetTokenAccount,
@@ -873,7 +929,10 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           userShareTokenAccount: shareTokenMintUserAccount,
           config: ownerConfigPda,
           referrerStateInPool: null,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
           userStateInPool: initialUserPoolPda,
+          nativeUnwrapAccount: null,
         })
         .signers([testUserA])
         .rpc();
@@ -900,6 +959,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -916,6 +977,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -924,7 +987,10 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: null,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
             userStateInPool: userPoolPda,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
@@ -955,6 +1021,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           creator: creator.publicKey,
           shareTokenMint,
           assetTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           poolShareTokenAccount,
           poolAssetTokenAccount,
           creatorShareTokenAccount,
@@ -989,6 +1057,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           pool: poolPda,
           poolAssetTokenAccount,
           poolShareTokenAccount,
@@ -1003,6 +1073,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
         .accounts({
           assetTokenMint,
           shareTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           user: testUserA.publicKey,
           pool: poolPda,
           poolAssetTokenAccount,
@@ -1011,7 +1083,10 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           userShareTokenAccount: shareTokenMintUserAccount,
           config: ownerConfigPda,
           referrerStateInPool: null,
+          secondLevelReferrerStateInPool: null,
+          referrerRegistration: null,
           userStateInPool: userPoolPda,
+          nativeUnwrapAccount: null,
         })
         .signers([testUserA])
         .rpc();
@@ -1028,6 +1103,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -1036,6 +1113,9 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: null,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
@@ -1054,6 +1134,8 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
           .accounts({
             assetTokenMint,
             shareTokenMint,
+            assetTokenProgram: TOKEN_PROGRAM_ID,
+            shareTokenProgram: TOKEN_PROGRAM_ID,
             user: testUserA.publicKey,
             pool: poolPda,
             poolAssetTokenAccount,
@@ -1062,6 +1144,9 @@ describe("Fjord LBP - Sell - shares for exact assets", () => {
             userShareTokenAccount: shareTokenMintUserAccount,
             config: ownerConfigPda,
             referrerStateInPool: null,
+            secondLevelReferrerStateInPool: null,
+            referrerRegistration: null,
+            nativeUnwrapAccount: null,
           })
           .signers([testUserA])
           .rpc()
@@ -1,7 +1,7 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { findProgramAddressSync } from "@project-serum/anchor/dist/cjs/utils/pubkey";
-import { getAssociatedTokenAddress } from "@solana/spl-token";
+import { getAssociatedTokenAddress, TOKEN_PROGRAM_ID } from "@solana/spl-token";
 import { Keypair, PublicKey } from "@solana/web3.js";
 import chai, { expect } from "chai";
 import chaiAsPromised from "chai-as-promised";
@@ -9,6 +9,8 @@ import chaiAsPromised from "chai-as-promised";
 import {
   BN,
   BigNumber,
+  DEFAULT_SALE_END_TIME_BN,
+  DEFAULT_SALE_START_TIME_BN,
   PERCENTAGE_BASIS_POINTS,
   testMerkleWhitelistedAddresses,
   generateRandomSalt,
@@ -131,13 +133,25 @@ describe("Fjord LBP - Initialization", () => {
       maxSharePrice,
       maxSharesOut,
       maxAssetsIn,
+      minAssetsRaised,
+      maxAssetsRaised,
+      maxAssetsPerUser,
+      maxSharesPerUser,
+      minAssetsPerBuy,
       startWeightBasisPoints,
       endWeightBasisPoints,
+      weightCurve,
+      weightKnots,
       saleStartTime,
       saleEndTime,
       vestCliff,
       vestEnd,
       whitelistMerkleRoot,
+      whitelistLeafVersion,
+      whitelistSigner,
+      whitelistTierStartTimes,
+      publicSaleTime,
+      referralSchedule,
       sellingAllowed,
     } = createMockpoolConfig({
       salt: randomSalt,
@@ -165,13 +179,25 @@ describe("Fjord LBP - Initialization", () => {
         maxSharePrice,
         maxSharesOut,
         maxAssetsIn,
+        minAssetsRaised,
+        maxAssetsRaised,
+        maxAssetsPerUser,
+        maxSharesPerUser,
+        minAssetsPerBuy,
         startWeightBasisPoints,
         endWeightBasisPoints,
+        weightCurve,
+        weightKnots,
         saleStartTime,
         saleEndTime,
         vestCliff,
         vestEnd,
         whitelistMerkleRoot,
+        whitelistLeafVersion,
+        whitelistSigner,
+        whitelistTierStartTimes,
+        publicSaleTime,
+        referralSchedule,
         sellingAllowed
       )
       .accounts(accounts)
@@ -274,6 +300,8 @@ describe("Fjord LBP - Initialization", () => {
         .accounts({
           ...accounts,
           shareTokenMint: assetTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           poolShareTokenAccount: poolAssetTokenAccount,
           creatorShareTokenAccount: creatorAssetTokenAccount,
         })
@@ -490,6 +518,8 @@ describe("Fjord LBP - Initialization", () => {
           creator: creator.publicKey,
           shareTokenMint,
           assetTokenMint,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           poolShareTokenAccount,
           poolAssetTokenAccount,
           creatorShareTokenAccount,
@@ -923,6 +953,8 @@ describe("Fjord LBP - Initialization", () => {
         .accounts({
           ...accounts,
           assetTokenMint: undefined,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
         })
         .rpc()
     ).to.be.rejectedWith("Invalid arguments: pool not provided.");
@@ -971,6 +1003,8 @@ describe("Fjord LBP - Initialization", () => {
         .initializePool(...formattedPoolParams)
         .accounts({
           ...accounts,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           poolAssetTokenAccount: undefined,
         })
         .rpc()
@@ -998,6 +1032,8 @@ describe("Fjord LBP - Initialization", () => {
         .initializePool(...formattedPoolParams)
         .accounts({
           ...accounts,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           creatorShareTokenAccount: undefined,
         })
         .rpc()
@@ -1025,6 +1061,8 @@ describe("Fjord LBP - Initialization", () => {
         .initializePool(...formattedPoolParams)
         .accounts({
           ...accounts,
+          assetTokenProgram: TOKEN_PROGRAM_ID,
+          shareTokenProgram: TOKEN_PROGRAM_ID,
           creatorAssetTokenAccount: undefined,
         })
         .rpc()
@@ -1206,6 +1244,142 @@ describe("Fjord LBP - Initialization", () => {
     ).to.be.rejected;
   });
 
+  it("Should not deploy if minAssetsRaised is greater than maxAssetsRaised", async () => {
+    const sharesAmount = initialProjectTokenBalanceCreator;
+    const assetsAmount = initialCollateralTokenBalanceCreator;
+
+    // Create pool with a soft cap above its hard cap
+    const poolParams = createMockpoolConfig({
+      salt: randomSalt,
+
+      shares: sharesAmount,
+      assets: assetsAmount,
+      minAssetsRaised: BN(2000),
+      maxAssetsRaised: BN(1000),
+    });
+
+    const formattedPoolParams = formatPoolParams(poolParams);
+
+    // Deploy the pool
+    await expect(
+      program.methods
+        .initializePool(...formattedPoolParams)
+        .accounts(accounts)
+        .rpc()
+    ).to.be.rejectedWith("InvalidSaleCaps");
+  });
+
+  it("Should not deploy if a whitelist tier opens after the sale ends", async () => {
+    const sharesAmount = initialProjectTokenBalanceCreator;
+    const assetsAmount = initialCollateralTokenBalanceCreator;
+
+    // Create pool whose second tier never opens
+    const poolParams = createMockpoolConfig({
+      salt: randomSalt,
+      shares: sharesAmount,
+      assets: assetsAmount,
+      whitelistTierStartTimes: [BN(0), DEFAULT_SALE_END_TIME_BN, BN(0), BN(0)],
+    });
+
+    const formattedPoolParams = formatPoolParams(poolParams);
+
+    // Deploy the pool
+    await expect(
+      program.methods
+        .initializePool(...formattedPoolParams)
+        .accounts(accounts)
+        .rpc()
+    ).to.be.rejectedWith("InvalidWhitelistTier");
+  });
+
+  it("Should not deploy if the public sale starts after the sale ends", async () => {
+    const sharesAmount = initialProjectTokenBalanceCreator;
+    const assetsAmount = initialCollateralTokenBalanceCreator;
+
+    // Create pool whose public sale never starts
+    const poolParams = createMockpoolConfig({
+      salt: randomSalt,
+      shares: sharesAmount,
+      assets: assetsAmount,
+      publicSaleTime: DEFAULT_SALE_END_TIME_BN,
+    });
+
+    const formattedPoolParams = formatPoolParams(poolParams);
+
+    // Deploy the pool
+    await expect(
+      program.methods
+        .initializePool(...formattedPoolParams)
+        .accounts(accounts)
+        .rpc()
+    ).to.be.rejectedWith("InvalidPublicSaleTime");
+  });
+
+  it("Should not deploy if the piecewise weight knots are out of order", async () => {
+    const sharesAmount = initialProjectTokenBalanceCreator;
+    const assetsAmount = initialCollateralTokenBalanceCreator;
+
+    // Create pool whose second knot comes before the first one
+    const weightKnots = createMockpoolConfig().weightKnots;
+    weightKnots[0] = {
+      timestamp: DEFAULT_SALE_START_TIME_BN.add(BN(2 * 3600)),
+      weightBasisPoints: 40 * PERCENTAGE_BASIS_POINTS,
+    };
+    weightKnots[1] = {
+      timestamp: DEFAULT_SALE_START_TIME_BN.add(BN(3600)),
+      weightBasisPoints: 30 * PERCENTAGE_BASIS_POINTS,
+    };
+    const poolParams = createMockpoolConfig({
+      salt: randomSalt,
+      shares: sharesAmount,
+      assets: assetsAmount,
+      weightCurve: { piecewise: {} },
+      weightKnots,
+    });
+
+    const formattedPoolParams = formatPoolParams(poolParams);
+
+    // Deploy the pool
+    await expect(
+      program.methods
+        .initializePool(...formattedPoolParams)
+        .accounts(accounts)
+        .rpc()
+    ).to.be.rejectedWith("InvalidWeightConfig");
+  });
+
+  it("Should not deploy if the referral tiers are out of order", async () => {
+    const sharesAmount = initialProjectTokenBalanceCreator;
+    const assetsAmount = initialCollateralTokenBalanceCreator;
+
+    // Create pool whose second referral tier needs less volume than the first one
+    const referralSchedule = createMockpoolConfig().referralSchedule;
+    referralSchedule.tiers[0] = {
+      minReferredVolume: BN(2000),
+      feeBasisPoints: 3 * PERCENTAGE_BASIS_POINTS,
+    };
+    referralSchedule.tiers[1] = {
+      minReferredVolume: BN(1000),
+      feeBasisPoints: 2 * PERCENTAGE_BASIS_POINTS,
+    };
+    const poolParams = createMockpoolConfig({
+      salt: randomSalt,
+      shares: sharesAmount,
+      assets: assetsAmount,
+      referralSchedule,
+    });
+
+    const formattedPoolParams = formatPoolParams(poolParams);
+
+    // Deploy the pool
+    await expect(
+      program.methods
+        .initializePool(...formattedPoolParams)
+        .accounts(accounts)
+        .rpc()
+    ).to.be.rejectedWith("InvalidReferralSchedule");
+  });
+
   it("Should deploy with a positive value for maxAssetsIn if maxAssetsIn is a negative number", async () => {
     const sharesAmount = initialProjectTokenBalanceCreator;
     const assetsAmount = initialCollateralTokenBalanceCreator;
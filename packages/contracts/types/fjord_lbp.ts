@@ -86,6 +86,18 @@ export type FjordLbp = {
         {
           name: "swapFee";
           type: "u16";
+        },
+        {
+          name: "maxPlatformFee";
+          type: "u16";
+        },
+        {
+          name: "maxReferralFee";
+          type: "u16";
+        },
+        {
+          name: "maxSwapFee";
+          type: "u16";
         }
       ];
     },
@@ -153,6 +165,20 @@ export type FjordLbp = {
           isMut: true;
           isSigner: false;
         },
+        {
+          name: "config";
+          isMut: false;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "owner_config";
+              }
+            ];
+          };
+        },
         {
           name: "creator";
           isMut: true;
@@ -207,6 +233,26 @@ export type FjordLbp = {
           name: "maxAssetsIn";
           type: "u64";
         },
+        {
+          name: "minAssetsRaised";
+          type: "u64";
+        },
+        {
+          name: "maxAssetsRaised";
+          type: "u64";
+        },
+        {
+          name: "maxAssetsPerUser";
+          type: "u64";
+        },
+        {
+          name: "maxSharesPerUser";
+          type: "u64";
+        },
+        {
+          name: "minAssetsPerBuy";
+          type: "u64";
+        },
         {
           name: "startWeightBasisPoints";
           type: "u16";
@@ -215,6 +261,23 @@ export type FjordLbp = {
           name: "endWeightBasisPoints";
           type: "u16";
         },
+        {
+          name: "weightCurve";
+          type: {
+            defined: "WeightCurve";
+          };
+        },
+        {
+          name: "weightKnots";
+          type: {
+            array: [
+              {
+                defined: "WeightKnot";
+              },
+              8
+            ];
+          };
+        },
         {
           name: "saleStartTime";
           type: "i64";
@@ -237,6 +300,32 @@ export type FjordLbp = {
             array: ["u8", 32];
           };
         },
+        {
+          name: "whitelistLeafVersion";
+          type: {
+            defined: "WhitelistLeafVersion";
+          };
+        },
+        {
+          name: "whitelistSigner";
+          type: "publicKey";
+        },
+        {
+          name: "whitelistTierStartTimes";
+          type: {
+            array: ["i64", 4];
+          };
+        },
+        {
+          name: "publicSaleTime";
+          type: "i64";
+        },
+        {
+          name: "referralSchedule";
+          type: {
+            defined: "ReferralSchedule";
+          };
+        },
         {
           name: "sellingAllowed";
           type: "bool";
@@ -366,6 +455,34 @@ export type FjordLbp = {
             ];
           };
         },
+        {
+          name: "secondLevelReferrerStateInPool";
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "referrerRegistration";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "referrer";
+              },
+              {
+                kind: "arg";
+                type: {
+                  option: "publicKey";
+                };
+                path: "referrer";
+              }
+            ];
+          };
+        },
         {
           name: "user";
           isMut: true;
@@ -385,6 +502,12 @@ export type FjordLbp = {
           name: "systemProgram";
           isMut: false;
           isSigner: false;
+        },
+        {
+          name: "instructionsSysvar";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
         }
       ];
       args: [
@@ -400,9 +523,7 @@ export type FjordLbp = {
           name: "merkleProof";
           type: {
             option: {
-              vec: {
-                array: ["u8", 32];
-              };
+              defined: "WhitelistProof";
             };
           };
         },
@@ -537,6 +658,34 @@ export type FjordLbp = {
             ];
           };
         },
+        {
+          name: "secondLevelReferrerStateInPool";
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "referrerRegistration";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "referrer";
+              },
+              {
+                kind: "arg";
+                type: {
+                  option: "publicKey";
+                };
+                path: "referrer";
+              }
+            ];
+          };
+        },
         {
           name: "user";
           isMut: true;
@@ -556,6 +705,12 @@ export type FjordLbp = {
           name: "systemProgram";
           isMut: false;
           isSigner: false;
+        },
+        {
+          name: "instructionsSysvar";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
         }
       ];
       args: [
@@ -571,9 +726,7 @@ export type FjordLbp = {
           name: "merkleProof";
           type: {
             option: {
-              vec: {
-                array: ["u8", 32];
-              };
+              defined: "WhitelistProof";
             };
           };
         },
@@ -708,6 +861,34 @@ export type FjordLbp = {
             ];
           };
         },
+        {
+          name: "secondLevelReferrerStateInPool";
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "referrerRegistration";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "referrer";
+              },
+              {
+                kind: "arg";
+                type: {
+                  option: "publicKey";
+                };
+                path: "referrer";
+              }
+            ];
+          };
+        },
         {
           name: "user";
           isMut: true;
@@ -727,6 +908,12 @@ export type FjordLbp = {
           name: "systemProgram";
           isMut: false;
           isSigner: false;
+        },
+        {
+          name: "instructionsSysvar";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
         }
       ];
       args: [
@@ -742,9 +929,7 @@ export type FjordLbp = {
           name: "merkleProof";
           type: {
             option: {
-              vec: {
-                array: ["u8", 32];
-              };
+              defined: "WhitelistProof";
             };
           };
         },
@@ -879,6 +1064,34 @@ export type FjordLbp = {
             ];
           };
         },
+        {
+          name: "secondLevelReferrerStateInPool";
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "referrerRegistration";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "referrer";
+              },
+              {
+                kind: "arg";
+                type: {
+                  option: "publicKey";
+                };
+                path: "referrer";
+              }
+            ];
+          };
+        },
         {
           name: "user";
           isMut: true;
@@ -898,6 +1111,12 @@ export type FjordLbp = {
           name: "systemProgram";
           isMut: false;
           isSigner: false;
+        },
+        {
+          name: "instructionsSysvar";
+          isMut: false;
+          isSigner: false;
+          isOptional: true;
         }
       ];
       args: [
@@ -913,9 +1132,7 @@ export type FjordLbp = {
           name: "merkleProof";
           type: {
             option: {
-              vec: {
-                array: ["u8", 32];
-              };
+              defined: "WhitelistProof";
             };
           };
         },
@@ -1089,7 +1306,7 @@ export type FjordLbp = {
         },
         {
           name: "pool";
-          isMut: false;
+          isMut: true;
           isSigner: false;
           pda: {
             seeds: [
@@ -1189,7 +1406,7 @@ export type FjordLbp = {
       ];
     },
     {
-      name: "previewAssetsIn";
+      name: "refund";
       accounts: [
         {
           name: "assetTokenMint";
@@ -1203,7 +1420,7 @@ export type FjordLbp = {
         },
         {
           name: "pool";
-          isMut: false;
+          isMut: true;
           isSigner: false;
           pda: {
             seeds: [
@@ -1236,61 +1453,108 @@ export type FjordLbp = {
         },
         {
           name: "poolAssetTokenAccount";
-          isMut: false;
+          isMut: true;
           isSigner: false;
         },
         {
-          name: "poolShareTokenAccount";
-          isMut: false;
+          name: "userAssetTokenAccount";
+          isMut: true;
           isSigner: false;
+          isOptional: true;
         },
         {
-          name: "config";
-          isMut: false;
+          name: "userStateInPool";
+          isMut: true;
           isSigner: false;
           pda: {
             seeds: [
               {
-                kind: "const";
-                type: "string";
-                value: "owner_config";
+                kind: "account";
+                type: "publicKey";
+                path: "user";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "LiquidityBootstrappingPool";
+                path: "pool";
               }
             ];
           };
-        }
-      ];
-      args: [
-        {
-          name: "sharesOut";
-          type: "u64";
-        }
-      ];
-      returns: "u64";
-    },
-    {
-      name: "previewSharesIn";
-      accounts: [
-        {
-          name: "assetTokenMint";
-          isMut: false;
-          isSigner: false;
-        },
-        {
-          name: "shareTokenMint";
-          isMut: false;
-          isSigner: false;
         },
         {
-          name: "pool";
-          isMut: false;
+          name: "nativeUnwrapAccount";
+          isMut: true;
           isSigner: false;
+          isOptional: true;
           pda: {
             seeds: [
               {
-                kind: "account";
-                type: "publicKey";
-                account: "Mint";
-                path: "share_token_mint";
+                kind: "const";
+                type: "string";
+                value: "native_unwrap";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "LiquidityBootstrappingPool";
+                path: "pool";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                path: "user";
+              }
+            ];
+          };
+        },
+        {
+          name: "user";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "associatedTokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "assetTokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "shareTokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "claimReferralRewards";
+      accounts: [
+        {
+          name: "assetTokenMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "pool";
+          isMut: true;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "LiquidityBootstrappingPool";
+                path: "pool.share_token";
               },
               {
                 kind: "account";
@@ -1315,39 +1579,86 @@ export type FjordLbp = {
         },
         {
           name: "poolAssetTokenAccount";
-          isMut: false;
+          isMut: true;
           isSigner: false;
         },
         {
-          name: "poolShareTokenAccount";
-          isMut: false;
+          name: "referrerAssetTokenAccount";
+          isMut: true;
           isSigner: false;
+          isOptional: true;
         },
         {
-          name: "config";
-          isMut: false;
+          name: "referrerStateInPool";
+          isMut: true;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                type: "publicKey";
+                path: "referrer";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "LiquidityBootstrappingPool";
+                path: "pool";
+              }
+            ];
+          };
+        },
+        {
+          name: "nativeUnwrapAccount";
+          isMut: true;
           isSigner: false;
+          isOptional: true;
           pda: {
             seeds: [
               {
                 kind: "const";
                 type: "string";
-                value: "owner_config";
+                value: "native_unwrap";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "LiquidityBootstrappingPool";
+                path: "pool";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                path: "referrer";
               }
             ];
           };
-        }
-      ];
-      args: [
+        },
         {
-          name: "assetsOut";
-          type: "u64";
+          name: "referrer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "associatedTokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "assetTokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
         }
       ];
-      returns: "u64";
+      args: [];
     },
     {
-      name: "previewSharesOut";
+      name: "previewAssetsIn";
       accounts: [
         {
           name: "assetTokenMint";
@@ -1419,14 +1730,14 @@ export type FjordLbp = {
       ];
       args: [
         {
-          name: "assetsIn";
+          name: "sharesOut";
           type: "u64";
         }
       ];
       returns: "u64";
     },
     {
-      name: "previewAssetsOut";
+      name: "previewSharesIn";
       accounts: [
         {
           name: "assetTokenMint";
@@ -1498,14 +1809,14 @@ export type FjordLbp = {
       ];
       args: [
         {
-          name: "sharesIn";
+          name: "assetsOut";
           type: "u64";
         }
       ];
       returns: "u64";
     },
     {
-      name: "reservesAndWeights";
+      name: "previewSharesOut";
       accounts: [
         {
           name: "assetTokenMint";
@@ -1575,17 +1886,30 @@ export type FjordLbp = {
           };
         }
       ];
-      args: [];
-      returns: {
-        defined: "ComputedReservesAndWeights";
-      };
+      args: [
+        {
+          name: "assetsIn";
+          type: "u64";
+        }
+      ];
+      returns: "u64";
     },
     {
-      name: "togglePause";
+      name: "previewAssetsOut";
       accounts: [
+        {
+          name: "assetTokenMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "shareTokenMint";
+          isMut: false;
+          isSigner: false;
+        },
         {
           name: "pool";
-          isMut: true;
+          isMut: false;
           isSigner: false;
           pda: {
             seeds: [
@@ -1604,7 +1928,8 @@ export type FjordLbp = {
               {
                 kind: "account";
                 type: "publicKey";
-                path: "creator";
+                account: "LiquidityBootstrappingPool";
+                path: "pool.creator";
               },
               {
                 kind: "account";
@@ -1616,39 +1941,18 @@ export type FjordLbp = {
           };
         },
         {
-          name: "assetTokenMint";
-          isMut: false;
-          isSigner: false;
-        },
-        {
-          name: "shareTokenMint";
+          name: "poolAssetTokenAccount";
           isMut: false;
           isSigner: false;
         },
         {
-          name: "creator";
-          isMut: false;
-          isSigner: true;
-        },
-        {
-          name: "tokenProgram";
+          name: "poolShareTokenAccount";
           isMut: false;
           isSigner: false;
         },
-        {
-          name: "systemProgram";
-          isMut: false;
-          isSigner: false;
-        }
-      ];
-      args: [];
-    },
-    {
-      name: "setFees";
-      accounts: [
         {
           name: "config";
-          isMut: true;
+          isMut: false;
           isSigner: false;
           pda: {
             seeds: [
@@ -1659,822 +1963,4276 @@ export type FjordLbp = {
               }
             ];
           };
-        },
-        {
-          name: "owner";
-          isMut: false;
-          isSigner: true;
-        },
-        {
-          name: "systemProgram";
-          isMut: false;
-          isSigner: false;
         }
       ];
       args: [
         {
-          name: "platformFee";
-          type: {
-            option: "u16";
-          };
-        },
-        {
-          name: "referralFee";
-          type: {
-            option: "u16";
-          };
-        },
-        {
-          name: "swapFee";
-          type: {
-            option: "u16";
-          };
+          name: "sharesIn";
+          type: "u64";
         }
       ];
+      returns: "u64";
     },
     {
-      name: "setTreasuryFeeRecipients";
+      name: "reservesAndWeights";
       accounts: [
         {
-          name: "treasury";
-          isMut: true;
+          name: "assetTokenMint";
+          isMut: false;
           isSigner: false;
-          pda: {
-            seeds: [
-              {
-                kind: "const";
-                type: "string";
-                value: "treasury";
-              }
-            ];
-          };
         },
         {
-          name: "config";
-          isMut: true;
+          name: "shareTokenMint";
+          isMut: false;
           isSigner: false;
-          pda: {
-            seeds: [
-              {
-                kind: "const";
-                type: "string";
-                value: "owner_config";
-              }
-            ];
-          };
         },
         {
-          name: "owner";
-          isMut: true;
-          isSigner: true;
-        },
-        {
-          name: "systemProgram";
+          name: "pool";
           isMut: false;
           isSigner: false;
-        }
-      ];
-      args: [
-        {
-          name: "swapFeeRecipient";
-          type: {
-            option: "publicKey";
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "Mint";
+                path: "share_token_mint";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "Mint";
+                path: "asset_token_mint";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "LiquidityBootstrappingPool";
+                path: "pool.creator";
+              },
+              {
+                kind: "account";
+                type: "string";
+                account: "LiquidityBootstrappingPool";
+                path: "pool.salt";
+              }
+            ];
           };
         },
         {
-          name: "feeRecipients";
-          type: {
-            vec: "publicKey";
-          };
+          name: "poolAssetTokenAccount";
+          isMut: false;
+          isSigner: false;
         },
         {
-          name: "feePercentages";
-          type: {
-            vec: "u16";
+          name: "poolShareTokenAccount";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "config";
+          isMut: false;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "owner_config";
+              }
+            ];
           };
         }
       ];
+      args: [];
+      returns: {
+        defined: "ComputedReservesAndWeights";
+      };
     },
     {
-      name: "nominateNewOwner";
+      name: "togglePause";
       accounts: [
         {
-          name: "config";
+          name: "pool";
           isMut: true;
           isSigner: false;
           pda: {
             seeds: [
               {
-                kind: "const";
+                kind: "account";
+                type: "publicKey";
+                account: "Mint";
+                path: "share_token_mint";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "Mint";
+                path: "asset_token_mint";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                path: "creator";
+              },
+              {
+                kind: "account";
                 type: "string";
-                value: "owner_config";
+                account: "LiquidityBootstrappingPool";
+                path: "pool.salt";
               }
             ];
           };
         },
         {
-          name: "owner";
+          name: "assetTokenMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "shareTokenMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "creator";
           isMut: false;
           isSigner: true;
-        }
-      ];
-      args: [
+        },
         {
-          name: "newOwnerKey";
-          type: "publicKey";
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
         }
       ];
+      args: [];
     },
     {
-      name: "acceptNewOwner";
+      name: "cancelPool";
       accounts: [
         {
-          name: "config";
+          name: "assetTokenMint";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "shareTokenMint";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "pool";
           isMut: true;
           isSigner: false;
           pda: {
             seeds: [
               {
-                kind: "const";
+                kind: "account";
+                type: "publicKey";
+                account: "Mint";
+                path: "share_token_mint";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "Mint";
+                path: "asset_token_mint";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                path: "creator";
+              },
+              {
+                kind: "account";
                 type: "string";
-                value: "owner_config";
+                account: "LiquidityBootstrappingPool";
+                path: "pool.salt";
               }
             ];
           };
         },
         {
-          name: "newOwner";
-          isMut: false;
+          name: "poolAssetTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "poolShareTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "creatorAssetTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "creatorShareTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "creator";
+          isMut: true;
           isSigner: true;
+        },
+        {
+          name: "associatedTokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "assetTokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "shareTokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
         }
       ];
       args: [];
-    }
-  ];
-  accounts: [
+    },
     {
-      name: "liquidityBootstrappingPool";
-      docs: [
-        "Account storing the information of the liquidity bootstrapping pool"
-      ];
-      type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "assetToken";
-            type: "publicKey";
-          },
-          {
-            name: "shareToken";
-            type: "publicKey";
-          },
-          {
-            name: "creator";
-            type: "publicKey";
-          },
-          {
-            name: "virtualAssets";
-            type: "u64";
-          },
-          {
-            name: "virtualShares";
-            type: "u64";
-          },
-          {
-            name: "maxSharePrice";
-            type: "u64";
-          },
-          {
-            name: "maxSharesOut";
-            type: "u64";
-          },
-          {
-            name: "maxAssetsIn";
-            type: "u64";
-          },
-          {
-            name: "startWeightBasisPoints";
-            type: "u16";
-          },
-          {
-            name: "endWeightBasisPoints";
-            type: "u16";
-          },
-          {
-            name: "saleStartTime";
-            type: "i64";
-          },
-          {
-            name: "saleEndTime";
-            type: "i64";
-          },
-          {
-            name: "vestCliff";
-            type: "i64";
-          },
+      name: "closePoolAccounts";
+      accounts: [
+        {
+          name: "assetTokenMint";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "shareTokenMint";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "pool";
+          isMut: true;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "Mint";
+                path: "share_token_mint";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "Mint";
+                path: "asset_token_mint";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                path: "creator";
+              },
+              {
+                kind: "account";
+                type: "string";
+                account: "LiquidityBootstrappingPool";
+                path: "pool.salt";
+              }
+            ];
+          };
+        },
+        {
+          name: "poolAssetTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "poolShareTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "creatorAssetTokenAccount";
+          isMut: true;
+          isSigner: false;
+          isOptional: true;
+        },
+        {
+          name: "creatorShareTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "creator";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "associatedTokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "assetTokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "shareTokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "updatePoolConfig";
+      accounts: [
+        {
+          name: "assetTokenMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "shareTokenMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "pool";
+          isMut: true;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "Mint";
+                path: "share_token_mint";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "Mint";
+                path: "asset_token_mint";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                path: "creator";
+              },
+              {
+                kind: "account";
+                type: "string";
+                account: "LiquidityBootstrappingPool";
+                path: "pool.salt";
+              }
+            ];
+          };
+        },
+        {
+          name: "poolAssetTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "poolShareTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "creatorAssetTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "creatorShareTokenAccount";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "creator";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "assetTokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "shareTokenProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "assets";
+          type: "u64";
+        },
+        {
+          name: "shares";
+          type: "u64";
+        },
+        {
+          name: "virtualAssets";
+          type: {
+            option: "u64";
+          };
+        },
+        {
+          name: "virtualShares";
+          type: {
+            option: "u64";
+          };
+        },
+        {
+          name: "maxSharePrice";
+          type: {
+            option: "u64";
+          };
+        },
+        {
+          name: "startWeightBasisPoints";
+          type: {
+            option: "u16";
+          };
+        },
+        {
+          name: "endWeightBasisPoints";
+          type: {
+            option: "u16";
+          };
+        },
+        {
+          name: "saleStartTime";
+          type: {
+            option: "i64";
+          };
+        },
+        {
+          name: "saleEndTime";
+          type: {
+            option: "i64";
+          };
+        },
+        {
+          name: "vestCliff";
+          type: {
+            option: "i64";
+          };
+        },
+        {
+          name: "vestEnd";
+          type: {
+            option: "i64";
+          };
+        },
+        {
+          name: "weightCurve";
+          type: {
+            option: {
+              defined: "WeightCurve";
+            };
+          };
+        },
+        {
+          name: "weightKnots";
+          type: {
+            option: {
+              array: [
+                {
+                  defined: "WeightKnot";
+                },
+                8
+              ];
+            };
+          };
+        }
+      ];
+    },
+    {
+      name: "setWhitelistMerkleRoot";
+      accounts: [
+        {
+          name: "pool";
+          isMut: true;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "Mint";
+                path: "share_token_mint";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "Mint";
+                path: "asset_token_mint";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                path: "creator";
+              },
+              {
+                kind: "account";
+                type: "string";
+                account: "LiquidityBootstrappingPool";
+                path: "pool.salt";
+              }
+            ];
+          };
+        },
+        {
+          name: "assetTokenMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "shareTokenMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "creator";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "whitelistMerkleRoot";
+          type: {
+            array: ["u8", 32];
+          };
+        }
+      ];
+    },
+    {
+      name: "setPublicSaleTime";
+      accounts: [
+        {
+          name: "pool";
+          isMut: true;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "Mint";
+                path: "share_token_mint";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "Mint";
+                path: "asset_token_mint";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                path: "creator";
+              },
+              {
+                kind: "account";
+                type: "string";
+                account: "LiquidityBootstrappingPool";
+                path: "pool.salt";
+              }
+            ];
+          };
+        },
+        {
+          name: "assetTokenMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "shareTokenMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "creator";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "publicSaleTime";
+          type: "i64";
+        }
+      ];
+    },
+    {
+      name: "setWeightManager";
+      accounts: [
+        {
+          name: "pool";
+          isMut: true;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "Mint";
+                path: "share_token_mint";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "Mint";
+                path: "asset_token_mint";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                path: "creator";
+              },
+              {
+                kind: "account";
+                type: "string";
+                account: "LiquidityBootstrappingPool";
+                path: "pool.salt";
+              }
+            ];
+          };
+        },
+        {
+          name: "assetTokenMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "shareTokenMint";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "creator";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "tokenProgram";
+          isMut: false;
+          isSigner: false;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "weightManager";
+          type: "publicKey";
+        }
+      ];
+    },
+    {
+      name: "adjustWeights";
+      accounts: [
+        {
+          name: "pool";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "authority";
+          isMut: false;
+          isSigner: true;
+        }
+      ];
+      args: [
+        {
+          name: "endWeightBasisPoints";
+          type: {
+            option: "u16";
+          };
+        },
+        {
+          name: "paused";
+          type: {
+            option: "bool";
+          };
+        }
+      ];
+    },
+    {
+      name: "proposeFees";
+      accounts: [
+        {
+          name: "config";
+          isMut: true;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "owner_config";
+              }
+            ];
+          };
+        },
+        {
+          name: "owner";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "platformFee";
+          type: {
+            option: "u16";
+          };
+        },
+        {
+          name: "referralFee";
+          type: {
+            option: "u16";
+          };
+        },
+        {
+          name: "swapFee";
+          type: {
+            option: "u16";
+          };
+        },
+        {
+          name: "feeChangeDelay";
+          type: {
+            option: "i64";
+          };
+        }
+      ];
+    },
+    {
+      name: "executeFees";
+      accounts: [
+        {
+          name: "config";
+          isMut: true;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "owner_config";
+              }
+            ];
+          };
+        },
+        {
+          name: "owner";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "lowerFeeCeilings";
+      accounts: [
+        {
+          name: "config";
+          isMut: true;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "owner_config";
+              }
+            ];
+          };
+        },
+        {
+          name: "owner";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "maxPlatformFee";
+          type: {
+            option: "u16";
+          };
+        },
+        {
+          name: "maxReferralFee";
+          type: {
+            option: "u16";
+          };
+        },
+        {
+          name: "maxSwapFee";
+          type: {
+            option: "u16";
+          };
+        }
+      ];
+    },
+    {
+      name: "proposeTreasuryFeeRecipients";
+      accounts: [
+        {
+          name: "treasury";
+          isMut: true;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "treasury";
+              }
+            ];
+          };
+        },
+        {
+          name: "config";
+          isMut: true;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "owner_config";
+              }
+            ];
+          };
+        },
+        {
+          name: "owner";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "swapFeeRecipient";
+          type: {
+            option: "publicKey";
+          };
+        },
+        {
+          name: "feeRecipients";
+          type: {
+            vec: "publicKey";
+          };
+        },
+        {
+          name: "feePercentages";
+          type: {
+            vec: "u16";
+          };
+        }
+      ];
+    },
+    {
+      name: "executeTreasuryFeeRecipients";
+      accounts: [
+        {
+          name: "treasury";
+          isMut: true;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "treasury";
+              }
+            ];
+          };
+        },
+        {
+          name: "config";
+          isMut: false;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "owner_config";
+              }
+            ];
+          };
+        },
+        {
+          name: "owner";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "setPoolFees";
+      accounts: [
+        {
+          name: "pool";
+          isMut: true;
+          isSigner: false;
+          relations: ["creator"];
+        },
+        {
+          name: "config";
+          isMut: false;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "owner_config";
+              }
+            ];
+          };
+        },
+        {
+          name: "owner";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "creator";
+          isMut: false;
+          isSigner: true;
+        }
+      ];
+      args: [
+        {
+          name: "platformFee";
+          type: {
+            option: "u16";
+          };
+        },
+        {
+          name: "referralFee";
+          type: {
+            option: "u16";
+          };
+        },
+        {
+          name: "swapFee";
+          type: {
+            option: "u16";
+          };
+        }
+      ];
+    },
+    {
+      name: "registerReferrer";
+      accounts: [
+        {
+          name: "referrerRegistration";
+          isMut: true;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "referrer";
+              },
+              {
+                kind: "arg";
+                type: "publicKey";
+                path: "referrer";
+              }
+            ];
+          };
+        },
+        {
+          name: "config";
+          isMut: false;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "owner_config";
+              }
+            ];
+          };
+        },
+        {
+          name: "owner";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "referrer";
+          type: "publicKey";
+        }
+      ];
+    },
+    {
+      name: "unregisterReferrer";
+      accounts: [
+        {
+          name: "referrerRegistration";
+          isMut: true;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "referrer";
+              },
+              {
+                kind: "account";
+                type: "publicKey";
+                account: "ReferrerRegistration";
+                path: "referrer_registration.referrer";
+              }
+            ];
+          };
+        },
+        {
+          name: "config";
+          isMut: false;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "owner_config";
+              }
+            ];
+          };
+        },
+        {
+          name: "owner";
+          isMut: true;
+          isSigner: true;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "setReferrerRegistryRequired";
+      accounts: [
+        {
+          name: "config";
+          isMut: true;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "owner_config";
+              }
+            ];
+          };
+        },
+        {
+          name: "owner";
+          isMut: false;
+          isSigner: true;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "required";
+          type: "bool";
+        }
+      ];
+    },
+    {
+      name: "nominateNewOwner";
+      accounts: [
+        {
+          name: "config";
+          isMut: true;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "owner_config";
+              }
+            ];
+          };
+        },
+        {
+          name: "owner";
+          isMut: false;
+          isSigner: true;
+        }
+      ];
+      args: [
+        {
+          name: "newOwnerKey";
+          type: "publicKey";
+        }
+      ];
+    },
+    {
+      name: "acceptNewOwner";
+      accounts: [
+        {
+          name: "config";
+          isMut: true;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "owner_config";
+              }
+            ];
+          };
+        },
+        {
+          name: "newOwner";
+          isMut: false;
+          isSigner: true;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "migrateOwnerConfig";
+      accounts: [
+        {
+          name: "config";
+          isMut: true;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "owner_config";
+              }
+            ];
+          };
+        },
+        {
+          name: "treasury";
+          isMut: true;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "treasury";
+              }
+            ];
+          };
+        },
+        {
+          name: "owner";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [
+        {
+          name: "maxPlatformFee";
+          type: "u16";
+        },
+        {
+          name: "maxReferralFee";
+          type: "u16";
+        },
+        {
+          name: "maxSwapFee";
+          type: "u16";
+        }
+      ];
+    },
+    {
+      name: "migratePool";
+      accounts: [
+        {
+          name: "pool";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "config";
+          isMut: false;
+          isSigner: false;
+          pda: {
+            seeds: [
+              {
+                kind: "const";
+                type: "string";
+                value: "owner_config";
+              }
+            ];
+          };
+        },
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    },
+    {
+      name: "migrateUserState";
+      accounts: [
+        {
+          name: "userStateInPool";
+          isMut: true;
+          isSigner: false;
+        },
+        {
+          name: "payer";
+          isMut: true;
+          isSigner: true;
+        },
+        {
+          name: "systemProgram";
+          isMut: false;
+          isSigner: false;
+        }
+      ];
+      args: [];
+    }
+  ];
+  accounts: [
+    {
+      name: "liquidityBootstrappingPool";
+      docs: [
+        "Account storing the information of the liquidity bootstrapping pool"
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "assetToken";
+            type: "publicKey";
+          },
+          {
+            name: "shareToken";
+            type: "publicKey";
+          },
+          {
+            name: "creator";
+            type: "publicKey";
+          },
+          {
+            name: "virtualAssets";
+            type: "u64";
+          },
+          {
+            name: "virtualShares";
+            type: "u64";
+          },
+          {
+            name: "maxSharePrice";
+            type: "u64";
+          },
+          {
+            name: "maxSharesOut";
+            type: "u64";
+          },
+          {
+            name: "maxAssetsIn";
+            type: "u64";
+          },
+          {
+            name: "minAssetsRaised";
+            type: "u64";
+          },
+          {
+            name: "maxAssetsRaised";
+            type: "u64";
+          },
+          {
+            name: "maxAssetsPerUser";
+            type: "u64";
+          },
+          {
+            name: "maxSharesPerUser";
+            type: "u64";
+          },
+          {
+            name: "minAssetsPerBuy";
+            type: "u64";
+          },
+          {
+            name: "startWeightBasisPoints";
+            type: "u16";
+          },
+          {
+            name: "endWeightBasisPoints";
+            type: "u16";
+          },
+          {
+            name: "weightCurve";
+            type: {
+              defined: "WeightCurve";
+            };
+          },
+          {
+            name: "weightKnots";
+            type: {
+              array: [
+                {
+                  defined: "WeightKnot";
+                },
+                8
+              ];
+            };
+          },
+          {
+            name: "weightAdjustment";
+            type: {
+              defined: "WeightAdjustment";
+            };
+          },
+          {
+            name: "weightManager";
+            type: "publicKey";
+          },
+          {
+            name: "saleStartTime";
+            type: "i64";
+          },
+          {
+            name: "saleEndTime";
+            type: "i64";
+          },
+          {
+            name: "vestCliff";
+            type: "i64";
+          },
+          {
+            name: "vestEnd";
+            type: "i64";
+          },
+          {
+            name: "sellingAllowed";
+            type: "bool";
+          },
+          {
+            name: "referralSchedule";
+            type: {
+              defined: "ReferralSchedule";
+            };
+          },
+          {
+            name: "platformFee";
+            type: "u16";
+          },
+          {
+            name: "referralFee";
+            type: "u16";
+          },
+          {
+            name: "swapFee";
+            type: "u16";
+          },
+          {
+            name: "totalPurchased";
+            type: "u64";
+          },
+          {
+            name: "totalReferred";
+            type: "u64";
+          },
+          {
+            name: "totalAssetsRaised";
+            type: "u64";
+          },
+          {
+            name: "totalAssetsPaid";
+            type: "u64";
+          },
+          {
+            name: "totalSwapFeesAsset";
+            type: "u64";
+          },
+          {
+            name: "totalSwapFeesShare";
+            type: "u64";
+          },
+          {
+            name: "closed";
+            type: "bool";
+          },
+          {
+            name: "refunding";
+            type: "bool";
+          },
+          {
+            name: "paused";
+            type: "bool";
+          },
+          {
+            name: "traded";
+            type: "bool";
+          },
+          {
+            name: "whitelistMerkleRoot";
+            type: {
+              array: ["u8", 32];
+            };
+          },
+          {
+            name: "whitelistLeafVersion";
+            type: {
+              defined: "WhitelistLeafVersion";
+            };
+          },
+          {
+            name: "whitelistSigner";
+            type: "publicKey";
+          },
+          {
+            name: "whitelistTierStartTimes";
+            type: {
+              array: ["i64", 4];
+            };
+          },
+          {
+            name: "publicSaleTime";
+            type: "i64";
+          },
+          {
+            name: "bump";
+            type: "u8";
+          },
+          {
+            name: "salt";
+            type: "string";
+          }
+        ];
+      };
+    },
+    {
+      name: "closedPool";
+      docs: [
+        "Marker left at the address of a pool once its accounts are closed, so that no pool can be initialized there",
+        "again and inherit the user states left in it"
+      ];
+      type: {
+        kind: "struct";
+        fields: [];
+      };
+    },
+    {
+      name: "userStateInPool";
+      docs: [
+        "Account storing the information of the user in the liquidity bootstrapping pool"
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "purchasedShares";
+            type: "u64";
+          },
+          {
+            name: "referredAssets";
+            type: "u64";
+          },
+          {
+            name: "redeemedShares";
+            type: "u64";
+          },
+          {
+            name: "assetsPaid";
+            type: "u64";
+          },
+          {
+            name: "referrer";
+            type: "publicKey";
+          },
+          {
+            name: "referredVolume";
+            type: "u64";
+          },
+          {
+            name: "assetsSpent";
+            type: "u64";
+          }
+        ];
+      };
+    },
+    {
+      name: "ownerConfig";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "owner";
+            type: "publicKey";
+          },
+          {
+            name: "pendingOwner";
+            type: {
+              option: "publicKey";
+            };
+          },
+          {
+            name: "treasury";
+            type: "publicKey";
+          },
+          {
+            name: "platformFee";
+            type: "u16";
+          },
+          {
+            name: "referralFee";
+            type: "u16";
+          },
+          {
+            name: "swapFee";
+            type: "u16";
+          },
+          {
+            name: "maxPlatformFee";
+            type: "u16";
+          },
+          {
+            name: "maxReferralFee";
+            type: "u16";
+          },
+          {
+            name: "maxSwapFee";
+            type: "u16";
+          },
+          {
+            name: "feeChangeDelay";
+            type: "i64";
+          },
+          {
+            name: "pendingFees";
+            type: {
+              option: {
+                defined: "PendingFees";
+              };
+            };
+          },
+          {
+            name: "referrerRegistryRequired";
+            type: "bool";
+          },
+          {
+            name: "bump";
+            type: "u8";
+          }
+        ];
+      };
+    },
+    {
+      name: "treasury";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "swapFeeRecipient";
+            type: "publicKey";
+          },
+          {
+            name: "feeRecipients";
+            type: {
+              vec: {
+                defined: "FeeMapping";
+              };
+            };
+          },
+          {
+            name: "pendingFeeRecipients";
+            type: {
+              option: {
+                defined: "PendingFeeRecipients";
+              };
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "referrerRegistration";
+      docs: [
+        "Account registering a referrer allowed to earn referral fees when the referrer registry is required"
+      ];
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "referrer";
+            type: "publicKey";
+          },
+          {
+            name: "bump";
+            type: "u8";
+          }
+        ];
+      };
+    }
+  ];
+  types: [
+    {
+      name: "ComputedReservesAndWeights";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "assetReserve";
+            type: "u128";
+          },
+          {
+            name: "shareReserve";
+            type: "u128";
+          },
+          {
+            name: "assetWeight";
+            type: "u128";
+          },
+          {
+            name: "shareWeight";
+            type: "u128";
+          }
+        ];
+      };
+    },
+    {
+      name: "FeeMapping";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "user";
+            type: "publicKey";
+          },
+          {
+            name: "percentage";
+            type: "u16";
+          }
+        ];
+      };
+    },
+    {
+      name: "PendingFeeRecipients";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "swapFeeRecipient";
+            type: "publicKey";
+          },
+          {
+            name: "feeRecipients";
+            type: {
+              vec: {
+                defined: "FeeMapping";
+              };
+            };
+          },
+          {
+            name: "executableAt";
+            type: "i64";
+          }
+        ];
+      };
+    },
+    {
+      name: "PendingFees";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "platformFee";
+            type: "u16";
+          },
+          {
+            name: "referralFee";
+            type: "u16";
+          },
+          {
+            name: "swapFee";
+            type: "u16";
+          },
+          {
+            name: "feeChangeDelay";
+            type: "i64";
+          },
           {
-            name: "vestEnd";
+            name: "executableAt";
+            type: "i64";
+          }
+        ];
+      };
+    },
+    {
+      name: "WhitelistProof";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "tier";
+            type: "u8";
+          },
+          {
+            name: "maxAllocation";
+            type: "u64";
+          },
+          {
+            name: "proof";
+            type: {
+              vec: {
+                array: ["u8", 32];
+              };
+            };
+          }
+        ];
+      };
+    },
+    {
+      name: "WhitelistLeafVersion";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "Legacy";
+          },
+          {
+            name: "Tiered";
+          },
+          {
+            name: "Standard";
+          }
+        ];
+      };
+    },
+    {
+      name: "WeightKnot";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "timestamp";
+            type: "i64";
+          },
+          {
+            name: "weightBasisPoints";
+            type: "u16";
+          }
+        ];
+      };
+    },
+    {
+      name: "WeightCurve";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "Linear";
+          },
+          {
+            name: "Exponential";
+            fields: [
+              {
+                name: "halfLife";
+                type: "i64";
+              }
+            ];
+          },
+          {
+            name: "Piecewise";
+          }
+        ];
+      };
+    },
+    {
+      name: "WeightAdjustment";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "timestamp";
             type: "i64";
           },
-          {
-            name: "sellingAllowed";
-            type: "bool";
+          {
+            name: "weightBasisPoints";
+            type: "u16";
+          },
+          {
+            name: "paused";
+            type: "bool";
+          }
+        ];
+      };
+    },
+    {
+      name: "ReferralTier";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "minReferredVolume";
+            type: "u64";
+          },
+          {
+            name: "feeBasisPoints";
+            type: "u16";
+          }
+        ];
+      };
+    },
+    {
+      name: "ReferralSchedule";
+      type: {
+        kind: "struct";
+        fields: [
+          {
+            name: "feeBasisPoints";
+            type: {
+              option: "u16";
+            };
+          },
+          {
+            name: "tiers";
+            type: {
+              array: [
+                {
+                  defined: "ReferralTier";
+                },
+                4
+              ];
+            };
+          },
+          {
+            name: "secondLevelBasisPoints";
+            type: "u16";
+          }
+        ];
+      };
+    },
+    {
+      name: "AccessControlError";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "Unauthorized";
+          },
+          {
+            name: "NotUpgradeAuthority";
+          }
+        ];
+      };
+    },
+    {
+      name: "SafeMathError";
+      type: {
+        kind: "enum";
+        variants: [
+          {
+            name: "AdditionOverflow";
+          },
+          {
+            name: "SubtractionUnderflow";
+          },
+          {
+            name: "MultiplicationOverflow";
+          },
+          {
+            name: "DivisionUnderflow";
+          },
+          {
+            name: "ExponentiationOverflow";
+          },
+          {
+            name: "ConversionOverflow";
+          },
+          {
+            name: "AmountInTooLarge";
+          },
+          {
+            name: "AmountOutTooLarge";
+          },
+          {
+            name: "LogarithmUndefined";
+          },
+          {
+            name: "InvalidSharesIn";
+          },
+          {
+            name: "InvalidAssetsIn";
+          }
+        ];
+      };
+    }
+  ];
+  events: [
+    {
+      name: "PoolCreatedEvent";
+      fields: [
+        {
+          name: "pool";
+          type: "publicKey";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "Buy";
+      fields: [
+        {
+          name: "user";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "assets";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "shares";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "swapFee";
+          type: "u64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "Sell";
+      fields: [
+        {
+          name: "user";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "shares";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "assets";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "swapFee";
+          type: "u64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "SaleFinalized";
+      fields: [
+        {
+          name: "pool";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "totalAssetsRaised";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "saleEndTime";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "Close";
+      fields: [
+        {
+          name: "assets";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "platformFees";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "swapFeesAsset";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "swapFeesShare";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "refunding";
+          type: "bool";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "Redeem";
+      fields: [
+        {
+          name: "caller";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "shares";
+          type: "u64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "Referral";
+      fields: [
+        {
+          name: "pool";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "referrer";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "user";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "assets";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "referredAssets";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "secondLevelReferrer";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "secondLevelReferredAssets";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "isSell";
+          type: "bool";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "ReferralRewardsClaimed";
+      fields: [
+        {
+          name: "pool";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "referrer";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "assets";
+          type: "u64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "Refund";
+      fields: [
+        {
+          name: "caller";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "assets";
+          type: "u64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "Cancel";
+      fields: [
+        {
+          name: "pool";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "assets";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "shares";
+          type: "u64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "PoolAccountsClosed";
+      fields: [
+        {
+          name: "pool";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "assets";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "shares";
+          type: "u64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "WhitelistUpdated";
+      fields: [
+        {
+          name: "pool";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "whitelistMerkleRoot";
+          type: {
+            array: ["u8", 32];
+          };
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "PublicSaleTimeUpdated";
+      fields: [
+        {
+          name: "pool";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "publicSaleTime";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "PoolConfigUpdated";
+      fields: [
+        {
+          name: "pool";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "assets";
+          type: "u64";
+          index: false;
+        },
+        {
+          name: "shares";
+          type: "u64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "WeightsAdjusted";
+      fields: [
+        {
+          name: "pool";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "authority";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "weightBasisPoints";
+          type: "u16";
+          index: false;
+        },
+        {
+          name: "endWeightBasisPoints";
+          type: "u16";
+          index: false;
+        },
+        {
+          name: "paused";
+          type: "bool";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "PoolFeesUpdated";
+      fields: [
+        {
+          name: "pool";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "platformFee";
+          type: "u16";
+          index: false;
+        },
+        {
+          name: "referralFee";
+          type: "u16";
+          index: false;
+        },
+        {
+          name: "swapFee";
+          type: "u16";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "FeesProposed";
+      fields: [
+        {
+          name: "platformFee";
+          type: "u16";
+          index: false;
+        },
+        {
+          name: "referralFee";
+          type: "u16";
+          index: false;
+        },
+        {
+          name: "swapFee";
+          type: "u16";
+          index: false;
+        },
+        {
+          name: "feeChangeDelay";
+          type: "i64";
+          index: false;
+        },
+        {
+          name: "executableAt";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "FeesExecuted";
+      fields: [
+        {
+          name: "platformFee";
+          type: "u16";
+          index: false;
+        },
+        {
+          name: "referralFee";
+          type: "u16";
+          index: false;
+        },
+        {
+          name: "swapFee";
+          type: "u16";
+          index: false;
+        },
+        {
+          name: "feeChangeDelay";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "FeeRecipientsProposed";
+      fields: [
+        {
+          name: "swapFeeRecipient";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "feeRecipients";
+          type: {
+            vec: {
+              defined: "FeeMapping";
+            };
+          };
+          index: false;
+        },
+        {
+          name: "executableAt";
+          type: "i64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "FeeRecipientsExecuted";
+      fields: [
+        {
+          name: "swapFeeRecipient";
+          type: "publicKey";
+          index: false;
+        },
+        {
+          name: "feeRecipients";
+          type: {
+            vec: {
+              defined: "FeeMapping";
+            };
+          };
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "FeeCeilingsLowered";
+      fields: [
+        {
+          name: "maxPlatformFee";
+          type: "u16";
+          index: false;
+        },
+        {
+          name: "maxReferralFee";
+          type: "u16";
+          index: false;
+        },
+        {
+          name: "maxSwapFee";
+          type: "u16";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "PreviewAssetsIn";
+      fields: [
+        {
+          name: "assetsIn";
+          type: "u64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "PreviewAssetsOut";
+      fields: [
+        {
+          name: "assetsOut";
+          type: "u64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "PreviewSharesIn";
+      fields: [
+        {
+          name: "sharesIn";
+          type: "u64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "PreviewSharesOut";
+      fields: [
+        {
+          name: "sharesOut";
+          type: "u64";
+          index: false;
+        }
+      ];
+    },
+    {
+      name: "ReservesAndWeights";
+      fields: [
+        {
+          name: "assetReserve";
+          type: "u128";
+          index: false;
+        },
+        {
+          name: "shareReserve";
+          type: "u128";
+          index: false;
+        },
+        {
+          name: "assetWeight";
+          type: "u128";
+          index: false;
+        },
+        {
+          name: "shareWeight";
+          type: "u128";
+          index: false;
+        }
+      ];
+    }
+  ];
+  errors: [
+    {
+      code: 6000;
+      name: "InvalidAssetOrShare";
+      msg: "Asset and share token mints must be different";
+    },
+    {
+      code: 6001;
+      name: "SalePeriodLow";
+      msg: "Sale period is too low";
+    },
+    {
+      code: 6002;
+      name: "InvalidVestCliff";
+      msg: "Vesting cliff time should be less than sale end";
+    },
+    {
+      code: 6003;
+      name: "InvalidVestEnd";
+      msg: "Vesting end time should be greater or equal to vest cliff ";
+    },
+    {
+      code: 6004;
+      name: "InvalidWeightConfig";
+      msg: "Invalid start or end weight";
+    },
+    {
+      code: 6005;
+      name: "InvalidAssetValue";
+      msg: "Asset value cannot be 0";
+    },
+    {
+      code: 6006;
+      name: "MaxFeeExceeded";
+      msg: "Max Fee Exceeded";
+    },
+    {
+      code: 6007;
+      name: "InvalidPercentageSum";
+      msg: "Fee percentages must add up to 100% (10000)";
+    },
+    {
+      code: 6008;
+      name: "AssetsInExceeded";
+      msg: "Max allowed assets in exceeded";
+    },
+    {
+      code: 6009;
+      name: "SharesOutExceeded";
+      msg: "Max allowed shares out exceeded";
+    },
+    {
+      code: 6010;
+      name: "WhitelistProof";
+      msg: "Whitelist verification failed";
+    },
+    {
+      code: 6011;
+      name: "SlippageExceeded";
+      msg: "Slippage limit is exceeded";
+    },
+    {
+      code: 6012;
+      name: "SellingDisallowed";
+      msg: "Selling is disallowed";
+    },
+    {
+      code: 6013;
+      name: "TradingDisallowed";
+      msg: "Trading is disallowed";
+    },
+    {
+      code: 6014;
+      name: "ClosingDisallowed";
+      msg: "Closing is disallowed";
+    },
+    {
+      code: 6015;
+      name: "RedeemingDisallowed";
+      msg: "Redeeming is disallowed";
+    },
+    {
+      code: 6016;
+      name: "CallerDisallowed";
+      msg: "Caller is disallowed";
+    },
+    {
+      code: 6017;
+      name: "InvalidSellingAllowed";
+      msg: "Invalid selling allowed value";
+    },
+    {
+      code: 6018;
+      name: "InvalidShareValue";
+      msg: "Share value cannot be 0";
+    },
+    {
+      code: 6019;
+      name: "InvalidSharePrice";
+      msg: "Invalid share price";
+    },
+    {
+      code: 6020;
+      name: "InvalidMaxSharesOut";
+      msg: "Max shares out cannot be 0";
+    },
+    {
+      code: 6021;
+      name: "InvalidMaxAssetsIn";
+      msg: "Max assets in cannot be 0";
+    },
+    {
+      code: 6022;
+      name: "InsufficientShares";
+      msg: "There are insuffcient shares to transfer in your account";
+    },
+    {
+      code: 6023;
+      name: "InsufficientAssets";
+      msg: "There are insuffcient assets to transfer in your account";
+    },
+    {
+      code: 6024;
+      name: "Paused";
+      msg: "The pool is paused";
+    },
+    {
+      code: 6025;
+      name: "InvalidFeeRecipients";
+      msg: "The fee recipient and percentages must match in length";
+    },
+    {
+      code: 6026;
+      name: "InvalidFeeRecipientWritable";
+      msg: "The fee recipient must be writable";
+    },
+    {
+      code: 6027;
+      name: "InvalidCreator";
+      msg: "Supplied account must match the pool creator pubkey";
+    },
+    {
+      code: 6028;
+      name: "InvalidSwapFeeRecipient";
+      msg: "Invalid swap fee recipient";
+    },
+    {
+      code: 6029;
+      name: "DuplicateFeeRecipient";
+      msg: "Duplicate fee recipient";
+    },
+    {
+      code: 6030;
+      name: "ZeroSlippage";
+      msg: "Zero Slippage";
+    },
+    {
+      code: 6031;
+      name: "MissingAssetTokenAccount";
+      msg: "The asset token account is required unless native SOL is used";
+    },
+    {
+      code: 6032;
+      name: "NativeSolDisallowed";
+      msg: "Native SOL can only be used in pools whose asset is wrapped SOL";
+    },
+    {
+      code: 6033;
+      name: "MissingNativeUnwrapAccount";
+      msg: "The native unwrap account is required to receive native SOL";
+    },
+    {
+      code: 6034;
+      name: "UnexpectedNativeUnwrapAccount";
+      msg: "The native unwrap account is only used to receive native SOL";
+    },
+    {
+      code: 6035;
+      name: "InvalidSaleCaps";
+      msg: "The minimum amount of assets raised cannot exceed the hard cap";
+    },
+    {
+      code: 6036;
+      name: "HardCapExceeded";
+      msg: "The purchase would raise more assets than the hard cap";
+    },
+    {
+      code: 6037;
+      name: "AccountAlreadyMigrated";
+      msg: "The account already has the current layout";
+    },
+    {
+      code: 6038;
+      name: "RefundingDisallowed";
+      msg: "Refunds are only available once a pool is closed below its soft cap";
+    },
+    {
+      code: 6039;
+      name: "UserAssetsInExceeded";
+      msg: "Assets in exceeds the maximum amount of assets per user";
+    },
+    {
+      code: 6040;
+      name: "UserSharesOutExceeded";
+      msg: "Shares out exceeds the maximum number of shares per user";
+    },
+    {
+      code: 6041;
+      name: "AssetsInTooLow";
+      msg: "Assets in is below the minimum amount of assets per buy";
+    },
+    {
+      code: 6042;
+      name: "InvalidMinAssetsPerBuy";
+      msg: "The minimum amount of assets per buy cannot exceed the per-wallet cap, the hard cap or the maximum assets in";
+    },
+    {
+      code: 6043;
+      name: "InvalidWhitelistTier";
+      msg: "Invalid whitelist tier";
+    },
+    {
+      code: 6044;
+      name: "WhitelistTierNotOpen";
+      msg: "The whitelist tier of the user is not open yet";
+    },
+    {
+      code: 6045;
+      name: "WhitelistAllocationExceeded";
+      msg: "Assets in exceeds the whitelist allocation of the user";
+    },
+    {
+      code: 6046;
+      name: "WhitelistUpdateDisallowed";
+      msg: "The whitelist can only be updated before the sale ends";
+    },
+    {
+      code: 6047;
+      name: "InvalidPublicSaleTime";
+      msg: "The public sale must start before the sale ends";
+    },
+    {
+      code: 6048;
+      name: "InvalidWhitelistAttestation";
+      msg: "The whitelist attestation is missing or invalid";
+    },
+    {
+      code: 6049;
+      name: "WhitelistAttestationExpired";
+      msg: "The whitelist attestation has expired";
+    },
+    {
+      code: 6050;
+      name: "CancellingDisallowed";
+      msg: "Cancelling is only allowed as long as no shares were ever bought in the pool";
+    },
+    {
+      code: 6051;
+      name: "UpdatingDisallowed";
+      msg: "The pool configuration can only be updated before the sale starts";
+    },
+    {
+      code: 6052;
+      name: "PoolAccountsClosingDisallowed";
+      msg: "The pool accounts can only be closed once the pool is closed and everything owed is claimed, or after the grace period";
+    },
+    {
+      code: 6053;
+      name: "WeightAdjustmentDisallowed";
+      msg: "The weight schedule can only be adjusted during the sale";
+    },
+    {
+      code: 6054;
+      name: "UnauthorizedWeightAdjustment";
+      msg: "Only the creator or the weight manager can adjust the weight schedule";
+    },
+    {
+      code: 6055;
+      name: "SelfReferral";
+      msg: "Users cannot refer themselves";
+    },
+    {
+      code: 6056;
+      name: "InvalidReferrer";
+      msg: "The referrer must come with its state in the pool and match the referrer of the user's earlier purchases";
+    },
+    {
+      code: 6057;
+      name: "UnregisteredReferrer";
+      msg: "The referrer is not registered";
+    },
+    {
+      code: 6058;
+      name: "InvalidReferralSchedule";
+      msg: "Referral fees must not exceed 100% and the referral tiers must come first in increasing volume order";
+    },
+    {
+      code: 6059;
+      name: "ReferralClaimDisallowed";
+      msg: "Referral rewards can only be claimed once the pool is closed, unless it is refunding";
+    },
+    {
+      code: 6060;
+      name: "PoolFeesUpdateDisallowed";
+      msg: "The fees of a pool can only be overridden until it is closed";
+    },
+    {
+      code: 6061;
+      name: "FeeChangeNotProposed";
+      msg: "No fee change is pending";
+    },
+    {
+      code: 6062;
+      name: "FeeChangeNotReady";
+      msg: "The fee change can only be executed once its delay has elapsed";
+    },
+    {
+      code: 6063;
+      name: "FeeCeilingExceeded";
+      msg: "The fees must not exceed their ceilings";
+    },
+    {
+      code: 6064;
+      name: "FeeCeilingRaised";
+      msg: "Fee ceilings can only be lowered";
+    },
+    {
+      code: 6065;
+      name: "InvalidFeeChangeDelay";
+      msg: "The fee change delay must be between one and thirty days";
+    }
+  ];
+};
+
+export const IDL: FjordLbp = {
+  version: "0.1.0",
+  name: "fjord_lbp",
+  instructions: [
+    {
+      name: "initializeOwnerConfig",
+      accounts: [
+        {
+          name: "config",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "owner_config",
+              },
+            ],
+          },
+        },
+        {
+          name: "treasury",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "treasury",
+              },
+            ],
+          },
+        },
+        {
+          name: "program",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "programData",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "authority",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "ownerKey",
+          type: "publicKey",
+        },
+        {
+          name: "swapFeeRecipient",
+          type: "publicKey",
+        },
+        {
+          name: "feeRecipients",
+          type: {
+            vec: "publicKey",
+          },
+        },
+        {
+          name: "feePercentages",
+          type: {
+            vec: "u16",
+          },
+        },
+        {
+          name: "platformFee",
+          type: "u16",
+        },
+        {
+          name: "referralFee",
+          type: "u16",
+        },
+        {
+          name: "swapFee",
+          type: "u16",
+        },
+        {
+          name: "maxPlatformFee",
+          type: "u16",
+        },
+        {
+          name: "maxReferralFee",
+          type: "u16",
+        },
+        {
+          name: "maxSwapFee",
+          type: "u16",
+        },
+      ],
+    },
+    {
+      name: "initializePool",
+      accounts: [
+        {
+          name: "pool",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "Mint",
+                path: "share_token_mint",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "Mint",
+                path: "asset_token_mint",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                path: "creator",
+              },
+              {
+                kind: "arg",
+                type: "string",
+                path: "salt",
+              },
+            ],
+          },
+        },
+        {
+          name: "assetTokenMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "shareTokenMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "poolShareTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "poolAssetTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "creatorAssetTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "creatorShareTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "config",
+          isMut: false,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "owner_config",
+              },
+            ],
           },
-          {
-            name: "totalPurchased";
-            type: "u64";
+        },
+        {
+          name: "creator",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "associatedTokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "salt",
+          type: "string",
+        },
+        {
+          name: "assets",
+          type: "u64",
+        },
+        {
+          name: "shares",
+          type: "u64",
+        },
+        {
+          name: "virtualAssets",
+          type: "u64",
+        },
+        {
+          name: "virtualShares",
+          type: "u64",
+        },
+        {
+          name: "maxSharePrice",
+          type: "u64",
+        },
+        {
+          name: "maxSharesOut",
+          type: "u64",
+        },
+        {
+          name: "maxAssetsIn",
+          type: "u64",
+        },
+        {
+          name: "minAssetsRaised",
+          type: "u64",
+        },
+        {
+          name: "maxAssetsRaised",
+          type: "u64",
+        },
+        {
+          name: "maxAssetsPerUser",
+          type: "u64",
+        },
+        {
+          name: "maxSharesPerUser",
+          type: "u64",
+        },
+        {
+          name: "minAssetsPerBuy",
+          type: "u64",
+        },
+        {
+          name: "startWeightBasisPoints",
+          type: "u16",
+        },
+        {
+          name: "endWeightBasisPoints",
+          type: "u16",
+        },
+        {
+          name: "weightCurve",
+          type: {
+            defined: "WeightCurve",
           },
-          {
-            name: "totalReferred";
-            type: "u64";
+        },
+        {
+          name: "weightKnots",
+          type: {
+            array: [
+              {
+                defined: "WeightKnot",
+              },
+              8,
+            ],
           },
-          {
-            name: "totalSwapFeesAsset";
-            type: "u64";
+        },
+        {
+          name: "saleStartTime",
+          type: "i64",
+        },
+        {
+          name: "saleEndTime",
+          type: "i64",
+        },
+        {
+          name: "vestCliff",
+          type: "i64",
+        },
+        {
+          name: "vestEnd",
+          type: "i64",
+        },
+        {
+          name: "whitelistMerkleRoot",
+          type: {
+            array: ["u8", 32],
+          },
+        },
+        {
+          name: "whitelistLeafVersion",
+          type: {
+            defined: "WhitelistLeafVersion",
+          },
+        },
+        {
+          name: "whitelistSigner",
+          type: "publicKey",
+        },
+        {
+          name: "whitelistTierStartTimes",
+          type: {
+            array: ["i64", 4],
+          },
+        },
+        {
+          name: "publicSaleTime",
+          type: "i64",
+        },
+        {
+          name: "referralSchedule",
+          type: {
+            defined: "ReferralSchedule",
+          },
+        },
+        {
+          name: "sellingAllowed",
+          type: "bool",
+        },
+      ],
+    },
+    {
+      name: "swapExactAssetsForShares",
+      accounts: [
+        {
+          name: "assetTokenMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "shareTokenMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "pool",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "Mint",
+                path: "share_token_mint",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "Mint",
+                path: "asset_token_mint",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "LiquidityBootstrappingPool",
+                path: "pool.creator",
+              },
+              {
+                kind: "account",
+                type: "string",
+                account: "LiquidityBootstrappingPool",
+                path: "pool.salt",
+              },
+            ],
+          },
+        },
+        {
+          name: "poolAssetTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "poolShareTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userAssetTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userShareTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "config",
+          isMut: false,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "owner_config",
+              },
+            ],
           },
-          {
-            name: "totalSwapFeesShare";
-            type: "u64";
+        },
+        {
+          name: "userStateInPool",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "account",
+                type: "publicKey",
+                path: "user",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "LiquidityBootstrappingPool",
+                path: "pool",
+              },
+            ],
           },
-          {
-            name: "closed";
-            type: "bool";
+        },
+        {
+          name: "referrerStateInPool",
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+          pda: {
+            seeds: [
+              {
+                kind: "arg",
+                type: {
+                  option: "publicKey",
+                },
+                path: "referrer",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "LiquidityBootstrappingPool",
+                path: "pool",
+              },
+            ],
           },
-          {
-            name: "paused";
-            type: "bool";
+        },
+        {
+          name: "secondLevelReferrerStateInPool",
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "referrerRegistration",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "referrer",
+              },
+              {
+                kind: "arg",
+                type: {
+                  option: "publicKey",
+                },
+                path: "referrer",
+              },
+            ],
           },
-          {
-            name: "whitelistMerkleRoot";
-            type: {
-              array: ["u8", 32];
-            };
+        },
+        {
+          name: "user",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "associatedTokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "instructionsSysvar",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+      ],
+      args: [
+        {
+          name: "assetsIn",
+          type: "u64",
+        },
+        {
+          name: "minSharesOut",
+          type: "u64",
+        },
+        {
+          name: "merkleProof",
+          type: {
+            option: {
+              defined: "WhitelistProof",
+            },
           },
-          {
-            name: "bump";
-            type: "u8";
+        },
+        {
+          name: "referrer",
+          type: {
+            option: "publicKey",
           },
-          {
-            name: "salt";
-            type: "string";
-          }
-        ];
-      };
+        },
+      ],
     },
     {
-      name: "userStateInPool";
-      docs: [
-        "Account storing the information of the user in the liquidity bootstrapping pool"
-      ];
-      type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "purchasedShares";
-            type: "u64";
-          },
-          {
-            name: "referredAssets";
-            type: "u64";
+      name: "swapAssetsForExactShares",
+      accounts: [
+        {
+          name: "assetTokenMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "shareTokenMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "pool",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "Mint",
+                path: "share_token_mint",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "Mint",
+                path: "asset_token_mint",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "LiquidityBootstrappingPool",
+                path: "pool.creator",
+              },
+              {
+                kind: "account",
+                type: "string",
+                account: "LiquidityBootstrappingPool",
+                path: "pool.salt",
+              },
+            ],
           },
-          {
-            name: "redeemedShares";
-            type: "u64";
-          }
-        ];
-      };
-    },
-    {
-      name: "ownerConfig";
-      type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "owner";
-            type: "publicKey";
+        },
+        {
+          name: "poolAssetTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "poolShareTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userAssetTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userShareTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "config",
+          isMut: false,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "owner_config",
+              },
+            ],
           },
-          {
-            name: "pendingOwner";
-            type: {
-              option: "publicKey";
-            };
+        },
+        {
+          name: "userStateInPool",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "account",
+                type: "publicKey",
+                path: "user",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "LiquidityBootstrappingPool",
+                path: "pool",
+              },
+            ],
           },
-          {
-            name: "treasury";
-            type: "publicKey";
+        },
+        {
+          name: "referrerStateInPool",
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+          pda: {
+            seeds: [
+              {
+                kind: "arg",
+                type: {
+                  option: "publicKey",
+                },
+                path: "referrer",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "LiquidityBootstrappingPool",
+                path: "pool",
+              },
+            ],
           },
-          {
-            name: "platformFee";
-            type: "u16";
+        },
+        {
+          name: "secondLevelReferrerStateInPool",
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "referrerRegistration",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "referrer",
+              },
+              {
+                kind: "arg",
+                type: {
+                  option: "publicKey",
+                },
+                path: "referrer",
+              },
+            ],
           },
-          {
-            name: "referralFee";
-            type: "u16";
+        },
+        {
+          name: "user",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "associatedTokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "instructionsSysvar",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+      ],
+      args: [
+        {
+          name: "sharesOut",
+          type: "u64",
+        },
+        {
+          name: "maxAssetsIn",
+          type: "u64",
+        },
+        {
+          name: "merkleProof",
+          type: {
+            option: {
+              defined: "WhitelistProof",
+            },
           },
-          {
-            name: "swapFee";
-            type: "u16";
+        },
+        {
+          name: "referrer",
+          type: {
+            option: "publicKey",
           },
-          {
-            name: "bump";
-            type: "u8";
-          }
-        ];
-      };
+        },
+      ],
     },
     {
-      name: "treasury";
-      type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "swapFeeRecipient";
-            type: "publicKey";
+      name: "swapExactSharesForAssets",
+      accounts: [
+        {
+          name: "assetTokenMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "shareTokenMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "pool",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "Mint",
+                path: "share_token_mint",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "Mint",
+                path: "asset_token_mint",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "LiquidityBootstrappingPool",
+                path: "pool.creator",
+              },
+              {
+                kind: "account",
+                type: "string",
+                account: "LiquidityBootstrappingPool",
+                path: "pool.salt",
+              },
+            ],
           },
-          {
-            name: "feeRecipients";
-            type: {
-              vec: {
-                defined: "FeeMapping";
-              };
-            };
-          }
-        ];
-      };
-    }
-  ];
-  types: [
-    {
-      name: "ComputedReservesAndWeights";
-      type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "assetReserve";
-            type: "u64";
+        },
+        {
+          name: "poolAssetTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "poolShareTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userAssetTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userShareTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "config",
+          isMut: false,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "owner_config",
+              },
+            ],
+          },
+        },
+        {
+          name: "userStateInPool",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "account",
+                type: "publicKey",
+                path: "user",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "LiquidityBootstrappingPool",
+                path: "pool",
+              },
+            ],
           },
-          {
-            name: "shareReserve";
-            type: "u64";
+        },
+        {
+          name: "referrerStateInPool",
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+          pda: {
+            seeds: [
+              {
+                kind: "arg",
+                type: {
+                  option: "publicKey",
+                },
+                path: "referrer",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "LiquidityBootstrappingPool",
+                path: "pool",
+              },
+            ],
           },
-          {
-            name: "assetWeight";
-            type: "u64";
+        },
+        {
+          name: "secondLevelReferrerStateInPool",
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "referrerRegistration",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "referrer",
+              },
+              {
+                kind: "arg",
+                type: {
+                  option: "publicKey",
+                },
+                path: "referrer",
+              },
+            ],
           },
-          {
-            name: "shareWeight";
-            type: "u64";
-          }
-        ];
-      };
-    },
-    {
-      name: "FeeMapping";
-      type: {
-        kind: "struct";
-        fields: [
-          {
-            name: "user";
-            type: "publicKey";
+        },
+        {
+          name: "user",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "associatedTokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "instructionsSysvar",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+      ],
+      args: [
+        {
+          name: "sharesIn",
+          type: "u64",
+        },
+        {
+          name: "minAssetsOut",
+          type: "u64",
+        },
+        {
+          name: "merkleProof",
+          type: {
+            option: {
+              defined: "WhitelistProof",
+            },
           },
-          {
-            name: "percentage";
-            type: "u16";
-          }
-        ];
-      };
-    },
-    {
-      name: "AccessControlError";
-      type: {
-        kind: "enum";
-        variants: [
-          {
-            name: "Unauthorized";
+        },
+        {
+          name: "referrer",
+          type: {
+            option: "publicKey",
           },
-          {
-            name: "NotUpgradeAuthority";
-          }
-        ];
-      };
+        },
+      ],
     },
     {
-      name: "SafeMathError";
-      type: {
-        kind: "enum";
-        variants: [
-          {
-            name: "AdditionOverflow";
-          },
-          {
-            name: "SubtractionUnderflow";
-          },
-          {
-            name: "MultiplicationOverflow";
-          },
-          {
-            name: "DivisionUnderflow";
-          },
-          {
-            name: "ExponentiationOverflow";
-          },
-          {
-            name: "ConversionOverflow";
+      name: "swapSharesForExactAssets",
+      accounts: [
+        {
+          name: "assetTokenMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "shareTokenMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "pool",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "Mint",
+                path: "share_token_mint",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "Mint",
+                path: "asset_token_mint",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "LiquidityBootstrappingPool",
+                path: "pool.creator",
+              },
+              {
+                kind: "account",
+                type: "string",
+                account: "LiquidityBootstrappingPool",
+                path: "pool.salt",
+              },
+            ],
           },
-          {
-            name: "AmountInTooLarge";
+        },
+        {
+          name: "poolAssetTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "poolShareTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userAssetTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userShareTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "config",
+          isMut: false,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "owner_config",
+              },
+            ],
           },
-          {
-            name: "AmountOutTooLarge";
+        },
+        {
+          name: "userStateInPool",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "account",
+                type: "publicKey",
+                path: "user",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "LiquidityBootstrappingPool",
+                path: "pool",
+              },
+            ],
           },
-          {
-            name: "LogarithmUndefined";
+        },
+        {
+          name: "referrerStateInPool",
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+          pda: {
+            seeds: [
+              {
+                kind: "arg",
+                type: {
+                  option: "publicKey",
+                },
+                path: "referrer",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "LiquidityBootstrappingPool",
+                path: "pool",
+              },
+            ],
           },
-          {
-            name: "InvalidSharesIn";
+        },
+        {
+          name: "secondLevelReferrerStateInPool",
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "referrerRegistration",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "referrer",
+              },
+              {
+                kind: "arg",
+                type: {
+                  option: "publicKey",
+                },
+                path: "referrer",
+              },
+            ],
           },
-          {
-            name: "InvalidAssetsIn";
-          }
-        ];
-      };
-    }
-  ];
-  events: [
-    {
-      name: "PoolCreatedEvent";
-      fields: [
+        },
         {
-          name: "pool";
-          type: "publicKey";
-          index: false;
-        }
-      ];
-    },
-    {
-      name: "Buy";
-      fields: [
+          name: "user",
+          isMut: true,
+          isSigner: true,
+        },
         {
-          name: "user";
-          type: "publicKey";
-          index: false;
+          name: "associatedTokenProgram",
+          isMut: false,
+          isSigner: false,
         },
         {
-          name: "assets";
-          type: "u64";
-          index: false;
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
         },
         {
-          name: "shares";
-          type: "u64";
-          index: false;
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
         },
         {
-          name: "swapFee";
-          type: "u64";
-          index: false;
-        }
-      ];
-    },
-    {
-      name: "Sell";
-      fields: [
+          name: "instructionsSysvar",
+          isMut: false,
+          isSigner: false,
+          isOptional: true,
+        },
+      ],
+      args: [
         {
-          name: "user";
-          type: "publicKey";
-          index: false;
+          name: "assetsOut",
+          type: "u64",
         },
         {
-          name: "shares";
-          type: "u64";
-          index: false;
+          name: "maxSharesIn",
+          type: "u64",
         },
         {
-          name: "assets";
-          type: "u64";
-          index: false;
+          name: "merkleProof",
+          type: {
+            option: {
+              defined: "WhitelistProof",
+            },
+          },
         },
         {
-          name: "swapFee";
-          type: "u64";
-          index: false;
-        }
-      ];
+          name: "referrer",
+          type: {
+            option: "publicKey",
+          },
+        },
+      ],
     },
     {
-      name: "Close";
-      fields: [
+      name: "closePool",
+      accounts: [
         {
-          name: "assets";
-          type: "u64";
-          index: false;
+          name: "assetTokenMint",
+          isMut: false,
+          isSigner: false,
         },
         {
-          name: "platformFees";
-          type: "u64";
-          index: false;
+          name: "shareTokenMint",
+          isMut: false,
+          isSigner: false,
         },
         {
-          name: "swapFeesAsset";
-          type: "u64";
-          index: false;
+          name: "pool",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "Mint",
+                path: "share_token_mint",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "Mint",
+                path: "asset_token_mint",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "LiquidityBootstrappingPool",
+                path: "pool.creator",
+              },
+              {
+                kind: "account",
+                type: "string",
+                account: "LiquidityBootstrappingPool",
+                path: "pool.salt",
+              },
+            ],
+          },
         },
         {
-          name: "swapFeesShare";
-          type: "u64";
-          index: false;
-        }
-      ];
-    },
-    {
-      name: "Redeem";
-      fields: [
+          name: "poolAssetTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
         {
-          name: "caller";
-          type: "publicKey";
-          index: false;
+          name: "poolShareTokenAccount",
+          isMut: true,
+          isSigner: false,
         },
         {
-          name: "shares";
-          type: "u64";
-          index: false;
-        }
-      ];
-    },
-    {
-      name: "PreviewAssetsIn";
-      fields: [
+          name: "treasuryAssetTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treasuryShareTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "treasury",
+          isMut: false,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "treasury",
+              },
+            ],
+          },
+        },
+        {
+          name: "creatorAssetTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "creatorShareTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "poolCreator",
+          isMut: false,
+          isSigner: false,
+        },
         {
-          name: "assetsIn";
-          type: "u64";
-          index: false;
-        }
-      ];
-    },
-    {
-      name: "PreviewAssetsOut";
-      fields: [
+          name: "swapFeeRecipientAssetTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
         {
-          name: "assetsOut";
-          type: "u64";
-          index: false;
-        }
-      ];
-    },
-    {
-      name: "PreviewSharesIn";
-      fields: [
+          name: "swapFeeRecipientShareTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
         {
-          name: "sharesIn";
-          type: "u64";
-          index: false;
-        }
-      ];
-    },
-    {
-      name: "PreviewSharesOut";
-      fields: [
+          name: "swapFeeRecipient",
+          isMut: false,
+          isSigner: false,
+        },
         {
-          name: "sharesOut";
-          type: "u64";
-          index: false;
-        }
-      ];
-    },
-    {
-      name: "ReservesAndWeights";
-      fields: [
+          name: "ownerConfig",
+          isMut: false,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "owner_config",
+              },
+            ],
+          },
+        },
         {
-          name: "assetReserve";
-          type: "u64";
-          index: false;
+          name: "user",
+          isMut: true,
+          isSigner: true,
         },
         {
-          name: "shareReserve";
-          type: "u64";
-          index: false;
+          name: "associatedTokenProgram",
+          isMut: false,
+          isSigner: false,
         },
         {
-          name: "assetWeight";
-          type: "u64";
-          index: false;
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
         },
         {
-          name: "shareWeight";
-          type: "u64";
-          index: false;
-        }
-      ];
-    }
-  ];
-  errors: [
-    {
-      code: 6000;
-      name: "InvalidAssetOrShare";
-      msg: "Asset and share token mints must be different";
-    },
-    {
-      code: 6001;
-      name: "SalePeriodLow";
-      msg: "Sale period is too low";
-    },
-    {
-      code: 6002;
-      name: "InvalidVestCliff";
-      msg: "Vesting cliff time should be less than sale end";
-    },
-    {
-      code: 6003;
-      name: "InvalidVestEnd";
-      msg: "Vesting end time should be greater or equal to vest cliff ";
-    },
-    {
-      code: 6004;
-      name: "InvalidWeightConfig";
-      msg: "Invalid start or end weight";
-    },
-    {
-      code: 6005;
-      name: "InvalidAssetValue";
-      msg: "Asset value cannot be 0";
-    },
-    {
-      code: 6006;
-      name: "MaxFeeExceeded";
-      msg: "Max Fee Exceeded";
-    },
-    {
-      code: 6007;
-      name: "InvalidPercentageSum";
-      msg: "Fee percentages must add up to 100% (10000)";
-    },
-    {
-      code: 6008;
-      name: "AssetsInExceeded";
-      msg: "Max allowed assets in exceeded";
-    },
-    {
-      code: 6009;
-      name: "SharesOutExceeded";
-      msg: "Max allowed shares out exceeded";
-    },
-    {
-      code: 6010;
-      name: "WhitelistProof";
-      msg: "Whitelist verification failed";
-    },
-    {
-      code: 6011;
-      name: "SlippageExceeded";
-      msg: "Slippage limit is exceeded";
-    },
-    {
-      code: 6012;
-      name: "SellingDisallowed";
-      msg: "Selling is disallowed";
-    },
-    {
-      code: 6013;
-      name: "TradingDisallowed";
-      msg: "Trading is disallowed";
-    },
-    {
-      code: 6014;
-      name: "ClosingDisallowed";
-      msg: "Closing is disallowed";
-    },
-    {
-      code: 6015;
-      name: "RedeemingDisallowed";
-      msg: "Redeeming is disallowed";
-    },
-    {
-      code: 6016;
-      name: "CallerDisallowed";
-      msg: "Caller is disallowed";
-    },
-    {
-      code: 6017;
-      name: "InvalidSellingAllowed";
-      msg: "Invalid selling allowed value";
-    },
-    {
-      code: 6018;
-      name: "InvalidShareValue";
-      msg: "Share value cannot be 0";
-    },
-    {
-      code: 6019;
-      name: "InvalidSharePrice";
-      msg: "Invalid share price";
-    },
-    {
-      code: 6020;
-      name: "InvalidMaxSharesOut";
-      msg: "Max shares out cannot be 0";
-    },
-    {
-      code: 6021;
-      name: "InvalidMaxAssetsIn";
-      msg: "Max assets in cannot be 0";
-    },
-    {
-      code: 6022;
-      name: "InsufficientShares";
-      msg: "There are insuffcient shares to transfer in your account";
-    },
-    {
-      code: 6023;
-      name: "InsufficientAssets";
-      msg: "There are insuffcient assets to transfer in your account";
-    },
-    {
-      code: 6024;
-      name: "Paused";
-      msg: "The pool is paused";
-    },
-    {
-      code: 6025;
-      name: "InvalidFeeRecipients";
-      msg: "The fee recipient and percentages must match in length";
-    },
-    {
-      code: 6026;
-      name: "InvalidFeeRecipientWritable";
-      msg: "The fee recipient must be writable";
-    },
-    {
-      code: 6027;
-      name: "InvalidCreator";
-      msg: "Supplied account must match the pool creator pubkey";
-    },
-    {
-      code: 6028;
-      name: "InvalidSwapFeeRecipient";
-      msg: "Invalid swap fee recipient";
-    },
-    {
-      code: 6029;
-      name: "DuplicateFeeRecipient";
-      msg: "Duplicate fee recipient";
-    },
-    {
-      code: 6030;
-      name: "ZeroSlippage";
-      msg: "Zero Slippage";
-    }
-  ];
-};
-
-export const IDL: FjordLbp = {
-  version: "0.1.0",
-  name: "fjord_lbp",
-  instructions: [
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
     {
-      name: "initializeOwnerConfig",
+      name: "redeem",
       accounts: [
         {
-          name: "config",
+          name: "assetTokenMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "shareTokenMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "pool",
           isMut: true,
           isSigner: false,
           pda: {
             seeds: [
               {
-                kind: "const",
+                kind: "account",
+                type: "publicKey",
+                account: "Mint",
+                path: "share_token_mint",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "Mint",
+                path: "asset_token_mint",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "LiquidityBootstrappingPool",
+                path: "pool.creator",
+              },
+              {
+                kind: "account",
                 type: "string",
-                value: "owner_config",
+                account: "LiquidityBootstrappingPool",
+                path: "pool.salt",
               },
             ],
           },
         },
         {
-          name: "treasury",
+          name: "poolAssetTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "poolShareTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userAssetTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userShareTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userStateInPool",
           isMut: true,
           isSigner: false,
           pda: {
             seeds: [
               {
-                kind: "const",
-                type: "string",
-                value: "treasury",
+                kind: "account",
+                type: "publicKey",
+                path: "user",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "LiquidityBootstrappingPool",
+                path: "pool",
               },
             ],
           },
         },
         {
-          name: "program",
-          isMut: false,
-          isSigner: false,
+          name: "user",
+          isMut: true,
+          isSigner: true,
         },
         {
-          name: "programData",
+          name: "associatedTokenProgram",
           isMut: false,
           isSigner: false,
         },
         {
-          name: "authority",
-          isMut: true,
-          isSigner: true,
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
         },
         {
           name: "systemProgram",
@@ -2484,42 +6242,150 @@ export const IDL: FjordLbp = {
       ],
       args: [
         {
-          name: "ownerKey",
-          type: "publicKey",
+          name: "referred",
+          type: "bool",
         },
+      ],
+    },
+    {
+      name: "refund",
+      accounts: [
         {
-          name: "swapFeeRecipient",
-          type: "publicKey",
+          name: "assetTokenMint",
+          isMut: false,
+          isSigner: false,
         },
         {
-          name: "feeRecipients",
-          type: {
-            vec: "publicKey",
+          name: "shareTokenMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "pool",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "Mint",
+                path: "share_token_mint",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "Mint",
+                path: "asset_token_mint",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "LiquidityBootstrappingPool",
+                path: "pool.creator",
+              },
+              {
+                kind: "account",
+                type: "string",
+                account: "LiquidityBootstrappingPool",
+                path: "pool.salt",
+              },
+            ],
           },
         },
         {
-          name: "feePercentages",
-          type: {
-            vec: "u16",
+          name: "poolAssetTokenAccount",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "userAssetTokenAccount",
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+        },
+        {
+          name: "userStateInPool",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "account",
+                type: "publicKey",
+                path: "user",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "LiquidityBootstrappingPool",
+                path: "pool",
+              },
+            ],
           },
         },
         {
-          name: "platformFee",
-          type: "u16",
+          name: "nativeUnwrapAccount",
+          isMut: true,
+          isSigner: false,
+          isOptional: true,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "native_unwrap",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "LiquidityBootstrappingPool",
+                path: "pool",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                path: "user",
+              },
+            ],
+          },
         },
         {
-          name: "referralFee",
-          type: "u16",
+          name: "user",
+          isMut: true,
+          isSigner: true,
         },
         {
-          name: "swapFee",
-          type: "u16",
+          name: "associatedTokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "assetTokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "shareTokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
         },
       ],
+      args: [],
     },
     {
-      name: "initializePool",
+      name: "claimReferralRewards",
       accounts: [
+        {
+          name: "assetTokenMint",
+          isMut: false,
+          isSigner: false,
+        },
         {
           name: "pool",
           isMut: true,
@@ -2529,8 +6395,8 @@ export const IDL: FjordLbp = {
               {
                 kind: "account",
                 type: "publicKey",
-                account: "Mint",
-                path: "share_token_mint",
+                account: "LiquidityBootstrappingPool",
+                path: "pool.share_token",
               },
               {
                 kind: "account",
@@ -2541,48 +6407,77 @@ export const IDL: FjordLbp = {
               {
                 kind: "account",
                 type: "publicKey",
-                path: "creator",
+                account: "LiquidityBootstrappingPool",
+                path: "pool.creator",
               },
               {
-                kind: "arg",
+                kind: "account",
                 type: "string",
-                path: "salt",
+                account: "LiquidityBootstrappingPool",
+                path: "pool.salt",
               },
             ],
           },
         },
         {
-          name: "assetTokenMint",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "shareTokenMint",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "poolShareTokenAccount",
+          name: "poolAssetTokenAccount",
           isMut: true,
           isSigner: false,
         },
         {
-          name: "poolAssetTokenAccount",
+          name: "referrerAssetTokenAccount",
           isMut: true,
           isSigner: false,
+          isOptional: true,
         },
         {
-          name: "creatorAssetTokenAccount",
+          name: "referrerStateInPool",
           isMut: true,
           isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "account",
+                type: "publicKey",
+                path: "referrer",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "LiquidityBootstrappingPool",
+                path: "pool",
+              },
+            ],
+          },
         },
         {
-          name: "creatorShareTokenAccount",
+          name: "nativeUnwrapAccount",
           isMut: true,
           isSigner: false,
+          isOptional: true,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "native_unwrap",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "LiquidityBootstrappingPool",
+                path: "pool",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                path: "referrer",
+              },
+            ],
+          },
         },
         {
-          name: "creator",
+          name: "referrer",
           isMut: true,
           isSigner: true,
         },
@@ -2592,7 +6487,7 @@ export const IDL: FjordLbp = {
           isSigner: false,
         },
         {
-          name: "tokenProgram",
+          name: "assetTokenProgram",
           isMut: false,
           isSigner: false,
         },
@@ -2602,77 +6497,10 @@ export const IDL: FjordLbp = {
           isSigner: false,
         },
       ],
-      args: [
-        {
-          name: "salt",
-          type: "string",
-        },
-        {
-          name: "assets",
-          type: "u64",
-        },
-        {
-          name: "shares",
-          type: "u64",
-        },
-        {
-          name: "virtualAssets",
-          type: "u64",
-        },
-        {
-          name: "virtualShares",
-          type: "u64",
-        },
-        {
-          name: "maxSharePrice",
-          type: "u64",
-        },
-        {
-          name: "maxSharesOut",
-          type: "u64",
-        },
-        {
-          name: "maxAssetsIn",
-          type: "u64",
-        },
-        {
-          name: "startWeightBasisPoints",
-          type: "u16",
-        },
-        {
-          name: "endWeightBasisPoints",
-          type: "u16",
-        },
-        {
-          name: "saleStartTime",
-          type: "i64",
-        },
-        {
-          name: "saleEndTime",
-          type: "i64",
-        },
-        {
-          name: "vestCliff",
-          type: "i64",
-        },
-        {
-          name: "vestEnd",
-          type: "i64",
-        },
-        {
-          name: "whitelistMerkleRoot",
-          type: {
-            array: ["u8", 32],
-          },
-        },
-        {
-          name: "sellingAllowed",
-          type: "bool",
-        },
-      ],
+      args: [],
     },
     {
-      name: "swapExactAssetsForShares",
+      name: "previewAssetsIn",
       accounts: [
         {
           name: "assetTokenMint",
@@ -2686,7 +6514,7 @@ export const IDL: FjordLbp = {
         },
         {
           name: "pool",
-          isMut: true,
+          isMut: false,
           isSigner: false,
           pda: {
             seeds: [
@@ -2719,22 +6547,12 @@ export const IDL: FjordLbp = {
         },
         {
           name: "poolAssetTokenAccount",
-          isMut: true,
+          isMut: false,
           isSigner: false,
         },
         {
           name: "poolShareTokenAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "userAssetTokenAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "userShareTokenAccount",
-          isMut: true,
+          isMut: false,
           isSigner: false,
         },
         {
@@ -2751,99 +6569,96 @@ export const IDL: FjordLbp = {
             ],
           },
         },
+      ],
+      args: [
         {
-          name: "userStateInPool",
-          isMut: true,
+          name: "sharesOut",
+          type: "u64",
+        },
+      ],
+      returns: "u64",
+    },
+    {
+      name: "previewSharesIn",
+      accounts: [
+        {
+          name: "assetTokenMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "shareTokenMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "pool",
+          isMut: false,
           isSigner: false,
           pda: {
             seeds: [
               {
                 kind: "account",
                 type: "publicKey",
-                path: "user",
+                account: "Mint",
+                path: "share_token_mint",
               },
               {
                 kind: "account",
                 type: "publicKey",
-                account: "LiquidityBootstrappingPool",
-                path: "pool",
+                account: "Mint",
+                path: "asset_token_mint",
               },
-            ],
-          },
-        },
-        {
-          name: "referrerStateInPool",
-          isMut: true,
-          isSigner: false,
-          isOptional: true,
-          pda: {
-            seeds: [
               {
-                kind: "arg",
-                type: {
-                  option: "publicKey",
-                },
-                path: "referrer",
+                kind: "account",
+                type: "publicKey",
+                account: "LiquidityBootstrappingPool",
+                path: "pool.creator",
               },
               {
                 kind: "account",
-                type: "publicKey",
+                type: "string",
                 account: "LiquidityBootstrappingPool",
-                path: "pool",
+                path: "pool.salt",
               },
             ],
           },
         },
         {
-          name: "user",
-          isMut: true,
-          isSigner: true,
-        },
-        {
-          name: "associatedTokenProgram",
+          name: "poolAssetTokenAccount",
           isMut: false,
           isSigner: false,
         },
         {
-          name: "tokenProgram",
+          name: "poolShareTokenAccount",
           isMut: false,
           isSigner: false,
         },
         {
-          name: "systemProgram",
+          name: "config",
           isMut: false,
           isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "owner_config",
+              },
+            ],
+          },
         },
       ],
       args: [
         {
-          name: "assetsIn",
-          type: "u64",
-        },
-        {
-          name: "minSharesOut",
+          name: "assetsOut",
           type: "u64",
         },
-        {
-          name: "merkleProof",
-          type: {
-            option: {
-              vec: {
-                array: ["u8", 32],
-              },
-            },
-          },
-        },
-        {
-          name: "referrer",
-          type: {
-            option: "publicKey",
-          },
-        },
       ],
+      returns: "u64",
     },
     {
-      name: "swapAssetsForExactShares",
+      name: "previewSharesOut",
       accounts: [
         {
           name: "assetTokenMint",
@@ -2857,7 +6672,7 @@ export const IDL: FjordLbp = {
         },
         {
           name: "pool",
-          isMut: true,
+          isMut: false,
           isSigner: false,
           pda: {
             seeds: [
@@ -2890,22 +6705,12 @@ export const IDL: FjordLbp = {
         },
         {
           name: "poolAssetTokenAccount",
-          isMut: true,
+          isMut: false,
           isSigner: false,
         },
         {
           name: "poolShareTokenAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "userAssetTokenAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "userShareTokenAccount",
-          isMut: true,
+          isMut: false,
           isSigner: false,
         },
         {
@@ -2922,99 +6727,96 @@ export const IDL: FjordLbp = {
             ],
           },
         },
+      ],
+      args: [
+        {
+          name: "assetsIn",
+          type: "u64",
+        },
+      ],
+      returns: "u64",
+    },
+    {
+      name: "previewAssetsOut",
+      accounts: [
+        {
+          name: "assetTokenMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "shareTokenMint",
+          isMut: false,
+          isSigner: false,
+        },
         {
-          name: "userStateInPool",
-          isMut: true,
+          name: "pool",
+          isMut: false,
           isSigner: false,
           pda: {
             seeds: [
               {
                 kind: "account",
                 type: "publicKey",
-                path: "user",
+                account: "Mint",
+                path: "share_token_mint",
               },
               {
                 kind: "account",
                 type: "publicKey",
-                account: "LiquidityBootstrappingPool",
-                path: "pool",
+                account: "Mint",
+                path: "asset_token_mint",
               },
-            ],
-          },
-        },
-        {
-          name: "referrerStateInPool",
-          isMut: true,
-          isSigner: false,
-          isOptional: true,
-          pda: {
-            seeds: [
               {
-                kind: "arg",
-                type: {
-                  option: "publicKey",
-                },
-                path: "referrer",
+                kind: "account",
+                type: "publicKey",
+                account: "LiquidityBootstrappingPool",
+                path: "pool.creator",
               },
               {
                 kind: "account",
-                type: "publicKey",
+                type: "string",
                 account: "LiquidityBootstrappingPool",
-                path: "pool",
+                path: "pool.salt",
               },
             ],
           },
         },
         {
-          name: "user",
-          isMut: true,
-          isSigner: true,
-        },
-        {
-          name: "associatedTokenProgram",
+          name: "poolAssetTokenAccount",
           isMut: false,
           isSigner: false,
         },
         {
-          name: "tokenProgram",
+          name: "poolShareTokenAccount",
           isMut: false,
           isSigner: false,
         },
         {
-          name: "systemProgram",
+          name: "config",
           isMut: false,
           isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "owner_config",
+              },
+            ],
+          },
         },
       ],
       args: [
         {
-          name: "sharesOut",
-          type: "u64",
-        },
-        {
-          name: "maxAssetsIn",
+          name: "sharesIn",
           type: "u64",
         },
-        {
-          name: "merkleProof",
-          type: {
-            option: {
-              vec: {
-                array: ["u8", 32],
-              },
-            },
-          },
-        },
-        {
-          name: "referrer",
-          type: {
-            option: "publicKey",
-          },
-        },
       ],
+      returns: "u64",
     },
     {
-      name: "swapExactSharesForAssets",
+      name: "reservesAndWeights",
       accounts: [
         {
           name: "assetTokenMint",
@@ -3028,7 +6830,7 @@ export const IDL: FjordLbp = {
         },
         {
           name: "pool",
-          isMut: true,
+          isMut: false,
           isSigner: false,
           pda: {
             seeds: [
@@ -3061,22 +6863,12 @@ export const IDL: FjordLbp = {
         },
         {
           name: "poolAssetTokenAccount",
-          isMut: true,
+          isMut: false,
           isSigner: false,
         },
         {
           name: "poolShareTokenAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "userAssetTokenAccount",
-          isMut: true,
-          isSigner: false,
-        },
-        {
-          name: "userShareTokenAccount",
-          isMut: true,
+          isMut: false,
           isSigner: false,
         },
         {
@@ -3093,8 +6885,17 @@ export const IDL: FjordLbp = {
             ],
           },
         },
+      ],
+      args: [],
+      returns: {
+        defined: "ComputedReservesAndWeights",
+      },
+    },
+    {
+      name: "togglePause",
+      accounts: [
         {
-          name: "userStateInPool",
+          name: "pool",
           isMut: true,
           isSigner: false,
           pda: {
@@ -3102,50 +6903,44 @@ export const IDL: FjordLbp = {
               {
                 kind: "account",
                 type: "publicKey",
-                path: "user",
+                account: "Mint",
+                path: "share_token_mint",
               },
               {
                 kind: "account",
                 type: "publicKey",
-                account: "LiquidityBootstrappingPool",
-                path: "pool",
+                account: "Mint",
+                path: "asset_token_mint",
               },
-            ],
-          },
-        },
-        {
-          name: "referrerStateInPool",
-          isMut: true,
-          isSigner: false,
-          isOptional: true,
-          pda: {
-            seeds: [
               {
-                kind: "arg",
-                type: {
-                  option: "publicKey",
-                },
-                path: "referrer",
+                kind: "account",
+                type: "publicKey",
+                path: "creator",
               },
               {
                 kind: "account",
-                type: "publicKey",
+                type: "string",
                 account: "LiquidityBootstrappingPool",
-                path: "pool",
+                path: "pool.salt",
               },
             ],
           },
         },
         {
-          name: "user",
-          isMut: true,
-          isSigner: true,
+          name: "assetTokenMint",
+          isMut: false,
+          isSigner: false,
         },
         {
-          name: "associatedTokenProgram",
+          name: "shareTokenMint",
           isMut: false,
           isSigner: false,
         },
+        {
+          name: "creator",
+          isMut: false,
+          isSigner: true,
+        },
         {
           name: "tokenProgram",
           isMut: false,
@@ -3157,44 +6952,19 @@ export const IDL: FjordLbp = {
           isSigner: false,
         },
       ],
-      args: [
-        {
-          name: "sharesIn",
-          type: "u64",
-        },
-        {
-          name: "minAssetsOut",
-          type: "u64",
-        },
-        {
-          name: "merkleProof",
-          type: {
-            option: {
-              vec: {
-                array: ["u8", 32],
-              },
-            },
-          },
-        },
-        {
-          name: "referrer",
-          type: {
-            option: "publicKey",
-          },
-        },
-      ],
+      args: [],
     },
     {
-      name: "swapSharesForExactAssets",
+      name: "cancelPool",
       accounts: [
         {
           name: "assetTokenMint",
-          isMut: false,
+          isMut: true,
           isSigner: false,
         },
         {
           name: "shareTokenMint",
-          isMut: false,
+          isMut: true,
           isSigner: false,
         },
         {
@@ -3218,8 +6988,7 @@ export const IDL: FjordLbp = {
               {
                 kind: "account",
                 type: "publicKey",
-                account: "LiquidityBootstrappingPool",
-                path: "pool.creator",
+                path: "creator",
               },
               {
                 kind: "account",
@@ -3241,122 +7010,139 @@ export const IDL: FjordLbp = {
           isSigner: false,
         },
         {
-          name: "userAssetTokenAccount",
+          name: "creatorAssetTokenAccount",
           isMut: true,
           isSigner: false,
         },
         {
-          name: "userShareTokenAccount",
+          name: "creatorShareTokenAccount",
           isMut: true,
           isSigner: false,
         },
         {
-          name: "config",
+          name: "creator",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "associatedTokenProgram",
           isMut: false,
           isSigner: false,
-          pda: {
-            seeds: [
-              {
-                kind: "const",
-                type: "string",
-                value: "owner_config",
-              },
-            ],
-          },
         },
         {
-          name: "userStateInPool",
+          name: "assetTokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "shareTokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "closePoolAccounts",
+      accounts: [
+        {
+          name: "assetTokenMint",
           isMut: true,
           isSigner: false,
-          pda: {
-            seeds: [
-              {
-                kind: "account",
-                type: "publicKey",
-                path: "user",
-              },
-              {
-                kind: "account",
-                type: "publicKey",
-                account: "LiquidityBootstrappingPool",
-                path: "pool",
-              },
-            ],
-          },
         },
         {
-          name: "referrerStateInPool",
+          name: "shareTokenMint",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "pool",
           isMut: true,
           isSigner: false,
-          isOptional: true,
           pda: {
             seeds: [
               {
-                kind: "arg",
-                type: {
-                  option: "publicKey",
-                },
-                path: "referrer",
+                kind: "account",
+                type: "publicKey",
+                account: "Mint",
+                path: "share_token_mint",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "Mint",
+                path: "asset_token_mint",
               },
               {
                 kind: "account",
                 type: "publicKey",
+                path: "creator",
+              },
+              {
+                kind: "account",
+                type: "string",
                 account: "LiquidityBootstrappingPool",
-                path: "pool",
+                path: "pool.salt",
               },
             ],
           },
         },
         {
-          name: "user",
+          name: "poolAssetTokenAccount",
           isMut: true,
-          isSigner: true,
+          isSigner: false,
         },
         {
-          name: "associatedTokenProgram",
-          isMut: false,
+          name: "poolShareTokenAccount",
+          isMut: true,
           isSigner: false,
         },
         {
-          name: "tokenProgram",
-          isMut: false,
+          name: "creatorAssetTokenAccount",
+          isMut: true,
           isSigner: false,
+          isOptional: true,
         },
         {
-          name: "systemProgram",
-          isMut: false,
+          name: "creatorShareTokenAccount",
+          isMut: true,
           isSigner: false,
         },
-      ],
-      args: [
         {
-          name: "assetsOut",
-          type: "u64",
+          name: "creator",
+          isMut: true,
+          isSigner: true,
         },
         {
-          name: "maxSharesIn",
-          type: "u64",
+          name: "associatedTokenProgram",
+          isMut: false,
+          isSigner: false,
         },
         {
-          name: "merkleProof",
-          type: {
-            option: {
-              vec: {
-                array: ["u8", 32],
-              },
-            },
-          },
+          name: "assetTokenProgram",
+          isMut: false,
+          isSigner: false,
         },
         {
-          name: "referrer",
-          type: {
-            option: "publicKey",
-          },
+          name: "shareTokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
         },
       ],
+      args: [],
     },
     {
-      name: "closePool",
+      name: "updatePoolConfig",
       accounts: [
         {
           name: "assetTokenMint",
@@ -3389,8 +7175,7 @@ export const IDL: FjordLbp = {
               {
                 kind: "account",
                 type: "publicKey",
-                account: "LiquidityBootstrappingPool",
-                path: "pool.creator",
+                path: "creator",
               },
               {
                 kind: "account",
@@ -3412,112 +7197,123 @@ export const IDL: FjordLbp = {
           isSigner: false,
         },
         {
-          name: "treasuryAssetTokenAccount",
+          name: "creatorAssetTokenAccount",
           isMut: true,
           isSigner: false,
         },
         {
-          name: "treasuryShareTokenAccount",
+          name: "creatorShareTokenAccount",
           isMut: true,
           isSigner: false,
         },
         {
-          name: "treasury",
+          name: "creator",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "assetTokenProgram",
           isMut: false,
           isSigner: false,
-          pda: {
-            seeds: [
-              {
-                kind: "const",
-                type: "string",
-                value: "treasury",
-              },
-            ],
-          },
         },
         {
-          name: "creatorAssetTokenAccount",
-          isMut: true,
+          name: "shareTokenProgram",
+          isMut: false,
           isSigner: false,
         },
+      ],
+      args: [
         {
-          name: "creatorShareTokenAccount",
-          isMut: true,
-          isSigner: false,
+          name: "assets",
+          type: "u64",
         },
         {
-          name: "poolCreator",
-          isMut: false,
-          isSigner: false,
+          name: "shares",
+          type: "u64",
         },
         {
-          name: "swapFeeRecipientAssetTokenAccount",
-          isMut: true,
-          isSigner: false,
+          name: "virtualAssets",
+          type: {
+            option: "u64",
+          },
         },
         {
-          name: "swapFeeRecipientShareTokenAccount",
-          isMut: true,
-          isSigner: false,
+          name: "virtualShares",
+          type: {
+            option: "u64",
+          },
         },
         {
-          name: "swapFeeRecipient",
-          isMut: false,
-          isSigner: false,
+          name: "maxSharePrice",
+          type: {
+            option: "u64",
+          },
         },
         {
-          name: "ownerConfig",
-          isMut: false,
-          isSigner: false,
-          pda: {
-            seeds: [
-              {
-                kind: "const",
-                type: "string",
-                value: "owner_config",
-              },
-            ],
+          name: "startWeightBasisPoints",
+          type: {
+            option: "u16",
           },
         },
         {
-          name: "user",
-          isMut: true,
-          isSigner: true,
+          name: "endWeightBasisPoints",
+          type: {
+            option: "u16",
+          },
         },
         {
-          name: "associatedTokenProgram",
-          isMut: false,
-          isSigner: false,
+          name: "saleStartTime",
+          type: {
+            option: "i64",
+          },
         },
         {
-          name: "tokenProgram",
-          isMut: false,
-          isSigner: false,
+          name: "saleEndTime",
+          type: {
+            option: "i64",
+          },
         },
         {
-          name: "systemProgram",
-          isMut: false,
-          isSigner: false,
+          name: "vestCliff",
+          type: {
+            option: "i64",
+          },
         },
-      ],
-      args: [],
-    },
-    {
-      name: "redeem",
-      accounts: [
         {
-          name: "assetTokenMint",
-          isMut: false,
-          isSigner: false,
+          name: "vestEnd",
+          type: {
+            option: "i64",
+          },
         },
         {
-          name: "shareTokenMint",
-          isMut: false,
-          isSigner: false,
+          name: "weightCurve",
+          type: {
+            option: {
+              defined: "WeightCurve",
+            },
+          },
+        },
+        {
+          name: "weightKnots",
+          type: {
+            option: {
+              array: [
+                {
+                  defined: "WeightKnot",
+                },
+                8,
+              ],
+            },
+          },
         },
+      ],
+    },
+    {
+      name: "setWhitelistMerkleRoot",
+      accounts: [
         {
           name: "pool",
-          isMut: false,
+          isMut: true,
           isSigner: false,
           pda: {
             seeds: [
@@ -3536,8 +7332,7 @@ export const IDL: FjordLbp = {
               {
                 kind: "account",
                 type: "publicKey",
-                account: "LiquidityBootstrappingPool",
-                path: "pool.creator",
+                path: "creator",
               },
               {
                 kind: "account",
@@ -3549,27 +7344,45 @@ export const IDL: FjordLbp = {
           },
         },
         {
-          name: "poolAssetTokenAccount",
-          isMut: true,
+          name: "assetTokenMint",
+          isMut: false,
           isSigner: false,
         },
         {
-          name: "poolShareTokenAccount",
-          isMut: true,
+          name: "shareTokenMint",
+          isMut: false,
           isSigner: false,
         },
         {
-          name: "userAssetTokenAccount",
-          isMut: true,
+          name: "creator",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
           isSigner: false,
         },
         {
-          name: "userShareTokenAccount",
-          isMut: true,
+          name: "systemProgram",
+          isMut: false,
           isSigner: false,
         },
+      ],
+      args: [
         {
-          name: "userStateInPool",
+          name: "whitelistMerkleRoot",
+          type: {
+            array: ["u8", 32],
+          },
+        },
+      ],
+    },
+    {
+      name: "setPublicSaleTime",
+      accounts: [
+        {
+          name: "pool",
           isMut: true,
           isSigner: false,
           pda: {
@@ -3577,26 +7390,43 @@ export const IDL: FjordLbp = {
               {
                 kind: "account",
                 type: "publicKey",
-                path: "user",
+                account: "Mint",
+                path: "share_token_mint",
+              },
+              {
+                kind: "account",
+                type: "publicKey",
+                account: "Mint",
+                path: "asset_token_mint",
               },
               {
                 kind: "account",
                 type: "publicKey",
+                path: "creator",
+              },
+              {
+                kind: "account",
+                type: "string",
                 account: "LiquidityBootstrappingPool",
-                path: "pool",
+                path: "pool.salt",
               },
             ],
           },
         },
         {
-          name: "user",
-          isMut: true,
-          isSigner: true,
+          name: "assetTokenMint",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "shareTokenMint",
+          isMut: false,
+          isSigner: false,
         },
         {
-          name: "associatedTokenProgram",
+          name: "creator",
           isMut: false,
-          isSigner: false,
+          isSigner: true,
         },
         {
           name: "tokenProgram",
@@ -3611,27 +7441,17 @@ export const IDL: FjordLbp = {
       ],
       args: [
         {
-          name: "referred",
-          type: "bool",
+          name: "publicSaleTime",
+          type: "i64",
         },
       ],
     },
     {
-      name: "previewAssetsIn",
+      name: "setWeightManager",
       accounts: [
-        {
-          name: "assetTokenMint",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "shareTokenMint",
-          isMut: false,
-          isSigner: false,
-        },
         {
           name: "pool",
-          isMut: false,
+          isMut: true,
           isSigner: false,
           pda: {
             seeds: [
@@ -3650,8 +7470,7 @@ export const IDL: FjordLbp = {
               {
                 kind: "account",
                 type: "publicKey",
-                account: "LiquidityBootstrappingPool",
-                path: "pool.creator",
+                path: "creator",
               },
               {
                 kind: "account",
@@ -3663,18 +7482,73 @@ export const IDL: FjordLbp = {
           },
         },
         {
-          name: "poolAssetTokenAccount",
+          name: "assetTokenMint",
           isMut: false,
           isSigner: false,
         },
         {
-          name: "poolShareTokenAccount",
+          name: "shareTokenMint",
           isMut: false,
           isSigner: false,
         },
         {
-          name: "config",
+          name: "creator",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "tokenProgram",
+          isMut: false,
+          isSigner: false,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [
+        {
+          name: "weightManager",
+          type: "publicKey",
+        },
+      ],
+    },
+    {
+      name: "adjustWeights",
+      accounts: [
+        {
+          name: "pool",
+          isMut: true,
+          isSigner: false,
+        },
+        {
+          name: "authority",
           isMut: false,
+          isSigner: true,
+        },
+      ],
+      args: [
+        {
+          name: "endWeightBasisPoints",
+          type: {
+            option: "u16",
+          },
+        },
+        {
+          name: "paused",
+          type: {
+            option: "bool",
+          },
+        },
+      ],
+    },
+    {
+      name: "proposeFees",
+      accounts: [
+        {
+          name: "config",
+          isMut: true,
           isSigner: false,
           pda: {
             seeds: [
@@ -3686,74 +7560,143 @@ export const IDL: FjordLbp = {
             ],
           },
         },
+        {
+          name: "owner",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
       ],
       args: [
         {
-          name: "sharesOut",
-          type: "u64",
+          name: "platformFee",
+          type: {
+            option: "u16",
+          },
+        },
+        {
+          name: "referralFee",
+          type: {
+            option: "u16",
+          },
+        },
+        {
+          name: "swapFee",
+          type: {
+            option: "u16",
+          },
+        },
+        {
+          name: "feeChangeDelay",
+          type: {
+            option: "i64",
+          },
         },
       ],
-      returns: "u64",
     },
     {
-      name: "previewSharesIn",
+      name: "executeFees",
       accounts: [
         {
-          name: "assetTokenMint",
-          isMut: false,
+          name: "config",
+          isMut: true,
           isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "owner_config",
+              },
+            ],
+          },
         },
         {
-          name: "shareTokenMint",
+          name: "owner",
           isMut: false,
-          isSigner: false,
+          isSigner: true,
         },
         {
-          name: "pool",
+          name: "systemProgram",
           isMut: false,
           isSigner: false,
+        },
+      ],
+      args: [],
+    },
+    {
+      name: "lowerFeeCeilings",
+      accounts: [
+        {
+          name: "config",
+          isMut: true,
+          isSigner: false,
           pda: {
             seeds: [
               {
-                kind: "account",
-                type: "publicKey",
-                account: "Mint",
-                path: "share_token_mint",
-              },
-              {
-                kind: "account",
-                type: "publicKey",
-                account: "Mint",
-                path: "asset_token_mint",
-              },
-              {
-                kind: "account",
-                type: "publicKey",
-                account: "LiquidityBootstrappingPool",
-                path: "pool.creator",
-              },
-              {
-                kind: "account",
+                kind: "const",
                 type: "string",
-                account: "LiquidityBootstrappingPool",
-                path: "pool.salt",
+                value: "owner_config",
               },
             ],
           },
         },
         {
-          name: "poolAssetTokenAccount",
+          name: "owner",
           isMut: false,
-          isSigner: false,
+          isSigner: true,
         },
         {
-          name: "poolShareTokenAccount",
+          name: "systemProgram",
           isMut: false,
           isSigner: false,
         },
+      ],
+      args: [
+        {
+          name: "maxPlatformFee",
+          type: {
+            option: "u16",
+          },
+        },
+        {
+          name: "maxReferralFee",
+          type: {
+            option: "u16",
+          },
+        },
+        {
+          name: "maxSwapFee",
+          type: {
+            option: "u16",
+          },
+        },
+      ],
+    },
+    {
+      name: "proposeTreasuryFeeRecipients",
+      accounts: [
+        {
+          name: "treasury",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "treasury",
+              },
+            ],
+          },
+        },
         {
           name: "config",
-          isMut: false,
+          isMut: true,
           isSigner: false,
           pda: {
             seeds: [
@@ -3765,70 +7708,90 @@ export const IDL: FjordLbp = {
             ],
           },
         },
+        {
+          name: "owner",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
       ],
       args: [
         {
-          name: "assetsOut",
-          type: "u64",
+          name: "swapFeeRecipient",
+          type: {
+            option: "publicKey",
+          },
+        },
+        {
+          name: "feeRecipients",
+          type: {
+            vec: "publicKey",
+          },
+        },
+        {
+          name: "feePercentages",
+          type: {
+            vec: "u16",
+          },
         },
       ],
-      returns: "u64",
     },
     {
-      name: "previewSharesOut",
+      name: "executeTreasuryFeeRecipients",
       accounts: [
         {
-          name: "assetTokenMint",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "shareTokenMint",
-          isMut: false,
+          name: "treasury",
+          isMut: true,
           isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "treasury",
+              },
+            ],
+          },
         },
         {
-          name: "pool",
+          name: "config",
           isMut: false,
           isSigner: false,
           pda: {
             seeds: [
               {
-                kind: "account",
-                type: "publicKey",
-                account: "Mint",
-                path: "share_token_mint",
-              },
-              {
-                kind: "account",
-                type: "publicKey",
-                account: "Mint",
-                path: "asset_token_mint",
-              },
-              {
-                kind: "account",
-                type: "publicKey",
-                account: "LiquidityBootstrappingPool",
-                path: "pool.creator",
-              },
-              {
-                kind: "account",
+                kind: "const",
                 type: "string",
-                account: "LiquidityBootstrappingPool",
-                path: "pool.salt",
+                value: "owner_config",
               },
             ],
           },
         },
         {
-          name: "poolAssetTokenAccount",
+          name: "owner",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "systemProgram",
           isMut: false,
           isSigner: false,
         },
+      ],
+      args: [],
+    },
+    {
+      name: "setPoolFees",
+      accounts: [
         {
-          name: "poolShareTokenAccount",
-          isMut: false,
+          name: "pool",
+          isMut: true,
           isSigner: false,
+          relations: ["creator"],
         },
         {
           name: "config",
@@ -3844,71 +7807,60 @@ export const IDL: FjordLbp = {
             ],
           },
         },
+        {
+          name: "owner",
+          isMut: false,
+          isSigner: true,
+        },
+        {
+          name: "creator",
+          isMut: false,
+          isSigner: true,
+        },
       ],
       args: [
         {
-          name: "assetsIn",
-          type: "u64",
+          name: "platformFee",
+          type: {
+            option: "u16",
+          },
         },
-      ],
-      returns: "u64",
-    },
-    {
-      name: "previewAssetsOut",
-      accounts: [
         {
-          name: "assetTokenMint",
-          isMut: false,
-          isSigner: false,
+          name: "referralFee",
+          type: {
+            option: "u16",
+          },
         },
         {
-          name: "shareTokenMint",
-          isMut: false,
-          isSigner: false,
+          name: "swapFee",
+          type: {
+            option: "u16",
+          },
         },
+      ],
+    },
+    {
+      name: "registerReferrer",
+      accounts: [
         {
-          name: "pool",
-          isMut: false,
+          name: "referrerRegistration",
+          isMut: true,
           isSigner: false,
           pda: {
             seeds: [
               {
-                kind: "account",
-                type: "publicKey",
-                account: "Mint",
-                path: "share_token_mint",
-              },
-              {
-                kind: "account",
-                type: "publicKey",
-                account: "Mint",
-                path: "asset_token_mint",
+                kind: "const",
+                type: "string",
+                value: "referrer",
               },
               {
-                kind: "account",
+                kind: "arg",
                 type: "publicKey",
-                account: "LiquidityBootstrappingPool",
-                path: "pool.creator",
-              },
-              {
-                kind: "account",
-                type: "string",
-                account: "LiquidityBootstrappingPool",
-                path: "pool.salt",
+                path: "referrer",
               },
             ],
           },
         },
-        {
-          name: "poolAssetTokenAccount",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "poolShareTokenAccount",
-          isMut: false,
-          isSigner: false,
-        },
         {
           name: "config",
           isMut: false,
@@ -3923,71 +7875,47 @@ export const IDL: FjordLbp = {
             ],
           },
         },
-      ],
-      args: [
         {
-          name: "sharesIn",
-          type: "u64",
+          name: "owner",
+          isMut: true,
+          isSigner: true,
         },
-      ],
-      returns: "u64",
-    },
-    {
-      name: "reservesAndWeights",
-      accounts: [
         {
-          name: "assetTokenMint",
+          name: "systemProgram",
           isMut: false,
           isSigner: false,
         },
+      ],
+      args: [
         {
-          name: "shareTokenMint",
-          isMut: false,
-          isSigner: false,
+          name: "referrer",
+          type: "publicKey",
         },
+      ],
+    },
+    {
+      name: "unregisterReferrer",
+      accounts: [
         {
-          name: "pool",
-          isMut: false,
+          name: "referrerRegistration",
+          isMut: true,
           isSigner: false,
           pda: {
             seeds: [
               {
-                kind: "account",
-                type: "publicKey",
-                account: "Mint",
-                path: "share_token_mint",
-              },
-              {
-                kind: "account",
-                type: "publicKey",
-                account: "Mint",
-                path: "asset_token_mint",
+                kind: "const",
+                type: "string",
+                value: "referrer",
               },
               {
                 kind: "account",
                 type: "publicKey",
-                account: "LiquidityBootstrappingPool",
-                path: "pool.creator",
-              },
-              {
-                kind: "account",
-                type: "string",
-                account: "LiquidityBootstrappingPool",
-                path: "pool.salt",
+                account: "ReferrerRegistration",
+                path: "referrer_registration.referrer",
               },
             ],
           },
         },
-        {
-          name: "poolAssetTokenAccount",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "poolShareTokenAccount",
-          isMut: false,
-          isSigner: false,
-        },
         {
           name: "config",
           isMut: false,
@@ -4002,77 +7930,51 @@ export const IDL: FjordLbp = {
             ],
           },
         },
+        {
+          name: "owner",
+          isMut: true,
+          isSigner: true,
+        },
       ],
       args: [],
-      returns: {
-        defined: "ComputedReservesAndWeights",
-      },
     },
     {
-      name: "togglePause",
+      name: "setReferrerRegistryRequired",
       accounts: [
         {
-          name: "pool",
+          name: "config",
           isMut: true,
           isSigner: false,
           pda: {
             seeds: [
               {
-                kind: "account",
-                type: "publicKey",
-                account: "Mint",
-                path: "share_token_mint",
-              },
-              {
-                kind: "account",
-                type: "publicKey",
-                account: "Mint",
-                path: "asset_token_mint",
-              },
-              {
-                kind: "account",
-                type: "publicKey",
-                path: "creator",
-              },
-              {
-                kind: "account",
+                kind: "const",
                 type: "string",
-                account: "LiquidityBootstrappingPool",
-                path: "pool.salt",
+                value: "owner_config",
               },
             ],
           },
         },
         {
-          name: "assetTokenMint",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "shareTokenMint",
-          isMut: false,
-          isSigner: false,
-        },
-        {
-          name: "creator",
+          name: "owner",
           isMut: false,
           isSigner: true,
         },
         {
-          name: "tokenProgram",
+          name: "systemProgram",
           isMut: false,
           isSigner: false,
         },
+      ],
+      args: [
         {
-          name: "systemProgram",
-          isMut: false,
-          isSigner: false,
+          name: "required",
+          type: "bool",
         },
       ],
-      args: [],
     },
     {
-      name: "setFees",
+      name: "nominateNewOwner",
       accounts: [
         {
           name: "config",
@@ -4093,38 +7995,44 @@ export const IDL: FjordLbp = {
           isMut: false,
           isSigner: true,
         },
-        {
-          name: "systemProgram",
-          isMut: false,
-          isSigner: false,
-        },
       ],
       args: [
         {
-          name: "platformFee",
-          type: {
-            option: "u16",
-          },
+          name: "newOwnerKey",
+          type: "publicKey",
         },
+      ],
+    },
+    {
+      name: "acceptNewOwner",
+      accounts: [
         {
-          name: "referralFee",
-          type: {
-            option: "u16",
+          name: "config",
+          isMut: true,
+          isSigner: false,
+          pda: {
+            seeds: [
+              {
+                kind: "const",
+                type: "string",
+                value: "owner_config",
+              },
+            ],
           },
         },
         {
-          name: "swapFee",
-          type: {
-            option: "u16",
-          },
+          name: "newOwner",
+          isMut: false,
+          isSigner: true,
         },
       ],
+      args: [],
     },
     {
-      name: "setTreasuryFeeRecipients",
+      name: "migrateOwnerConfig",
       accounts: [
         {
-          name: "treasury",
+          name: "config",
           isMut: true,
           isSigner: false,
           pda: {
@@ -4132,13 +8040,13 @@ export const IDL: FjordLbp = {
               {
                 kind: "const",
                 type: "string",
-                value: "treasury",
+                value: "owner_config",
               },
             ],
           },
         },
         {
-          name: "config",
+          name: "treasury",
           isMut: true,
           isSigner: false,
           pda: {
@@ -4146,7 +8054,7 @@ export const IDL: FjordLbp = {
               {
                 kind: "const",
                 type: "string",
-                value: "owner_config",
+                value: "treasury",
               },
             ],
           },
@@ -4164,32 +8072,31 @@ export const IDL: FjordLbp = {
       ],
       args: [
         {
-          name: "swapFeeRecipient",
-          type: {
-            option: "publicKey",
-          },
+          name: "maxPlatformFee",
+          type: "u16",
         },
         {
-          name: "feeRecipients",
-          type: {
-            vec: "publicKey",
-          },
+          name: "maxReferralFee",
+          type: "u16",
         },
         {
-          name: "feePercentages",
-          type: {
-            vec: "u16",
-          },
+          name: "maxSwapFee",
+          type: "u16",
         },
       ],
     },
     {
-      name: "nominateNewOwner",
+      name: "migratePool",
       accounts: [
         {
-          name: "config",
+          name: "pool",
           isMut: true,
           isSigner: false,
+        },
+        {
+          name: "config",
+          isMut: false,
+          isSigner: false,
           pda: {
             seeds: [
               {
@@ -4201,196 +8108,485 @@ export const IDL: FjordLbp = {
           },
         },
         {
-          name: "owner",
-          isMut: false,
+          name: "payer",
+          isMut: true,
           isSigner: true,
         },
-      ],
-      args: [
         {
-          name: "newOwnerKey",
-          type: "publicKey",
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
         },
       ],
+      args: [],
     },
     {
-      name: "acceptNewOwner",
+      name: "migrateUserState",
       accounts: [
         {
-          name: "config",
+          name: "userStateInPool",
           isMut: true,
           isSigner: false,
-          pda: {
-            seeds: [
-              {
-                kind: "const",
-                type: "string",
-                value: "owner_config",
-              },
-            ],
+        },
+        {
+          name: "payer",
+          isMut: true,
+          isSigner: true,
+        },
+        {
+          name: "systemProgram",
+          isMut: false,
+          isSigner: false,
+        },
+      ],
+      args: [],
+    },
+  ],
+  accounts: [
+    {
+      name: "liquidityBootstrappingPool",
+      docs: [
+        "Account storing the information of the liquidity bootstrapping pool",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "assetToken",
+            type: "publicKey",
+          },
+          {
+            name: "shareToken",
+            type: "publicKey",
+          },
+          {
+            name: "creator",
+            type: "publicKey",
+          },
+          {
+            name: "virtualAssets",
+            type: "u64",
+          },
+          {
+            name: "virtualShares",
+            type: "u64",
+          },
+          {
+            name: "maxSharePrice",
+            type: "u64",
+          },
+          {
+            name: "maxSharesOut",
+            type: "u64",
+          },
+          {
+            name: "maxAssetsIn",
+            type: "u64",
+          },
+          {
+            name: "minAssetsRaised",
+            type: "u64",
+          },
+          {
+            name: "maxAssetsRaised",
+            type: "u64",
+          },
+          {
+            name: "maxAssetsPerUser",
+            type: "u64",
+          },
+          {
+            name: "maxSharesPerUser",
+            type: "u64",
+          },
+          {
+            name: "minAssetsPerBuy",
+            type: "u64",
+          },
+          {
+            name: "startWeightBasisPoints",
+            type: "u16",
+          },
+          {
+            name: "endWeightBasisPoints",
+            type: "u16",
+          },
+          {
+            name: "weightCurve",
+            type: {
+              defined: "WeightCurve",
+            },
+          },
+          {
+            name: "weightKnots",
+            type: {
+              array: [
+                {
+                  defined: "WeightKnot",
+                },
+                8,
+              ],
+            },
+          },
+          {
+            name: "weightAdjustment",
+            type: {
+              defined: "WeightAdjustment",
+            },
+          },
+          {
+            name: "weightManager",
+            type: "publicKey",
+          },
+          {
+            name: "saleStartTime",
+            type: "i64",
+          },
+          {
+            name: "saleEndTime",
+            type: "i64",
+          },
+          {
+            name: "vestCliff",
+            type: "i64",
+          },
+          {
+            name: "vestEnd",
+            type: "i64",
+          },
+          {
+            name: "sellingAllowed",
+            type: "bool",
+          },
+          {
+            name: "referralSchedule",
+            type: {
+              defined: "ReferralSchedule",
+            },
+          },
+          {
+            name: "platformFee",
+            type: "u16",
+          },
+          {
+            name: "referralFee",
+            type: "u16",
+          },
+          {
+            name: "swapFee",
+            type: "u16",
+          },
+          {
+            name: "totalPurchased",
+            type: "u64",
+          },
+          {
+            name: "totalReferred",
+            type: "u64",
+          },
+          {
+            name: "totalAssetsRaised",
+            type: "u64",
+          },
+          {
+            name: "totalAssetsPaid",
+            type: "u64",
+          },
+          {
+            name: "totalSwapFeesAsset",
+            type: "u64",
+          },
+          {
+            name: "totalSwapFeesShare",
+            type: "u64",
+          },
+          {
+            name: "closed",
+            type: "bool",
+          },
+          {
+            name: "refunding",
+            type: "bool",
+          },
+          {
+            name: "paused",
+            type: "bool",
+          },
+          {
+            name: "traded",
+            type: "bool",
+          },
+          {
+            name: "whitelistMerkleRoot",
+            type: {
+              array: ["u8", 32],
+            },
+          },
+          {
+            name: "whitelistLeafVersion",
+            type: {
+              defined: "WhitelistLeafVersion",
+            },
+          },
+          {
+            name: "whitelistSigner",
+            type: "publicKey",
+          },
+          {
+            name: "whitelistTierStartTimes",
+            type: {
+              array: ["i64", 4],
+            },
+          },
+          {
+            name: "publicSaleTime",
+            type: "i64",
+          },
+          {
+            name: "bump",
+            type: "u8",
           },
-        },
-        {
-          name: "newOwner",
-          isMut: false,
-          isSigner: true,
-        },
+          {
+            name: "salt",
+            type: "string",
+          },
+        ],
+      },
+    },
+    {
+      name: "closedPool",
+      docs: [
+        "Marker left at the address of a pool once its accounts are closed, so that no pool can be initialized there",
+        "again and inherit the user states left in it",
       ],
-      args: [],
+      type: {
+        kind: "struct",
+        fields: [],
+      },
     },
-  ],
-  accounts: [
     {
-      name: "liquidityBootstrappingPool",
+      name: "userStateInPool",
       docs: [
-        "Account storing the information of the liquidity bootstrapping pool",
+        "Account storing the information of the user in the liquidity bootstrapping pool",
       ],
       type: {
         kind: "struct",
         fields: [
           {
-            name: "assetToken",
-            type: "publicKey",
+            name: "purchasedShares",
+            type: "u64",
           },
           {
-            name: "shareToken",
-            type: "publicKey",
+            name: "referredAssets",
+            type: "u64",
           },
           {
-            name: "creator",
-            type: "publicKey",
+            name: "redeemedShares",
+            type: "u64",
           },
           {
-            name: "virtualAssets",
+            name: "assetsPaid",
             type: "u64",
           },
           {
-            name: "virtualShares",
-            type: "u64",
+            name: "referrer",
+            type: "publicKey",
           },
           {
-            name: "maxSharePrice",
+            name: "referredVolume",
             type: "u64",
           },
           {
-            name: "maxSharesOut",
+            name: "assetsSpent",
             type: "u64",
           },
+        ],
+      },
+    },
+    {
+      name: "ownerConfig",
+      type: {
+        kind: "struct",
+        fields: [
           {
-            name: "maxAssetsIn",
-            type: "u64",
+            name: "owner",
+            type: "publicKey",
           },
           {
-            name: "startWeightBasisPoints",
-            type: "u16",
+            name: "pendingOwner",
+            type: {
+              option: "publicKey",
+            },
           },
           {
-            name: "endWeightBasisPoints",
+            name: "treasury",
+            type: "publicKey",
+          },
+          {
+            name: "platformFee",
             type: "u16",
           },
           {
-            name: "saleStartTime",
-            type: "i64",
+            name: "referralFee",
+            type: "u16",
           },
           {
-            name: "saleEndTime",
-            type: "i64",
+            name: "swapFee",
+            type: "u16",
           },
           {
-            name: "vestCliff",
-            type: "i64",
+            name: "maxPlatformFee",
+            type: "u16",
           },
           {
-            name: "vestEnd",
-            type: "i64",
+            name: "maxReferralFee",
+            type: "u16",
           },
           {
-            name: "sellingAllowed",
-            type: "bool",
+            name: "maxSwapFee",
+            type: "u16",
           },
           {
-            name: "totalPurchased",
-            type: "u64",
+            name: "feeChangeDelay",
+            type: "i64",
           },
           {
-            name: "totalReferred",
-            type: "u64",
+            name: "pendingFees",
+            type: {
+              option: {
+                defined: "PendingFees",
+              },
+            },
           },
           {
-            name: "totalSwapFeesAsset",
-            type: "u64",
+            name: "referrerRegistryRequired",
+            type: "bool",
           },
           {
-            name: "totalSwapFeesShare",
-            type: "u64",
+            name: "bump",
+            type: "u8",
           },
+        ],
+      },
+    },
+    {
+      name: "treasury",
+      type: {
+        kind: "struct",
+        fields: [
           {
-            name: "closed",
-            type: "bool",
+            name: "swapFeeRecipient",
+            type: "publicKey",
           },
           {
-            name: "paused",
-            type: "bool",
+            name: "feeRecipients",
+            type: {
+              vec: {
+                defined: "FeeMapping",
+              },
+            },
           },
           {
-            name: "whitelistMerkleRoot",
+            name: "pendingFeeRecipients",
             type: {
-              array: ["u8", 32],
+              option: {
+                defined: "PendingFeeRecipients",
+              },
             },
           },
+        ],
+      },
+    },
+    {
+      name: "referrerRegistration",
+      docs: [
+        "Account registering a referrer allowed to earn referral fees when the referrer registry is required",
+      ],
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "referrer",
+            type: "publicKey",
+          },
           {
             name: "bump",
             type: "u8",
           },
-          {
-            name: "salt",
-            type: "string",
-          },
         ],
       },
     },
+  ],
+  types: [
     {
-      name: "userStateInPool",
-      docs: [
-        "Account storing the information of the user in the liquidity bootstrapping pool",
-      ],
+      name: "ComputedReservesAndWeights",
       type: {
         kind: "struct",
         fields: [
           {
-            name: "purchasedShares",
-            type: "u64",
+            name: "assetReserve",
+            type: "u128",
           },
           {
-            name: "referredAssets",
-            type: "u64",
+            name: "shareReserve",
+            type: "u128",
           },
           {
-            name: "redeemedShares",
-            type: "u64",
+            name: "assetWeight",
+            type: "u128",
+          },
+          {
+            name: "shareWeight",
+            type: "u128",
           },
         ],
       },
     },
     {
-      name: "ownerConfig",
+      name: "FeeMapping",
       type: {
         kind: "struct",
         fields: [
           {
-            name: "owner",
+            name: "user",
             type: "publicKey",
           },
           {
-            name: "pendingOwner",
+            name: "percentage",
+            type: "u16",
+          },
+        ],
+      },
+    },
+    {
+      name: "PendingFeeRecipients",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "swapFeeRecipient",
+            type: "publicKey",
+          },
+          {
+            name: "feeRecipients",
             type: {
-              option: "publicKey",
+              vec: {
+                defined: "FeeMapping",
+              },
             },
           },
           {
-            name: "treasury",
-            type: "publicKey",
+            name: "executableAt",
+            type: "i64",
           },
+        ],
+      },
+    },
+    {
+      name: "PendingFees",
+      type: {
+        kind: "struct",
+        fields: [
           {
             name: "platformFee",
             type: "u16",
@@ -4404,69 +8600,156 @@ export const IDL: FjordLbp = {
             type: "u16",
           },
           {
-            name: "bump",
-            type: "u8",
+            name: "feeChangeDelay",
+            type: "i64",
+          },
+          {
+            name: "executableAt",
+            type: "i64",
+          },
+        ],
+      },
+    },
+    {
+      name: "WhitelistProof",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "tier",
+            type: "u8",
+          },
+          {
+            name: "maxAllocation",
+            type: "u64",
+          },
+          {
+            name: "proof",
+            type: {
+              vec: {
+                array: ["u8", 32],
+              },
+            },
+          },
+        ],
+      },
+    },
+    {
+      name: "WhitelistLeafVersion",
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "Legacy",
+          },
+          {
+            name: "Tiered",
+          },
+          {
+            name: "Standard",
+          },
+        ],
+      },
+    },
+    {
+      name: "WeightKnot",
+      type: {
+        kind: "struct",
+        fields: [
+          {
+            name: "timestamp",
+            type: "i64",
+          },
+          {
+            name: "weightBasisPoints",
+            type: "u16",
+          },
+        ],
+      },
+    },
+    {
+      name: "WeightCurve",
+      type: {
+        kind: "enum",
+        variants: [
+          {
+            name: "Linear",
+          },
+          {
+            name: "Exponential",
+            fields: [
+              {
+                name: "halfLife",
+                type: "i64",
+              },
+            ],
+          },
+          {
+            name: "Piecewise",
           },
         ],
       },
     },
     {
-      name: "treasury",
+      name: "WeightAdjustment",
       type: {
         kind: "struct",
         fields: [
           {
-            name: "swapFeeRecipient",
-            type: "publicKey",
+            name: "timestamp",
+            type: "i64",
           },
           {
-            name: "feeRecipients",
-            type: {
-              vec: {
-                defined: "FeeMapping",
-              },
-            },
+            name: "weightBasisPoints",
+            type: "u16",
+          },
+          {
+            name: "paused",
+            type: "bool",
           },
         ],
       },
     },
-  ],
-  types: [
     {
-      name: "ComputedReservesAndWeights",
+      name: "ReferralTier",
       type: {
         kind: "struct",
         fields: [
           {
-            name: "assetReserve",
-            type: "u64",
-          },
-          {
-            name: "shareReserve",
-            type: "u64",
-          },
-          {
-            name: "assetWeight",
+            name: "minReferredVolume",
             type: "u64",
           },
           {
-            name: "shareWeight",
-            type: "u64",
+            name: "feeBasisPoints",
+            type: "u16",
           },
         ],
       },
     },
     {
-      name: "FeeMapping",
+      name: "ReferralSchedule",
       type: {
         kind: "struct",
         fields: [
           {
-            name: "user",
-            type: "publicKey",
+            name: "feeBasisPoints",
+            type: {
+              option: "u16",
+            },
           },
           {
-            name: "percentage",
+            name: "tiers",
+            type: {
+              array: [
+                {
+                  defined: "ReferralTier",
+                },
+                4,
+              ],
+            },
+          },
+          {
+            name: "secondLevelBasisPoints",
             type: "u16",
           },
         ],
@@ -4524,26 +8807,293 @@ export const IDL: FjordLbp = {
           {
             name: "InvalidAssetsIn",
           },
-        ],
-      },
+        ],
+      },
+    },
+  ],
+  events: [
+    {
+      name: "PoolCreatedEvent",
+      fields: [
+        {
+          name: "pool",
+          type: "publicKey",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "Buy",
+      fields: [
+        {
+          name: "user",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "assets",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "shares",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "swapFee",
+          type: "u64",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "Sell",
+      fields: [
+        {
+          name: "user",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "shares",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "assets",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "swapFee",
+          type: "u64",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "SaleFinalized",
+      fields: [
+        {
+          name: "pool",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "totalAssetsRaised",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "saleEndTime",
+          type: "i64",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "Close",
+      fields: [
+        {
+          name: "assets",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "platformFees",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "swapFeesAsset",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "swapFeesShare",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "refunding",
+          type: "bool",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "Redeem",
+      fields: [
+        {
+          name: "caller",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "shares",
+          type: "u64",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "Referral",
+      fields: [
+        {
+          name: "pool",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "referrer",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "user",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "assets",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "referredAssets",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "secondLevelReferrer",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "secondLevelReferredAssets",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "isSell",
+          type: "bool",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "ReferralRewardsClaimed",
+      fields: [
+        {
+          name: "pool",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "referrer",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "assets",
+          type: "u64",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "Refund",
+      fields: [
+        {
+          name: "caller",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "assets",
+          type: "u64",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "Cancel",
+      fields: [
+        {
+          name: "pool",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "assets",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "shares",
+          type: "u64",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "PoolAccountsClosed",
+      fields: [
+        {
+          name: "pool",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "assets",
+          type: "u64",
+          index: false,
+        },
+        {
+          name: "shares",
+          type: "u64",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "WhitelistUpdated",
+      fields: [
+        {
+          name: "pool",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "whitelistMerkleRoot",
+          type: {
+            array: ["u8", 32],
+          },
+          index: false,
+        },
+      ],
     },
-  ],
-  events: [
     {
-      name: "PoolCreatedEvent",
+      name: "PublicSaleTimeUpdated",
       fields: [
         {
           name: "pool",
           type: "publicKey",
           index: false,
         },
+        {
+          name: "publicSaleTime",
+          type: "i64",
+          index: false,
+        },
       ],
     },
     {
-      name: "Buy",
+      name: "PoolConfigUpdated",
       fields: [
         {
-          name: "user",
+          name: "pool",
           type: "publicKey",
           index: false,
         },
@@ -4557,74 +9107,177 @@ export const IDL: FjordLbp = {
           type: "u64",
           index: false,
         },
+      ],
+    },
+    {
+      name: "WeightsAdjusted",
+      fields: [
         {
-          name: "swapFee",
-          type: "u64",
+          name: "pool",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "authority",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "weightBasisPoints",
+          type: "u16",
+          index: false,
+        },
+        {
+          name: "endWeightBasisPoints",
+          type: "u16",
+          index: false,
+        },
+        {
+          name: "paused",
+          type: "bool",
           index: false,
         },
       ],
     },
     {
-      name: "Sell",
+      name: "PoolFeesUpdated",
       fields: [
         {
-          name: "user",
+          name: "pool",
           type: "publicKey",
           index: false,
         },
         {
-          name: "shares",
-          type: "u64",
+          name: "platformFee",
+          type: "u16",
           index: false,
         },
         {
-          name: "assets",
-          type: "u64",
+          name: "referralFee",
+          type: "u16",
           index: false,
         },
         {
           name: "swapFee",
-          type: "u64",
+          type: "u16",
           index: false,
         },
       ],
     },
     {
-      name: "Close",
+      name: "FeesProposed",
       fields: [
         {
-          name: "assets",
-          type: "u64",
+          name: "platformFee",
+          type: "u16",
           index: false,
         },
         {
-          name: "platformFees",
-          type: "u64",
+          name: "referralFee",
+          type: "u16",
           index: false,
         },
         {
-          name: "swapFeesAsset",
-          type: "u64",
+          name: "swapFee",
+          type: "u16",
           index: false,
         },
         {
-          name: "swapFeesShare",
-          type: "u64",
+          name: "feeChangeDelay",
+          type: "i64",
+          index: false,
+        },
+        {
+          name: "executableAt",
+          type: "i64",
           index: false,
         },
       ],
     },
     {
-      name: "Redeem",
+      name: "FeesExecuted",
       fields: [
         {
-          name: "caller",
+          name: "platformFee",
+          type: "u16",
+          index: false,
+        },
+        {
+          name: "referralFee",
+          type: "u16",
+          index: false,
+        },
+        {
+          name: "swapFee",
+          type: "u16",
+          index: false,
+        },
+        {
+          name: "feeChangeDelay",
+          type: "i64",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "FeeRecipientsProposed",
+      fields: [
+        {
+          name: "swapFeeRecipient",
           type: "publicKey",
           index: false,
         },
         {
-          name: "shares",
-          type: "u64",
+          name: "feeRecipients",
+          type: {
+            vec: {
+              defined: "FeeMapping",
+            },
+          },
+          index: false,
+        },
+        {
+          name: "executableAt",
+          type: "i64",
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "FeeRecipientsExecuted",
+      fields: [
+        {
+          name: "swapFeeRecipient",
+          type: "publicKey",
+          index: false,
+        },
+        {
+          name: "feeRecipients",
+          type: {
+            vec: {
+              defined: "FeeMapping",
+            },
+          },
+          index: false,
+        },
+      ],
+    },
+    {
+      name: "FeeCeilingsLowered",
+      fields: [
+        {
+          name: "maxPlatformFee",
+          type: "u16",
+          index: false,
+        },
+        {
+          name: "maxReferralFee",
+          type: "u16",
+          index: false,
+        },
+        {
+          name: "maxSwapFee",
+          type: "u16",
           index: false,
         },
       ],
@@ -4674,22 +9327,22 @@ export const IDL: FjordLbp = {
       fields: [
         {
           name: "assetReserve",
-          type: "u64",
+          type: "u128",
           index: false,
         },
         {
           name: "shareReserve",
-          type: "u64",
+          type: "u128",
           index: false,
         },
         {
           name: "assetWeight",
-          type: "u64",
+          type: "u128",
           index: false,
         },
         {
           name: "shareWeight",
-          type: "u64",
+          type: "u128",
           index: false,
         },
       ],
@@ -4851,5 +9504,180 @@ export const IDL: FjordLbp = {
       name: "ZeroSlippage",
       msg: "Zero Slippage",
     },
+    {
+      code: 6031,
+      name: "MissingAssetTokenAccount",
+      msg: "The asset token account is required unless native SOL is used",
+    },
+    {
+      code: 6032,
+      name: "NativeSolDisallowed",
+      msg: "Native SOL can only be used in pools whose asset is wrapped SOL",
+    },
+    {
+      code: 6033,
+      name: "MissingNativeUnwrapAccount",
+      msg: "The native unwrap account is required to receive native SOL",
+    },
+    {
+      code: 6034,
+      name: "UnexpectedNativeUnwrapAccount",
+      msg: "The native unwrap account is only used to receive native SOL",
+    },
+    {
+      code: 6035,
+      name: "InvalidSaleCaps",
+      msg: "The minimum amount of assets raised cannot exceed the hard cap",
+    },
+    {
+      code: 6036,
+      name: "HardCapExceeded",
+      msg: "The purchase would raise more assets than the hard cap",
+    },
+    {
+      code: 6037,
+      name: "AccountAlreadyMigrated",
+      msg: "The account already has the current layout",
+    },
+    {
+      code: 6038,
+      name: "RefundingDisallowed",
+      msg: "Refunds are only available once a pool is closed below its soft cap",
+    },
+    {
+      code: 6039,
+      name: "UserAssetsInExceeded",
+      msg: "Assets in exceeds the maximum amount of assets per user",
+    },
+    {
+      code: 6040,
+      name: "UserSharesOutExceeded",
+      msg: "Shares out exceeds the maximum number of shares per user",
+    },
+    {
+      code: 6041,
+      name: "AssetsInTooLow",
+      msg: "Assets in is below the minimum amount of assets per buy",
+    },
+    {
+      code: 6042,
+      name: "InvalidMinAssetsPerBuy",
+      msg: "The minimum amount of assets per buy cannot exceed the per-wallet cap, the hard cap or the maximum assets in",
+    },
+    {
+      code: 6043,
+      name: "InvalidWhitelistTier",
+      msg: "Invalid whitelist tier",
+    },
+    {
+      code: 6044,
+      name: "WhitelistTierNotOpen",
+      msg: "The whitelist tier of the user is not open yet",
+    },
+    {
+      code: 6045,
+      name: "WhitelistAllocationExceeded",
+      msg: "Assets in exceeds the whitelist allocation of the user",
+    },
+    {
+      code: 6046,
+      name: "WhitelistUpdateDisallowed",
+      msg: "The whitelist can only be updated before the sale ends",
+    },
+    {
+      code: 6047,
+      name: "InvalidPublicSaleTime",
+      msg: "The public sale must start before the sale ends",
+    },
+    {
+      code: 6048,
+      name: "InvalidWhitelistAttestation",
+      msg: "The whitelist attestation is missing or invalid",
+    },
+    {
+      code: 6049,
+      name: "WhitelistAttestationExpired",
+      msg: "The whitelist attestation has expired",
+    },
+    {
+      code: 6050,
+      name: "CancellingDisallowed",
+      msg: "Cancelling is only allowed as long as no shares were ever bought in the pool",
+    },
+    {
+      code: 6051,
+      name: "UpdatingDisallowed",
+      msg: "The pool configuration can only be updated before the sale starts",
+    },
+    {
+      code: 6052,
+      name: "PoolAccountsClosingDisallowed",
+      msg: "The pool accounts can only be closed once the pool is closed and everything owed is claimed, or after the grace period",
+    },
+    {
+      code: 6053,
+      name: "WeightAdjustmentDisallowed",
+      msg: "The weight schedule can only be adjusted during the sale",
+    },
+    {
+      code: 6054,
+      name: "UnauthorizedWeightAdjustment",
+      msg: "Only the creator or the weight manager can adjust the weight schedule",
+    },
+    {
+      code: 6055,
+      name: "SelfReferral",
+      msg: "Users cannot refer themselves",
+    },
+    {
+      code: 6056,
+      name: "InvalidReferrer",
+      msg: "The referrer must come with its state in the pool and match the referrer of the user's earlier purchases",
+    },
+    {
+      code: 6057,
+      name: "UnregisteredReferrer",
+      msg: "The referrer is not registered",
+    },
+    {
+      code: 6058,
+      name: "InvalidReferralSchedule",
+      msg: "Referral fees must not exceed 100% and the referral tiers must come first in increasing volume order",
+    },
+    {
+      code: 6059,
+      name: "ReferralClaimDisallowed",
+      msg: "Referral rewards can only be claimed once the pool is closed, unless it is refunding",
+    },
+    {
+      code: 6060,
+      name: "PoolFeesUpdateDisallowed",
+      msg: "The fees of a pool can only be overridden until it is closed",
+    },
+    {
+      code: 6061,
+      name: "FeeChangeNotProposed",
+      msg: "No fee change is pending",
+    },
+    {
+      code: 6062,
+      name: "FeeChangeNotReady",
+      msg: "The fee change can only be executed once its delay has elapsed",
+    },
+    {
+      code: 6063,
+      name: "FeeCeilingExceeded",
+      msg: "The fees must not exceed their ceilings",
+    },
+    {
+      code: 6064,
+      name: "FeeCeilingRaised",
+      msg: "Fee ceilings can only be lowered",
+    },
+    {
+      code: 6065,
+      name: "InvalidFeeChangeDelay",
+      msg: "The fee change delay must be between one and thirty days",
+    },
   ],
 };